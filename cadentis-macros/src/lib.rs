@@ -111,6 +111,41 @@ pub fn join(input: TokenStream) -> TokenStream {
 /// - Futures are polled in declaration order.
 /// - The result of the selected handler is returned.
 /// - If no branches are provided, the macro expands to `()`.
+///
+/// # Selecting in a loop
+///
+/// Branch futures are created when the `select!` is evaluated and
+/// dropped once it returns. Inside a `loop`, every iteration therefore
+/// starts each branch over: harmless for stateless futures such as a
+/// `sleep`, but a stateful future loses its progress each time another
+/// branch wins.
+///
+/// To keep a future alive across iterations, pin it outside the loop and
+/// pass it by mutable reference (`&mut fut`). Such a branch is polled in
+/// place rather than boxed, and is only dropped by its owner. A future
+/// must not be polled again once it has completed: replace it (for
+/// instance with [`Pin::set`](std::pin::Pin::set)) or leave the loop.
+///
+/// ```ignore
+/// let mut collect = std::pin::pin!(async {
+///     let mut messages = Vec::new();
+///     while let Some(message) = rx.recv().await {
+///         messages.push(message);
+///     }
+///     messages
+/// });
+///
+/// let messages = loop {
+///     let done = select!(
+///         &mut collect => |messages| Some(messages),
+///         sleep(Duration::from_millis(100)) => |_| None,
+///     );
+///
+///     if let Some(messages) = done {
+///         break messages;
+///     }
+/// };
+/// ```
 #[proc_macro]
 pub fn select(input: TokenStream) -> TokenStream {
     let branches = utils::parse_select_branches(input);
@@ -136,9 +171,18 @@ pub fn select(input: TokenStream) -> TokenStream {
 
     for (i, (future, _handler)) in branches.iter().enumerate() {
         let idx = i + 1;
-        out.push_str(&format!(
-            "let mut __f{idx} = ::std::boxed::Box::pin({future});\n"
-        ));
+
+        // A `&mut fut` branch borrows a future owned, and pinned, by the
+        // caller: poll it in place so that its state outlives the select.
+        if future.trim_start().starts_with('&') {
+            out.push_str(&format!(
+                "let mut __f{idx} = ::std::pin::Pin::new({future});\n"
+            ));
+        } else {
+            out.push_str(&format!(
+                "let mut __f{idx} = ::std::boxed::Box::pin({future});\n"
+            ));
+        }
     }
 
     out.push_str("\nlet __res = ::std::future::poll_fn(move |cx| {\n");
@@ -173,6 +217,83 @@ pub fn select(input: TokenStream) -> TokenStream {
     })
 }
 
+/// Awaits the first future that completes and returns its output as an
+/// enum, leaving the handling to the caller.
+///
+/// # Syntax
+///
+/// ```ignore
+/// match select_enum!(fut1, fut2) {
+///     Selected::Branch0(v) => { ... }
+///     Selected::Branch1(v) => { ... }
+/// }
+/// ```
+///
+/// The macro resolves to a `cadentis::tools::Selected` whose variant
+/// `BranchN` holds the output of the `N`th future (counting from zero).
+/// Unused variants are uninhabited, so the `match` only needs one arm
+/// per future.
+///
+/// # Semantics
+///
+/// - Futures are polled in declaration order.
+/// - The first future to resolve wins. All other futures are dropped.
+/// - At most 8 futures are supported.
+/// - If no futures are provided, the macro expands to `()`.
+#[proc_macro]
+pub fn select_enum(input: TokenStream) -> TokenStream {
+    let args = utils::split_args(input);
+    let count = args.len();
+
+    if count == 0 {
+        return "()".parse().unwrap();
+    }
+
+    if count > 8 {
+        return "compile_error!(\"select_enum! supports at most 8 futures\");"
+            .parse()
+            .unwrap();
+    }
+
+    let mut out = String::new();
+    out.push_str("{\n");
+
+    for (i, expr_tokens) in args.iter().enumerate() {
+        let expr = utils::tokens_to_string(expr_tokens);
+        out.push_str(&format!(
+            "let mut __f{i} = ::std::boxed::Box::pin({expr});\n"
+        ));
+    }
+
+    // Naming only the used parameters lets the others fall back to
+    // their `Infallible` defaults.
+    let params = vec!["_"; count].join(", ");
+
+    out.push_str(&format!(
+        "\nlet __res: ::cadentis::tools::Selected<{params}> = ::std::future::poll_fn(move |cx| {{\n"
+    ));
+    out.push_str("    use ::std::task::Poll;\n");
+    out.push_str("    use ::std::future::Future;\n");
+
+    for i in 0..count {
+        out.push_str(&format!(
+            "    if let Poll::Ready(val) = __f{i}.as_mut().poll(cx) {{\n\
+                 return Poll::Ready(::cadentis::tools::Selected::Branch{i}(val));\n\
+             }}\n"
+        ));
+    }
+
+    out.push_str("    Poll::Pending\n");
+    out.push_str("}).await;\n");
+    out.push_str("__res\n");
+    out.push_str("}\n");
+
+    out.parse().unwrap_or_else(|err| {
+        let msg = format!("select_enum macro error: {err}");
+        format!("compile_error!(\"{}\");", msg).parse().unwrap()
+    })
+}
+
 /// Marks an async function as the runtime entry point.
 ///
 /// This attribute transforms an `async fn main` into a synchronous
@@ -244,7 +365,7 @@ pub fn main(attr: TokenStream, item: TokenStream) -> TokenStream {
         builder.push_str(&format!(".worker_threads({})", n));
     }
 
-    builder.push_str(".build()");
+    builder.push_str(".build_unwrap()");
 
     let new_block = format!(
         "{{
@@ -306,7 +427,7 @@ pub fn test(_attr: TokenStream, item: TokenStream) -> TokenStream {
 
     let new_block = format!(
         "{{
-        let runtime = ::cadentis::RuntimeBuilder::new().build();
+        let runtime = ::cadentis::RuntimeBuilder::new().build_unwrap();
         runtime
             .block_on(async move {{ {} }});
     }}",
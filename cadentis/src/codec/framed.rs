@@ -0,0 +1,202 @@
+use super::{Decoder, Encoder};
+use crate::io::{AsyncRead, AsyncWrite};
+use crate::stream::Stream;
+
+use std::future::poll_fn;
+use std::io;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+/// Number of bytes requested from the transport per read.
+const READ_CHUNK: usize = 8 * 1024;
+
+/// A transport of frames on top of a byte stream.
+///
+/// `Framed` reads bytes from `IO` and decodes them with the codec, as a
+/// [`Stream`] of `Result<Item, Error>`; frames are sent with
+/// [`send`](Self::send), or buffered with [`feed`](Self::feed) and
+/// written together by [`flush`](Self::flush).
+///
+/// The stream ends once the transport reaches end of stream and every
+/// buffered frame has been decoded.
+pub struct Framed<IO, C> {
+    /// The underlying transport.
+    io: IO,
+
+    /// The codec encoding and decoding frames.
+    codec: C,
+
+    /// Bytes read but not decoded yet.
+    read_buffer: Vec<u8>,
+
+    /// Encoded bytes not written yet.
+    write_buffer: Vec<u8>,
+
+    /// Whether the transport reached end of stream.
+    eof: bool,
+}
+
+impl<IO, C> Framed<IO, C> {
+    /// Wraps `io`, framing it with `codec`.
+    pub fn new(io: IO, codec: C) -> Self {
+        Self {
+            io,
+            codec,
+            read_buffer: Vec::new(),
+            write_buffer: Vec::new(),
+            eof: false,
+        }
+    }
+
+    /// Returns a reference to the underlying transport.
+    pub fn get_ref(&self) -> &IO {
+        &self.io
+    }
+
+    /// Returns a mutable reference to the underlying transport.
+    ///
+    /// Reading from or writing to the transport directly corrupts the
+    /// framing unless the buffers are empty.
+    pub fn get_mut(&mut self) -> &mut IO {
+        &mut self.io
+    }
+
+    /// Returns a reference to the codec.
+    pub fn codec(&self) -> &C {
+        &self.codec
+    }
+
+    /// Returns a mutable reference to the codec.
+    pub fn codec_mut(&mut self) -> &mut C {
+        &mut self.codec
+    }
+
+    /// Returns the bytes read but not decoded yet.
+    pub fn read_buffer(&self) -> &[u8] {
+        &self.read_buffer
+    }
+
+    /// Consumes the `Framed`, returning the underlying transport.
+    ///
+    /// Buffered bytes, read or to be written, are lost.
+    pub fn into_inner(self) -> IO {
+        self.io
+    }
+}
+
+impl<IO: AsyncWrite + Unpin, C> Framed<IO, C> {
+    /// Encodes `item` into the write buffer without writing it.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of the codec if `item` cannot be encoded.
+    pub fn feed<Item>(&mut self, item: Item) -> Result<(), C::Error>
+    where
+        C: Encoder<Item>,
+    {
+        self.codec.encode(item, &mut self.write_buffer)
+    }
+
+    /// Encodes `item` and writes it, with every buffered frame, to the
+    /// transport.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of the codec, or of the transport converted
+    /// into it.
+    pub async fn send<Item>(&mut self, item: Item) -> Result<(), C::Error>
+    where
+        C: Encoder<Item>,
+    {
+        self.feed(item)?;
+        self.flush().await?;
+
+        Ok(())
+    }
+
+    /// Writes every buffered frame to the transport and flushes it.
+    ///
+    /// # Errors
+    ///
+    /// Returns `WriteZero` if the transport stops accepting bytes, or
+    /// any error it reports.
+    pub async fn flush(&mut self) -> io::Result<()> {
+        poll_fn(|cx| self.poll_flush(cx)).await
+    }
+
+    /// Attempts to write every buffered frame and flush the transport.
+    pub fn poll_flush(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        while !self.write_buffer.is_empty() {
+            let n = match Pin::new(&mut self.io).poll_write(cx, &self.write_buffer) {
+                Poll::Ready(Ok(n)) => n,
+                Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
+                Poll::Pending => return Poll::Pending,
+            };
+
+            if n == 0 {
+                return Poll::Ready(Err(io::Error::new(
+                    io::ErrorKind::WriteZero,
+                    "failed to write frame to transport",
+                )));
+            }
+
+            self.write_buffer.drain(..n);
+        }
+
+        Pin::new(&mut self.io).poll_flush(cx)
+    }
+}
+
+impl<IO: AsyncRead + Unpin, C: Decoder> Framed<IO, C> {
+    /// Reads from the transport into the read buffer.
+    ///
+    /// Returns the number of bytes read, `0` at end of stream.
+    fn poll_fill(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<usize>> {
+        let len = self.read_buffer.len();
+        self.read_buffer.resize(len + READ_CHUNK, 0);
+
+        let result = Pin::new(&mut self.io).poll_read(cx, &mut self.read_buffer[len..]);
+        let n = match &result {
+            Poll::Ready(Ok(n)) => *n,
+            _ => 0,
+        };
+
+        self.read_buffer.truncate(len + n);
+
+        result
+    }
+}
+
+impl<IO: AsyncRead + Unpin, C: Decoder + Unpin> Stream for Framed<IO, C> {
+    type Item = Result<C::Item, C::Error>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        loop {
+            if this.eof {
+                let frame = this.codec.decode_eof(&mut this.read_buffer);
+
+                // A partial frame is reported once, then the stream ends.
+                if frame.is_err() {
+                    this.read_buffer.clear();
+                }
+
+                return Poll::Ready(frame.transpose());
+            }
+
+            match this.codec.decode(&mut this.read_buffer) {
+                Ok(Some(frame)) => return Poll::Ready(Some(Ok(frame))),
+                Ok(None) => {}
+                Err(e) => return Poll::Ready(Some(Err(e))),
+            }
+
+            match this.poll_fill(cx) {
+                Poll::Ready(Ok(0)) => this.eof = true,
+                Poll::Ready(Ok(_)) => {}
+                Poll::Ready(Err(e)) => return Poll::Ready(Some(Err(e.into()))),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
@@ -0,0 +1,100 @@
+use super::{Decoder, Encoder};
+
+use std::io;
+
+/// Size of the length prefix, in bytes.
+const HEADER_LEN: usize = 4;
+
+/// Default limit on the size of a frame: 8 MiB.
+const DEFAULT_MAX_FRAME_LENGTH: usize = 8 * 1024 * 1024;
+
+/// A codec for frames prefixed by their length.
+///
+/// Each frame is a 4-byte big-endian length followed by that many bytes
+/// of payload, the same layout as [`UdpFramed`](crate::net::UdpFramed).
+/// Frames longer than the maximum frame length are rejected with
+/// `InvalidData` on both ends.
+#[derive(Debug, Clone)]
+pub struct LengthDelimitedCodec {
+    /// Largest accepted payload, in bytes.
+    max_frame_length: usize,
+}
+
+impl LengthDelimitedCodec {
+    /// Creates a codec accepting frames up to 8 MiB.
+    pub fn new() -> Self {
+        Self {
+            max_frame_length: DEFAULT_MAX_FRAME_LENGTH,
+        }
+    }
+
+    /// Sets the largest accepted payload, in bytes.
+    ///
+    /// The limit cannot exceed `u32::MAX`, the largest encodable length.
+    pub fn max_frame_length(mut self, max_frame_length: usize) -> Self {
+        self.max_frame_length = max_frame_length.min(u32::MAX as usize);
+        self
+    }
+
+    /// Returns an error if a payload of `len` bytes is too large.
+    fn check_length(&self, len: usize) -> io::Result<()> {
+        if len > self.max_frame_length {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "frame of {len} bytes exceeds the maximum of {}",
+                    self.max_frame_length
+                ),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for LengthDelimitedCodec {
+    /// Returns a codec accepting frames up to 8 MiB.
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Decoder for LengthDelimitedCodec {
+    type Item = Vec<u8>;
+    type Error = io::Error;
+
+    fn decode(&mut self, src: &mut Vec<u8>) -> io::Result<Option<Vec<u8>>> {
+        let Some(header) = src.first_chunk::<HEADER_LEN>() else {
+            return Ok(None);
+        };
+
+        let len = u32::from_be_bytes(*header) as usize;
+        self.check_length(len)?;
+
+        if src.len() < HEADER_LEN + len {
+            // Avoid reallocating once per read while the payload arrives.
+            src.reserve(HEADER_LEN + len - src.len());
+            return Ok(None);
+        }
+
+        let frame = src[HEADER_LEN..HEADER_LEN + len].to_vec();
+        src.drain(..HEADER_LEN + len);
+
+        Ok(Some(frame))
+    }
+}
+
+impl<T: AsRef<[u8]>> Encoder<T> for LengthDelimitedCodec {
+    type Error = io::Error;
+
+    fn encode(&mut self, frame: T, dst: &mut Vec<u8>) -> io::Result<()> {
+        let frame = frame.as_ref();
+        self.check_length(frame.len())?;
+
+        dst.reserve(HEADER_LEN + frame.len());
+        dst.extend_from_slice(&(frame.len() as u32).to_be_bytes());
+        dst.extend_from_slice(frame);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,135 @@
+use super::{Decoder, Encoder};
+
+use std::io;
+
+/// A codec for newline-delimited UTF-8 text.
+///
+/// Decoded lines do not include the trailing `\n` (nor `\r\n`); encoded
+/// lines are terminated by a single `\n`. A last line without newline
+/// is still returned at end of stream.
+///
+/// A line over the maximum length fails to decode once, and is then
+/// skipped up to its newline: decoding resumes with the next line.
+#[derive(Debug, Clone, Default)]
+pub struct LinesCodec {
+    /// Longest accepted line, in bytes, excluding the newline.
+    max_length: Option<usize>,
+
+    /// Number of bytes already searched for a newline.
+    searched: usize,
+
+    /// Whether the rest of a line over the maximum length is being
+    /// skipped, up to its newline.
+    discarding: bool,
+}
+
+impl LinesCodec {
+    /// Creates a codec accepting lines of any length.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a codec rejecting lines longer than `max_length` bytes.
+    ///
+    /// Without a limit, a peer never sending a newline makes the read
+    /// buffer grow without bound.
+    pub fn new_with_max_length(max_length: usize) -> Self {
+        Self {
+            max_length: Some(max_length),
+            searched: 0,
+            discarding: false,
+        }
+    }
+
+    /// Returns the longest accepted line, if limited.
+    pub fn max_length(&self) -> Option<usize> {
+        self.max_length
+    }
+}
+
+/// Converts a line without its terminator into a `String`.
+fn into_line(mut bytes: Vec<u8>) -> io::Result<String> {
+    if bytes.last() == Some(&b'\r') {
+        bytes.pop();
+    }
+
+    String::from_utf8(bytes)
+        .map_err(|_| io::Error::new(io::ErrorKind::InvalidData, "line is not valid UTF-8"))
+}
+
+impl Decoder for LinesCodec {
+    type Item = String;
+    type Error = io::Error;
+
+    fn decode(&mut self, src: &mut Vec<u8>) -> io::Result<Option<String>> {
+        if self.discarding {
+            let Some(end) = src.iter().position(|&b| b == b'\n') else {
+                src.clear();
+                return Ok(None);
+            };
+
+            src.drain(..=end);
+            self.discarding = false;
+        }
+
+        let Some(offset) = src[self.searched..].iter().position(|&b| b == b'\n') else {
+            self.searched = src.len();
+
+            if self.max_length.is_some_and(|max| src.len() > max) {
+                // The newline ending the line has not arrived yet.
+                src.clear();
+                self.searched = 0;
+                self.discarding = true;
+
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "line exceeds the maximum length",
+                ));
+            }
+
+            return Ok(None);
+        };
+
+        let end = self.searched + offset;
+        self.searched = 0;
+
+        if self.max_length.is_some_and(|max| end > max) {
+            src.drain(..=end);
+
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "line exceeds the maximum length",
+            ));
+        }
+
+        let mut line: Vec<u8> = src.drain(..=end).collect();
+        line.pop();
+
+        into_line(line).map(Some)
+    }
+
+    fn decode_eof(&mut self, src: &mut Vec<u8>) -> io::Result<Option<String>> {
+        if let Some(line) = self.decode(src)? {
+            return Ok(Some(line));
+        }
+
+        if src.is_empty() {
+            return Ok(None);
+        }
+
+        self.searched = 0;
+
+        into_line(std::mem::take(src)).map(Some)
+    }
+}
+
+impl<T: AsRef<str>> Encoder<T> for LinesCodec {
+    type Error = io::Error;
+
+    fn encode(&mut self, line: T, dst: &mut Vec<u8>) -> io::Result<()> {
+        dst.extend_from_slice(line.as_ref().as_bytes());
+        dst.push(b'\n');
+
+        Ok(())
+    }
+}
@@ -0,0 +1,78 @@
+//! Framing of byte streams into messages.
+//!
+//! A codec describes how messages are laid out in a byte stream; a
+//! [`Framed`] wraps any [`AsyncRead`](crate::io::AsyncRead) /
+//! [`AsyncWrite`](crate::io::AsyncWrite) transport with a codec to read
+//! and write whole messages instead of bytes.
+//!
+//! It includes:
+//! - [`Decoder`] and [`Encoder`], the traits implemented by codecs,
+//! - [`Framed`], a [`Stream`](crate::stream::Stream) of decoded frames
+//!   that also sends encoded frames,
+//! - [`LinesCodec`] for newline-delimited text,
+//! - [`LengthDelimitedCodec`] for frames prefixed by their length.
+//!
+//! # Examples
+//!
+//! ```rust,ignore
+//! let stream = TcpStream::connect("127.0.0.1:8080").await?;
+//! let mut framed = Framed::new(stream, LinesCodec::new());
+//!
+//! framed.send("PING").await?;
+//!
+//! while let Some(line) = framed.next().await {
+//!     println!("{}", line?);
+//! }
+//! ```
+
+mod framed;
+mod length_delimited;
+mod lines;
+
+pub use framed::Framed;
+pub use length_delimited::LengthDelimitedCodec;
+pub use lines::LinesCodec;
+
+use std::io;
+
+/// Decodes frames from a buffer of bytes.
+pub trait Decoder {
+    /// The type of decoded frames.
+    type Item;
+
+    /// The error returned on malformed input or transport failures.
+    type Error: From<io::Error>;
+
+    /// Attempts to decode one frame from the front of `src`.
+    ///
+    /// On success, the bytes of the frame must be removed from `src`.
+    /// Returns `Ok(None)` if `src` does not hold a complete frame yet:
+    /// more bytes are read before the next call.
+    fn decode(&mut self, src: &mut Vec<u8>) -> Result<Option<Self::Item>, Self::Error>;
+
+    /// Decodes a frame once the transport reached end of stream.
+    ///
+    /// Called repeatedly until it returns `Ok(None)`. The default
+    /// implementation decodes the remaining complete frames and fails
+    /// with `UnexpectedEof` if a partial frame is left.
+    fn decode_eof(&mut self, src: &mut Vec<u8>) -> Result<Option<Self::Item>, Self::Error> {
+        match self.decode(src)? {
+            Some(frame) => Ok(Some(frame)),
+            None if src.is_empty() => Ok(None),
+            None => Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "bytes remaining in stream after the last frame",
+            )
+            .into()),
+        }
+    }
+}
+
+/// Encodes frames into a buffer of bytes.
+pub trait Encoder<Item> {
+    /// The error returned when a frame cannot be encoded or sent.
+    type Error: From<io::Error>;
+
+    /// Appends the encoding of `item` to `dst`.
+    fn encode(&mut self, item: Item, dst: &mut Vec<u8>) -> Result<(), Self::Error>;
+}
@@ -0,0 +1,178 @@
+//! Interoperability with the `futures` crate.
+//!
+//! This module is available with the `futures` feature. It provides
+//! [`Compat`], a wrapper bridging the Cadentis I/O and stream traits
+//! with their `futures` counterparts, so that libraries written against
+//! `futures` (codecs, stream adapters, protocol implementations) can be
+//! plugged into Cadentis types, and the other way around.
+//!
+//! `Compat<T>` implements:
+//! - the `futures` traits when `T` implements the Cadentis ones
+//!   ([`AsyncRead`], [`AsyncWrite`], [`AsyncBufRead`], [`Stream`]),
+//! - the Cadentis traits when `T` implements the `futures` ones.
+//!
+//! # Examples
+//!
+//! ```rust,ignore
+//! use cadentis::compat::CompatExt;
+//! use futures::AsyncReadExt;
+//!
+//! let mut stream = TcpStream::connect("127.0.0.1:8080").await?.compat();
+//! let mut data = Vec::new();
+//! stream.read_to_end(&mut data).await?;
+//! ```
+
+use crate::io::{AsyncBufRead, AsyncRead, AsyncWrite};
+use crate::stream::Stream;
+
+use std::io;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+/// Adapts a type between the Cadentis and `futures` traits.
+///
+/// Created with [`Compat::new`], [`CompatExt::compat`] or `From`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Compat<T> {
+    inner: T,
+}
+
+impl<T> Compat<T> {
+    /// Wraps `inner`.
+    pub fn new(inner: T) -> Self {
+        Self { inner }
+    }
+
+    /// Returns a reference to the wrapped value.
+    pub fn get_ref(&self) -> &T {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the wrapped value.
+    pub fn get_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+
+    /// Unwraps this `Compat`, returning the wrapped value.
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+
+    /// Projects the pin onto the wrapped value.
+    ///
+    /// # Safety
+    ///
+    /// This projection uses `unsafe` but is sound because the wrapped
+    /// value is never moved out of a pinned `Compat`.
+    fn project(self: Pin<&mut Self>) -> Pin<&mut T> {
+        unsafe { self.map_unchecked_mut(|this| &mut this.inner) }
+    }
+}
+
+impl<T> From<T> for Compat<T> {
+    fn from(inner: T) -> Self {
+        Self::new(inner)
+    }
+}
+
+/// Extension trait wrapping any value in a [`Compat`].
+pub trait CompatExt: Sized {
+    /// Wraps `self` in a [`Compat`].
+    fn compat(self) -> Compat<Self> {
+        Compat::new(self)
+    }
+}
+
+impl<T> CompatExt for T {}
+
+impl<T: AsyncRead> futures_io::AsyncRead for Compat<T> {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buffer: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        AsyncRead::poll_read(self.project(), cx, buffer)
+    }
+}
+
+impl<T: AsyncWrite> futures_io::AsyncWrite for Compat<T> {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buffer: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        AsyncWrite::poll_write(self.project(), cx, buffer)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        AsyncWrite::poll_flush(self.project(), cx)
+    }
+
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        AsyncWrite::poll_shutdown(self.project(), cx)
+    }
+}
+
+impl<T: AsyncBufRead> futures_io::AsyncBufRead for Compat<T> {
+    fn poll_fill_buf(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<&[u8]>> {
+        AsyncBufRead::poll_fill_buf(self.project(), cx)
+    }
+
+    fn consume(self: Pin<&mut Self>, amount: usize) {
+        AsyncBufRead::consume(self.project(), amount)
+    }
+}
+
+impl<T: Stream> futures_core::Stream for Compat<T> {
+    type Item = T::Item;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<T::Item>> {
+        Stream::poll_next(self.project(), cx)
+    }
+}
+
+impl<T: futures_io::AsyncRead> AsyncRead for Compat<T> {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buffer: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        futures_io::AsyncRead::poll_read(self.project(), cx, buffer)
+    }
+}
+
+impl<T: futures_io::AsyncWrite> AsyncWrite for Compat<T> {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buffer: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        futures_io::AsyncWrite::poll_write(self.project(), cx, buffer)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        futures_io::AsyncWrite::poll_flush(self.project(), cx)
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        futures_io::AsyncWrite::poll_close(self.project(), cx)
+    }
+}
+
+impl<T: futures_io::AsyncBufRead> AsyncBufRead for Compat<T> {
+    fn poll_fill_buf(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<&[u8]>> {
+        futures_io::AsyncBufRead::poll_fill_buf(self.project(), cx)
+    }
+
+    fn consume(self: Pin<&mut Self>, amount: usize) {
+        futures_io::AsyncBufRead::consume(self.project(), amount)
+    }
+}
+
+impl<T: futures_core::Stream> Stream for Compat<T> {
+    type Item = T::Item;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<T::Item>> {
+        futures_core::Stream::poll_next(self.project(), cx)
+    }
+}
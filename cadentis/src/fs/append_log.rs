@@ -0,0 +1,93 @@
+use super::File;
+use crate::sync::Mutex;
+
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// An append-only log file.
+///
+/// `AppendLog` packages the usual pattern for append-only logs: the file
+/// is opened in append mode (`O_APPEND`), so every write lands at the
+/// current end of the file, even when several handles or processes
+/// append to it concurrently.
+///
+/// Each record written with [`write_line`](Self::write_line) is issued as
+/// a single write. Writes from tasks sharing the same `AppendLog` are
+/// serialized, so records never interleave; appenders using separate
+/// handles rely on the OS, which appends each write atomically on local
+/// filesystems.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// let log = AppendLog::open("events.log").await?;
+///
+/// log.write_line(b"service started").await?;
+/// log.flush().await?;
+/// ```
+pub struct AppendLog {
+    /// The underlying file, opened in append mode.
+    file: File,
+
+    /// Serializes the records written through this handle.
+    lock: Mutex<()>,
+}
+
+impl AppendLog {
+    /// Opens the log at `path` for appending, creating it if absent.
+    ///
+    /// Existing content is preserved.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be opened or created.
+    pub async fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        let file = fs::OpenOptions::new()
+            .append(true)
+            .create(true)
+            .open(path)?;
+
+        Ok(Self {
+            file: File::from_std(file),
+            lock: Mutex::new(()),
+        })
+    }
+
+    /// Appends `line` to the log as a single record.
+    ///
+    /// A newline is added unless `line` already ends with one. The whole
+    /// record is handed to the OS in a single write, so it is not
+    /// interleaved with the records of other appenders.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error reported while writing.
+    pub async fn write_line(&self, line: &[u8]) -> io::Result<()> {
+        let mut record = Vec::with_capacity(line.len() + 1);
+        record.extend_from_slice(line);
+
+        if !record.ends_with(b"\n") {
+            record.push(b'\n');
+        }
+
+        let _guard = self.lock.lock().await;
+
+        self.file.write_all(&record).await
+    }
+
+    /// Flushes the appended records to the storage device.
+    ///
+    /// Records are handed to the OS as soon as `write_line` completes;
+    /// `flush` additionally waits until they are durable, so they survive
+    /// a crash of the machine.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error reported by the OS while syncing the file.
+    pub async fn flush(&self) -> io::Result<()> {
+        let _guard = self.lock.lock().await;
+
+        self.file.sync_data()
+    }
+}
@@ -0,0 +1,148 @@
+//! Filesystem operations run on the blocking pool.
+//!
+//! The native types of [`fs`](crate::fs) cover the common paths: opening,
+//! reading and writing files, listing directories. The long tail of
+//! `std::fs` (metadata, permissions, links, copies...) is offered here
+//! instead: each function runs its `std::fs` counterpart on the runtime
+//! blocking pool and resolves with its result, so the behavior is that of
+//! the standard library on every platform and worker threads never wait
+//! on the disk.
+//!
+//! [`run`] offloads any other blocking operation the same way.
+//!
+//! # Examples
+//!
+//! ```rust,ignore
+//! let mut permissions = fs::blocking::metadata("deploy.sh").await?.permissions();
+//! permissions.set_readonly(true);
+//! fs::blocking::set_permissions("deploy.sh", permissions).await?;
+//! ```
+
+use crate::runtime::blocking::spawn_blocking;
+
+use std::fs::{self, Metadata, Permissions};
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Runs `f` on the blocking pool and resolves with its result.
+///
+/// Use it for filesystem operations this module does not wrap, or to
+/// batch several of them in a single round trip to the pool.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// let total = fs::blocking::run(move || {
+///     let mut total = 0;
+///     for entry in std::fs::read_dir(&root)? {
+///         total += entry?.metadata()?.len();
+///     }
+///     io::Result::Ok(total)
+/// })
+/// .await?;
+/// ```
+pub async fn run<F, T>(f: F) -> T
+where
+    F: FnOnce() -> T + Send + 'static,
+    T: Send + 'static,
+{
+    spawn_blocking(f).await
+}
+
+/// Queries the metadata of `path`, following symbolic links.
+///
+/// See [`std::fs::metadata`].
+pub async fn metadata(path: impl AsRef<Path>) -> io::Result<Metadata> {
+    let path = path.as_ref().to_owned();
+    run(move || fs::metadata(path)).await
+}
+
+/// Queries the metadata of `path`, without following symbolic links.
+///
+/// See [`std::fs::symlink_metadata`].
+pub async fn symlink_metadata(path: impl AsRef<Path>) -> io::Result<Metadata> {
+    let path = path.as_ref().to_owned();
+    run(move || fs::symlink_metadata(path)).await
+}
+
+/// Changes the permissions of `path`.
+///
+/// See [`std::fs::set_permissions`].
+pub async fn set_permissions(path: impl AsRef<Path>, permissions: Permissions) -> io::Result<()> {
+    let path = path.as_ref().to_owned();
+    run(move || fs::set_permissions(path, permissions)).await
+}
+
+/// Returns the canonical, absolute form of `path`.
+///
+/// See [`std::fs::canonicalize`].
+pub async fn canonicalize(path: impl AsRef<Path>) -> io::Result<PathBuf> {
+    let path = path.as_ref().to_owned();
+    run(move || fs::canonicalize(path)).await
+}
+
+/// Reads the target of the symbolic link at `path`.
+///
+/// See [`std::fs::read_link`].
+pub async fn read_link(path: impl AsRef<Path>) -> io::Result<PathBuf> {
+    let path = path.as_ref().to_owned();
+    run(move || fs::read_link(path)).await
+}
+
+/// Creates a symbolic link at `link` pointing to `original`.
+///
+/// See [`std::os::unix::fs::symlink`].
+#[cfg(unix)]
+pub async fn symlink(original: impl AsRef<Path>, link: impl AsRef<Path>) -> io::Result<()> {
+    let (original, link) = (original.as_ref().to_owned(), link.as_ref().to_owned());
+    run(move || std::os::unix::fs::symlink(original, link)).await
+}
+
+/// Creates a hard link at `link` pointing to `original`.
+///
+/// See [`std::fs::hard_link`].
+pub async fn hard_link(original: impl AsRef<Path>, link: impl AsRef<Path>) -> io::Result<()> {
+    let (original, link) = (original.as_ref().to_owned(), link.as_ref().to_owned());
+    run(move || fs::hard_link(original, link)).await
+}
+
+/// Copies the contents and permissions of `from` to `to`, returning the
+/// number of bytes copied.
+///
+/// See [`std::fs::copy`].
+pub async fn copy(from: impl AsRef<Path>, to: impl AsRef<Path>) -> io::Result<u64> {
+    let (from, to) = (from.as_ref().to_owned(), to.as_ref().to_owned());
+    run(move || fs::copy(from, to)).await
+}
+
+/// Renames `from` to `to`, replacing `to` if it exists.
+///
+/// See [`std::fs::rename`].
+pub async fn rename(from: impl AsRef<Path>, to: impl AsRef<Path>) -> io::Result<()> {
+    let (from, to) = (from.as_ref().to_owned(), to.as_ref().to_owned());
+    run(move || fs::rename(from, to)).await
+}
+
+/// Removes the file at `path`.
+///
+/// See [`std::fs::remove_file`].
+pub async fn remove_file(path: impl AsRef<Path>) -> io::Result<()> {
+    let path = path.as_ref().to_owned();
+    run(move || fs::remove_file(path)).await
+}
+
+/// Removes the empty directory at `path`.
+///
+/// See [`std::fs::remove_dir`].
+pub async fn remove_dir(path: impl AsRef<Path>) -> io::Result<()> {
+    let path = path.as_ref().to_owned();
+    run(move || fs::remove_dir(path)).await
+}
+
+/// Removes the directory at `path` and everything it contains.
+///
+/// See [`std::fs::remove_dir_all`].
+pub async fn remove_dir_all(path: impl AsRef<Path>) -> io::Result<()> {
+    let path = path.as_ref().to_owned();
+    run(move || fs::remove_dir_all(path)).await
+}
@@ -1,10 +1,16 @@
+use crate::io::AsyncWrite;
 use crate::reactor::future::{ReadFuture, WriteFuture};
 
 use nucleus::fs::sys_open;
 use nucleus::fs::{CREATEFLAGS, OPENFLAGS};
 use nucleus::io::{RawFd, sys_close};
 use std::ffi::CString;
-use std::io;
+use std::fs;
+use std::io::{self, Write};
+use std::mem::ManuallyDrop;
+use std::path::Path;
+use std::pin::Pin;
+use std::task::{Context, Poll};
 
 /// An asynchronous file handle.
 ///
@@ -13,9 +19,21 @@ use std::io;
 ///
 /// All read and write operations return futures that complete when
 /// the underlying file descriptor becomes ready.
+///
+/// Dropping a `File` closes it without flushing it to the storage
+/// device, and any error is ignored. Writers that need durability or
+/// want to see such errors should call [`close`](Self::close) instead.
+///
+/// Through [`AsyncWrite`], writes go straight to the operating system,
+/// so flushing has nothing left to hand over; with
+/// [`set_sync_on_flush`](Self::set_sync_on_flush), a flush also syncs
+/// the file to the storage device.
 pub struct File {
     /// File descriptor associated with this file.
     fd: RawFd,
+
+    /// Whether [`AsyncWrite::poll_flush`] syncs the file to the device.
+    sync_on_flush: bool,
 }
 
 impl File {
@@ -23,26 +41,59 @@ impl File {
     ///
     /// The file is opened with non-blocking flags and integrated
     /// with the runtime reactor.
+    ///
+    /// # Errors
+    ///
+    /// Returns the OS error reported by the open call, such as
+    /// `NotFound` if the file does not exist or `PermissionDenied`, and
+    /// `InvalidInput` if `path` contains a NUL byte. A `File` is never
+    /// returned for a file that failed to open.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// let file = File::open("config.toml").await?;
+    /// ```
     pub async fn open(path: &str) -> io::Result<Self> {
         let c_path = CString::new(path)?;
         let fd = Self::open_with_flags(c_path, OPENFLAGS)?;
 
-        Ok(Self { fd })
+        Ok(Self::from_fd(fd))
     }
 
     /// Creates a file for writing, truncating it if it already exists.
     ///
     /// The file is opened with non-blocking flags and integrated
     /// with the runtime reactor.
+    ///
+    /// # Errors
+    ///
+    /// Returns the OS error reported by the open call, such as
+    /// `NotFound` if the parent directory does not exist, and
+    /// `InvalidInput` if `path` contains a NUL byte.
     pub async fn create(path: &str) -> io::Result<Self> {
         let c_path = CString::new(path)?;
         let fd = Self::open_with_flags(c_path, CREATEFLAGS)?;
 
-        Ok(Self { fd })
+        Ok(Self::from_fd(fd))
+    }
+
+    /// Wraps an open file descriptor.
+    fn from_fd(fd: RawFd) -> Self {
+        Self {
+            fd,
+            sync_on_flush: false,
+        }
     }
 
     /// Opens a file using the provided raw flags.
+    ///
+    /// The failure sentinel of the platform (`u64::MAX` on Windows, a
+    /// negative descriptor on Unix) is turned into the OS error it stands
+    /// for, so no invalid descriptor ever leaves this function.
     fn open_with_flags(c_path: CString, flags: RawFd) -> io::Result<RawFd> {
+        // SAFETY: `c_path` is a valid NUL-terminated string that outlives
+        // the call.
         let fd = unsafe { sys_open(c_path.as_ptr(), flags, 0o644) };
 
         #[cfg(windows)]
@@ -73,6 +124,159 @@ impl File {
         WriteFuture::new(self.fd, buffer)
     }
 
+    /// Sets whether flushing the file through [`AsyncWrite`] also syncs
+    /// its content to the storage device.
+    ///
+    /// Off by default: a flush then only guarantees that the written
+    /// bytes were handed to the operating system, which they already are
+    /// once each write completes. Turning it on makes every flush as
+    /// durable, and as slow, as [`close`](Self::close).
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// let mut journal = File::create("journal.log").await?;
+    /// journal.set_sync_on_flush(true);
+    ///
+    /// let mut writer = BufWriter::new(journal);
+    /// writer.write_all(&entry).await?;
+    /// writer.flush().await?; // on disk from here on
+    /// ```
+    pub fn set_sync_on_flush(&mut self, sync: bool) {
+        self.sync_on_flush = sync;
+    }
+
+    /// Truncates or extends the file to exactly `size` bytes.
+    ///
+    /// Shrinking the file discards the bytes past `size`; growing it
+    /// fills the new space with zeros. The file position is unchanged.
+    /// This maps to `ftruncate` on Unix and `SetEndOfFile` on Windows.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file was not opened for writing.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// let file = File::create("data.bin").await?;
+    /// file.set_len(1024 * 1024)?; // preallocate 1 MiB
+    /// ```
+    pub fn set_len(&self, size: u64) -> io::Result<()> {
+        self.with_std(|file| file.set_len(size))
+    }
+
+    /// Flushes the file to the storage device, then closes it.
+    ///
+    /// Unlike dropping the file, this reports the errors the flush runs
+    /// into, such as a full disk or a failed write-back of data accepted
+    /// by earlier writes.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error reported by the flush, or else by closing the
+    /// file. The file is closed in every case.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// let file = File::create("report.csv").await?;
+    /// file.write_all(&rows).await?;
+    /// file.close().await?;
+    /// ```
+    pub async fn close(self) -> io::Result<()> {
+        let synced = self.sync_data();
+
+        // Closed here rather than by `Drop`, which ignores the error.
+        let fd = ManuallyDrop::new(self).fd;
+
+        #[cfg(unix)]
+        let closed = if sys_close(fd) < 0 {
+            Err(io::Error::last_os_error())
+        } else {
+            Ok(())
+        };
+
+        // SAFETY: `fd` is the handle this file owned, closed only here.
+        #[cfg(windows)]
+        let closed = if unsafe { crate::sys::CloseHandle(fd as usize as *mut _) } == 0 {
+            Err(io::Error::last_os_error())
+        } else {
+            Ok(())
+        };
+
+        synced.and(closed)
+    }
+
+    /// Takes ownership of the descriptor of a `std::fs::File`.
+    pub(super) fn from_std(file: fs::File) -> Self {
+        #[cfg(unix)]
+        let fd = {
+            use std::os::fd::IntoRawFd;
+            file.into_raw_fd()
+        };
+
+        #[cfg(windows)]
+        let fd = {
+            use std::os::windows::io::IntoRawHandle;
+            file.into_raw_handle() as usize as RawFd
+        };
+
+        Self::from_fd(fd)
+    }
+
+    /// Flushes the file content to the storage device.
+    ///
+    /// Maps to `fdatasync` on Unix and `FlushFileBuffers` on Windows.
+    pub(super) fn sync_data(&self) -> io::Result<()> {
+        self.with_std(|file| file.sync_data())
+    }
+
+    /// Returns the file descriptor of the file.
+    pub(crate) fn fd(&self) -> RawFd {
+        self.fd
+    }
+
+    /// Reads up to `buffer.len()` bytes starting at `offset`, without
+    /// moving the file position.
+    ///
+    /// Maps to `pread` on Unix and `ReadFile` with an offset on Windows.
+    pub(crate) fn read_at(&self, buffer: &mut [u8], offset: u64) -> io::Result<usize> {
+        self.with_std(|file| {
+            #[cfg(unix)]
+            {
+                use std::os::unix::fs::FileExt;
+                file.read_at(buffer, offset)
+            }
+
+            #[cfg(windows)]
+            {
+                use std::os::windows::fs::FileExt;
+                file.seek_read(buffer, offset)
+            }
+        })
+    }
+
+    /// Runs `f` on a `std::fs::File` borrowing this file descriptor.
+    ///
+    /// The borrowed handle is never dropped, so the descriptor stays
+    /// owned by `self`.
+    fn with_std<R>(&self, f: impl FnOnce(&fs::File) -> R) -> R {
+        #[cfg(unix)]
+        let file = {
+            use std::os::fd::FromRawFd;
+            ManuallyDrop::new(unsafe { fs::File::from_raw_fd(self.fd) })
+        };
+
+        #[cfg(windows)]
+        let file = {
+            use std::os::windows::io::{FromRawHandle, RawHandle};
+            ManuallyDrop::new(unsafe { fs::File::from_raw_handle(self.fd as usize as RawHandle) })
+        };
+
+        f(&file)
+    }
+
     /// Writes the entire buffer to the file.
     ///
     /// This method repeatedly calls [`write`](Self::write) until the
@@ -99,9 +303,57 @@ impl File {
     }
 }
 
+impl AsyncWrite for File {
+    /// Writes `buffer` at the current file position.
+    ///
+    /// Regular files are always ready for writing: the data is handed to
+    /// the operating system before this returns.
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buffer: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        match self.with_std(|mut file| file.write(buffer)) {
+            Err(e) if e.kind() == io::ErrorKind::WouldBlock => {
+                cx.waker().wake_by_ref();
+                Poll::Pending
+            }
+            result => Poll::Ready(result),
+        }
+    }
+
+    /// Resolves immediately, after syncing the file to the storage
+    /// device if [`set_sync_on_flush`](File::set_sync_on_flush) is on.
+    fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        if self.sync_on_flush {
+            return Poll::Ready(self.sync_data());
+        }
+
+        Poll::Ready(Ok(()))
+    }
+
+    /// Flushes the file; files have no write side to shut down.
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.poll_flush(cx)
+    }
+}
+
 impl Drop for File {
-    /// Closes the file descriptor.
+    /// Closes the file descriptor, ignoring errors.
     fn drop(&mut self) {
         sys_close(self.fd);
     }
 }
+
+/// Truncates or extends the file at `path` to exactly `size` bytes.
+///
+/// This is the path-based counterpart of [`File::set_len`]: the file
+/// must exist, and new space is filled with zeros.
+///
+/// # Errors
+///
+/// Returns an error if the file does not exist or cannot be opened for
+/// writing.
+pub async fn truncate(path: impl AsRef<Path>, size: u64) -> io::Result<()> {
+    fs::OpenOptions::new().write(true).open(path)?.set_len(size)
+}
@@ -5,13 +5,26 @@
 //!
 //! It exposes high-level types for:
 //! - working with directories ([`Dir`]),
-//! - reading from and writing to files ([`File`]).
+//! - reading from and writing to files ([`File`]),
+//! - appending records to log files ([`AppendLog`]),
+//! - listing directories ([`read_dir`]) and walking directory trees
+//!   ([`walk_dir`]) as [`Stream`](crate::stream::Stream)s,
+//! - the less common `std::fs` operations, run on the blocking pool
+//!   ([`blocking`]).
 //!
 //! These types integrate with the runtime and avoid blocking
 //! the executor threads.
 
+pub mod blocking;
+
+mod append_log;
 mod dir;
 mod file;
+mod read_dir;
+mod walk_dir;
 
+pub use append_log::AppendLog;
 pub use dir::Dir;
-pub use file::File;
+pub use file::{File, truncate};
+pub use read_dir::{DirEntry, ReadDir, read_dir};
+pub use walk_dir::{WalkDir, walk_dir};
@@ -0,0 +1,104 @@
+use crate::stream::Stream;
+
+use std::ffi::OsString;
+use std::fs::{self, FileType};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+/// Returns a stream over the entries of a directory.
+///
+/// This is the async equivalent of `std::fs::read_dir`. The returned
+/// [`ReadDir`] yields one entry per poll, so listing a large directory
+/// never holds a worker for long. The `.` and `..` entries are skipped.
+///
+/// # Errors
+///
+/// Returns an error if `path` does not exist, is not a directory, or
+/// cannot be read.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// let mut entries = fs::read_dir("assets").await?;
+///
+/// while let Some(entry) = entries.next().await {
+///     println!("{}", entry?.path().display());
+/// }
+/// ```
+pub async fn read_dir(path: impl AsRef<Path>) -> io::Result<ReadDir> {
+    ReadDir::open(path.as_ref())
+}
+
+/// Stream of the entries of a directory, created by [`read_dir`].
+pub struct ReadDir {
+    /// Underlying directory iterator.
+    inner: fs::ReadDir,
+}
+
+impl ReadDir {
+    /// Opens the directory at `path`.
+    pub(crate) fn open(path: &Path) -> io::Result<Self> {
+        Ok(Self {
+            inner: fs::read_dir(path)?,
+        })
+    }
+}
+
+impl Stream for ReadDir {
+    type Item = io::Result<DirEntry>;
+
+    /// Yields the next directory entry.
+    fn poll_next(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        let entry = match this.inner.next() {
+            Some(Ok(entry)) => entry,
+            Some(Err(e)) => return Poll::Ready(Some(Err(e))),
+            None => return Poll::Ready(None),
+        };
+
+        Poll::Ready(Some(entry.file_type().map(|file_type| DirEntry {
+            path: entry.path(),
+            file_type,
+        })))
+    }
+}
+
+/// An entry returned by [`ReadDir`] or [`WalkDir`](super::WalkDir).
+#[derive(Debug, Clone)]
+pub struct DirEntry {
+    /// Full path of the entry.
+    pub(crate) path: PathBuf,
+
+    /// Type of the entry itself (symbolic links are not followed).
+    pub(crate) file_type: FileType,
+}
+
+impl DirEntry {
+    /// Returns the full path of the entry.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns the final component of the entry path.
+    pub fn file_name(&self) -> OsString {
+        self.path
+            .file_name()
+            .map(|name| name.to_os_string())
+            .unwrap_or_default()
+    }
+
+    /// Returns the type of the entry.
+    ///
+    /// Symbolic links are reported as such and are not followed.
+    pub fn file_type(&self) -> FileType {
+        self.file_type
+    }
+
+    /// Consumes the entry, returning its path.
+    pub fn into_path(self) -> PathBuf {
+        self.path
+    }
+}
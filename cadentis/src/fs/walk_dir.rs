@@ -0,0 +1,161 @@
+use super::read_dir::{DirEntry, ReadDir};
+use crate::stream::Stream;
+
+use std::collections::HashSet;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+/// Returns a stream recursively walking the directory tree under `root`.
+///
+/// Every file, directory, and symbolic link below `root` is yielded
+/// exactly once, parents before their children. `root` itself is not
+/// yielded. The walk is configured with [`WalkDir::max_depth`] and
+/// [`WalkDir::follow_symlinks`].
+///
+/// The tree is read lazily with [`read_dir`](super::read_dir), one entry
+/// per poll, so walking a large tree never holds a worker for long.
+/// Errors (unreadable directory, entry vanishing during the walk) are
+/// yielded in place and the walk continues with the next entry.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// let mut walk = fs::walk_dir("src").max_depth(3);
+///
+/// while let Some(entry) = walk.next().await {
+///     let entry = entry?;
+///
+///     if entry.file_type().is_file() {
+///         println!("{}", entry.path().display());
+///     }
+/// }
+/// ```
+pub fn walk_dir(root: impl AsRef<Path>) -> WalkDir {
+    WalkDir {
+        root: Some(root.as_ref().to_path_buf()),
+        max_depth: usize::MAX,
+        follow_symlinks: false,
+        stack: Vec::new(),
+        visited: HashSet::new(),
+        error: None,
+    }
+}
+
+/// Stream of the entries of a directory tree, created by [`walk_dir`].
+pub struct WalkDir {
+    /// Root of the walk, taken when the walk starts.
+    root: Option<PathBuf>,
+
+    /// Maximum depth of the yielded entries (children of root are depth 1).
+    max_depth: usize,
+
+    /// Whether symbolic links to directories are descended into.
+    follow_symlinks: bool,
+
+    /// Directories being read, with the depth of their entries.
+    stack: Vec<(ReadDir, usize)>,
+
+    /// Canonical paths of the directories already descended into.
+    ///
+    /// Only tracked when following symbolic links, which is the only way
+    /// a walk can loop.
+    visited: HashSet<PathBuf>,
+
+    /// Error raised while opening a directory, yielded after its entry.
+    error: Option<io::Error>,
+}
+
+impl WalkDir {
+    /// Limits the walk to entries at most `depth` levels below the root.
+    ///
+    /// A depth of `1` only yields the direct children of the root; `0`
+    /// yields nothing. The default is unlimited.
+    pub fn max_depth(mut self, depth: usize) -> Self {
+        self.max_depth = depth;
+        self
+    }
+
+    /// Sets whether symbolic links to directories are descended into.
+    ///
+    /// When enabled, directories already visited through another path
+    /// are yielded but not descended into again, which guards against
+    /// symlink cycles. Disabled by default.
+    pub fn follow_symlinks(mut self, follow: bool) -> Self {
+        self.follow_symlinks = follow;
+        self
+    }
+
+    /// Starts reading the directory at `path`, whose entries are at `depth`.
+    ///
+    /// Returns `Ok(false)` if the directory was already visited.
+    fn descend(&mut self, path: &Path, depth: usize) -> io::Result<bool> {
+        if self.follow_symlinks && !self.visited.insert(fs::canonicalize(path)?) {
+            return Ok(false);
+        }
+
+        self.stack.push((ReadDir::open(path)?, depth));
+
+        Ok(true)
+    }
+
+    /// Returns `true` if the walk should descend into `entry`.
+    fn is_dir(&self, entry: &DirEntry) -> bool {
+        let file_type = entry.file_type();
+
+        if file_type.is_dir() {
+            return true;
+        }
+
+        self.follow_symlinks
+            && file_type.is_symlink()
+            && fs::metadata(entry.path()).is_ok_and(|meta| meta.is_dir())
+    }
+}
+
+impl Stream for WalkDir {
+    type Item = io::Result<DirEntry>;
+
+    /// Yields the next entry of the tree, depth-first.
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        if let Some(root) = this.root.take()
+            && this.max_depth > 0
+            && let Err(e) = this.descend(&root, 1)
+        {
+            return Poll::Ready(Some(Err(e)));
+        }
+
+        if let Some(e) = this.error.take() {
+            return Poll::Ready(Some(Err(e)));
+        }
+
+        loop {
+            let Some((dir, depth)) = this.stack.last_mut() else {
+                return Poll::Ready(None);
+            };
+
+            let depth = *depth;
+
+            let entry = match Pin::new(dir).poll_next(cx) {
+                Poll::Ready(Some(Ok(entry))) => entry,
+                Poll::Ready(Some(Err(e))) => return Poll::Ready(Some(Err(e))),
+                Poll::Ready(None) => {
+                    this.stack.pop();
+                    continue;
+                }
+                Poll::Pending => return Poll::Pending,
+            };
+
+            if depth < this.max_depth && this.is_dir(&entry) {
+                // Yield the directory itself first; the error follows it.
+                this.error = this.descend(entry.path(), depth + 1).err();
+            }
+
+            return Poll::Ready(Some(Ok(entry)));
+        }
+    }
+}
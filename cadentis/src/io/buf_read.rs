@@ -0,0 +1,159 @@
+use super::AsyncRead;
+use crate::stream::Stream;
+
+use std::io;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+/// Reads bytes from a buffered source asynchronously.
+///
+/// This is the asynchronous equivalent of [`std::io::BufRead`]: the
+/// source exposes its internal buffer through
+/// [`poll_fill_buf`](Self::poll_fill_buf), and the caller reports how
+/// many bytes it used with [`consume`](Self::consume). This lets parsers
+/// look for delimiters without copying every byte through an extra
+/// buffer.
+pub trait AsyncBufRead: AsyncRead {
+    /// Attempts to return the buffered bytes, refilling the buffer from
+    /// the underlying source when it is empty.
+    ///
+    /// An empty slice means end of stream.
+    fn poll_fill_buf(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<&[u8]>>;
+
+    /// Marks `amount` bytes of the buffer as consumed.
+    ///
+    /// `amount` must not exceed the length of the slice returned by the
+    /// last call to [`poll_fill_buf`](Self::poll_fill_buf).
+    fn consume(self: Pin<&mut Self>, amount: usize);
+}
+
+impl<R: AsyncBufRead + Unpin + ?Sized> AsyncBufRead for &mut R {
+    fn poll_fill_buf(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<&[u8]>> {
+        Pin::new(&mut **self.get_mut()).poll_fill_buf(cx)
+    }
+
+    fn consume(mut self: Pin<&mut Self>, amount: usize) {
+        Pin::new(&mut **self).consume(amount)
+    }
+}
+
+impl<R: AsyncBufRead + Unpin + ?Sized> AsyncBufRead for Box<R> {
+    fn poll_fill_buf(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<&[u8]>> {
+        Pin::new(&mut **self.get_mut()).poll_fill_buf(cx)
+    }
+
+    fn consume(mut self: Pin<&mut Self>, amount: usize) {
+        Pin::new(&mut **self).consume(amount)
+    }
+}
+
+/// Extension trait providing helpers for [`AsyncBufRead`] types.
+///
+/// This trait is implemented for every type implementing [`AsyncBufRead`].
+pub trait AsyncBufReadExt: AsyncBufRead {
+    /// Returns a stream over the lines of this reader.
+    ///
+    /// Each line is yielded without its trailing `\n` or `\r\n`. A last
+    /// line missing its newline is still yielded before the end of the
+    /// stream.
+    ///
+    /// # Errors
+    ///
+    /// Yields `InvalidData` for a line that is not valid UTF-8, and any
+    /// error reported by the underlying reader.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// let mut lines = BufReader::new(stream).lines();
+    ///
+    /// while let Some(line) = lines.next().await {
+    ///     handle(line?);
+    /// }
+    /// ```
+    fn lines(self) -> Lines<Self>
+    where
+        Self: Sized,
+    {
+        Lines {
+            reader: self,
+            line: Vec::new(),
+        }
+    }
+}
+
+impl<R: AsyncBufRead + ?Sized> AsyncBufReadExt for R {}
+
+/// Stream returned by [`AsyncBufReadExt::lines`].
+pub struct Lines<R> {
+    reader: R,
+
+    /// Bytes of the current line read so far, across buffer refills.
+    line: Vec<u8>,
+}
+
+impl<R> Lines<R> {
+    /// Consumes the stream, returning the underlying reader.
+    ///
+    /// The bytes of a partially read line are discarded.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+}
+
+impl<R: AsyncBufRead> Stream for Lines<R> {
+    type Item = io::Result<String>;
+
+    /// Accumulates buffered bytes until a newline or the end of stream.
+    ///
+    /// # Safety
+    ///
+    /// This implementation uses `unsafe` pin projections but is sound
+    /// because the inner reader is never moved after being pinned.
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = unsafe { self.get_unchecked_mut() };
+        let mut reader = unsafe { Pin::new_unchecked(&mut this.reader) };
+
+        loop {
+            let (found, used) = match reader.as_mut().poll_fill_buf(cx) {
+                Poll::Ready(Ok([])) => {
+                    if this.line.is_empty() {
+                        return Poll::Ready(None);
+                    }
+
+                    // Last line without a trailing newline.
+                    return Poll::Ready(Some(take_line(&mut this.line)));
+                }
+                Poll::Ready(Ok(available)) => match available.iter().position(|&b| b == b'\n') {
+                    Some(i) => {
+                        this.line.extend_from_slice(&available[..i]);
+                        (true, i + 1)
+                    }
+                    None => {
+                        this.line.extend_from_slice(available);
+                        (false, available.len())
+                    }
+                },
+                Poll::Ready(Err(e)) => return Poll::Ready(Some(Err(e))),
+                Poll::Pending => return Poll::Pending,
+            };
+
+            reader.as_mut().consume(used);
+
+            if found {
+                return Poll::Ready(Some(take_line(&mut this.line)));
+            }
+        }
+    }
+}
+
+/// Takes the accumulated line, stripping a trailing `\r`.
+fn take_line(line: &mut Vec<u8>) -> io::Result<String> {
+    let mut bytes = std::mem::take(line);
+
+    if bytes.last() == Some(&b'\r') {
+        bytes.pop();
+    }
+
+    String::from_utf8(bytes).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
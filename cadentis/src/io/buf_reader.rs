@@ -0,0 +1,147 @@
+use super::{AsyncBufRead, AsyncRead};
+
+use std::io;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+/// Default capacity of the internal buffer, in bytes.
+const DEFAULT_CAPACITY: usize = 8 * 1024;
+
+/// Adds buffering to an [`AsyncRead`] source.
+///
+/// `BufReader` reads large chunks from the underlying reader into an
+/// internal buffer and serves small reads from it, reducing the number
+/// of calls made to the underlying reader. This is the asynchronous
+/// equivalent of [`std::io::BufReader`].
+pub struct BufReader<R> {
+    /// The underlying reader.
+    inner: R,
+
+    /// Internal buffer.
+    buffer: Box<[u8]>,
+
+    /// Position of the next unread byte in `buffer`.
+    pos: usize,
+
+    /// Number of valid bytes in `buffer`.
+    filled: usize,
+}
+
+impl<R> BufReader<R> {
+    /// Creates a new `BufReader` with a default buffer capacity (8 KiB).
+    pub fn new(inner: R) -> Self {
+        Self::with_capacity(DEFAULT_CAPACITY, inner)
+    }
+
+    /// Creates a new `BufReader` with the given buffer capacity.
+    pub fn with_capacity(capacity: usize, inner: R) -> Self {
+        Self {
+            inner,
+            buffer: vec![0; capacity].into_boxed_slice(),
+            pos: 0,
+            filled: 0,
+        }
+    }
+
+    /// Returns a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the underlying reader.
+    ///
+    /// Reading directly from the underlying reader skips the bytes that
+    /// are currently buffered.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Returns the bytes currently buffered and not yet read.
+    pub fn buffer(&self) -> &[u8] {
+        &self.buffer[self.pos..self.filled]
+    }
+
+    /// Consumes the `BufReader`, returning the underlying reader.
+    ///
+    /// Any buffered bytes that were not read are discarded.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: AsyncRead> AsyncRead for BufReader<R> {
+    /// Serves the read from the internal buffer, refilling it from the
+    /// underlying reader when it is empty.
+    ///
+    /// Reads at least as large as the buffer capacity bypass the buffer
+    /// when it is empty.
+    ///
+    /// # Safety
+    ///
+    /// This implementation uses `unsafe` pin projections but is sound
+    /// because the inner reader is never moved after being pinned.
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buffer: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = unsafe { self.get_unchecked_mut() };
+        let inner = unsafe { Pin::new_unchecked(&mut this.inner) };
+
+        if this.pos == this.filled {
+            if buffer.len() >= this.buffer.len() {
+                return inner.poll_read(cx, buffer);
+            }
+
+            match inner.poll_read(cx, &mut this.buffer) {
+                Poll::Ready(Ok(n)) => {
+                    this.pos = 0;
+                    this.filled = n;
+                }
+                Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+
+        let n = std::cmp::min(buffer.len(), this.filled - this.pos);
+
+        buffer[..n].copy_from_slice(&this.buffer[this.pos..this.pos + n]);
+        this.pos += n;
+
+        Poll::Ready(Ok(n))
+    }
+}
+
+impl<R: AsyncRead> AsyncBufRead for BufReader<R> {
+    /// Returns the unread buffered bytes, refilling the buffer from the
+    /// underlying reader once it is fully consumed.
+    ///
+    /// # Safety
+    ///
+    /// This implementation uses `unsafe` pin projections but is sound
+    /// because the inner reader is never moved after being pinned.
+    fn poll_fill_buf(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<&[u8]>> {
+        let this = unsafe { self.get_unchecked_mut() };
+
+        if this.pos == this.filled {
+            let inner = unsafe { Pin::new_unchecked(&mut this.inner) };
+
+            match inner.poll_read(cx, &mut this.buffer) {
+                Poll::Ready(Ok(n)) => {
+                    this.pos = 0;
+                    this.filled = n;
+                }
+                Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+
+        Poll::Ready(Ok(&this.buffer[this.pos..this.filled]))
+    }
+
+    fn consume(self: Pin<&mut Self>, amount: usize) {
+        let this = unsafe { self.get_unchecked_mut() };
+
+        this.pos = std::cmp::min(this.pos + amount, this.filled);
+    }
+}
@@ -0,0 +1,178 @@
+use super::AsyncWrite;
+
+use std::io;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+/// Default capacity of the internal buffer, in bytes.
+const DEFAULT_CAPACITY: usize = 8 * 1024;
+
+/// Adds buffering to an [`AsyncWrite`] sink.
+///
+/// `BufWriter` coalesces many small writes into larger ones: bytes are
+/// accumulated in an internal buffer and only handed to the underlying
+/// writer when the buffer fills up or when [`flush`] is called. This is
+/// the asynchronous equivalent of [`std::io::BufWriter`].
+///
+/// Buffered bytes are **not** written on drop; call [`flush`] (or
+/// [`shutdown`]) before dropping the writer.
+///
+/// [`flush`]: super::AsyncWriteExt::flush
+/// [`shutdown`]: super::AsyncWriteExt::shutdown
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// let (_, write_half) = stream.split();
+/// let mut writer = BufWriter::new(write_half);
+///
+/// for line in lines {
+///     writer.write_all(line.as_bytes()).await?;
+/// }
+///
+/// writer.flush().await?;
+/// ```
+pub struct BufWriter<W> {
+    /// The underlying writer.
+    inner: W,
+
+    /// Bytes waiting to be written.
+    buffer: Vec<u8>,
+
+    /// Number of bytes of `buffer` already handed to the inner writer
+    /// during an in-progress flush.
+    written: usize,
+
+    /// Maximum number of bytes buffered before flushing.
+    capacity: usize,
+}
+
+impl<W> BufWriter<W> {
+    /// Creates a new `BufWriter` with a default buffer capacity (8 KiB).
+    pub fn new(inner: W) -> Self {
+        Self::with_capacity(DEFAULT_CAPACITY, inner)
+    }
+
+    /// Creates a new `BufWriter` with the given buffer capacity.
+    pub fn with_capacity(capacity: usize, inner: W) -> Self {
+        Self {
+            inner,
+            buffer: Vec::with_capacity(capacity),
+            written: 0,
+            capacity,
+        }
+    }
+
+    /// Returns a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the underlying writer.
+    ///
+    /// Writing directly to the underlying writer may reorder bytes with
+    /// respect to the buffered ones.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Returns the bytes currently buffered.
+    pub fn buffer(&self) -> &[u8] {
+        &self.buffer[self.written..]
+    }
+
+    /// Consumes the `BufWriter`, returning the underlying writer.
+    ///
+    /// Any buffered bytes that were not flushed are discarded.
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+impl<W: AsyncWrite> BufWriter<W> {
+    /// Writes the whole internal buffer to the underlying writer.
+    ///
+    /// # Safety
+    ///
+    /// This implementation uses `unsafe` pin projections but is sound
+    /// because the inner writer is never moved after being pinned.
+    fn poll_flush_buffer(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let this = unsafe { self.get_unchecked_mut() };
+
+        while this.written < this.buffer.len() {
+            let inner = unsafe { Pin::new_unchecked(&mut this.inner) };
+
+            match inner.poll_write(cx, &this.buffer[this.written..]) {
+                Poll::Ready(Ok(0)) => {
+                    return Poll::Ready(Err(io::Error::new(
+                        io::ErrorKind::WriteZero,
+                        "failed to write the buffered data",
+                    )));
+                }
+                Poll::Ready(Ok(n)) => this.written += n,
+                Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+
+        this.buffer.clear();
+        this.written = 0;
+
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<W: AsyncWrite> AsyncWrite for BufWriter<W> {
+    /// Buffers `buffer`, flushing the internal buffer first if it
+    /// would overflow.
+    ///
+    /// Writes at least as large as the buffer capacity bypass the buffer
+    /// and go straight to the underlying writer.
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buffer: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        if self.buffer.len() + buffer.len() > self.capacity {
+            match self.as_mut().poll_flush_buffer(cx) {
+                Poll::Ready(Ok(())) => {}
+                Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+
+        let this = unsafe { self.get_unchecked_mut() };
+
+        if buffer.len() >= this.capacity {
+            return unsafe { Pin::new_unchecked(&mut this.inner) }.poll_write(cx, buffer);
+        }
+
+        this.buffer.extend_from_slice(buffer);
+
+        Poll::Ready(Ok(buffer.len()))
+    }
+
+    /// Writes the buffered bytes, then flushes the underlying writer.
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        match self.as_mut().poll_flush_buffer(cx) {
+            Poll::Ready(Ok(())) => {}
+            Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
+            Poll::Pending => return Poll::Pending,
+        }
+
+        let this = unsafe { self.get_unchecked_mut() };
+        unsafe { Pin::new_unchecked(&mut this.inner) }.poll_flush(cx)
+    }
+
+    /// Writes the buffered bytes, then shuts down the underlying writer.
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        match self.as_mut().poll_flush_buffer(cx) {
+            Poll::Ready(Ok(())) => {}
+            Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
+            Poll::Pending => return Poll::Pending,
+        }
+
+        let this = unsafe { self.get_unchecked_mut() };
+        unsafe { Pin::new_unchecked(&mut this.inner) }.poll_shutdown(cx)
+    }
+}
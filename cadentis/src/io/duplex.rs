@@ -0,0 +1,199 @@
+use super::{AsyncRead, AsyncWrite};
+
+use std::collections::VecDeque;
+use std::io;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+
+/// Creates a pair of connected in-memory streams.
+///
+/// Bytes written to one end are read from the other, in both directions,
+/// without involving the OS. Each direction buffers at most `capacity`
+/// bytes: once it is full, writes wait until the other end reads, as
+/// they would on a socket. This makes `duplex` handy to test codecs and
+/// protocols, or to connect in-process producers and consumers.
+///
+/// Dropping an end, or shutting down its write side, makes the other
+/// end read the end of stream once it has drained the buffered bytes.
+/// Writing to an end whose peer was dropped fails with `BrokenPipe`.
+///
+/// # Panics
+///
+/// Panics if `capacity` is zero.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// let (client, server) = io::duplex(64);
+///
+/// let mut client = Framed::new(client, LinesCodec::new());
+/// client.send("hello".to_string()).await?;
+/// ```
+pub fn duplex(capacity: usize) -> (DuplexStream, DuplexStream) {
+    assert!(capacity > 0, "duplex capacity must be > 0");
+
+    let one = Arc::new(Mutex::new(Pipe::new(capacity)));
+    let two = Arc::new(Mutex::new(Pipe::new(capacity)));
+
+    (
+        DuplexStream {
+            read: one.clone(),
+            write: two.clone(),
+        },
+        DuplexStream {
+            read: two,
+            write: one,
+        },
+    )
+}
+
+/// One end of an in-memory stream, created by [`duplex`].
+pub struct DuplexStream {
+    /// Bytes written by the other end.
+    read: Arc<Mutex<Pipe>>,
+
+    /// Bytes written by this end.
+    write: Arc<Mutex<Pipe>>,
+}
+
+/// A bounded, one-way byte buffer.
+struct Pipe {
+    /// Bytes written but not read yet.
+    buffer: VecDeque<u8>,
+
+    /// Maximum number of buffered bytes.
+    capacity: usize,
+
+    /// Whether the writing end was shut down or dropped.
+    write_closed: bool,
+
+    /// Whether the reading end was dropped.
+    read_closed: bool,
+
+    /// Task waiting for bytes to read.
+    read_waker: Option<Waker>,
+
+    /// Task waiting for room to write.
+    write_waker: Option<Waker>,
+}
+
+impl Pipe {
+    fn new(capacity: usize) -> Self {
+        Self {
+            buffer: VecDeque::with_capacity(capacity),
+            capacity,
+            write_closed: false,
+            read_closed: false,
+            read_waker: None,
+            write_waker: None,
+        }
+    }
+
+    /// Marks the writing end closed and wakes the reader.
+    fn close_write(&mut self) {
+        self.write_closed = true;
+
+        if let Some(waker) = self.read_waker.take() {
+            waker.wake();
+        }
+    }
+
+    /// Marks the reading end closed and wakes the writer.
+    fn close_read(&mut self) {
+        self.read_closed = true;
+
+        if let Some(waker) = self.write_waker.take() {
+            waker.wake();
+        }
+    }
+}
+
+impl AsyncRead for DuplexStream {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buffer: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        let mut pipe = self.read.lock().unwrap();
+
+        if buffer.is_empty() {
+            return Poll::Ready(Ok(0));
+        }
+
+        if pipe.buffer.is_empty() {
+            if pipe.write_closed {
+                return Poll::Ready(Ok(0));
+            }
+
+            pipe.read_waker = Some(cx.waker().clone());
+            return Poll::Pending;
+        }
+
+        let n = buffer.len().min(pipe.buffer.len());
+        for (dst, src) in buffer.iter_mut().zip(pipe.buffer.drain(..n)) {
+            *dst = src;
+        }
+
+        if let Some(waker) = pipe.write_waker.take() {
+            waker.wake();
+        }
+
+        Poll::Ready(Ok(n))
+    }
+}
+
+impl AsyncWrite for DuplexStream {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buffer: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        let mut pipe = self.write.lock().unwrap();
+
+        if pipe.read_closed || pipe.write_closed {
+            return Poll::Ready(Err(io::ErrorKind::BrokenPipe.into()));
+        }
+
+        if buffer.is_empty() {
+            return Poll::Ready(Ok(0));
+        }
+
+        let room = pipe.capacity - pipe.buffer.len();
+        if room == 0 {
+            pipe.write_waker = Some(cx.waker().clone());
+            return Poll::Pending;
+        }
+
+        let n = buffer.len().min(room);
+        pipe.buffer.extend(&buffer[..n]);
+
+        if let Some(waker) = pipe.read_waker.take() {
+            waker.wake();
+        }
+
+        Poll::Ready(Ok(n))
+    }
+
+    /// Bytes are readable as soon as they are written: there is nothing
+    /// to flush.
+    fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    /// Closes the write side: the other end reads the end of stream once
+    /// it has drained the buffered bytes.
+    fn poll_shutdown(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.write.lock().unwrap().close_write();
+
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl Drop for DuplexStream {
+    /// Closes both directions, waking the other end.
+    fn drop(&mut self) {
+        self.write.lock().unwrap().close_write();
+        self.read.lock().unwrap().close_read();
+    }
+}
@@ -0,0 +1,35 @@
+//! Asynchronous I/O traits and utilities.
+//!
+//! This module defines the poll-based [`AsyncRead`] and [`AsyncWrite`]
+//! traits implemented by the runtime I/O types, together with extension
+//! traits providing `async fn`-style helpers on top of them.
+//!
+//! It includes:
+//! - [`AsyncRead`] / [`AsyncReadExt`] for reading bytes,
+//! - [`AsyncWrite`] / [`AsyncWriteExt`] for writing and flushing bytes,
+//! - [`AsyncBufRead`] / [`AsyncBufReadExt`] for buffered sources and
+//!   line-oriented protocols,
+//! - [`BufReader`] and [`BufWriter`] for buffering small reads and writes,
+//! - [`duplex`] for connected in-memory streams, handy in tests,
+//! - [`Registration`] for waiting on custom descriptors with the reactor.
+//!
+//! These traits let generic code (codecs, buffered wrappers, protocol
+//! state machines) work uniformly over sockets and other byte streams.
+
+mod buf_read;
+mod buf_reader;
+mod buf_writer;
+mod duplex;
+mod read;
+#[cfg(unix)]
+mod registration;
+mod write;
+
+pub use buf_read::{AsyncBufRead, AsyncBufReadExt, Lines};
+pub use buf_reader::BufReader;
+pub use buf_writer::BufWriter;
+pub use duplex::{DuplexStream, duplex};
+pub use read::{AsyncRead, AsyncReadExt, Read, ReadExact, ReadTimeout};
+#[cfg(unix)]
+pub use registration::{Interest, Registration};
+pub use write::{AsyncWrite, AsyncWriteExt, Flush, Shutdown, Write, WriteAll};
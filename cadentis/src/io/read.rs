@@ -0,0 +1,192 @@
+use crate::time::timeout::{Timeout, timeout};
+
+use std::future::Future;
+use std::io;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+/// Reads bytes from a source asynchronously.
+///
+/// This is the asynchronous equivalent of [`std::io::Read`]: instead of
+/// blocking, [`poll_read`](Self::poll_read) returns [`Poll::Pending`] and
+/// wakes the task once data is available.
+pub trait AsyncRead {
+    /// Attempts to read bytes into `buffer`.
+    ///
+    /// Returns:
+    /// - `Poll::Ready(Ok(n))` with the number of bytes read, where `0`
+    ///   means end of stream (or an empty `buffer`),
+    /// - `Poll::Pending` if no data is available yet (the current task is
+    ///   woken once the source becomes readable),
+    /// - `Poll::Ready(Err(e))` on I/O errors.
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buffer: &mut [u8],
+    ) -> Poll<io::Result<usize>>;
+}
+
+impl<R: AsyncRead + Unpin + ?Sized> AsyncRead for &mut R {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buffer: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut **self).poll_read(cx, buffer)
+    }
+}
+
+impl<R: AsyncRead + Unpin + ?Sized> AsyncRead for Box<R> {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buffer: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut **self).poll_read(cx, buffer)
+    }
+}
+
+/// Extension trait providing `async` helpers for [`AsyncRead`] types.
+///
+/// This trait is implemented for every type implementing [`AsyncRead`].
+pub trait AsyncReadExt: AsyncRead {
+    /// Reads up to `buffer.len()` bytes.
+    ///
+    /// Resolves to the number of bytes read; `0` means end of stream.
+    fn read<'a>(&'a mut self, buffer: &'a mut [u8]) -> Read<'a, Self>
+    where
+        Self: Unpin,
+    {
+        Read {
+            reader: self,
+            buffer,
+        }
+    }
+
+    /// Reads exactly `buffer.len()` bytes.
+    ///
+    /// # Errors
+    ///
+    /// Returns `UnexpectedEof` if the stream ends before the buffer is
+    /// filled.
+    fn read_exact<'a>(&'a mut self, buffer: &'a mut [u8]) -> ReadExact<'a, Self>
+    where
+        Self: Unpin,
+    {
+        ReadExact {
+            reader: self,
+            buffer,
+            filled: 0,
+        }
+    }
+
+    /// Reads up to `buffer.len()` bytes, giving up after `duration`.
+    ///
+    /// Behaves like [`read`](Self::read), but fails with
+    /// [`io::ErrorKind::TimedOut`] if no data arrives in time. A read that
+    /// times out consumes nothing, so the reader can be used again
+    /// afterwards.
+    ///
+    /// On a [`TcpStream`](crate::net::TcpStream), whose inherent
+    /// `read_timeout` returns the configured timeout, call it as
+    /// `AsyncReadExt::read_timeout(&mut stream, ..)`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// match stream.read_timeout(&mut buffer, Duration::from_secs(5)).await {
+    ///     Ok(0) => return Ok(()),
+    ///     Ok(n) => handle(&buffer[..n]),
+    ///     Err(e) if e.kind() == io::ErrorKind::TimedOut => send_ping(&mut stream).await?,
+    ///     Err(e) => return Err(e),
+    /// }
+    /// ```
+    fn read_timeout<'a>(
+        &'a mut self,
+        buffer: &'a mut [u8],
+        duration: Duration,
+    ) -> ReadTimeout<'a, Self>
+    where
+        Self: Unpin,
+    {
+        ReadTimeout {
+            read: timeout(duration, self.read(buffer)),
+        }
+    }
+}
+
+impl<R: AsyncRead + ?Sized> AsyncReadExt for R {}
+
+/// Future returned by [`AsyncReadExt::read`].
+pub struct Read<'a, R: ?Sized> {
+    reader: &'a mut R,
+    buffer: &'a mut [u8],
+}
+
+impl<R: AsyncRead + Unpin + ?Sized> Future for Read<'_, R> {
+    type Output = io::Result<usize>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+
+        Pin::new(&mut *this.reader).poll_read(cx, this.buffer)
+    }
+}
+
+/// Future returned by [`AsyncReadExt::read_timeout`].
+pub struct ReadTimeout<'a, R: ?Sized> {
+    read: Timeout<Read<'a, R>>,
+}
+
+impl<R: AsyncRead + Unpin + ?Sized> Future for ReadTimeout<'_, R> {
+    type Output = io::Result<usize>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        match Pin::new(&mut self.get_mut().read).poll(cx) {
+            Poll::Ready(Ok(result)) => Poll::Ready(result),
+            Poll::Ready(Err(())) => Poll::Ready(Err(io::Error::new(
+                io::ErrorKind::TimedOut,
+                "read timed out",
+            ))),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+/// Future returned by [`AsyncReadExt::read_exact`].
+pub struct ReadExact<'a, R: ?Sized> {
+    reader: &'a mut R,
+    buffer: &'a mut [u8],
+
+    /// Number of bytes already read into `buffer`.
+    filled: usize,
+}
+
+impl<R: AsyncRead + Unpin + ?Sized> Future for ReadExact<'_, R> {
+    type Output = io::Result<()>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+
+        while this.filled < this.buffer.len() {
+            let n = match Pin::new(&mut *this.reader).poll_read(cx, &mut this.buffer[this.filled..])
+            {
+                Poll::Ready(Ok(n)) => n,
+                Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
+                Poll::Pending => return Poll::Pending,
+            };
+
+            if n == 0 {
+                return Poll::Ready(Err(io::Error::new(
+                    io::ErrorKind::UnexpectedEof,
+                    "stream ended before the buffer was filled",
+                )));
+            }
+
+            this.filled += n;
+        }
+
+        Poll::Ready(Ok(()))
+    }
+}
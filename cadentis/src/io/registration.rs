@@ -0,0 +1,236 @@
+use crate::reactor::readiness::Readiness;
+use crate::sync::AtomicWaker;
+use crate::sys;
+
+use std::future::poll_fn;
+use std::io;
+use std::ops::BitOr;
+use std::os::fd::AsRawFd;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::task::{Context, Poll, Wake, Waker};
+
+/// Readiness a [`Registration`] can wait for.
+///
+/// Combine interests with `|`:
+///
+/// ```rust,ignore
+/// let interest = Interest::READABLE | Interest::WRITABLE;
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Interest {
+    read: bool,
+    write: bool,
+}
+
+impl Interest {
+    /// Interest in the source becoming readable.
+    pub const READABLE: Self = Self {
+        read: true,
+        write: false,
+    };
+
+    /// Interest in the source becoming writable.
+    pub const WRITABLE: Self = Self {
+        read: false,
+        write: true,
+    };
+
+    /// Returns `true` if the interest includes readability.
+    pub const fn is_readable(self) -> bool {
+        self.read
+    }
+
+    /// Returns `true` if the interest includes writability.
+    pub const fn is_writable(self) -> bool {
+        self.write
+    }
+}
+
+impl BitOr for Interest {
+    type Output = Self;
+
+    fn bitor(self, other: Self) -> Self {
+        Self {
+            read: self.read || other.read,
+            write: self.write || other.write,
+        }
+    }
+}
+
+/// A custom I/O source registered with the runtime reactor.
+///
+/// `Registration` takes ownership of any source exposing a file
+/// descriptor (a pipe, a `timerfd`, a descriptor inherited from a parent
+/// process or handed out by a third-party library, ...) and lets tasks
+/// wait for it to become readable or writable. The source itself is
+/// still read and written by the caller, through
+/// [`get_ref`](Self::get_ref) or [`get_mut`](Self::get_mut).
+///
+/// The descriptor is removed from the reactor when the registration is
+/// dropped, before the source is, so it is never closed while the
+/// reactor still watches it. [`into_inner`](Self::into_inner) gives the
+/// source back, no longer registered.
+///
+/// A wake-up only says that the source may be ready, as another reader
+/// may have drained it meanwhile: operations on it should be non-blocking
+/// and wait again when they report `WouldBlock`. One task may wait for
+/// the source to become readable while another waits for it to become
+/// writable, but only one task should wait in each direction at a time.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// let (reader, writer) = std::io::pipe()?;
+/// let mut pipe = Registration::new(reader, Interest::READABLE)?;
+///
+/// loop {
+///     pipe.readable().await?;
+///
+///     match pipe.get_mut().read(&mut buffer) {
+///         Ok(n) => break n,
+///         Err(err) if err.kind() == io::ErrorKind::WouldBlock => continue,
+///         Err(err) => return Err(err),
+///     }
+/// }
+/// ```
+pub struct Registration<T: AsRawFd> {
+    /// Readiness of the source, dropped before the source.
+    readiness: Readiness,
+
+    /// Readiness the source may be waited for.
+    interest: Interest,
+
+    /// The registered source.
+    source: T,
+}
+
+impl<T: AsRawFd> Registration<T> {
+    /// Registers `source` with the reactor of the current runtime.
+    ///
+    /// The descriptor is switched to non-blocking mode, and handed to the
+    /// reactor the first time a task waits on it. It stays non-blocking
+    /// once given back by [`into_inner`](Self::into_inner).
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidInput` if `interest` includes neither readability
+    /// nor writability, or the error reported while switching the
+    /// descriptor to non-blocking mode.
+    pub fn new(source: T, interest: Interest) -> io::Result<Self> {
+        if !interest.read && !interest.write {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "a registration needs a read or write interest",
+            ));
+        }
+
+        sys::set_nonblocking(source.as_raw_fd())?;
+
+        Ok(Self {
+            readiness: Readiness::new(source.as_raw_fd()),
+            interest,
+            source,
+        })
+    }
+
+    /// Waits until the source may be read from.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidInput` if the registration has no read interest.
+    pub async fn readable(&self) -> io::Result<()> {
+        self.ready(Interest::READABLE).await
+    }
+
+    /// Waits until the source may be written to.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidInput` if the registration has no write interest.
+    pub async fn writable(&self) -> io::Result<()> {
+        self.ready(Interest::WRITABLE).await
+    }
+
+    /// Returns the interest the registration was created with.
+    pub fn interest(&self) -> Interest {
+        self.interest
+    }
+
+    /// Returns a shared reference to the source.
+    pub fn get_ref(&self) -> &T {
+        &self.source
+    }
+
+    /// Returns a mutable reference to the source.
+    pub fn get_mut(&mut self) -> &mut T {
+        &mut self.source
+    }
+
+    /// Removes the source from the reactor and returns it.
+    pub fn into_inner(self) -> T {
+        let Self {
+            readiness, source, ..
+        } = self;
+
+        drop(readiness);
+        source
+    }
+
+    /// Waits once for the reactor to report `interest`.
+    async fn ready(&self, interest: Interest) -> io::Result<()> {
+        if (interest.read && !self.interest.read) || (interest.write && !self.interest.write) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "the registration was not created with this interest",
+            ));
+        }
+
+        let interest = nucleus::poll::Interest {
+            read: interest.read,
+            write: interest.write,
+        };
+        let wait = Arc::new(ReactorWake {
+            fired: AtomicBool::new(false),
+            task: AtomicWaker::new(),
+        });
+        let waker = Waker::from(wait.clone());
+
+        poll_fn(|cx| {
+            // Only a wake-up coming from the reactor ends the wait: the
+            // task may be polled for other reasons meanwhile.
+            if wait.fired.load(Ordering::Acquire) {
+                return Poll::Ready(Ok(()));
+            }
+
+            wait.task.register(cx.waker());
+
+            self.readiness
+                .poll_io(&mut Context::from_waker(&waker), interest, || {
+                    Err::<(), _>(io::ErrorKind::WouldBlock.into())
+                })
+        })
+        .await
+    }
+}
+
+/// Waker handed to the reactor, recording that it fired before waking
+/// the task.
+///
+/// A single `ReactorWake` serves a whole wait: polls only refresh the
+/// task waker it forwards to.
+struct ReactorWake {
+    fired: AtomicBool,
+    task: AtomicWaker,
+}
+
+impl Wake for ReactorWake {
+    fn wake(self: Arc<Self>) {
+        self.wake_by_ref();
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        self.fired.store(true, Ordering::Release);
+        self.task.wake();
+    }
+}
@@ -0,0 +1,205 @@
+use std::future::Future;
+use std::io;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+/// Writes bytes to a sink asynchronously.
+///
+/// This is the asynchronous equivalent of [`std::io::Write`]. Writes may
+/// be buffered by the implementation; [`poll_flush`](Self::poll_flush)
+/// resolves once every previously written byte has been handed to the
+/// underlying sink.
+pub trait AsyncWrite {
+    /// Attempts to write bytes from `buffer`.
+    ///
+    /// Returns the number of bytes accepted, which may be less than
+    /// `buffer.len()`.
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buffer: &[u8],
+    ) -> Poll<io::Result<usize>>;
+
+    /// Attempts to flush every buffered byte to the underlying sink.
+    ///
+    /// Resolves once every byte accepted by earlier writes has been
+    /// handed to the operating system: for a [`TcpStream`], once the
+    /// reactor has emptied the output buffer of the connection, and for
+    /// a [`File`], right away since its writes are not buffered. A peer
+    /// reading the connection can therefore receive everything written
+    /// before the flush. Handing bytes to the OS does not make them
+    /// durable; see [`File::set_sync_on_flush`] for that.
+    ///
+    /// Fails with `BrokenPipe` if the connection is closed while bytes
+    /// are still buffered.
+    ///
+    /// [`TcpStream`]: crate::net::TcpStream
+    /// [`File`]: crate::fs::File
+    /// [`File::set_sync_on_flush`]: crate::fs::File::set_sync_on_flush
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>>;
+
+    /// Attempts to flush and then shut down the write side of the sink.
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>>;
+}
+
+impl<W: AsyncWrite + Unpin + ?Sized> AsyncWrite for &mut W {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buffer: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut **self).poll_write(cx, buffer)
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut **self).poll_flush(cx)
+    }
+
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut **self).poll_shutdown(cx)
+    }
+}
+
+impl<W: AsyncWrite + Unpin + ?Sized> AsyncWrite for Box<W> {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buffer: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut **self).poll_write(cx, buffer)
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut **self).poll_flush(cx)
+    }
+
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut **self).poll_shutdown(cx)
+    }
+}
+
+/// Extension trait providing `async` helpers for [`AsyncWrite`] types.
+///
+/// This trait is implemented for every type implementing [`AsyncWrite`].
+pub trait AsyncWriteExt: AsyncWrite {
+    /// Writes bytes from `buffer`, resolving to the number accepted.
+    fn write<'a>(&'a mut self, buffer: &'a [u8]) -> Write<'a, Self>
+    where
+        Self: Unpin,
+    {
+        Write {
+            writer: self,
+            buffer,
+        }
+    }
+
+    /// Writes the entire buffer.
+    ///
+    /// # Errors
+    ///
+    /// Returns `WriteZero` if the sink stops accepting bytes.
+    fn write_all<'a>(&'a mut self, buffer: &'a [u8]) -> WriteAll<'a, Self>
+    where
+        Self: Unpin,
+    {
+        WriteAll {
+            writer: self,
+            buffer,
+        }
+    }
+
+    /// Flushes every buffered byte to the underlying sink.
+    fn flush(&mut self) -> Flush<'_, Self>
+    where
+        Self: Unpin,
+    {
+        Flush { writer: self }
+    }
+
+    /// Flushes and shuts down the write side of the sink.
+    fn shutdown(&mut self) -> Shutdown<'_, Self>
+    where
+        Self: Unpin,
+    {
+        Shutdown { writer: self }
+    }
+}
+
+impl<W: AsyncWrite + ?Sized> AsyncWriteExt for W {}
+
+/// Future returned by [`AsyncWriteExt::write`].
+pub struct Write<'a, W: ?Sized> {
+    writer: &'a mut W,
+    buffer: &'a [u8],
+}
+
+impl<W: AsyncWrite + Unpin + ?Sized> Future for Write<'_, W> {
+    type Output = io::Result<usize>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+
+        Pin::new(&mut *this.writer).poll_write(cx, this.buffer)
+    }
+}
+
+/// Future returned by [`AsyncWriteExt::write_all`].
+pub struct WriteAll<'a, W: ?Sized> {
+    writer: &'a mut W,
+
+    /// Bytes remaining to be written.
+    buffer: &'a [u8],
+}
+
+impl<W: AsyncWrite + Unpin + ?Sized> Future for WriteAll<'_, W> {
+    type Output = io::Result<()>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+
+        while !this.buffer.is_empty() {
+            let n = match Pin::new(&mut *this.writer).poll_write(cx, this.buffer) {
+                Poll::Ready(Ok(n)) => n,
+                Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
+                Poll::Pending => return Poll::Pending,
+            };
+
+            if n == 0 {
+                return Poll::Ready(Err(io::Error::new(
+                    io::ErrorKind::WriteZero,
+                    "write returned zero bytes",
+                )));
+            }
+
+            this.buffer = &this.buffer[n..];
+        }
+
+        Poll::Ready(Ok(()))
+    }
+}
+
+/// Future returned by [`AsyncWriteExt::flush`].
+pub struct Flush<'a, W: ?Sized> {
+    writer: &'a mut W,
+}
+
+impl<W: AsyncWrite + Unpin + ?Sized> Future for Flush<'_, W> {
+    type Output = io::Result<()>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        Pin::new(&mut *self.get_mut().writer).poll_flush(cx)
+    }
+}
+
+/// Future returned by [`AsyncWriteExt::shutdown`].
+pub struct Shutdown<'a, W: ?Sized> {
+    writer: &'a mut W,
+}
+
+impl<W: AsyncWrite + Unpin + ?Sized> Future for Shutdown<'_, W> {
+    type Output = io::Result<()>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        Pin::new(&mut *self.get_mut().writer).poll_shutdown(cx)
+    }
+}
@@ -15,7 +15,7 @@
 //! - **Async TCP networking** with listener and stream abstractions
 //! - **Timer primitives** including sleep, timeout, and intervals
 //! - **Async synchronization primitives** (mutexes, channels, and coordination tools)
-//! - **Ergonomic macros** like `#[cadentis::main]`, `#[cadentis::test]`, `join!`, and `select!`
+//! - **Ergonomic macros** like `#[cadentis::main]`, `#[cadentis::test]`, `join!`, `select!`, and `select_enum!`
 //!
 //! ## Quick Start
 //!
@@ -33,17 +33,24 @@
 //!     });
 //!
 //!     // Wait for the task to finish
-//!     handle.await;
+//!     handle.await.expect("task failed");
 //! }
 //! ```
 //!
 //! ## Modules
 //!
+//! - [`codec`] — Framing of byte streams into messages
 //! - [`fs`] — Async file and directory operations
+//! - [`io`] — Async I/O traits and buffered wrappers
 //! - [`net`] — Async networking (TCP listener/stream)
+//! - [`process`] — Async child processes (Unix)
+//! - [`runtime`] — The runtime, its handles and the global default runtime
+//! - [`signal`] — Async signal handling (Unix)
 //! - [`time`] — Timers, sleep, timeout, and intervals
 //! - [`sync`] — Async synchronization primitives
+//! - [`stream`] — The `Stream` trait and its combinators
 //! - [`tools`] — Utilities like retry mechanisms
+//! - `compat` — Adapters for the `futures` crate traits (`futures` feature)
 //!
 //! ## Getting Started
 //!
@@ -55,17 +62,30 @@
 //! ```
 
 mod reactor;
-mod runtime;
+mod sys;
 mod utils;
 
+pub mod codec;
+#[cfg(feature = "futures")]
+pub mod compat;
 pub mod fs;
+pub mod io;
 pub mod net;
+#[cfg(unix)]
+pub mod process;
+pub mod runtime;
+#[cfg(unix)]
+pub mod signal;
+pub mod stream;
 pub mod sync;
 pub mod time;
 pub mod tools;
 
+pub use reactor::stats::ReactorStats;
 pub use runtime::builder::RuntimeBuilder;
+pub use runtime::metrics::RuntimeMetrics;
 pub use runtime::task;
 pub use runtime::yield_now::yield_now;
+pub use runtime::{BusyLoopAction, StealStrategy};
 
 pub use cadentis_macros::*;
@@ -0,0 +1,226 @@
+//! Accepting connections as non-blocking, close-on-exec sockets.
+//!
+//! Accepted sockets are used by the reactor in non-blocking mode, and
+//! must not leak into the child processes the program spawns. Linux and
+//! Android set both flags atomically with `accept4(2)`. Other unix
+//! platforms have no such call: the flags are set with `fcntl(2)` right
+//! after `accept(2)`, leaving a short window where a concurrent `exec`
+//! inherits the socket. Windows sockets are not inherited by default.
+//! Platforms whose socket addresses are not decoded here go through
+//! `nucleus` before setting the flags.
+
+use nucleus::io::RawFd;
+use std::io;
+use std::net::SocketAddr;
+
+/// Accepts a connection on the listening socket `fd`, returning the
+/// connected socket, non-blocking and close-on-exec, and the address of
+/// the peer.
+///
+/// Fails with `WouldBlock` if no connection is pending.
+pub(crate) fn accept(fd: RawFd) -> io::Result<(RawFd, SocketAddr)> {
+    sys::accept(fd)
+}
+
+/// Storage large enough and aligned for any socket address.
+#[cfg(any(
+    target_os = "linux",
+    target_os = "android",
+    target_os = "macos",
+    target_os = "ios",
+    target_os = "freebsd",
+    target_os = "dragonfly",
+    target_os = "openbsd",
+    target_os = "netbsd"
+))]
+#[repr(C, align(8))]
+struct Storage([u8; 128]);
+
+#[cfg(any(
+    target_os = "linux",
+    target_os = "android",
+    target_os = "macos",
+    target_os = "ios",
+    target_os = "freebsd",
+    target_os = "dragonfly",
+    target_os = "openbsd",
+    target_os = "netbsd"
+))]
+impl Storage {
+    /// Decodes the `len` first bytes as an IPv4 or IPv6 address, whose
+    /// family is `family`.
+    fn decode(&self, len: u32, family: u16) -> io::Result<SocketAddr> {
+        use std::net::{Ipv4Addr, Ipv6Addr, SocketAddrV4, SocketAddrV6};
+
+        let bytes = &self.0;
+        let port = u16::from_be_bytes([bytes[2], bytes[3]]);
+        let field = |at: usize| u32::from_ne_bytes(bytes[at..at + 4].try_into().unwrap());
+
+        match family {
+            sys::AF_INET if len >= 16 => {
+                let ip: [u8; 4] = bytes[4..8].try_into().unwrap();
+
+                Ok(SocketAddrV4::new(Ipv4Addr::from(ip), port).into())
+            }
+            sys::AF_INET6 if len >= 28 => {
+                let ip: [u8; 16] = bytes[8..24].try_into().unwrap();
+
+                Ok(SocketAddrV6::new(Ipv6Addr::from(ip), port, field(4), field(24)).into())
+            }
+            _ => Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "unsupported address family",
+            )),
+        }
+    }
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+mod sys {
+    use super::Storage;
+
+    use crate::sys::accept4;
+
+    use nucleus::io::{RawFd, sys_close};
+    use std::io;
+    use std::net::SocketAddr;
+
+    pub(super) const AF_INET: u16 = 2;
+    pub(super) const AF_INET6: u16 = 10;
+
+    const SOCK_NONBLOCK: i32 = 0o4000;
+    const SOCK_CLOEXEC: i32 = 0o2000000;
+
+    pub(super) fn accept(fd: RawFd) -> io::Result<(RawFd, SocketAddr)> {
+        let mut storage = Storage([0; 128]);
+        let mut len = storage.0.len() as u32;
+
+        // SAFETY: `storage` is writable for `len` bytes, and `len` is a
+        // live value the kernel updates with the address length.
+        let client = unsafe {
+            accept4(
+                fd,
+                storage.0.as_mut_ptr().cast(),
+                &mut len,
+                SOCK_NONBLOCK | SOCK_CLOEXEC,
+            )
+        };
+
+        if client < 0 {
+            return Err(io::Error::last_os_error());
+        }
+
+        let family = u16::from_ne_bytes([storage.0[0], storage.0[1]]);
+
+        match storage.decode(len, family) {
+            Ok(address) => Ok((client, address)),
+            Err(err) => {
+                sys_close(client);
+                Err(err)
+            }
+        }
+    }
+}
+
+#[cfg(any(
+    target_os = "macos",
+    target_os = "ios",
+    target_os = "freebsd",
+    target_os = "dragonfly",
+    target_os = "openbsd",
+    target_os = "netbsd"
+))]
+mod sys {
+    use super::Storage;
+
+    use nucleus::io::{RawFd, sys_close};
+    use std::io;
+    use std::net::SocketAddr;
+
+    pub(super) const AF_INET: u16 = 2;
+    #[cfg(any(target_os = "macos", target_os = "ios"))]
+    pub(super) const AF_INET6: u16 = 30;
+    #[cfg(any(target_os = "freebsd", target_os = "dragonfly"))]
+    pub(super) const AF_INET6: u16 = 28;
+    #[cfg(any(target_os = "openbsd", target_os = "netbsd"))]
+    pub(super) const AF_INET6: u16 = 24;
+
+    pub(super) fn accept(fd: RawFd) -> io::Result<(RawFd, SocketAddr)> {
+        let mut storage = Storage([0; 128]);
+        let mut len = storage.0.len() as u32;
+
+        // SAFETY: `storage` is writable for `len` bytes, and `len` is a
+        // live value the kernel updates with the address length.
+        let client = unsafe { crate::sys::accept(fd, storage.0.as_mut_ptr().cast(), &mut len) };
+
+        if client < 0 {
+            return Err(io::Error::last_os_error());
+        }
+
+        if let Err(err) = super::configure(client) {
+            sys_close(client);
+            return Err(err);
+        }
+
+        // BSD addresses start with their length, then a one-byte family.
+        let family = u16::from(storage.0[1]);
+
+        match storage.decode(len, family) {
+            Ok(address) => Ok((client, address)),
+            Err(err) => {
+                sys_close(client);
+                Err(err)
+            }
+        }
+    }
+}
+
+#[cfg(all(
+    unix,
+    not(any(
+        target_os = "linux",
+        target_os = "android",
+        target_os = "macos",
+        target_os = "ios",
+        target_os = "freebsd",
+        target_os = "dragonfly",
+        target_os = "openbsd",
+        target_os = "netbsd"
+    ))
+))]
+mod sys {
+    use nucleus::io::{RawFd, sys_close};
+    use nucleus::socket::sys_accept;
+    use std::io;
+    use std::net::SocketAddr;
+
+    pub(super) fn accept(fd: RawFd) -> io::Result<(RawFd, SocketAddr)> {
+        let (client, address) = sys_accept(fd)?;
+
+        if let Err(err) = super::configure(client) {
+            sys_close(client);
+            return Err(err);
+        }
+
+        Ok((client, address))
+    }
+}
+
+/// Makes the accepted socket `fd` close-on-exec and non-blocking.
+#[cfg(all(unix, not(any(target_os = "linux", target_os = "android"))))]
+fn configure(fd: RawFd) -> io::Result<()> {
+    crate::sys::set_cloexec(fd)?;
+    crate::sys::set_nonblocking(fd)
+}
+
+#[cfg(windows)]
+mod sys {
+    use nucleus::io::RawFd;
+    use nucleus::socket::sys_accept;
+    use std::io;
+    use std::net::SocketAddr;
+
+    pub(super) fn accept(fd: RawFd) -> io::Result<(RawFd, SocketAddr)> {
+        sys_accept(fd)
+    }
+}
@@ -0,0 +1,150 @@
+//! Conversion of the address arguments accepted by the socket types.
+
+use std::borrow::Cow;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6};
+
+/// Types usable as the address of [`TcpListener::bind`] and
+/// [`TcpStream::connect`].
+///
+/// This mirrors [`std::net::ToSocketAddrs`]. It is implemented for
+/// strings such as `"127.0.0.1:8080"` or `"example.com:443"`, for
+/// [`SocketAddr`] and its variants, for `(IpAddr, u16)` tuples, and for
+/// `(&str, u16)` tuples whose first element is an IP address or a host
+/// name. Host names are resolved with
+/// [`resolver::lookup_host`](super::resolver::lookup_host) by `connect`,
+/// while resolved addresses are used as is.
+///
+/// The trait is sealed: it cannot be implemented outside of Cadentis.
+///
+/// [`TcpListener::bind`]: super::TcpListener::bind
+/// [`TcpStream::connect`]: super::TcpStream::connect
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// let listener = TcpListener::bind(SocketAddr::from(([127, 0, 0, 1], 0)))?;
+/// let port = listener.local_addr()?.port();
+///
+/// let stream = TcpStream::connect((Ipv4Addr::LOCALHOST, port)).await?;
+/// ```
+pub trait ToSocketAddr: sealed::Sealed {}
+
+pub(crate) use sealed::Target;
+
+mod sealed {
+    use std::borrow::Cow;
+    use std::net::SocketAddr;
+
+    /// An address argument, ready to be used or resolved.
+    pub enum Target<'a> {
+        /// An address that needs no resolution.
+        Addr(SocketAddr),
+
+        /// A `host:port` string to resolve.
+        Host(Cow<'a, str>),
+    }
+
+    pub trait Sealed {
+        /// Converts the argument into an address or a host to resolve.
+        fn to_target(&self) -> Target<'_>;
+    }
+}
+
+impl sealed::Sealed for str {
+    fn to_target(&self) -> Target<'_> {
+        match self.parse() {
+            Ok(addr) => Target::Addr(addr),
+            Err(_) => Target::Host(Cow::Borrowed(self)),
+        }
+    }
+}
+
+impl ToSocketAddr for str {}
+
+impl sealed::Sealed for String {
+    fn to_target(&self) -> Target<'_> {
+        self.as_str().to_target()
+    }
+}
+
+impl ToSocketAddr for String {}
+
+/// Converts a `(host, port)` pair, where `host` may be an IP address.
+fn host_port_target(host: &str, port: u16) -> Target<'static> {
+    match host.parse::<IpAddr>() {
+        Ok(ip) => Target::Addr(SocketAddr::new(ip, port)),
+        Err(_) => Target::Host(Cow::Owned(format!("{host}:{port}"))),
+    }
+}
+
+impl sealed::Sealed for (&str, u16) {
+    fn to_target(&self) -> Target<'_> {
+        host_port_target(self.0, self.1)
+    }
+}
+
+impl ToSocketAddr for (&str, u16) {}
+
+impl sealed::Sealed for (String, u16) {
+    fn to_target(&self) -> Target<'_> {
+        host_port_target(&self.0, self.1)
+    }
+}
+
+impl ToSocketAddr for (String, u16) {}
+
+impl sealed::Sealed for SocketAddr {
+    fn to_target(&self) -> Target<'_> {
+        Target::Addr(*self)
+    }
+}
+
+impl ToSocketAddr for SocketAddr {}
+
+impl sealed::Sealed for SocketAddrV4 {
+    fn to_target(&self) -> Target<'_> {
+        Target::Addr(SocketAddr::from(*self))
+    }
+}
+
+impl ToSocketAddr for SocketAddrV4 {}
+
+impl sealed::Sealed for SocketAddrV6 {
+    fn to_target(&self) -> Target<'_> {
+        Target::Addr(SocketAddr::from(*self))
+    }
+}
+
+impl ToSocketAddr for SocketAddrV6 {}
+
+impl sealed::Sealed for (IpAddr, u16) {
+    fn to_target(&self) -> Target<'_> {
+        Target::Addr(SocketAddr::from(*self))
+    }
+}
+
+impl ToSocketAddr for (IpAddr, u16) {}
+
+impl sealed::Sealed for (Ipv4Addr, u16) {
+    fn to_target(&self) -> Target<'_> {
+        Target::Addr(SocketAddr::from(*self))
+    }
+}
+
+impl ToSocketAddr for (Ipv4Addr, u16) {}
+
+impl sealed::Sealed for (Ipv6Addr, u16) {
+    fn to_target(&self) -> Target<'_> {
+        Target::Addr(SocketAddr::from(*self))
+    }
+}
+
+impl ToSocketAddr for (Ipv6Addr, u16) {}
+
+impl<T: sealed::Sealed + ?Sized> sealed::Sealed for &T {
+    fn to_target(&self) -> Target<'_> {
+        (**self).to_target()
+    }
+}
+
+impl<T: ToSocketAddr + ?Sized> ToSocketAddr for &T {}
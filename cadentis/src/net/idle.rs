@@ -0,0 +1,185 @@
+use crate::io::{AsyncRead, AsyncWrite};
+use crate::time::Clock;
+use crate::time::clock::current_clock;
+use crate::time::sleep::Sleep;
+
+use std::future::Future;
+use std::io;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+
+/// A connection that times out once it stays idle for too long.
+///
+/// `IdleTimeout` wraps a stream, typically a
+/// [`TcpStream`](super::TcpStream), and tracks the last time data was
+/// read from or written to it. A read waiting for data fails with
+/// [`io::ErrorKind::TimedOut`] as soon as the connection has seen no
+/// activity for the configured duration; every successful read or write
+/// restarts that countdown. Writes are never interrupted.
+///
+/// This is meant for servers dropping clients that went silent, without
+/// wrapping each read in a [`timeout`](crate::time::timeout) whose
+/// duration must be recomputed from the last activity.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// let (stream, _) = listener.accept().await?;
+/// let mut conn = IdleTimeout::new(stream, Duration::from_secs(60));
+///
+/// loop {
+///     match conn.read(&mut buffer).await {
+///         Ok(0) => break,
+///         Ok(n) => conn.write_all(&buffer[..n]).await?,
+///         Err(e) if e.kind() == io::ErrorKind::TimedOut => break,
+///         Err(e) => return Err(e),
+///     }
+/// }
+/// ```
+pub struct IdleTimeout<S> {
+    /// The wrapped stream.
+    inner: S,
+
+    /// How long the connection may stay idle.
+    timeout: Duration,
+
+    /// When data was last read or written.
+    last_activity: Instant,
+
+    /// Timer waking a pending read, with the deadline it was armed for.
+    timer: Option<(Instant, Sleep)>,
+
+    /// Clock the idle time is measured against.
+    clock: Arc<dyn Clock>,
+}
+
+impl<S> IdleTimeout<S> {
+    /// Wraps `inner`, failing reads once it stays idle for `timeout`.
+    ///
+    /// The countdown starts immediately.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called outside of a running runtime.
+    pub fn new(inner: S, timeout: Duration) -> Self {
+        let clock = current_clock();
+
+        Self {
+            inner,
+            timeout,
+            last_activity: clock.now(),
+            timer: None,
+            clock,
+        }
+    }
+
+    /// Returns the configured idle timeout.
+    pub fn timeout(&self) -> Duration {
+        self.timeout
+    }
+
+    /// Returns how long the connection has been idle.
+    pub fn idle_time(&self) -> Duration {
+        self.clock
+            .now()
+            .saturating_duration_since(self.last_activity)
+    }
+
+    /// Returns a reference to the wrapped stream.
+    pub fn get_ref(&self) -> &S {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the wrapped stream.
+    ///
+    /// Data transferred directly through the stream does not count as
+    /// activity.
+    pub fn get_mut(&mut self) -> &mut S {
+        &mut self.inner
+    }
+
+    /// Consumes the wrapper, returning the wrapped stream.
+    pub fn into_inner(self) -> S {
+        self.inner
+    }
+
+    /// Records activity on the connection, restarting the countdown.
+    fn touch(&mut self) {
+        self.last_activity = self.clock.now();
+    }
+
+    /// Returns `TimedOut` once the connection has been idle for too
+    /// long, or arranges for the task to be woken when it will be.
+    fn poll_idle(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<usize>> {
+        loop {
+            let deadline = self.last_activity + self.timeout;
+
+            if self.clock.now() >= deadline {
+                self.timer = None;
+                return Poll::Ready(Err(io::Error::new(
+                    io::ErrorKind::TimedOut,
+                    "connection idle for too long",
+                )));
+            }
+
+            // Activity moved the deadline: re-arm the timer rather than
+            // resetting it on every read or write.
+            if self.timer.as_ref().map(|(armed, _)| *armed) != Some(deadline) {
+                self.timer = Some((deadline, Sleep::until(deadline)));
+            }
+
+            let (_, sleep) = self.timer.as_mut().expect("timer armed above");
+            if Pin::new(sleep).poll(cx).is_pending() {
+                return Poll::Pending;
+            }
+        }
+    }
+}
+
+impl<S: AsyncRead + Unpin> AsyncRead for IdleTimeout<S> {
+    /// Reads from the wrapped stream, failing with `TimedOut` if no data
+    /// arrives before the connection has been idle for too long.
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buffer: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+
+        match Pin::new(&mut this.inner).poll_read(cx, buffer) {
+            Poll::Ready(Ok(n)) => {
+                this.touch();
+                Poll::Ready(Ok(n))
+            }
+            Poll::Ready(Err(e)) => Poll::Ready(Err(e)),
+            Poll::Pending => this.poll_idle(cx),
+        }
+    }
+}
+
+impl<S: AsyncWrite + Unpin> AsyncWrite for IdleTimeout<S> {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buffer: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+
+        let poll = Pin::new(&mut this.inner).poll_write(cx, buffer);
+        if let Poll::Ready(Ok(_)) = poll {
+            this.touch();
+        }
+
+        poll
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.get_mut().inner).poll_flush(cx)
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.get_mut().inner).poll_shutdown(cx)
+    }
+}
@@ -1,16 +1,46 @@
-//! TCP networking primitives.
+//! TCP and UDP networking primitives.
 //!
-//! This module provides asynchronous TCP networking types built
+//! This module provides asynchronous networking types built
 //! on top of the runtime reactor and poller.
 //!
 //! It exposes high-level abstractions for:
 //! - listening for incoming TCP connections,
 //! - establishing outbound TCP connections,
-//! - performing non-blocking I/O on TCP streams.
+//! - performing non-blocking I/O on TCP streams,
+//! - exchanging UDP datagrams, optionally as length-prefixed messages,
+//! - exchanging datagrams over Unix domain sockets ([`UnixDatagram`]),
+//! - accepting addresses as strings, `SocketAddr`s or tuples ([`ToSocketAddr`]),
+//! - dropping connections that stay idle ([`IdleTimeout`]),
+//! - resolving host names without blocking ([`resolver`]),
+//! - shutting servers down gracefully ([`GracefulShutdown`]).
 //!
 //! These types integrate directly with the runtime and should be
 //! used instead of blocking `std::net` sockets.
+pub(crate) mod accept;
+mod addr;
+mod idle;
+mod sendfile;
+mod shutdown;
+pub(crate) mod sigpipe;
+mod sockopt;
 mod tcp;
+mod udp;
+#[cfg(unix)]
+mod unix;
 
+pub mod resolver;
+
+pub use addr::ToSocketAddr;
+pub use idle::IdleTimeout;
+pub use shutdown::{ConnectionGuard, GracefulShutdown};
 pub use tcp::listener::TcpListener;
-pub use tcp::stream::TcpStream;
+pub use tcp::stream::{ReadHalf, SharedWriteHalf, TcpStream, WriteHalf};
+pub use udp::framed::UdpFramed;
+pub use udp::socket::UdpSocket;
+#[cfg(unix)]
+pub use unix::datagram::UnixDatagram;
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub use unix::seqpacket::{UnixSeqpacket, UnixSeqpacketListener};
+
+#[doc(inline)]
+pub use crate::io::{BufReader, BufWriter};
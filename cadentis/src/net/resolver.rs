@@ -0,0 +1,227 @@
+//! Asynchronous host name resolution.
+//!
+//! [`lookup_host`] resolves a `host:port` string to socket addresses
+//! without blocking the calling task. Resolution goes through the
+//! process-wide resolver, which defaults to [`SystemResolver`] and can be
+//! replaced with [`set_resolver`] to plug in custom name resolution, such
+//! as a service registry. Successful results are cached for
+//! [`DEFAULT_CACHE_TTL`], or the duration given to [`set_cache_ttl`].
+//!
+//! [`TcpStream::connect`](crate::net::TcpStream::connect) resolves host
+//! names through this module.
+
+use crate::runtime::blocking::spawn_blocking;
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::io;
+use std::net::{SocketAddr, ToSocketAddrs};
+use std::pin::Pin;
+use std::sync::{Arc, Mutex as Mutex_std, OnceLock};
+use std::time::{Duration, Instant};
+use std::vec;
+
+/// How long resolved addresses are cached by default.
+pub const DEFAULT_CACHE_TTL: Duration = Duration::from_secs(30);
+
+/// Maximum number of host names kept in the cache.
+const CACHE_CAPACITY: usize = 1024;
+
+/// Future returned by [`Resolve::resolve`].
+pub type Resolving = Pin<Box<dyn Future<Output = io::Result<Vec<SocketAddr>>> + Send>>;
+
+/// A name resolution strategy.
+///
+/// Implementors turn a `host:port` string into the socket addresses to
+/// try, in order of preference. Install one with [`set_resolver`].
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// struct MeshResolver { registry: Arc<Registry> }
+///
+/// impl Resolve for MeshResolver {
+///     fn resolve(&self, host: String) -> Resolving {
+///         let registry = self.registry.clone();
+///         Box::pin(async move { registry.endpoints(&host).await })
+///     }
+/// }
+///
+/// resolver::set_resolver(MeshResolver { registry });
+/// ```
+pub trait Resolve: Send + Sync + 'static {
+    /// Resolves `host`, a `host:port` string, to socket addresses.
+    fn resolve(&self, host: String) -> Resolving;
+}
+
+/// The resolver of the operating system.
+///
+/// Runs the blocking `getaddrinfo` lookup of [`ToSocketAddrs`] on the
+/// runtime blocking pool, so that worker threads never wait on DNS.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SystemResolver;
+
+impl Resolve for SystemResolver {
+    fn resolve(&self, host: String) -> Resolving {
+        Box::pin(async move {
+            spawn_blocking(move || host.to_socket_addrs().map(Iterator::collect)).await
+        })
+    }
+}
+
+/// The process-wide resolver and its cache.
+struct Registry {
+    /// Resolver used on cache misses.
+    resolver: Mutex_std<Arc<dyn Resolve>>,
+
+    /// Cached results, by host.
+    cache: Mutex_std<Cache>,
+}
+
+/// Resolved addresses cached by [`lookup_host`].
+struct Cache {
+    /// Addresses of each host and when they expire, if ever.
+    entries: HashMap<String, (Option<Instant>, Vec<SocketAddr>)>,
+
+    /// How long new entries stay valid.
+    ttl: Duration,
+
+    /// Number of times the resolver was replaced, so that addresses
+    /// resolved by a previous resolver are not cached.
+    generation: u64,
+}
+
+/// Returns the process-wide registry, created on first use.
+fn registry() -> &'static Registry {
+    static REGISTRY: OnceLock<Registry> = OnceLock::new();
+
+    REGISTRY.get_or_init(|| Registry {
+        resolver: Mutex_std::new(Arc::new(SystemResolver)),
+        cache: Mutex_std::new(Cache {
+            entries: HashMap::new(),
+            ttl: DEFAULT_CACHE_TTL,
+            generation: 0,
+        }),
+    })
+}
+
+/// Resolves `host`, a `host:port` string, to socket addresses.
+///
+/// Literal addresses such as `"127.0.0.1:8080"` or `"[::1]:53"` are
+/// returned as is. Other names go through the installed resolver, and
+/// successful results are cached.
+///
+/// # Errors
+///
+/// Returns the error reported by the resolver, or `NotFound` if it
+/// resolved the name to no address.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// for addr in resolver::lookup_host("example.com:443").await? {
+///     println!("{addr}");
+/// }
+/// ```
+pub async fn lookup_host(host: &str) -> io::Result<vec::IntoIter<SocketAddr>> {
+    if let Ok(addr) = host.parse::<SocketAddr>() {
+        return Ok(vec![addr].into_iter());
+    }
+
+    let registry = registry();
+
+    // The generation is read before the resolver, so that a lookup racing
+    // with `set_resolver` never caches what the replaced resolver returns.
+    let generation = {
+        let cache = registry.cache.lock().unwrap();
+
+        if let Some(addrs) = cache.get(host) {
+            return Ok(addrs.into_iter());
+        }
+
+        cache.generation
+    };
+
+    let resolver = registry.resolver.lock().unwrap().clone();
+    let addrs = resolver.resolve(host.to_owned()).await?;
+
+    if addrs.is_empty() {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("no address found for {host}"),
+        ));
+    }
+
+    registry
+        .cache
+        .lock()
+        .unwrap()
+        .insert(host.to_owned(), addrs.clone(), generation);
+
+    Ok(addrs.into_iter())
+}
+
+/// Replaces the process-wide resolver used by [`lookup_host`].
+///
+/// The cache is cleared, so that no address resolved by the previous
+/// resolver is returned afterwards, even by lookups still in flight.
+pub fn set_resolver<R: Resolve>(resolver: R) {
+    let registry = registry();
+
+    *registry.resolver.lock().unwrap() = Arc::new(resolver);
+
+    let mut cache = registry.cache.lock().unwrap();
+    cache.generation = cache.generation.wrapping_add(1);
+    cache.entries.clear();
+}
+
+/// Sets how long [`lookup_host`] caches resolved addresses.
+///
+/// A zero duration disables caching, and a duration too long to be
+/// represented caches addresses forever. Entries already cached keep
+/// their expiry.
+pub fn set_cache_ttl(ttl: Duration) {
+    registry().cache.lock().unwrap().ttl = ttl;
+}
+
+/// Drops every cached address.
+pub fn clear_cache() {
+    registry().cache.lock().unwrap().entries.clear();
+}
+
+impl Cache {
+    /// Returns the unexpired addresses of `host`.
+    fn get(&self, host: &str) -> Option<Vec<SocketAddr>> {
+        let now = Instant::now();
+
+        self.entries
+            .get(host)
+            .filter(|(expiry, _)| expiry.is_none_or(|expiry| expiry > now))
+            .map(|(_, addrs)| addrs.clone())
+    }
+
+    /// Caches `addrs` for `host`, evicting expired entries when full.
+    ///
+    /// Addresses resolved before the resolver of generation `generation`
+    /// was replaced are not cached.
+    fn insert(&mut self, host: String, addrs: Vec<SocketAddr>, generation: u64) {
+        if self.ttl.is_zero() || generation != self.generation {
+            return;
+        }
+
+        let now = Instant::now();
+
+        if self.entries.len() >= CACHE_CAPACITY {
+            self.entries
+                .retain(|_, (expiry, _)| expiry.is_none_or(|expiry| expiry > now));
+
+            if self.entries.len() >= CACHE_CAPACITY {
+                self.entries.clear();
+            }
+        }
+
+        // A TTL past the representable instants never expires.
+        self.entries
+            .insert(host, (now.checked_add(self.ttl), addrs));
+    }
+}
@@ -0,0 +1,132 @@
+//! Zero-copy transfer of file contents to a socket.
+//!
+//! [`send_file`] wraps the platform `sendfile` call: `sendfile(2)` on
+//! Linux and Android, `sendfile(2)` with its BSD signature on macOS.
+//! Other platforms report [`io::ErrorKind::Unsupported`], and callers
+//! fall back to copying through user space.
+//!
+//! Windows is one of them. Its `TransmitFile` only completes without
+//! blocking through overlapped I/O, which the readiness-based reactor
+//! does not drive, so files are copied there too.
+
+use nucleus::io::RawFd;
+use std::io;
+
+/// Sends up to `len` bytes of `file`, starting at `offset`, to `socket`.
+///
+/// Returns the number of bytes sent, `0` once `offset` reaches the end
+/// of the file, or `WouldBlock` if the socket cannot take any byte. The
+/// file position is left unchanged.
+pub(crate) fn send_file(socket: RawFd, file: RawFd, offset: u64, len: usize) -> io::Result<usize> {
+    sys::send_file(socket, file, offset, len)
+}
+
+/// Returns whether `err` means that `sendfile` cannot be used for this
+/// file or socket, so the bytes must be copied instead.
+pub(crate) fn is_unsupported(err: &io::Error) -> bool {
+    err.kind() == io::ErrorKind::Unsupported
+        || err
+            .raw_os_error()
+            .is_some_and(|code| sys::UNSUPPORTED.contains(&code))
+}
+
+#[cfg(all(
+    any(target_os = "linux", target_os = "android"),
+    target_pointer_width = "64"
+))]
+mod sys {
+    use crate::sys::sendfile;
+
+    use nucleus::io::RawFd;
+    use std::io;
+
+    /// `EINVAL`, `ENOSYS` and `EOPNOTSUPP`.
+    pub(super) const UNSUPPORTED: [i32; 3] = [22, 38, 95];
+
+    pub(super) fn send_file(
+        socket: RawFd,
+        file: RawFd,
+        offset: u64,
+        len: usize,
+    ) -> io::Result<usize> {
+        let mut offset = i64::try_from(offset)
+            .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "offset too large"))?;
+
+        // SAFETY: `offset` is a live `i64`; the kernel only reads the
+        // descriptors and updates `offset`.
+        let n = unsafe { sendfile(socket, file, &mut offset, len) };
+
+        if n < 0 {
+            return Err(io::Error::last_os_error());
+        }
+
+        Ok(n as usize)
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod sys {
+    use crate::sys::sendfile;
+
+    use nucleus::io::RawFd;
+    use std::io;
+    use std::ptr;
+
+    /// `EINVAL`, `ENOTSUP` and `EOPNOTSUPP`.
+    pub(super) const UNSUPPORTED: [i32; 3] = [22, 45, 102];
+
+    /// `EAGAIN`.
+    const EAGAIN: i32 = 35;
+
+    pub(super) fn send_file(
+        socket: RawFd,
+        file: RawFd,
+        offset: u64,
+        len: usize,
+    ) -> io::Result<usize> {
+        let offset = i64::try_from(offset)
+            .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "offset too large"))?;
+        let mut sent = i64::try_from(len).unwrap_or(i64::MAX);
+
+        // SAFETY: `sent` is a live `i64` the kernel overwrites with the
+        // number of bytes sent; no header or trailer is passed.
+        let result = unsafe { sendfile(file, socket, offset, &mut sent, ptr::null_mut(), 0) };
+
+        if result != 0 {
+            let err = io::Error::last_os_error();
+
+            // A partial send reports `EAGAIN` along with the progress made.
+            if err.raw_os_error() != Some(EAGAIN) || sent == 0 {
+                return Err(err);
+            }
+        }
+
+        Ok(sent as usize)
+    }
+}
+
+#[cfg(not(any(
+    all(
+        any(target_os = "linux", target_os = "android"),
+        target_pointer_width = "64"
+    ),
+    target_os = "macos"
+)))]
+mod sys {
+    use nucleus::io::RawFd;
+    use std::io;
+
+    pub(super) const UNSUPPORTED: [i32; 0] = [];
+
+    pub(super) fn send_file(
+        _socket: RawFd,
+        _file: RawFd,
+        _offset: u64,
+        _len: usize,
+    ) -> io::Result<usize> {
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "sendfile is not available on this platform",
+        ))
+    }
+}
@@ -0,0 +1,174 @@
+use super::{TcpListener, TcpStream};
+use crate::sync::Notify;
+
+use std::future::{Future, poll_fn};
+use std::io;
+use std::net::SocketAddr;
+use std::pin::pin;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::task::Poll;
+
+/// Coordinates the graceful shutdown of a server.
+///
+/// A `GracefulShutdown` token is shared (by cloning) between the accept
+/// loop, the connection handlers, and whoever decides to stop the
+/// server:
+/// - [`accept`](Self::accept) accepts connections until the token is
+///   triggered, then returns `None`,
+/// - every accepted connection comes with a [`ConnectionGuard`] that
+///   keeps it counted as active until dropped,
+/// - [`shutdown`](Self::shutdown) triggers the token and resolves once
+///   every active connection has completed.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// let shutdown = GracefulShutdown::new();
+///
+/// let server = task::spawn({
+///     let shutdown = shutdown.clone();
+///     async move {
+///         while let Some(conn) = shutdown.accept(&listener).await {
+///             let (stream, _peer, guard) = conn?;
+///
+///             task::spawn(async move {
+///                 handle(stream).await;
+///                 drop(guard);
+///             });
+///         }
+///         io::Result::Ok(())
+///     }
+/// });
+///
+/// // Later: stop accepting and drain in-flight connections.
+/// shutdown.shutdown().await;
+/// ```
+#[derive(Clone, Default)]
+pub struct GracefulShutdown {
+    inner: Arc<Inner>,
+}
+
+/// State shared by every clone of a [`GracefulShutdown`].
+#[derive(Default)]
+struct Inner {
+    /// Whether shutdown has been triggered.
+    triggered: AtomicBool,
+
+    /// Number of connections still active.
+    active: AtomicUsize,
+
+    /// Notified when shutdown is triggered.
+    trigger: Notify,
+
+    /// Notified when the last active connection completes.
+    idle: Notify,
+}
+
+impl GracefulShutdown {
+    /// Creates a new, untriggered shutdown token.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Stops accepting new connections.
+    ///
+    /// Pending and future calls to [`accept`](Self::accept) return
+    /// `None`. Active connections are not interrupted.
+    pub fn trigger(&self) {
+        self.inner.triggered.store(true, Ordering::Release);
+        self.inner.trigger.notify_waiters();
+    }
+
+    /// Returns `true` once shutdown has been triggered.
+    pub fn is_triggered(&self) -> bool {
+        self.inner.triggered.load(Ordering::Acquire)
+    }
+
+    /// Returns the number of connections still active.
+    pub fn active_connections(&self) -> usize {
+        self.inner.active.load(Ordering::Acquire)
+    }
+
+    /// Accepts a connection from `listener`, unless shutdown is triggered.
+    ///
+    /// Returns `None` once the token is triggered, including while
+    /// waiting for a client. The returned [`ConnectionGuard`] counts the
+    /// connection as active until it is dropped.
+    pub async fn accept(
+        &self,
+        listener: &TcpListener,
+    ) -> Option<io::Result<(TcpStream, SocketAddr, ConnectionGuard)>> {
+        let mut triggered = pin!(self.inner.trigger.notified());
+        let mut accept = pin!(listener.accept());
+
+        poll_fn(|cx| {
+            // Register for the trigger before checking the flag, so that
+            // a trigger happening in between is not missed.
+            let notified = triggered.as_mut().poll(cx).is_ready();
+
+            if notified || self.is_triggered() {
+                return Poll::Ready(None);
+            }
+
+            match accept.as_mut().poll(cx) {
+                Poll::Ready(Ok((stream, address))) => {
+                    Poll::Ready(Some(Ok((stream, address, self.track()))))
+                }
+                Poll::Ready(Err(e)) => Poll::Ready(Some(Err(e))),
+                Poll::Pending => Poll::Pending,
+            }
+        })
+        .await
+    }
+
+    /// Counts a connection as active until the returned guard is dropped.
+    ///
+    /// Connections obtained through [`accept`](Self::accept) are already
+    /// tracked; use this for work accepted by other means.
+    pub fn track(&self) -> ConnectionGuard {
+        self.inner.active.fetch_add(1, Ordering::AcqRel);
+
+        ConnectionGuard {
+            inner: self.inner.clone(),
+        }
+    }
+
+    /// Triggers shutdown and waits for every active connection to complete.
+    pub async fn shutdown(&self) {
+        self.trigger();
+
+        loop {
+            let mut idle = pin!(self.inner.idle.notified());
+
+            // Register for the notification before checking the counter,
+            // so that the last guard dropping in between is not missed.
+            let registered = poll_fn(|cx| Poll::Ready(idle.as_mut().poll(cx).is_pending())).await;
+
+            if self.active_connections() == 0 {
+                return;
+            }
+
+            if registered {
+                idle.await;
+            }
+        }
+    }
+}
+
+/// Keeps a connection counted as active by a [`GracefulShutdown`].
+///
+/// Dropping the guard marks the connection as completed.
+pub struct ConnectionGuard {
+    inner: Arc<Inner>,
+}
+
+impl Drop for ConnectionGuard {
+    /// Marks the connection as completed, waking `shutdown` if it was
+    /// the last one.
+    fn drop(&mut self) {
+        if self.inner.active.fetch_sub(1, Ordering::AcqRel) == 1 {
+            self.inner.idle.notify_waiters();
+        }
+    }
+}
@@ -0,0 +1,76 @@
+//! Socket writes that never raise `SIGPIPE`.
+//!
+//! On unix, writing to a connection the peer has reset raises `SIGPIPE`,
+//! whose default action kills the process, instead of only failing with
+//! `EPIPE`. Linux and Android writes pass `MSG_NOSIGNAL` to `send(2)`;
+//! Apple platforms have no such flag, and sockets are created with
+//! `SO_NOSIGPIPE` instead. The write then only reports the error, which
+//! reaches the task waiting on it.
+
+use nucleus::io::RawFd;
+use std::io;
+
+/// Writes `buffer` to the socket `fd`, returning the number of bytes
+/// written, or `-1` with the error left in `errno`.
+pub(crate) fn send(fd: RawFd, buffer: &[u8]) -> isize {
+    sys::send(fd, buffer)
+}
+
+/// Keeps writes to the socket `fd` from raising `SIGPIPE`, on platforms
+/// where this is a property of the socket rather than of each write.
+pub(crate) fn suppress(fd: RawFd) -> io::Result<()> {
+    sys::suppress(fd)
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+mod sys {
+    use nucleus::io::RawFd;
+    use std::io;
+
+    const MSG_NOSIGNAL: i32 = 0x4000;
+
+    pub(super) fn send(fd: RawFd, buffer: &[u8]) -> isize {
+        // SAFETY: `buffer` is a live slice of `buffer.len()` bytes, which
+        // the kernel only reads.
+        unsafe { crate::sys::send(fd, buffer.as_ptr().cast(), buffer.len(), MSG_NOSIGNAL) }
+    }
+
+    pub(super) fn suppress(_: RawFd) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(any(target_os = "macos", target_os = "ios"))]
+mod sys {
+    use crate::net::sockopt;
+
+    use nucleus::io::{RawFd, sys_write};
+    use std::io;
+
+    pub(super) fn send(fd: RawFd, buffer: &[u8]) -> isize {
+        sys_write(fd, buffer)
+    }
+
+    pub(super) fn suppress(fd: RawFd) -> io::Result<()> {
+        sockopt::set_no_sigpipe(fd)
+    }
+}
+
+#[cfg(not(any(
+    target_os = "linux",
+    target_os = "android",
+    target_os = "macos",
+    target_os = "ios"
+)))]
+mod sys {
+    use nucleus::io::{RawFd, sys_write};
+    use std::io;
+
+    pub(super) fn send(fd: RawFd, buffer: &[u8]) -> isize {
+        sys_write(fd, buffer)
+    }
+
+    pub(super) fn suppress(_: RawFd) -> io::Result<()> {
+        Ok(())
+    }
+}
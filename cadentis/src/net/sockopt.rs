@@ -0,0 +1,163 @@
+//! Socket options not covered by the platform layer.
+//!
+//! These wrap `setsockopt` / `getsockopt` for the socket-level options
+//! the networking types expose, on unix and windows.
+
+use nucleus::io::RawFd;
+use std::io;
+
+/// A socket buffer, selecting `SO_RCVBUF` or `SO_SNDBUF`.
+#[derive(Clone, Copy)]
+pub(crate) enum Buffer {
+    /// The receive buffer (`SO_RCVBUF`).
+    Recv,
+
+    /// The send buffer (`SO_SNDBUF`).
+    Send,
+}
+
+/// Sets the size of a socket buffer, in bytes.
+///
+/// # Errors
+///
+/// Returns `InvalidInput` if `size` does not fit the option type, or the
+/// OS error reported by `setsockopt`.
+pub(crate) fn set_buffer_size(fd: RawFd, buffer: Buffer, size: usize) -> io::Result<()> {
+    let value = i32::try_from(size)
+        .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "buffer size is too large"))?;
+
+    sys::set_option(fd, sys::SOL_SOCKET, sys::option(buffer), value)
+}
+
+/// Returns the size of a socket buffer, in bytes, as reported by the OS.
+pub(crate) fn buffer_size(fd: RawFd, buffer: Buffer) -> io::Result<usize> {
+    let value = sys::get_option(fd, sys::SOL_SOCKET, sys::option(buffer))?;
+
+    Ok(value.max(0) as usize)
+}
+
+/// Keeps writes to the socket from raising `SIGPIPE` (`SO_NOSIGPIPE`).
+#[cfg(any(target_os = "macos", target_os = "ios"))]
+pub(crate) fn set_no_sigpipe(fd: RawFd) -> io::Result<()> {
+    sys::set_option(fd, sys::SOL_SOCKET, sys::SO_NOSIGPIPE, 1)
+}
+
+#[cfg(unix)]
+mod sys {
+    use super::Buffer;
+    use crate::sys::{getsockopt, setsockopt};
+
+    use nucleus::io::RawFd;
+    use std::io;
+    use std::mem;
+
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    pub(super) const SOL_SOCKET: i32 = 1;
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    const SO_SNDBUF: i32 = 7;
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    const SO_RCVBUF: i32 = 8;
+
+    #[cfg(not(any(target_os = "linux", target_os = "android")))]
+    pub(super) const SOL_SOCKET: i32 = 0xffff;
+    #[cfg(not(any(target_os = "linux", target_os = "android")))]
+    const SO_SNDBUF: i32 = 0x1001;
+    #[cfg(not(any(target_os = "linux", target_os = "android")))]
+    const SO_RCVBUF: i32 = 0x1002;
+
+    #[cfg(any(target_os = "macos", target_os = "ios"))]
+    pub(super) const SO_NOSIGPIPE: i32 = 0x1022;
+
+    pub(super) fn option(buffer: Buffer) -> i32 {
+        match buffer {
+            Buffer::Recv => SO_RCVBUF,
+            Buffer::Send => SO_SNDBUF,
+        }
+    }
+
+    pub(super) fn set_option(fd: RawFd, level: i32, name: i32, value: i32) -> io::Result<()> {
+        let len = mem::size_of::<i32>() as u32;
+
+        // SAFETY: `value` is a live `i32` and `len` is its size.
+        let result = unsafe { setsockopt(fd, level, name, &value as *const i32 as _, len) };
+
+        if result != 0 {
+            return Err(io::Error::last_os_error());
+        }
+
+        Ok(())
+    }
+
+    pub(super) fn get_option(fd: RawFd, level: i32, name: i32) -> io::Result<i32> {
+        let mut value = 0i32;
+        let mut len = mem::size_of::<i32>() as u32;
+
+        // SAFETY: `value` and `len` are live and writable, and `len` holds
+        // the size of `value`.
+        let result = unsafe { getsockopt(fd, level, name, &mut value as *mut i32 as _, &mut len) };
+
+        if result != 0 {
+            return Err(io::Error::last_os_error());
+        }
+
+        Ok(value)
+    }
+}
+
+#[cfg(windows)]
+mod sys {
+    use super::Buffer;
+    use crate::sys::{getsockopt, setsockopt};
+
+    use nucleus::io::RawFd;
+    use std::io;
+    use std::mem;
+
+    pub(super) const SOL_SOCKET: i32 = 0xffff;
+    const SO_SNDBUF: i32 = 0x1001;
+    const SO_RCVBUF: i32 = 0x1002;
+
+    pub(super) fn option(buffer: Buffer) -> i32 {
+        match buffer {
+            Buffer::Recv => SO_RCVBUF,
+            Buffer::Send => SO_SNDBUF,
+        }
+    }
+
+    pub(super) fn set_option(fd: RawFd, level: i32, name: i32, value: i32) -> io::Result<()> {
+        let len = mem::size_of::<i32>() as i32;
+
+        // SAFETY: `value` is a live `i32` and `len` is its size.
+        let result =
+            unsafe { setsockopt(fd as usize, level, name, &value as *const i32 as _, len) };
+
+        if result != 0 {
+            return Err(io::Error::last_os_error());
+        }
+
+        Ok(())
+    }
+
+    pub(super) fn get_option(fd: RawFd, level: i32, name: i32) -> io::Result<i32> {
+        let mut value = 0i32;
+        let mut len = mem::size_of::<i32>() as i32;
+
+        // SAFETY: `value` and `len` are live and writable, and `len` holds
+        // the size of `value`.
+        let result = unsafe {
+            getsockopt(
+                fd as usize,
+                level,
+                name,
+                &mut value as *mut i32 as _,
+                &mut len,
+            )
+        };
+
+        if result != 0 {
+            return Err(io::Error::last_os_error());
+        }
+
+        Ok(value)
+    }
+}
@@ -1,5 +1,8 @@
 use super::stream::TcpStream;
+use crate::net::ToSocketAddr;
+use crate::net::addr::Target;
 use crate::reactor::future::AcceptFuture;
+use crate::sync::{OwnedSemaphorePermit, Semaphore};
 
 use nucleus::address::sys_parse_sockaddr;
 use nucleus::io::{RawFd, sys_close};
@@ -7,7 +10,9 @@ use nucleus::socket::{
     sys_bind, sys_ipv6_is_necessary, sys_listen, sys_set_reuseaddr, sys_socket, sys_sockname,
 };
 use std::io;
-use std::net::SocketAddr;
+use std::mem::ManuallyDrop;
+use std::net::{self, SocketAddr};
+use std::sync::Arc;
 
 /// An asynchronous TCP listener.
 ///
@@ -23,16 +28,20 @@ pub struct TcpListener {
 impl TcpListener {
     /// Binds a TCP listener to the given address.
     ///
-    /// The address must be a valid socket address string, such as
-    /// `"127.0.0.1:8080"` or `"[::1]:8080"`.
+    /// The address is a [`SocketAddr`], an `(ip, port)` tuple, or a
+    /// socket address string such as `"127.0.0.1:8080"` or
+    /// `"[::1]:8080"`; see [`ToSocketAddr`].
     ///
     /// This function:
     /// - creates a non-blocking socket,
     /// - enables `SO_REUSEADDR`,
     /// - configures IPv6 dual-stack if applicable,
     /// - binds and starts listening.
-    pub fn bind(address: &str) -> io::Result<Self> {
-        let (storage, len) = sys_parse_sockaddr(address)?;
+    pub fn bind(address: impl ToSocketAddr) -> io::Result<Self> {
+        let (storage, len) = match address.to_target() {
+            Target::Addr(addr) => sys_parse_sockaddr(&addr.to_string())?,
+            Target::Host(host) => sys_parse_sockaddr(&host)?,
+        };
         let domain = storage.ss_family as i32;
 
         let fd = sys_socket(domain)?;
@@ -45,17 +54,119 @@ impl TcpListener {
         Ok(Self { fd })
     }
 
+    /// Creates a listener from an already bound `std::net::TcpListener`.
+    ///
+    /// This is how sockets handed over by the environment (systemd
+    /// socket activation, a parent process, ...) are served with
+    /// Cadentis. The socket is switched to non-blocking mode.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the socket cannot be made non-blocking.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// // fd 3 is the first socket passed by systemd.
+    /// let std_listener = unsafe { std::net::TcpListener::from_raw_fd(3) };
+    /// let listener = TcpListener::from_std(std_listener)?;
+    /// ```
+    pub fn from_std(listener: net::TcpListener) -> io::Result<Self> {
+        listener.set_nonblocking(true)?;
+
+        #[cfg(unix)]
+        let fd = {
+            use std::os::fd::IntoRawFd;
+            listener.into_raw_fd()
+        };
+
+        #[cfg(windows)]
+        let fd = {
+            use std::os::windows::io::IntoRawSocket;
+            listener.into_raw_socket()
+        };
+
+        Ok(Self { fd })
+    }
+
+    /// Converts this listener back into a `std::net::TcpListener`.
+    ///
+    /// The returned listener is still in non-blocking mode; call
+    /// `set_nonblocking(false)` on it to use it with blocking calls.
+    pub fn into_std(self) -> net::TcpListener {
+        // The socket now belongs to the std listener: skip our `Drop`.
+        let fd = ManuallyDrop::new(self).fd;
+
+        #[cfg(unix)]
+        {
+            use std::os::fd::FromRawFd;
+            unsafe { net::TcpListener::from_raw_fd(fd) }
+        }
+
+        #[cfg(windows)]
+        {
+            use std::os::windows::io::FromRawSocket;
+            unsafe { net::TcpListener::from_raw_socket(fd) }
+        }
+    }
+
     /// Accepts an incoming TCP connection.
     ///
     /// This method asynchronously waits until a client connects,
     /// then returns a [`TcpStream`] and the peer address.
+    ///
+    /// If the process runs out of file descriptors, the connection is
+    /// left in the backlog and accepting is retried after a short
+    /// backoff, rather than failing with `EMFILE` / `ENFILE`.
     pub async fn accept(&self) -> io::Result<(TcpStream, SocketAddr)> {
         let (fd, address) = AcceptFuture::new(self.fd).await?;
 
         Ok((TcpStream::new(fd), address))
     }
 
+    /// Accepts an incoming TCP connection once `limit` has a permit free.
+    ///
+    /// The semaphore caps the number of live connections: while all its
+    /// permits are held, no connection is accepted and new clients wait
+    /// in the backlog. The returned permit is released when dropped, so
+    /// keep it alongside the connection and drop both together.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// let limit = Arc::new(Semaphore::new(1024));
+    ///
+    /// loop {
+    ///     let (stream, _, permit) = listener.accept_limited(&limit).await?;
+    ///
+    ///     task::spawn(async move {
+    ///         serve(stream).await;
+    ///         drop(permit);
+    ///     });
+    /// }
+    /// ```
+    pub async fn accept_limited(
+        &self,
+        limit: &Arc<Semaphore>,
+    ) -> io::Result<(TcpStream, SocketAddr, OwnedSemaphorePermit)> {
+        let permit = limit.clone().acquire_owned().await;
+        let (stream, address) = self.accept().await?;
+
+        Ok((stream, address, permit))
+    }
+
     /// Returns the local socket address of this listener.
+    ///
+    /// When the listener was bound to port `0`, the returned address
+    /// carries the ephemeral port assigned by the operating system, for
+    /// both IPv4 and IPv6 listeners.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// let listener = TcpListener::bind("127.0.0.1:0")?;
+    /// let port = listener.local_addr()?.port();
+    /// ```
     pub fn local_addr(&self) -> io::Result<SocketAddr> {
         sys_sockname(self.fd)
     }
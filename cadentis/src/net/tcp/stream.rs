@@ -1,15 +1,34 @@
+use crate::fs::File;
+use crate::io::{AsyncRead, AsyncWrite};
+use crate::net::ToSocketAddr;
+use crate::net::addr::Target;
+use crate::net::resolver;
+use crate::net::sendfile;
+use crate::net::sigpipe;
+use crate::net::sockopt::{self, Buffer};
 use crate::reactor::command::Command;
-use crate::reactor::future::{ConnectFuture, ReadFutureStream, WriteFutureStream};
+use crate::reactor::future::{ConnectFuture, OwnedReadFuture, ReadFutureStream, WriteFutureStream};
 use crate::reactor::io::{IoEntry, Stream};
 use crate::runtime::context::CURRENT_REACTOR;
+use crate::tools::RetryConfig;
 
-use nucleus::address::{sockaddr_storage_to_socketaddr, sys_parse_sockaddr};
+use nucleus::address::sys_parse_sockaddr;
 use nucleus::io::{RawFd, sys_close};
 use nucleus::poll::Interest;
 use nucleus::socket::{sys_ipv6_is_necessary, sys_set_reuseaddr, sys_shutdown, sys_socket};
+use std::future::poll_fn;
 use std::io;
-use std::net::Shutdown;
+use std::mem::ManuallyDrop;
+use std::net::{self, Shutdown, SocketAddr};
+use std::pin::Pin;
+use std::ptr;
 use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+/// Largest chunk handed to a single `sendfile` call or copied at once by
+/// the fallback of [`TcpStream::send_file`].
+const SEND_FILE_CHUNK: usize = 1 << 20;
 
 /// An asynchronous TCP stream.
 ///
@@ -34,18 +53,16 @@ impl TcpStream {
     ///
     /// Panics if called outside of a running runtime (no reactor in context).
     pub fn new(fd: RawFd) -> Self {
-        let stream = Arc::new(Mutex::new(Stream {
-            fd,
-            in_buffer: Vec::new(),
-            out_buffer: Vec::new(),
-            read_waiters: Vec::new(),
-            write_waiters: Vec::new(),
-        }));
+        // Best effort: where it fails, a reset connection may still raise
+        // `SIGPIPE`, which most programs ignore anyway.
+        let _ = sigpipe::suppress(fd);
 
         CURRENT_REACTOR.with(|cell| {
             let binding = cell.borrow();
             let reactor = binding.as_ref().expect("no reactor in context");
 
+            let stream = Arc::new(Mutex::new(Stream::new(fd, reactor.clone())));
+
             let interest = Interest {
                 read: true,
                 write: true,
@@ -56,9 +73,97 @@ impl TcpStream {
                 interest,
                 entry: IoEntry::Stream(stream.clone()),
             });
-        });
 
-        Self { stream }
+            Self { stream }
+        })
+    }
+
+    /// Creates a stream from a connected `std::net::TcpStream`.
+    ///
+    /// The socket is switched to non-blocking mode and registered with
+    /// the reactor, which lets Cadentis serve connections accepted or
+    /// inherited outside the runtime.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the socket cannot be made non-blocking.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called outside of a running runtime (no reactor in context).
+    pub fn from_std(stream: net::TcpStream) -> io::Result<Self> {
+        stream.set_nonblocking(true)?;
+
+        #[cfg(unix)]
+        let fd = {
+            use std::os::fd::IntoRawFd;
+            stream.into_raw_fd()
+        };
+
+        #[cfg(windows)]
+        let fd = {
+            use std::os::windows::io::IntoRawSocket;
+            stream.into_raw_socket()
+        };
+
+        Ok(Self::new(fd))
+    }
+
+    /// Converts this stream back into a `std::net::TcpStream`.
+    ///
+    /// The socket is deregistered from the reactor and returned in
+    /// non-blocking mode; call `set_nonblocking(false)` on it to use it
+    /// with blocking calls. This blocks until the reactor has let go of
+    /// the socket, which it then no longer reads from nor closes.
+    ///
+    /// Bytes already received into the stream's input buffer but not
+    /// read yet, and bytes still queued in its output buffer, are lost.
+    ///
+    /// # Errors
+    ///
+    /// Hands the stream back, still registered, if other handles to it
+    /// are alive: clones, split halves, or pending
+    /// [`read_buf`](Self::read_buf) futures.
+    pub fn into_std(self) -> Result<net::TcpStream, Self> {
+        let (fd, reactor) = {
+            let stream = self.stream.lock().unwrap();
+            (stream.fd, stream.reactor.clone())
+        };
+
+        reactor.detach(fd);
+
+        // Only checked once the reactor dropped its own reference.
+        if Arc::strong_count(&self.stream) > 1 {
+            let interest = self.stream.lock().unwrap().interest();
+
+            let _ = reactor.send(Command::Register {
+                fd,
+                interest,
+                entry: IoEntry::Stream(self.stream.clone()),
+            });
+
+            return Err(self);
+        }
+
+        // The socket now belongs to the std stream: skip our `Drop`,
+        // which would otherwise close it.
+        let this = ManuallyDrop::new(self);
+
+        // SAFETY: `this` is never used or dropped again, so the shared
+        // state is moved out exactly once.
+        drop(unsafe { ptr::read(&this.stream) });
+
+        #[cfg(unix)]
+        {
+            use std::os::fd::FromRawFd;
+            Ok(unsafe { net::TcpStream::from_raw_fd(fd) })
+        }
+
+        #[cfg(windows)]
+        {
+            use std::os::windows::io::FromRawSocket;
+            Ok(unsafe { net::TcpStream::from_raw_socket(fd) })
+        }
     }
 
     /// Returns a future that reads up to `buffer.len()` bytes.
@@ -66,10 +171,53 @@ impl TcpStream {
     /// This reads from the stream's internal input buffer filled by
     /// the reactor. If no data is available yet, the current task is
     /// registered as a read waiter.
+    ///
+    /// # Cancel safety
+    ///
+    /// This method is cancel safe. Bytes are taken out of the input
+    /// buffer only when the future completes, so if it is dropped before
+    /// completing, for instance because another branch of a
+    /// [`select!`](crate::select) completed first, no data is lost: the
+    /// next read sees every byte received so far.
     pub fn read<'a>(&'a self, buffer: &'a mut [u8]) -> ReadFutureStream<'a> {
         ReadFutureStream::new(self.stream.clone(), buffer)
     }
 
+    /// Returns a future that reads up to `buffer.len()` bytes into an
+    /// owned buffer.
+    ///
+    /// Unlike [`read`](Self::read), the future takes ownership of the
+    /// buffer instead of borrowing it, so it can be stored in a struct
+    /// or moved across tasks. It resolves with the buffer and the number
+    /// of bytes read, letting the caller reuse the allocation.
+    ///
+    /// As with `read`, the vector's length, not its capacity, bounds the
+    /// read: a `Vec::with_capacity(n)` of length `0` reads nothing.
+    ///
+    /// # Cancel safety
+    ///
+    /// This method is cancel safe, like [`read`](Self::read): dropping the
+    /// future before it completes loses no data, only the buffer.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// let mut buffer = vec![0u8; 4096];
+    ///
+    /// loop {
+    ///     let (buf, n) = stream.read_buf(buffer).await?;
+    ///     if n == 0 {
+    ///         break;
+    ///     }
+    ///
+    ///     process(&buf[..n]);
+    ///     buffer = buf;
+    /// }
+    /// ```
+    pub fn read_buf(&self, buffer: Vec<u8>) -> OwnedReadFuture {
+        OwnedReadFuture::new(self.stream.clone(), buffer)
+    }
+
     /// Returns a future that writes data from `buffer`.
     ///
     /// The data is appended to the stream's output buffer and is flushed
@@ -103,29 +251,453 @@ impl TcpStream {
         Ok(())
     }
 
+    /// Sends `len` bytes of `file`, starting at `offset`, to the peer.
+    ///
+    /// Bytes already queued by [`write`](Self::write) are flushed first,
+    /// so the file contents follow them on the wire. The contents are
+    /// then handed to the kernel with `sendfile` where available (Linux,
+    /// Android and macOS), without being copied through user space; the
+    /// task waits for the socket to be writable whenever it is full.
+    /// Elsewhere, Windows included, or when `sendfile` rejects the file,
+    /// the contents are read and written through the stream instead.
+    ///
+    /// Resolves with the number of bytes sent, which is less than `len`
+    /// only if the file ends first. The file position is left unchanged.
+    /// Other writes on this stream must not run concurrently.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error reported while reading the file or writing to
+    /// the socket.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// let file = File::open("index.html").await?;
+    /// let len = fs::metadata("index.html")?.len();
+    ///
+    /// stream.write_all(&headers).await?;
+    /// stream.send_file(&file, 0, len).await?;
+    /// ```
+    pub async fn send_file(&self, file: &File, offset: u64, len: u64) -> io::Result<u64> {
+        poll_fn(|cx| self.stream.lock().unwrap().poll_flush(cx)).await?;
+
+        let mut sent = 0;
+
+        while sent < len {
+            let chunk = (len - sent).min(SEND_FILE_CHUNK as u64) as usize;
+
+            let result = poll_fn(|cx| {
+                let mut stream = self.stream.lock().unwrap();
+
+                match sendfile::send_file(stream.fd, file.fd(), offset + sent, chunk) {
+                    Err(err) if err.kind() == io::ErrorKind::WouldBlock => {
+                        // Woken by the reactor once the socket is writable.
+                        stream.write_waiters.push(cx.waker().clone());
+                        Poll::Pending
+                    }
+                    result => {
+                        if let Ok(n) = result {
+                            stream.bytes_written += n as u64;
+                        }
+                        Poll::Ready(result)
+                    }
+                }
+            })
+            .await;
+
+            match result {
+                Ok(0) => break,
+                Ok(n) => sent += n as u64,
+                Err(err) if sent == 0 && sendfile::is_unsupported(&err) => {
+                    return self.copy_file(file, offset, len).await;
+                }
+                Err(err) => return Err(err),
+            }
+        }
+
+        Ok(sent)
+    }
+
+    /// Sends part of `file` by copying it through the output buffer.
+    ///
+    /// Fallback of [`send_file`](Self::send_file) when `sendfile` cannot
+    /// be used.
+    async fn copy_file(&self, file: &File, offset: u64, len: u64) -> io::Result<u64> {
+        let mut buffer = vec![0u8; (len as usize).min(SEND_FILE_CHUNK)];
+        let mut sent = 0;
+
+        while sent < len {
+            let chunk = (len - sent).min(buffer.len() as u64) as usize;
+            let n = file.read_at(&mut buffer[..chunk], offset + sent)?;
+
+            if n == 0 {
+                break;
+            }
+
+            self.write_all(&buffer[..n]).await?;
+            sent += n as u64;
+        }
+
+        Ok(sent)
+    }
+
     /// Establishes a TCP connection to `address`.
     ///
-    /// The address must be a string accepted by `SocketAddr::from_str`,
-    /// e.g. `"127.0.0.1:8080"` or `"[::1]:8080"`.
+    /// The address is a [`SocketAddr`], an `(ip, port)` tuple, a literal
+    /// socket address string such as `"127.0.0.1:8080"`, or a host name
+    /// given as `"host:port"` or `(host, port)`, which is resolved with
+    /// [`lookup_host`](crate::net::resolver::lookup_host); see
+    /// [`ToSocketAddr`]. Each resolved address is tried in turn until one
+    /// connects.
     ///
     /// This creates a non-blocking socket, configures common options
     /// (such as `SO_REUSEADDR`), performs the connection, and then
     /// registers the stream with the reactor.
-    pub async fn connect(address: &str) -> io::Result<Self> {
-        let (storage, _) = sys_parse_sockaddr(address)?;
-        let addr = sockaddr_storage_to_socketaddr(&storage)?;
+    ///
+    /// # Errors
+    ///
+    /// Returns the resolution error, or the error of the last address
+    /// tried if none of them accepted the connection.
+    pub async fn connect(address: impl ToSocketAddr) -> io::Result<Self> {
+        let addrs = match address.to_target() {
+            Target::Addr(addr) => return Self::connect_addr(addr).await,
+            Target::Host(host) => resolver::lookup_host(&host).await?,
+        };
+
+        let mut last_error = None;
+
+        for addr in addrs {
+            match Self::connect_addr(addr).await {
+                Ok(stream) => return Ok(stream),
+                Err(error) => last_error = Some(error),
+            }
+        }
+
+        Err(last_error.unwrap_or_else(|| {
+            io::Error::new(io::ErrorKind::InvalidInput, "no address to connect to")
+        }))
+    }
+
+    /// Establishes a TCP connection to `address`, retrying transient
+    /// failures according to `config`.
+    ///
+    /// Each attempt is a [`connect`](Self::connect). Failures that may go
+    /// away on their own, such as a refused connection while the server
+    /// restarts, a timeout or an unreachable network, are retried after
+    /// the interval of `config`. Other failures, such as an invalid
+    /// address, are returned right away.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first permanent error, or the error of the last
+    /// attempt once the retries of `config` are exhausted.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// // The database may still be starting up.
+    /// let policy = RetryConfig::new(10).interval(Duration::from_millis(500));
+    /// let stream = TcpStream::connect_retry("db.internal:5432", policy).await?;
+    /// ```
+    pub async fn connect_retry(
+        address: impl ToSocketAddr,
+        config: RetryConfig,
+    ) -> io::Result<Self> {
+        let address = match address.to_target() {
+            Target::Addr(addr) => addr.to_string(),
+            Target::Host(host) => host.into_owned(),
+        };
+
+        // A permanent error resolves the attempt successfully, which ends
+        // the retries: only transient errors are retried.
+        let attempt = config.retry(move || {
+            let address = address.clone();
+
+            async move {
+                match Self::connect(address).await {
+                    Err(err) if is_transient(&err) => Err(err),
+                    result => Ok(result),
+                }
+            }
+        });
+
+        attempt.await?
+    }
+
+    /// Establishes a TCP connection to a resolved address.
+    async fn connect_addr(addr: SocketAddr) -> io::Result<Self> {
+        let (storage, _) = sys_parse_sockaddr(&addr.to_string())?;
 
         let domain = storage.ss_family as i32;
         let fd = sys_socket(domain)?;
 
-        sys_set_reuseaddr(fd)?;
-        sys_ipv6_is_necessary(fd, domain)?;
+        let connected = async {
+            sys_set_reuseaddr(fd)?;
+            sys_ipv6_is_necessary(fd, domain)?;
+            ConnectFuture::new(fd, addr).await
+        };
 
-        ConnectFuture::new(fd, addr).await?;
+        // A failed attempt must not leak its socket: `connect_retry` may
+        // make many of them.
+        if let Err(err) = connected.await {
+            sys_close(fd);
+            return Err(err);
+        }
 
         Ok(Self::new(fd))
     }
 
+    /// Waits until every buffered byte has been written to the socket,
+    /// then drops the stream.
+    ///
+    /// Writes are queued in an output buffer that the reactor flushes in
+    /// the background, and dropping a `TcpStream` discards whatever is
+    /// still queued. Call `finish` instead of a plain `drop` when the
+    /// data must be handed to the kernel before the stream goes away.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error reported while flushing the output buffer.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// stream.write_all(&response).await?;
+    /// stream.finish().await?;
+    /// ```
+    pub async fn finish(self) -> io::Result<()> {
+        poll_fn(|cx| self.stream.lock().unwrap().poll_flush(cx)).await
+    }
+
+    /// Waits until the stream is readable.
+    ///
+    /// The reactor reads incoming bytes into the stream's input buffer:
+    /// the stream is readable once that buffer holds data or the peer
+    /// has closed its write half, so that the next
+    /// [`try_read`](Self::try_read) returns without `WouldBlock`, unless
+    /// another task drains the buffer first.
+    ///
+    /// # Cancel safety
+    ///
+    /// This method is cancel safe: it never consumes data.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// loop {
+    ///     stream.readable().await?;
+    ///
+    ///     match stream.try_read(&mut buf) {
+    ///         Ok(0) => break,
+    ///         Ok(n) => session.feed(&buf[..n]),
+    ///         Err(e) if e.kind() == io::ErrorKind::WouldBlock => continue,
+    ///         Err(e) => return Err(e),
+    ///     }
+    /// }
+    /// ```
+    pub async fn readable(&self) -> io::Result<()> {
+        poll_fn(|cx| self.poll_read_ready(cx)).await
+    }
+
+    /// Polls for read readiness.
+    ///
+    /// Returns `Poll::Pending` and schedules a wake-up of the current
+    /// task if the stream is not readable; see [`readable`](Self::readable).
+    pub fn poll_read_ready(&self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.stream.lock().unwrap().poll_read_ready(cx)
+    }
+
+    /// Reads buffered bytes into `buffer` without waiting.
+    ///
+    /// Returns the number of bytes read, `0` once the peer has closed its
+    /// write half and every byte has been read.
+    ///
+    /// # Errors
+    ///
+    /// Returns `WouldBlock` if no data is available yet.
+    pub fn try_read(&self, buffer: &mut [u8]) -> io::Result<usize> {
+        self.stream.lock().unwrap().try_read(buffer)
+    }
+
+    /// Waits until the stream is writable.
+    ///
+    /// The stream is writable once the reactor has flushed every byte
+    /// previously queued, so that the next [`try_write`](Self::try_write)
+    /// accepts data.
+    pub async fn writable(&self) -> io::Result<()> {
+        poll_fn(|cx| self.poll_write_ready(cx)).await
+    }
+
+    /// Polls for write readiness.
+    ///
+    /// Returns `Poll::Pending` and schedules a wake-up of the current
+    /// task if the stream is not writable; see [`writable`](Self::writable).
+    pub fn poll_write_ready(&self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.stream.lock().unwrap().poll_write_ready(cx)
+    }
+
+    /// Queues the whole of `buffer` for writing without waiting.
+    ///
+    /// The bytes are written by the reactor as the socket accepts them;
+    /// [`writable`](Self::writable) resolves once they all are.
+    ///
+    /// # Errors
+    ///
+    /// Returns `WouldBlock` while previously queued bytes are still being
+    /// written.
+    pub fn try_write(&self, buffer: &[u8]) -> io::Result<usize> {
+        self.stream.lock().unwrap().try_write(buffer)
+    }
+
+    /// Returns `true` once the peer has closed its write half.
+    ///
+    /// The connection may still be half-open: writes keep reaching the
+    /// peer, while reads return `0` once the buffered data is consumed.
+    pub fn is_eof(&self) -> bool {
+        self.stream.lock().unwrap().eof
+    }
+
+    /// Sets the read timeout of the stream.
+    ///
+    /// Once set, a [`read`](Self::read) that waits longer than `timeout`
+    /// for data fails with [`io::ErrorKind::TimedOut`]. The timer starts
+    /// when the read first has to wait. Passing `None` disables the
+    /// timeout, which is the default.
+    ///
+    /// The setting is shared with every clone and split half of the
+    /// stream.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidInput` if `timeout` is `Some(Duration::ZERO)`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// stream.set_read_timeout(Some(Duration::from_secs(5)))?;
+    ///
+    /// match stream.read(&mut buf).await {
+    ///     Err(e) if e.kind() == io::ErrorKind::TimedOut => { /* idle peer */ }
+    ///     other => { /* ... */ }
+    /// }
+    /// ```
+    pub fn set_read_timeout(&self, timeout: Option<Duration>) -> io::Result<()> {
+        check_timeout(timeout)?;
+
+        self.stream.lock().unwrap().read_timeout = timeout;
+        Ok(())
+    }
+
+    /// Returns the read timeout of the stream.
+    pub fn read_timeout(&self) -> Option<Duration> {
+        self.stream.lock().unwrap().read_timeout
+    }
+
+    /// Sets the write timeout of the stream.
+    ///
+    /// Once set, a [`write`](Self::write) whose data is not flushed to
+    /// the socket within `timeout` fails with [`io::ErrorKind::TimedOut`].
+    /// Passing `None` disables the timeout, which is the default.
+    ///
+    /// The setting is shared with every clone and split half of the
+    /// stream.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidInput` if `timeout` is `Some(Duration::ZERO)`.
+    pub fn set_write_timeout(&self, timeout: Option<Duration>) -> io::Result<()> {
+        check_timeout(timeout)?;
+
+        self.stream.lock().unwrap().write_timeout = timeout;
+        Ok(())
+    }
+
+    /// Returns the write timeout of the stream.
+    pub fn write_timeout(&self) -> Option<Duration> {
+        self.stream.lock().unwrap().write_timeout
+    }
+
+    /// Returns the total number of bytes received from the peer.
+    ///
+    /// Bytes are counted as the reactor reads them from the socket,
+    /// including those still buffered and not yet returned by a read.
+    /// The counter is shared with every clone and split half of the
+    /// stream.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// metrics.record_ingress(peer, stream.bytes_read());
+    /// ```
+    pub fn bytes_read(&self) -> u64 {
+        self.stream.lock().unwrap().bytes_read
+    }
+
+    /// Returns the total number of bytes sent to the peer.
+    ///
+    /// Bytes are counted once handed to the socket, including those sent
+    /// by [`send_file`](Self::send_file); bytes still waiting in the
+    /// output buffer are not. The counter is shared with every clone and
+    /// split half of the stream.
+    pub fn bytes_written(&self) -> u64 {
+        self.stream.lock().unwrap().bytes_written
+    }
+
+    /// Sets the size of the socket receive buffer (`SO_RCVBUF`), in bytes.
+    ///
+    /// A larger buffer lets the peer keep more data in flight, which
+    /// matters on links with a high bandwidth-delay product.
+    ///
+    /// The OS treats the value as a hint: it may round it, cap it to a
+    /// system limit, or (as Linux does) double it to account for its own
+    /// bookkeeping. Use [`recv_buffer_size`](Self::recv_buffer_size) to
+    /// read back the effective size.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidInput` if `size` exceeds `i32::MAX`, or the error
+    /// reported by the OS.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// stream.set_recv_buffer_size(4 * 1024 * 1024)?;
+    /// ```
+    pub fn set_recv_buffer_size(&self, size: usize) -> io::Result<()> {
+        sockopt::set_buffer_size(self.fd(), Buffer::Recv, size)
+    }
+
+    /// Returns the size of the socket receive buffer, in bytes, as
+    /// reported by the OS.
+    pub fn recv_buffer_size(&self) -> io::Result<usize> {
+        sockopt::buffer_size(self.fd(), Buffer::Recv)
+    }
+
+    /// Sets the size of the socket send buffer (`SO_SNDBUF`), in bytes.
+    ///
+    /// As with [`set_recv_buffer_size`](Self::set_recv_buffer_size), the
+    /// OS may round, cap, or double the requested value.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidInput` if `size` exceeds `i32::MAX`, or the error
+    /// reported by the OS.
+    pub fn set_send_buffer_size(&self, size: usize) -> io::Result<()> {
+        sockopt::set_buffer_size(self.fd(), Buffer::Send, size)
+    }
+
+    /// Returns the size of the socket send buffer, in bytes, as reported
+    /// by the OS.
+    pub fn send_buffer_size(&self) -> io::Result<usize> {
+        sockopt::buffer_size(self.fd(), Buffer::Send)
+    }
+
+    /// Returns the file descriptor of the socket.
+    fn fd(&self) -> RawFd {
+        self.stream.lock().unwrap().fd
+    }
+
     /// Shuts down the read, write, or both halves of this connection.
     pub fn shutdown(&self, how: Shutdown) -> io::Result<()> {
         sys_shutdown(self.stream.lock().unwrap().fd, how)
@@ -134,7 +706,21 @@ impl TcpStream {
     /// Splits the stream into a read half and a write half.
     ///
     /// Both halves share the underlying stream state and can be used
-    /// concurrently.
+    /// concurrently. They do not borrow the stream: each holds its own
+    /// reference to that state, so they are `Send + 'static` and can be
+    /// awaited together in a single [`join!`](crate::join), or moved to
+    /// separate tasks.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// let (reader, writer) = stream.split();
+    ///
+    /// let (sent, received) = join!(
+    ///     writer.write_all(&request),
+    ///     reader.read(&mut buffer),
+    /// );
+    /// ```
     pub fn split(&self) -> (ReadHalf, WriteHalf) {
         (
             ReadHalf {
@@ -145,13 +731,48 @@ impl TcpStream {
             },
         )
     }
+
+    /// Splits the stream into a read half and a cloneable write half.
+    ///
+    /// Like [`split`](Self::split), but the write half can be cloned and
+    /// handed to every task writing to the connection, such as log
+    /// shippers fanning into a single collector. See [`SharedWriteHalf`]
+    /// for the ordering guarantees.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// let (_, writer) = stream.split_shared();
+    ///
+    /// for source in sources {
+    ///     let writer = writer.clone();
+    ///     task::spawn(async move {
+    ///         while let Some(line) = source.next_line().await {
+    ///             writer.write_all(&line).await?;
+    ///         }
+    ///         io::Result::Ok(())
+    ///     });
+    /// }
+    /// ```
+    pub fn split_shared(&self) -> (ReadHalf, SharedWriteHalf) {
+        (
+            ReadHalf {
+                stream: self.stream.clone(),
+            },
+            SharedWriteHalf {
+                stream: self.stream.clone(),
+            },
+        )
+    }
 }
 
 impl Drop for TcpStream {
     /// Drops the stream.
     ///
     /// The underlying file descriptor is closed when the last reference
-    /// to the shared stream state is dropped.
+    /// to the shared stream state is dropped. Bytes still waiting in the
+    /// output buffer may be lost; use [`TcpStream::finish`] to wait for
+    /// them to be written first.
     fn drop(&mut self) {
         let fd = {
             let stream = self.stream.lock().unwrap();
@@ -171,9 +792,23 @@ pub struct ReadHalf {
 
 impl ReadHalf {
     /// Returns a future that reads up to `buffer.len()` bytes.
+    ///
+    /// This method is cancel safe; see [`TcpStream::read`].
     pub fn read<'a>(&'a self, buffer: &'a mut [u8]) -> ReadFutureStream<'a> {
         ReadFutureStream::new(self.stream.clone(), buffer)
     }
+
+    /// Returns a future that reads into an owned buffer.
+    ///
+    /// See [`TcpStream::read_buf`].
+    pub fn read_buf(&self, buffer: Vec<u8>) -> OwnedReadFuture {
+        OwnedReadFuture::new(self.stream.clone(), buffer)
+    }
+
+    /// Returns `true` once the peer has closed its write half.
+    pub fn is_eof(&self) -> bool {
+        self.stream.lock().unwrap().eof
+    }
 }
 
 /// The write half of a [`TcpStream`], created by [`TcpStream::split`].
@@ -211,4 +846,187 @@ impl WriteHalf {
 
         Ok(())
     }
+
+    /// Flushes the queued writes, then shuts down the write side of the
+    /// connection.
+    ///
+    /// The peer reads EOF once it has received everything written before,
+    /// while the [`ReadHalf`] keeps receiving: this is how protocols
+    /// signaling the end of a request by half-closing are spoken.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error reported while flushing the output buffer or
+    /// shutting down the socket.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// let (reader, writer) = stream.split();
+    ///
+    /// writer.write_all(&request).await?;
+    /// writer.shutdown().await?;
+    ///
+    /// let response = read_to_end(&reader).await?;
+    /// ```
+    pub async fn shutdown(&self) -> io::Result<()> {
+        poll_fn(|cx| poll_shutdown_write(&self.stream, cx)).await
+    }
+}
+
+/// A cloneable write half of a [`TcpStream`], created by
+/// [`TcpStream::split_shared`].
+///
+/// Every clone writes to the same connection. Concurrent writes are
+/// serialized through the lock of the stream: each call to
+/// [`write_all`](Self::write_all) queues its whole buffer at once, so the
+/// messages of different writers never interleave. Nothing else is atomic:
+/// a message written in several calls, or through [`AsyncWrite`], may be
+/// split by the writes of other clones.
+#[derive(Clone)]
+pub struct SharedWriteHalf {
+    stream: Arc<Mutex<Stream>>,
+}
+
+impl SharedWriteHalf {
+    /// Writes the entire buffer to the stream, as a single message.
+    ///
+    /// Resolves once the buffer, and whatever other clones queued before
+    /// it, has been written to the socket. Writes queued after it do not
+    /// delay it, so clones that keep writing cannot starve the others.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error reported while writing, such as `BrokenPipe` if
+    /// the connection was closed.
+    pub async fn write_all(&self, buffer: &[u8]) -> io::Result<()> {
+        // The future queues the whole buffer in one step, under the lock.
+        WriteFutureStream::new(self.stream.clone(), buffer).await?;
+
+        Ok(())
+    }
+
+    /// Flushes the queued writes of every clone, then shuts down the
+    /// write side of the connection.
+    ///
+    /// Further writes from any clone fail. See [`WriteHalf::shutdown`].
+    ///
+    /// # Errors
+    ///
+    /// Returns any error reported while flushing the output buffer or
+    /// shutting down the socket.
+    pub async fn shutdown(&self) -> io::Result<()> {
+        poll_fn(|cx| poll_shutdown_write(&self.stream, cx)).await
+    }
+}
+
+impl AsyncRead for TcpStream {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buffer: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        self.stream.lock().unwrap().poll_read(cx, buffer)
+    }
+}
+
+impl AsyncWrite for TcpStream {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buffer: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        self.stream.lock().unwrap().poll_write(cx, buffer)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.stream.lock().unwrap().poll_flush(cx)
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        poll_shutdown_write(&self.stream, cx)
+    }
+}
+
+impl AsyncRead for ReadHalf {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buffer: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        self.stream.lock().unwrap().poll_read(cx, buffer)
+    }
+}
+
+impl AsyncWrite for SharedWriteHalf {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buffer: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        self.stream.lock().unwrap().poll_write(cx, buffer)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.stream.lock().unwrap().poll_flush(cx)
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        poll_shutdown_write(&self.stream, cx)
+    }
+}
+
+impl AsyncWrite for WriteHalf {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buffer: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        self.stream.lock().unwrap().poll_write(cx, buffer)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.stream.lock().unwrap().poll_flush(cx)
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        poll_shutdown_write(&self.stream, cx)
+    }
+}
+
+/// Rejects zero timeouts, mirroring `std::net::TcpStream`.
+fn check_timeout(timeout: Option<Duration>) -> io::Result<()> {
+    if timeout == Some(Duration::ZERO) {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "cannot set a 0 duration timeout",
+        ));
+    }
+
+    Ok(())
+}
+
+/// Flushes the output buffer, then shuts down the write half of the socket.
+fn poll_shutdown_write(stream: &Mutex<Stream>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+    let mut stream = stream.lock().unwrap();
+
+    match stream.poll_flush(cx) {
+        Poll::Ready(Ok(())) => Poll::Ready(sys_shutdown(stream.fd, Shutdown::Write)),
+        other => other,
+    }
+}
+
+/// Returns whether a failed connect may succeed if attempted again.
+fn is_transient(err: &io::Error) -> bool {
+    matches!(
+        err.kind(),
+        io::ErrorKind::ConnectionRefused
+            | io::ErrorKind::ConnectionReset
+            | io::ErrorKind::ConnectionAborted
+            | io::ErrorKind::TimedOut
+            | io::ErrorKind::NetworkUnreachable
+            | io::ErrorKind::HostUnreachable
+            | io::ErrorKind::NetworkDown
+            | io::ErrorKind::Interrupted
+    )
 }
@@ -0,0 +1,162 @@
+use super::socket::UdpSocket;
+use crate::stream::Stream;
+
+use std::collections::VecDeque;
+use std::future::poll_fn;
+use std::io;
+use std::net::SocketAddr;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+/// Size of the length prefix preceding every message.
+const PREFIX_LEN: usize = 4;
+
+/// Largest UDP payload, and size of the receive buffer.
+const MAX_DATAGRAM: usize = 65_507;
+
+/// Length-prefixed messages over a [`UdpSocket`].
+///
+/// Every message is sent as one datagram holding a 4-byte big-endian
+/// length followed by the message bytes. A received datagram may carry
+/// several such frames back to back; they are yielded one by one, in
+/// order, so message boundaries are always preserved.
+///
+/// `UdpFramed` implements [`Stream`], yielding each received message
+/// together with the address of its sender.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// let mut framed = UdpFramed::new(UdpSocket::bind("127.0.0.1:0")?);
+///
+/// framed.send(b"hello", peer).await?;
+/// let (message, from) = framed.recv().await?;
+/// ```
+pub struct UdpFramed {
+    socket: UdpSocket,
+
+    /// Buffer receiving the datagrams, sized for the largest one.
+    buffer: Vec<u8>,
+
+    /// Messages decoded from the last datagram and not yielded yet.
+    pending: VecDeque<(Vec<u8>, SocketAddr)>,
+}
+
+impl UdpFramed {
+    /// Wraps `socket` to exchange length-prefixed messages.
+    pub fn new(socket: UdpSocket) -> Self {
+        Self {
+            socket,
+            buffer: vec![0; MAX_DATAGRAM],
+            pending: VecDeque::new(),
+        }
+    }
+
+    /// Returns a reference to the underlying socket.
+    pub fn get_ref(&self) -> &UdpSocket {
+        &self.socket
+    }
+
+    /// Consumes the wrapper, returning the underlying socket.
+    ///
+    /// Messages received but not yielded yet are lost.
+    pub fn into_inner(self) -> UdpSocket {
+        self.socket
+    }
+
+    /// Sends `message` to `target` as a single frame.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidInput` if the framed message does not fit in a
+    /// single datagram.
+    pub async fn send(&self, message: &[u8], target: SocketAddr) -> io::Result<()> {
+        if message.len() > MAX_DATAGRAM - PREFIX_LEN {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "message too large for a datagram",
+            ));
+        }
+
+        let mut frame = Vec::with_capacity(PREFIX_LEN + message.len());
+        frame.extend_from_slice(&(message.len() as u32).to_be_bytes());
+        frame.extend_from_slice(message);
+
+        self.socket.send_to(&frame, target).await?;
+
+        Ok(())
+    }
+
+    /// Receives the next message and the address of its sender.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidData` if a datagram does not hold well-formed
+    /// frames; the rest of that datagram is dropped.
+    pub async fn recv(&mut self) -> io::Result<(Vec<u8>, SocketAddr)> {
+        poll_fn(|cx| self.poll_recv(cx)).await
+    }
+
+    /// Attempts to receive the next message.
+    fn poll_recv(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<(Vec<u8>, SocketAddr)>> {
+        if let Some(message) = self.pending.pop_front() {
+            return Poll::Ready(Ok(message));
+        }
+
+        let (n, from) = match self.socket.poll_recv_from(cx, &mut self.buffer) {
+            Poll::Ready(Ok(received)) => received,
+            Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
+            Poll::Pending => return Poll::Pending,
+        };
+
+        if let Err(e) = decode(&self.buffer[..n], from, &mut self.pending) {
+            self.pending.clear();
+            return Poll::Ready(Err(e));
+        }
+
+        match self.pending.pop_front() {
+            Some(message) => Poll::Ready(Ok(message)),
+            None => Poll::Ready(Err(invalid_frame())),
+        }
+    }
+}
+
+impl Stream for UdpFramed {
+    type Item = io::Result<(Vec<u8>, SocketAddr)>;
+
+    /// Yields the next message; the stream never ends.
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.get_mut().poll_recv(cx).map(Some)
+    }
+}
+
+/// Splits `datagram` into its frames, queuing each message in `out`.
+fn decode(
+    mut datagram: &[u8],
+    from: SocketAddr,
+    out: &mut VecDeque<(Vec<u8>, SocketAddr)>,
+) -> io::Result<()> {
+    while !datagram.is_empty() {
+        let Some((prefix, rest)) = datagram.split_first_chunk::<PREFIX_LEN>() else {
+            return Err(invalid_frame());
+        };
+
+        let len = u32::from_be_bytes(*prefix) as usize;
+        if len > rest.len() {
+            return Err(invalid_frame());
+        }
+
+        out.push_back((rest[..len].to_vec(), from));
+        datagram = &rest[len..];
+    }
+
+    Ok(())
+}
+
+/// Returns the error reported for a malformed datagram.
+fn invalid_frame() -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        "malformed length-prefixed datagram",
+    )
+}
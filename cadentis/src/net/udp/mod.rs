@@ -0,0 +1,11 @@
+//! UDP networking implementation.
+//!
+//! This module contains the concrete UDP types built on top of the
+//! runtime reactor and poller.
+//!
+//! It is split into:
+//! - [`socket`]: asynchronous UDP sockets,
+//! - [`framed`]: length-prefixed messages carried over a UDP socket.
+
+pub mod framed;
+pub mod socket;
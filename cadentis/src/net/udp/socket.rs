@@ -0,0 +1,273 @@
+use crate::reactor::readiness::Readiness;
+use crate::time::timeout;
+
+use nucleus::io::RawFd;
+use nucleus::poll::Interest;
+use std::future::poll_fn;
+use std::io;
+use std::net::{self, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+/// An asynchronous UDP socket.
+///
+/// `UdpSocket` sends and receives datagrams without blocking: when the
+/// socket is not ready, the task waits for the reactor to report
+/// readiness.
+///
+/// It is the async equivalent of [`std::net::UdpSocket`]. The socket can
+/// be shared between tasks through an `Arc`: one task may receive while
+/// another sends, but only one task should wait to receive, and one to
+/// send, at a time.
+pub struct UdpSocket {
+    /// Readiness of the socket, deregistered before the socket closes.
+    readiness: Readiness,
+
+    /// Underlying non-blocking socket.
+    socket: net::UdpSocket,
+}
+
+impl UdpSocket {
+    /// Binds a UDP socket to the given address.
+    ///
+    /// The address must be a valid socket address string, such as
+    /// `"127.0.0.1:8080"` or `"[::1]:0"`.
+    pub fn bind(address: &str) -> io::Result<Self> {
+        Self::from_std(net::UdpSocket::bind(address)?)
+    }
+
+    /// Creates a socket from an already bound `std::net::UdpSocket`.
+    ///
+    /// The socket is switched to non-blocking mode.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the socket cannot be made non-blocking.
+    pub fn from_std(socket: net::UdpSocket) -> io::Result<Self> {
+        socket.set_nonblocking(true)?;
+
+        Ok(Self {
+            readiness: Readiness::new(raw_fd(&socket)),
+            socket,
+        })
+    }
+
+    /// Returns the local socket address of this socket.
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.socket.local_addr()
+    }
+
+    /// Joins the IPv4 multicast group `multiaddr` on `interface`.
+    ///
+    /// `interface` is the address of the local interface to join on;
+    /// [`Ipv4Addr::UNSPECIFIED`] lets the OS pick one. The socket must be
+    /// bound to the group port, usually on the unspecified address, to
+    /// receive the datagrams sent to the group.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// let socket = UdpSocket::bind("0.0.0.0:5353")?;
+    /// socket.join_multicast_v4(Ipv4Addr::new(224, 0, 0, 251), Ipv4Addr::UNSPECIFIED)?;
+    /// ```
+    pub fn join_multicast_v4(&self, multiaddr: Ipv4Addr, interface: Ipv4Addr) -> io::Result<()> {
+        self.socket.join_multicast_v4(&multiaddr, &interface)
+    }
+
+    /// Leaves the IPv4 multicast group `multiaddr` on `interface`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the socket is not a member of the group.
+    pub fn leave_multicast_v4(&self, multiaddr: Ipv4Addr, interface: Ipv4Addr) -> io::Result<()> {
+        self.socket.leave_multicast_v4(&multiaddr, &interface)
+    }
+
+    /// Joins the IPv6 multicast group `multiaddr` on the interface with
+    /// index `interface`.
+    ///
+    /// An interface index of `0` lets the OS pick the interface.
+    pub fn join_multicast_v6(&self, multiaddr: Ipv6Addr, interface: u32) -> io::Result<()> {
+        self.socket.join_multicast_v6(&multiaddr, interface)
+    }
+
+    /// Leaves the IPv6 multicast group `multiaddr` on the interface with
+    /// index `interface`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the socket is not a member of the group.
+    pub fn leave_multicast_v6(&self, multiaddr: Ipv6Addr, interface: u32) -> io::Result<()> {
+        self.socket.leave_multicast_v6(&multiaddr, interface)
+    }
+
+    /// Sets whether IPv4 multicast datagrams sent by this socket are
+    /// looped back to the local host.
+    ///
+    /// Loopback is enabled by default, which lets several processes on
+    /// the same host discover each other.
+    pub fn set_multicast_loop_v4(&self, on: bool) -> io::Result<()> {
+        self.socket.set_multicast_loop_v4(on)
+    }
+
+    /// Returns whether IPv4 multicast datagrams are looped back.
+    pub fn multicast_loop_v4(&self) -> io::Result<bool> {
+        self.socket.multicast_loop_v4()
+    }
+
+    /// Sets the time-to-live of IPv4 multicast datagrams sent by this
+    /// socket.
+    ///
+    /// The default of `1` keeps datagrams on the local network.
+    pub fn set_multicast_ttl_v4(&self, ttl: u32) -> io::Result<()> {
+        self.socket.set_multicast_ttl_v4(ttl)
+    }
+
+    /// Returns the time-to-live of IPv4 multicast datagrams.
+    pub fn multicast_ttl_v4(&self) -> io::Result<u32> {
+        self.socket.multicast_ttl_v4()
+    }
+
+    /// Sets whether IPv6 multicast datagrams sent by this socket are
+    /// looped back to the local host.
+    pub fn set_multicast_loop_v6(&self, on: bool) -> io::Result<()> {
+        self.socket.set_multicast_loop_v6(on)
+    }
+
+    /// Returns whether IPv6 multicast datagrams are looped back.
+    pub fn multicast_loop_v6(&self) -> io::Result<bool> {
+        self.socket.multicast_loop_v6()
+    }
+
+    /// Sends a datagram to `target`.
+    ///
+    /// Resolves with the number of bytes sent, which is always the whole
+    /// buffer for a datagram that fits the socket limits.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// socket.send_to(b"ping", peer).await?;
+    /// ```
+    pub async fn send_to(&self, buffer: &[u8], target: SocketAddr) -> io::Result<usize> {
+        poll_fn(|cx| self.poll_send_to(cx, buffer, target)).await
+    }
+
+    /// Receives a single datagram.
+    ///
+    /// Resolves with the number of bytes received and the address of the
+    /// sender. Bytes of the datagram that do not fit in `buffer` are
+    /// discarded.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// let mut buffer = [0u8; 1500];
+    /// let (n, peer) = socket.recv_from(&mut buffer).await?;
+    /// ```
+    pub async fn recv_from(&self, buffer: &mut [u8]) -> io::Result<(usize, SocketAddr)> {
+        poll_fn(|cx| self.poll_recv_from(cx, buffer)).await
+    }
+
+    /// Sends `request` to `target` and waits for its reply.
+    ///
+    /// This is the exchange of request/response protocols such as DNS:
+    /// the reply is received into `response`, and datagrams coming from
+    /// any other address than `target` are discarded meanwhile. Resolves
+    /// with the length of the reply.
+    ///
+    /// Giving up on the exchange leaves the socket usable: a reply
+    /// arriving after the timeout is simply received by the next call to
+    /// [`recv_from`](Self::recv_from) or `query`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `TimedOut` if no reply arrives from `target` within
+    /// `limit`, or the error of the send or receive operation.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// let mut reply = [0u8; 512];
+    /// let n = socket
+    ///     .query(&request, resolver_addr, &mut reply, Duration::from_secs(2))
+    ///     .await?;
+    /// ```
+    pub async fn query(
+        &self,
+        request: &[u8],
+        target: SocketAddr,
+        response: &mut [u8],
+        limit: Duration,
+    ) -> io::Result<usize> {
+        let exchange = async {
+            self.send_to(request, target).await?;
+
+            loop {
+                let (n, peer) = self.recv_from(response).await?;
+                if peer == target {
+                    return Ok(n);
+                }
+            }
+        };
+
+        timeout(limit, exchange).await.unwrap_or_else(|()| {
+            Err(io::Error::new(
+                io::ErrorKind::TimedOut,
+                "no reply received in time",
+            ))
+        })
+    }
+
+    /// Attempts to send a datagram to `target`.
+    ///
+    /// Returns `Poll::Pending` and schedules a wake-up once the socket
+    /// becomes writable if the datagram cannot be sent yet.
+    pub fn poll_send_to(
+        &self,
+        cx: &mut Context<'_>,
+        buffer: &[u8],
+        target: SocketAddr,
+    ) -> Poll<io::Result<usize>> {
+        let interest = Interest {
+            read: false,
+            write: true,
+        };
+
+        self.readiness
+            .poll_io(cx, interest, || self.socket.send_to(buffer, target))
+    }
+
+    /// Attempts to receive a single datagram.
+    ///
+    /// Returns `Poll::Pending` and schedules a wake-up once the socket
+    /// becomes readable if no datagram is queued.
+    pub fn poll_recv_from(
+        &self,
+        cx: &mut Context<'_>,
+        buffer: &mut [u8],
+    ) -> Poll<io::Result<(usize, SocketAddr)>> {
+        let interest = Interest {
+            read: true,
+            write: false,
+        };
+
+        self.readiness
+            .poll_io(cx, interest, || self.socket.recv_from(buffer))
+    }
+}
+
+/// Returns the raw descriptor of `socket`.
+fn raw_fd(socket: &net::UdpSocket) -> RawFd {
+    #[cfg(unix)]
+    {
+        use std::os::fd::AsRawFd;
+        socket.as_raw_fd()
+    }
+
+    #[cfg(windows)]
+    {
+        use std::os::windows::io::AsRawSocket;
+        socket.as_raw_socket()
+    }
+}
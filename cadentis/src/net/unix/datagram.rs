@@ -0,0 +1,178 @@
+use crate::reactor::readiness::Readiness;
+
+use nucleus::poll::Interest;
+use std::future::poll_fn;
+use std::io;
+use std::os::fd::AsRawFd;
+use std::os::unix::net::{self, SocketAddr};
+use std::path::Path;
+use std::task::{Context, Poll};
+
+/// An asynchronous Unix datagram socket.
+///
+/// `UnixDatagram` exchanges datagrams with other sockets of the same
+/// host, addressed by file system path. Like UDP, each receive returns
+/// exactly one message, so processes can talk in discrete messages
+/// without framing them; unlike UDP, delivery is reliable and ordered.
+///
+/// It is the async equivalent of [`std::os::unix::net::UnixDatagram`].
+/// The socket can be shared between tasks through an `Arc`, but a single
+/// task should wait on it in each direction at a time.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// let socket = UnixDatagram::bind("/run/nebula/agent.sock")?;
+///
+/// let mut buffer = [0u8; 4096];
+/// let (n, peer) = socket.recv_from(&mut buffer).await?;
+/// if let Some(path) = peer.as_pathname() {
+///     socket.send_to(b"ack", path).await?;
+/// }
+/// ```
+pub struct UnixDatagram {
+    /// Readiness of the socket, deregistered before the socket closes.
+    readiness: Readiness,
+
+    /// Underlying non-blocking socket.
+    socket: net::UnixDatagram,
+}
+
+impl UnixDatagram {
+    /// Creates a socket bound to the file system path `path`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a file already exists at `path`.
+    pub fn bind(path: impl AsRef<Path>) -> io::Result<Self> {
+        Self::from_std(net::UnixDatagram::bind(path)?)
+    }
+
+    /// Creates a socket that is not bound to any address.
+    ///
+    /// Such a socket can send datagrams, but peers cannot reply to it.
+    pub fn unbound() -> io::Result<Self> {
+        Self::from_std(net::UnixDatagram::unbound()?)
+    }
+
+    /// Creates a pair of connected, unnamed sockets.
+    pub fn pair() -> io::Result<(Self, Self)> {
+        let (a, b) = net::UnixDatagram::pair()?;
+
+        Ok((Self::from_std(a)?, Self::from_std(b)?))
+    }
+
+    /// Creates a socket from a `std::os::unix::net::UnixDatagram`.
+    ///
+    /// The socket is switched to non-blocking mode.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the socket cannot be made non-blocking.
+    pub fn from_std(socket: net::UnixDatagram) -> io::Result<Self> {
+        socket.set_nonblocking(true)?;
+
+        Ok(Self {
+            readiness: Readiness::new(socket.as_raw_fd()),
+            socket,
+        })
+    }
+
+    /// Connects the socket to the socket bound at `path`.
+    ///
+    /// [`send`](Self::send) then sends to that peer, and only datagrams
+    /// from it are received.
+    pub fn connect(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        self.socket.connect(path)
+    }
+
+    /// Returns the address this socket is bound to.
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.socket.local_addr()
+    }
+
+    /// Returns the address of the peer this socket is connected to.
+    pub fn peer_addr(&self) -> io::Result<SocketAddr> {
+        self.socket.peer_addr()
+    }
+
+    /// Sends a datagram to the socket bound at `path`.
+    ///
+    /// Resolves with the number of bytes sent, which is always the whole
+    /// buffer: a datagram is never split.
+    pub async fn send_to(&self, buffer: &[u8], path: impl AsRef<Path>) -> io::Result<usize> {
+        let path = path.as_ref();
+
+        poll_fn(|cx| self.poll_send_to(cx, buffer, path)).await
+    }
+
+    /// Receives a single datagram.
+    ///
+    /// Resolves with the number of bytes received and the address of the
+    /// sender. Bytes of the datagram that do not fit in `buffer` are
+    /// discarded.
+    pub async fn recv_from(&self, buffer: &mut [u8]) -> io::Result<(usize, SocketAddr)> {
+        poll_fn(|cx| self.poll_recv_from(cx, buffer)).await
+    }
+
+    /// Sends a datagram to the connected peer.
+    pub async fn send(&self, buffer: &[u8]) -> io::Result<usize> {
+        poll_fn(|cx| self.poll_send(cx, buffer)).await
+    }
+
+    /// Receives a single datagram from the connected peer.
+    pub async fn recv(&self, buffer: &mut [u8]) -> io::Result<usize> {
+        poll_fn(|cx| self.poll_recv(cx, buffer)).await
+    }
+
+    /// Attempts to send a datagram to the socket bound at `path`.
+    ///
+    /// Returns `Poll::Pending` and schedules a wake-up once the socket
+    /// becomes writable if the datagram cannot be sent yet.
+    pub fn poll_send_to(
+        &self,
+        cx: &mut Context<'_>,
+        buffer: &[u8],
+        path: &Path,
+    ) -> Poll<io::Result<usize>> {
+        self.readiness
+            .poll_io(cx, WRITE, || self.socket.send_to(buffer, path))
+    }
+
+    /// Attempts to receive a single datagram.
+    ///
+    /// Returns `Poll::Pending` and schedules a wake-up once the socket
+    /// becomes readable if no datagram is queued.
+    pub fn poll_recv_from(
+        &self,
+        cx: &mut Context<'_>,
+        buffer: &mut [u8],
+    ) -> Poll<io::Result<(usize, SocketAddr)>> {
+        self.readiness
+            .poll_io(cx, READ, || self.socket.recv_from(buffer))
+    }
+
+    /// Attempts to send a datagram to the connected peer.
+    pub fn poll_send(&self, cx: &mut Context<'_>, buffer: &[u8]) -> Poll<io::Result<usize>> {
+        self.readiness
+            .poll_io(cx, WRITE, || self.socket.send(buffer))
+    }
+
+    /// Attempts to receive a single datagram from the connected peer.
+    pub fn poll_recv(&self, cx: &mut Context<'_>, buffer: &mut [u8]) -> Poll<io::Result<usize>> {
+        self.readiness
+            .poll_io(cx, READ, || self.socket.recv(buffer))
+    }
+}
+
+/// Interest of receive operations.
+const READ: Interest = Interest {
+    read: true,
+    write: false,
+};
+
+/// Interest of send operations.
+const WRITE: Interest = Interest {
+    read: false,
+    write: true,
+};
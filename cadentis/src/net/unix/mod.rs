@@ -0,0 +1,14 @@
+//! Unix domain socket implementation.
+//!
+//! This module contains the `AF_UNIX` types built on top of the runtime
+//! reactor and poller. They are only available on Unix platforms.
+//!
+//! It is split into:
+//! - [`datagram`]: asynchronous datagram sockets, keeping message
+//!   boundaries without a length codec.
+//! - [`seqpacket`]: asynchronous sequenced-packet sockets, keeping
+//!   message boundaries over a connection, on Linux and Android.
+
+pub mod datagram;
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub mod seqpacket;
@@ -0,0 +1,282 @@
+use crate::reactor::readiness::Readiness;
+use crate::sys;
+
+use nucleus::poll::Interest;
+use std::future::poll_fn;
+use std::io;
+use std::os::fd::{AsRawFd, FromRawFd, OwnedFd, RawFd};
+use std::os::unix::ffi::OsStrExt;
+use std::path::Path;
+use std::ptr;
+use std::task::{Context, Poll};
+
+const AF_UNIX: i32 = 1;
+const SOCK_SEQPACKET: i32 = 5;
+const SOCK_CLOEXEC: i32 = 0o2000000;
+const MSG_NOSIGNAL: i32 = 0x4000;
+
+/// Length of the pending connection queue of a listener.
+const BACKLOG: i32 = 1024;
+
+/// An asynchronous Unix sequenced-packet socket.
+///
+/// `UnixSeqpacket` is a connection to another socket of the same host,
+/// like a [`UnixStream`](std::os::unix::net::UnixStream), that keeps
+/// message boundaries like a [`UnixDatagram`](super::datagram::UnixDatagram):
+/// each receive returns exactly one message, in the order it was sent.
+///
+/// The socket can be shared between tasks through an `Arc`, but a single
+/// task should wait on it in each direction at a time.
+///
+/// Sequenced-packet sockets are only provided on Linux and Android.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// let socket = UnixSeqpacket::connect("/run/nebula/control.sock")?;
+/// socket.send(b"status").await?;
+///
+/// let mut buffer = [0u8; 4096];
+/// let n = socket.recv(&mut buffer).await?;
+/// ```
+pub struct UnixSeqpacket {
+    /// Readiness of the socket, deregistered before the socket closes.
+    readiness: Readiness,
+
+    /// Underlying non-blocking socket.
+    fd: OwnedFd,
+}
+
+/// A Unix sequenced-packet socket listening for connections.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// let listener = UnixSeqpacketListener::bind("/run/nebula/control.sock")?;
+///
+/// loop {
+///     let socket = listener.accept().await?;
+///     task::spawn(async move { serve(socket).await });
+/// }
+/// ```
+pub struct UnixSeqpacketListener {
+    /// Readiness of the socket, deregistered before the socket closes.
+    readiness: Readiness,
+
+    /// Underlying non-blocking socket.
+    fd: OwnedFd,
+}
+
+impl UnixSeqpacket {
+    /// Connects to the listener bound at the file system path `path`.
+    ///
+    /// Connecting to a local socket completes at once, unless the backlog
+    /// of the listener is full, in which case this blocks until it is not.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no listener is bound at `path`, or if `path`
+    /// does not fit in a socket address.
+    pub fn connect(path: impl AsRef<Path>) -> io::Result<Self> {
+        let (address, len) = address(path.as_ref())?;
+        let fd = socket()?;
+
+        // SAFETY: `address` is a valid `sockaddr_un` of `len` bytes.
+        cvt(unsafe { sys::connect(fd.as_raw_fd(), (&raw const address).cast(), len) })?;
+
+        Self::new(fd)
+    }
+
+    /// Creates a pair of connected, unnamed sockets.
+    pub fn pair() -> io::Result<(Self, Self)> {
+        let mut fds = [-1; 2];
+
+        // SAFETY: `fds` has room for the two descriptors returned.
+        cvt(unsafe {
+            sys::socketpair(AF_UNIX, SOCK_SEQPACKET | SOCK_CLOEXEC, 0, fds.as_mut_ptr())
+        })?;
+
+        // SAFETY: both descriptors were just created and are owned here.
+        let (a, b) = unsafe { (OwnedFd::from_raw_fd(fds[0]), OwnedFd::from_raw_fd(fds[1])) };
+
+        Ok((Self::new(a)?, Self::new(b)?))
+    }
+
+    /// Wraps a connected socket, switching it to non-blocking mode.
+    fn new(fd: OwnedFd) -> io::Result<Self> {
+        sys::set_nonblocking(fd.as_raw_fd())?;
+
+        Ok(Self {
+            readiness: Readiness::new(fd.as_raw_fd()),
+            fd,
+        })
+    }
+
+    /// Sends a message to the peer.
+    ///
+    /// Resolves with the number of bytes sent, which is always the whole
+    /// buffer: a message is never split.
+    pub async fn send(&self, buffer: &[u8]) -> io::Result<usize> {
+        poll_fn(|cx| self.poll_send(cx, buffer)).await
+    }
+
+    /// Receives a single message from the peer.
+    ///
+    /// Resolves with the number of bytes received, or `0` once the peer
+    /// closed the connection. Bytes of the message that do not fit in
+    /// `buffer` are discarded.
+    pub async fn recv(&self, buffer: &mut [u8]) -> io::Result<usize> {
+        poll_fn(|cx| self.poll_recv(cx, buffer)).await
+    }
+
+    /// Attempts to send a message to the peer.
+    ///
+    /// Returns `Poll::Pending` and schedules a wake-up once the socket
+    /// becomes writable if the message cannot be sent yet.
+    pub fn poll_send(&self, cx: &mut Context<'_>, buffer: &[u8]) -> Poll<io::Result<usize>> {
+        let fd = self.fd.as_raw_fd();
+
+        self.readiness.poll_io(cx, WRITE, || {
+            // SAFETY: `buffer` is a live slice of `buffer.len()` bytes,
+            // which the kernel only reads.
+            let sent = unsafe { sys::send(fd, buffer.as_ptr().cast(), buffer.len(), MSG_NOSIGNAL) };
+            cvt_size(sent)
+        })
+    }
+
+    /// Attempts to receive a single message from the peer.
+    ///
+    /// Returns `Poll::Pending` and schedules a wake-up once the socket
+    /// becomes readable if no message is queued.
+    pub fn poll_recv(&self, cx: &mut Context<'_>, buffer: &mut [u8]) -> Poll<io::Result<usize>> {
+        let fd = self.fd.as_raw_fd();
+
+        self.readiness.poll_io(cx, READ, || {
+            // SAFETY: `buffer` is a live, writable slice of
+            // `buffer.len()` bytes.
+            let received = unsafe { sys::recv(fd, buffer.as_mut_ptr().cast(), buffer.len(), 0) };
+            cvt_size(received)
+        })
+    }
+}
+
+impl UnixSeqpacketListener {
+    /// Creates a listener bound to the file system path `path`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a file already exists at `path`, or if `path`
+    /// does not fit in a socket address.
+    pub fn bind(path: impl AsRef<Path>) -> io::Result<Self> {
+        let (address, len) = address(path.as_ref())?;
+        let fd = socket()?;
+
+        // SAFETY: `address` is a valid `sockaddr_un` of `len` bytes.
+        cvt(unsafe { sys::bind(fd.as_raw_fd(), (&raw const address).cast(), len) })?;
+        // SAFETY: `fd` is a bound socket.
+        cvt(unsafe { sys::listen(fd.as_raw_fd(), BACKLOG) })?;
+
+        sys::set_nonblocking(fd.as_raw_fd())?;
+
+        Ok(Self {
+            readiness: Readiness::new(fd.as_raw_fd()),
+            fd,
+        })
+    }
+
+    /// Accepts a new connection.
+    ///
+    /// Peers of sequenced-packet sockets are usually unnamed, so no
+    /// address is returned.
+    pub async fn accept(&self) -> io::Result<UnixSeqpacket> {
+        poll_fn(|cx| self.poll_accept(cx)).await
+    }
+
+    /// Attempts to accept a new connection.
+    ///
+    /// Returns `Poll::Pending` and schedules a wake-up once a connection
+    /// is pending if there is none yet.
+    pub fn poll_accept(&self, cx: &mut Context<'_>) -> Poll<io::Result<UnixSeqpacket>> {
+        let fd = self.fd.as_raw_fd();
+
+        let accepted = self.readiness.poll_io(cx, READ, || {
+            // SAFETY: no address is asked for, so null pointers are valid.
+            let client =
+                unsafe { sys::accept4(fd, ptr::null_mut(), ptr::null_mut(), SOCK_CLOEXEC) };
+            cvt(client)?;
+
+            // SAFETY: `client` was just accepted and is owned here.
+            Ok(unsafe { OwnedFd::from_raw_fd(client) })
+        });
+
+        accepted.map(|client| client.and_then(UnixSeqpacket::new))
+    }
+}
+
+/// Mirror of the C `sockaddr_un`.
+#[repr(C)]
+struct SockaddrUn {
+    family: u16,
+    path: [u8; 108],
+}
+
+/// Returns the socket address of `path`, along with its length.
+fn address(path: &Path) -> io::Result<(SockaddrUn, u32)> {
+    let bytes = path.as_os_str().as_bytes();
+
+    let mut address = SockaddrUn {
+        family: AF_UNIX as u16,
+        path: [0; 108],
+    };
+
+    // The path is NUL-terminated, which takes a byte of the room.
+    if bytes.len() >= address.path.len() || bytes.contains(&0) {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "path does not fit in a unix socket address",
+        ));
+    }
+    address.path[..bytes.len()].copy_from_slice(bytes);
+
+    let len = size_of::<u16>() + bytes.len() + 1;
+    Ok((address, len as u32))
+}
+
+/// Creates a blocking, close-on-exec sequenced-packet socket.
+fn socket() -> io::Result<OwnedFd> {
+    // SAFETY: plain system call without pointers.
+    let fd = cvt(unsafe { sys::socket(AF_UNIX, SOCK_SEQPACKET | SOCK_CLOEXEC, 0) })?;
+
+    // SAFETY: `fd` was just created and is owned here.
+    Ok(unsafe { OwnedFd::from_raw_fd(fd) })
+}
+
+/// Turns the `-1` result of a system call into the error it stands for.
+fn cvt(result: i32) -> io::Result<RawFd> {
+    if result < 0 {
+        Err(io::Error::last_os_error())
+    } else {
+        Ok(result)
+    }
+}
+
+/// Turns the `-1` result of a transfer into the error it stands for.
+fn cvt_size(result: isize) -> io::Result<usize> {
+    if result < 0 {
+        Err(io::Error::last_os_error())
+    } else {
+        Ok(result as usize)
+    }
+}
+
+/// Interest of receive and accept operations.
+const READ: Interest = Interest {
+    read: true,
+    write: false,
+};
+
+/// Interest of send operations.
+const WRITE: Interest = Interest {
+    read: false,
+    write: true,
+};
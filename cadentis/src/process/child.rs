@@ -0,0 +1,254 @@
+use super::sys;
+use crate::io::{AsyncRead, AsyncWrite};
+use crate::reactor::readiness::Readiness;
+use crate::time::sleep;
+
+use nucleus::io::{sys_read, sys_write};
+use nucleus::poll::Interest;
+use std::future::poll_fn;
+use std::io;
+use std::os::fd::{AsRawFd, OwnedFd};
+use std::pin::Pin;
+use std::process::{self, ExitStatus};
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+/// First delay between two checks of a child without pidfd support.
+const WAIT_BACKOFF_MIN: Duration = Duration::from_millis(1);
+
+/// Longest delay between two checks of a child without pidfd support.
+const WAIT_BACKOFF_MAX: Duration = Duration::from_millis(100);
+
+/// Read readiness.
+const READ: Interest = Interest {
+    read: true,
+    write: false,
+};
+
+/// Write readiness.
+const WRITE: Interest = Interest {
+    read: false,
+    write: true,
+};
+
+/// A handle to a child process, created by [`Command::spawn`](super::Command::spawn).
+///
+/// The piped standard streams of the child are available as public
+/// fields, like with [`std::process::Child`]; take them out of the
+/// handle to use them from another task.
+///
+/// Dropping a `Child` neither kills nor waits for the process.
+pub struct Child {
+    /// Standard input of the child, if piped.
+    pub stdin: Option<ChildStdin>,
+
+    /// Standard output of the child, if piped.
+    pub stdout: Option<ChildStdout>,
+
+    /// Standard error of the child, if piped.
+    pub stderr: Option<ChildStderr>,
+
+    /// The underlying process handle.
+    child: process::Child,
+
+    /// Descriptor readable once the child exits, where supported.
+    exit: Option<Pipe>,
+}
+
+impl Child {
+    /// Wraps a freshly spawned process.
+    pub(super) fn new(mut child: process::Child) -> io::Result<Self> {
+        let stdin = child.stdin.take().map(OwnedFd::from).map(Pipe::new);
+        let stdout = child.stdout.take().map(OwnedFd::from).map(Pipe::new);
+        let stderr = child.stderr.take().map(OwnedFd::from).map(Pipe::new);
+        let exit = sys::pidfd_open(child.id()).map(Pipe::new).transpose()?;
+
+        Ok(Self {
+            stdin: stdin
+                .transpose()?
+                .map(|pipe| ChildStdin { pipe: Some(pipe) }),
+            stdout: stdout.transpose()?.map(|pipe| ChildStdout { pipe }),
+            stderr: stderr.transpose()?.map(|pipe| ChildStderr { pipe }),
+            child,
+            exit,
+        })
+    }
+
+    /// Returns the OS identifier of the child process.
+    pub fn id(&self) -> u32 {
+        self.child.id()
+    }
+
+    /// Forces the child process to exit (`SIGKILL`).
+    ///
+    /// The child still has to be waited for to release its resources.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the signal cannot be sent.
+    pub fn kill(&mut self) -> io::Result<()> {
+        self.child.kill()
+    }
+
+    /// Returns the exit status of the child if it has exited, without
+    /// waiting.
+    pub fn try_wait(&mut self) -> io::Result<Option<ExitStatus>> {
+        self.child.try_wait()
+    }
+
+    /// Waits for the child to exit and returns its exit status.
+    ///
+    /// The standard input of the child, if piped, is closed first so that
+    /// a child reading it until end of file can terminate. The worker
+    /// thread is never blocked: on Linux the task waits on a pidfd
+    /// registered with the reactor, elsewhere the child is checked again
+    /// after a timer that backs off up to 100 ms.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error reported while waiting for the child.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// let status = Command::new("true").spawn()?.wait().await?;
+    /// assert!(status.success());
+    /// ```
+    pub async fn wait(&mut self) -> io::Result<ExitStatus> {
+        drop(self.stdin.take());
+
+        if let Some(exit) = &self.exit {
+            let child = &mut self.child;
+
+            return poll_fn(|cx| {
+                exit.readiness.poll_io(cx, READ, || match child.try_wait() {
+                    Ok(Some(status)) => Ok(status),
+                    Ok(None) => Err(io::ErrorKind::WouldBlock.into()),
+                    Err(e) => Err(e),
+                })
+            })
+            .await;
+        }
+
+        let mut backoff = WAIT_BACKOFF_MIN;
+
+        loop {
+            if let Some(status) = self.child.try_wait()? {
+                return Ok(status);
+            }
+
+            sleep(backoff).await;
+            backoff = (backoff * 2).min(WAIT_BACKOFF_MAX);
+        }
+    }
+}
+
+/// A non-blocking pipe end, or pidfd, registered with the reactor on demand.
+struct Pipe {
+    /// Readiness of the descriptor, deregistered before it closes.
+    readiness: Readiness,
+
+    /// The owned descriptor.
+    fd: OwnedFd,
+}
+
+impl Pipe {
+    /// Switches `fd` to non-blocking mode and takes ownership of it.
+    fn new(fd: OwnedFd) -> io::Result<Self> {
+        crate::sys::set_nonblocking(fd.as_raw_fd())?;
+
+        Ok(Self {
+            readiness: Readiness::new(fd.as_raw_fd()),
+            fd,
+        })
+    }
+
+    /// Attempts to read from the pipe.
+    fn poll_read(&self, cx: &mut Context<'_>, buffer: &mut [u8]) -> Poll<io::Result<usize>> {
+        self.readiness.poll_io(cx, READ, || {
+            let n = sys_read(self.fd.as_raw_fd(), buffer);
+
+            if n < 0 {
+                return Err(io::Error::last_os_error());
+            }
+
+            Ok(n as usize)
+        })
+    }
+
+    /// Attempts to write to the pipe.
+    fn poll_write(&self, cx: &mut Context<'_>, buffer: &[u8]) -> Poll<io::Result<usize>> {
+        self.readiness.poll_io(cx, WRITE, || {
+            let n = sys_write(self.fd.as_raw_fd(), buffer);
+
+            if n < 0 {
+                return Err(io::Error::last_os_error());
+            }
+
+            Ok(n as usize)
+        })
+    }
+}
+
+/// The standard input of a child process.
+///
+/// Shutting it down, or dropping it, closes the pipe: the child then
+/// reads end of file.
+pub struct ChildStdin {
+    /// The pipe, `None` once shut down.
+    pipe: Option<Pipe>,
+}
+
+/// The standard output of a child process.
+pub struct ChildStdout {
+    pipe: Pipe,
+}
+
+/// The standard error of a child process.
+pub struct ChildStderr {
+    pipe: Pipe,
+}
+
+impl AsyncWrite for ChildStdin {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buffer: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        match &self.pipe {
+            Some(pipe) => pipe.poll_write(cx, buffer),
+            None => Poll::Ready(Err(io::ErrorKind::BrokenPipe.into())),
+        }
+    }
+
+    /// Pipes are not buffered: flushing always succeeds immediately.
+    fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    /// Closes the pipe.
+    fn poll_shutdown(mut self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.pipe = None;
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl AsyncRead for ChildStdout {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buffer: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        self.pipe.poll_read(cx, buffer)
+    }
+}
+
+impl AsyncRead for ChildStderr {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buffer: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        self.pipe.poll_read(cx, buffer)
+    }
+}
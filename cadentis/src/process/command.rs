@@ -0,0 +1,179 @@
+use super::child::Child;
+use crate::io::AsyncReadExt;
+use crate::join;
+
+use std::ffi::OsStr;
+use std::io;
+use std::path::Path;
+use std::process::{self, ExitStatus, Output, Stdio};
+
+/// A process builder, the async equivalent of [`std::process::Command`].
+///
+/// By default, the child inherits the standard streams of the current
+/// process. Use [`Stdio::piped`] to read its output or feed its input
+/// asynchronously through the [`Child`] handles.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// let mut child = Command::new("ls")
+///     .arg("-l")
+///     .stdout(Stdio::piped())
+///     .spawn()?;
+///
+/// let status = child.wait().await?;
+/// ```
+pub struct Command {
+    inner: process::Command,
+}
+
+impl Command {
+    /// Creates a builder for running `program`.
+    ///
+    /// The program is looked up in the `PATH` if it is not a path.
+    pub fn new(program: impl AsRef<OsStr>) -> Self {
+        Self {
+            inner: process::Command::new(program),
+        }
+    }
+
+    /// Adds an argument to pass to the program.
+    pub fn arg(&mut self, arg: impl AsRef<OsStr>) -> &mut Self {
+        self.inner.arg(arg);
+        self
+    }
+
+    /// Adds several arguments to pass to the program.
+    pub fn args<I, S>(&mut self, args: I) -> &mut Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<OsStr>,
+    {
+        self.inner.args(args);
+        self
+    }
+
+    /// Sets an environment variable of the child.
+    pub fn env(&mut self, key: impl AsRef<OsStr>, value: impl AsRef<OsStr>) -> &mut Self {
+        self.inner.env(key, value);
+        self
+    }
+
+    /// Removes an environment variable from the child's environment.
+    pub fn env_remove(&mut self, key: impl AsRef<OsStr>) -> &mut Self {
+        self.inner.env_remove(key);
+        self
+    }
+
+    /// Sets the working directory of the child.
+    pub fn current_dir(&mut self, dir: impl AsRef<Path>) -> &mut Self {
+        self.inner.current_dir(dir);
+        self
+    }
+
+    /// Configures the standard input of the child.
+    pub fn stdin(&mut self, cfg: impl Into<Stdio>) -> &mut Self {
+        self.inner.stdin(cfg);
+        self
+    }
+
+    /// Configures the standard output of the child.
+    pub fn stdout(&mut self, cfg: impl Into<Stdio>) -> &mut Self {
+        self.inner.stdout(cfg);
+        self
+    }
+
+    /// Configures the standard error of the child.
+    pub fn stderr(&mut self, cfg: impl Into<Stdio>) -> &mut Self {
+        self.inner.stderr(cfg);
+        self
+    }
+
+    /// Spawns the program as a child process.
+    ///
+    /// Piped streams are switched to non-blocking mode and become
+    /// available on the returned [`Child`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the program cannot be started.
+    ///
+    /// # Panics
+    ///
+    /// Using the returned handles outside of a running runtime panics.
+    pub fn spawn(&mut self) -> io::Result<Child> {
+        Child::new(self.inner.spawn()?)
+    }
+
+    /// Runs the program to completion and collects its output.
+    ///
+    /// Standard output and standard error are captured, whatever their
+    /// configuration, and read concurrently so that a chatty child
+    /// never blocks on a full pipe. Standard input is closed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the program cannot be started or if reading
+    /// its output fails.
+    pub async fn output(&mut self) -> io::Result<Output> {
+        self.inner
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+
+        let mut child = self.spawn()?;
+        let mut stdout = child.stdout.take();
+        let mut stderr = child.stderr.take();
+
+        let (stdout, stderr) = join!(
+            async {
+                let mut buffer = Vec::new();
+                if let Some(pipe) = &mut stdout {
+                    read_to_end(pipe, &mut buffer).await?;
+                }
+                io::Result::Ok(buffer)
+            },
+            async {
+                let mut buffer = Vec::new();
+                if let Some(pipe) = &mut stderr {
+                    read_to_end(pipe, &mut buffer).await?;
+                }
+                io::Result::Ok(buffer)
+            }
+        );
+
+        let status = child.wait().await?;
+
+        Ok(Output {
+            status,
+            stdout: stdout?,
+            stderr: stderr?,
+        })
+    }
+
+    /// Runs the program to completion and returns its exit status.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the program cannot be started.
+    pub async fn status(&mut self) -> io::Result<ExitStatus> {
+        self.spawn()?.wait().await
+    }
+}
+
+/// Reads `pipe` until end of file, appending to `buffer`.
+async fn read_to_end<R: AsyncReadExt + Unpin>(
+    pipe: &mut R,
+    buffer: &mut Vec<u8>,
+) -> io::Result<()> {
+    let mut chunk = [0u8; 4096];
+
+    loop {
+        let n = pipe.read(&mut chunk).await?;
+        if n == 0 {
+            return Ok(());
+        }
+
+        buffer.extend_from_slice(&chunk[..n]);
+    }
+}
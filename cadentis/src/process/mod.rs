@@ -0,0 +1,30 @@
+//! Asynchronous child processes.
+//!
+//! This module mirrors [`std::process`] for use inside the runtime:
+//! - [`Command`] configures and spawns a child process,
+//! - [`Child`] waits for it without blocking a worker thread,
+//! - [`ChildStdin`], [`ChildStdout`] and [`ChildStderr`] expose its
+//!   standard streams as [`AsyncWrite`](crate::io::AsyncWrite) and
+//!   [`AsyncRead`](crate::io::AsyncRead) pipes driven by the reactor.
+//!
+//! On Linux, the exit of a child is observed through a pidfd registered
+//! with the reactor; elsewhere, the child is polled with a backoff timer.
+//!
+//! This module is only available on unix platforms.
+//!
+//! # Examples
+//!
+//! ```rust,ignore
+//! let output = Command::new("echo").arg("hello").output().await?;
+//! assert_eq!(output.stdout, b"hello\n");
+//! ```
+
+mod child;
+mod command;
+mod sys;
+
+pub use child::{Child, ChildStderr, ChildStdin, ChildStdout};
+pub use command::Command;
+
+#[doc(no_inline)]
+pub use std::process::{ExitStatus, Output, Stdio};
@@ -0,0 +1,40 @@
+//! Raw system calls backing the process module.
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+use crate::sys::syscall;
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+use std::ffi::c_long;
+#[cfg(any(target_os = "linux", target_os = "android"))]
+use std::os::fd::FromRawFd;
+use std::os::fd::OwnedFd;
+
+/// `pidfd_open`, with the same number on every Linux architecture.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+const SYS_PIDFD_OPEN: c_long = 434;
+
+/// Opens a descriptor that becomes readable once process `pid` exits.
+///
+/// Returns `None` if pidfds are not supported (Linux before 5.3, or
+/// another platform).
+pub(super) fn pidfd_open(pid: u32) -> Option<OwnedFd> {
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    {
+        // `PIDFD_NONBLOCK` is not needed: the descriptor is only polled.
+        // SAFETY: `pidfd_open` takes a pid and flags and returns a new
+        // descriptor owned by the caller, or -1.
+        let fd = unsafe { syscall(SYS_PIDFD_OPEN, pid as c_long, 0 as c_long) };
+        if fd < 0 {
+            return None;
+        }
+
+        // SAFETY: the descriptor was just created and is owned by nobody else.
+        Some(unsafe { OwnedFd::from_raw_fd(fd as i32) })
+    }
+
+    #[cfg(not(any(target_os = "linux", target_os = "android")))]
+    {
+        let _ = pid;
+        None
+    }
+}
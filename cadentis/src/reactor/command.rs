@@ -4,6 +4,7 @@ use nucleus::io::RawFd;
 use nucleus::poll::Interest;
 use std::sync::Arc;
 use std::sync::atomic::AtomicBool;
+use std::sync::mpsc::Sender;
 use std::task::Waker;
 use std::time::Instant;
 
@@ -37,6 +38,19 @@ pub(crate) enum Command {
         fd: RawFd,
     },
 
+    /// Deregisters a file descriptor, then acknowledges on `done`.
+    ///
+    /// Used to hand a socket over to code outside the runtime: once
+    /// acknowledged, the reactor no longer reads from, writes to or
+    /// closes the file descriptor.
+    Detach {
+        /// File descriptor to deregister.
+        fd: RawFd,
+
+        /// Acknowledgement channel, sent to once deregistered.
+        done: Sender<()>,
+    },
+
     /// Schedules a timer to fire at a specific deadline.
     ///
     /// The provided waker is called once the deadline is reached,
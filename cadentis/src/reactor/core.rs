@@ -1,19 +1,33 @@
 use super::command::Command;
-use super::io::IoEntry;
-use super::timer::TimerEntry;
+use super::io::{BufferBudget, IoEntry, Stream};
+use super::stats::{ReactorCounters, ReactorStats};
+use super::timer::{TimerBudget, TimerEntry, TimerQueue, TimerSlot};
+use crate::net::sigpipe;
 use crate::reactor::io::Waiting;
+use crate::runtime::wake_batched;
+use crate::time::Clock;
 use crate::utils::Slab;
 
-use nucleus::io::{RawFd, sys_close, sys_read, sys_write};
-use nucleus::poll::{Event, Poller, Waker};
-use std::collections::BinaryHeap;
+use nucleus::io::{RawFd, sys_close, sys_read};
+use nucleus::poll::{Event, Interest, Poller, Waker};
+use std::any::Any;
+use std::collections::HashMap;
 use std::io;
-use std::sync::Arc;
-use std::sync::atomic::Ordering;
+use std::mem;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::mpsc::SendError;
 use std::sync::mpsc::{Receiver, Sender, channel};
+use std::sync::{Arc, OnceLock};
 use std::thread;
-use std::time::Instant;
+use std::time::Duration;
+
+/// Bytes a stream may move in each direction per readiness event.
+///
+/// Bounding both directions keeps a peer flooding one of them from
+/// starving the other, or the other streams served by the reactor: the
+/// poller reports the stream again while it stays ready.
+const IO_BUDGET: usize = 64 * 1024;
 
 /// The reactor.
 ///
@@ -35,11 +49,49 @@ pub(crate) struct Reactor {
     /// Buffer used to collect I/O events from the poller.
     events: Vec<Event>,
 
-    /// Min-heap of pending timers ordered by deadline.
-    timers: BinaryHeap<TimerEntry>,
+    /// Pending timers, grouped by deadline.
+    timers: TimerQueue,
 
     /// Slab storing active I/O entries indexed by poller tokens.
     io: Slab<IoEntry>,
+
+    /// Poller token of every registered file descriptor.
+    ///
+    /// Used to update an existing registration in place when the same
+    /// file descriptor is registered again.
+    tokens: HashMap<RawFd, usize>,
+
+    /// Bytes buffered by the streams, against the runtime limit.
+    buffers: Arc<BufferBudget>,
+
+    /// Streams whose reads are paused for lack of buffer budget, by file
+    /// descriptor and token.
+    paused: Vec<(RawFd, usize)>,
+
+    /// Statistics published for observability.
+    stats: Arc<ReactorCounters>,
+
+    /// Time source used to fire timers.
+    clock: Arc<dyn Clock>,
+
+    /// Wake-up coordination shared with the handles.
+    signal: Arc<Signal>,
+}
+
+/// Coordinates wake-ups of the reactor between its handles.
+///
+/// Waking the poller costs a syscall on both sides, so handles only wake
+/// it while it is blocked, and only once per blocking poll: a burst of
+/// commands is picked up by a single wake-up.
+#[derive(Default)]
+struct Signal {
+    /// Whether the reactor is blocked in a poll, or about to be, and was
+    /// not woken yet.
+    polling: AtomicBool,
+
+    /// Whether something was sent since the reactor last drained its
+    /// commands.
+    notified: AtomicBool,
 }
 
 /// A handle used to communicate with the reactor thread.
@@ -55,23 +107,121 @@ pub(crate) struct ReactorHandle {
 
     /// Waker used to interrupt the poller.
     waker: Arc<Waker>,
+
+    /// Wake-up coordination shared with the reactor.
+    signal: Arc<Signal>,
+
+    /// Time source shared with the reactor.
+    clock: Arc<dyn Clock>,
+
+    /// Description of the error that stopped the reactor, if any.
+    failure: Arc<OnceLock<String>>,
+
+    /// Statistics published by the reactor thread.
+    stats: Arc<ReactorCounters>,
+
+    /// Timers armed on the reactor, against the runtime limit.
+    timers: Arc<TimerBudget>,
+
+    /// Bytes buffered by the streams, against the runtime limit.
+    buffers: Arc<BufferBudget>,
 }
 
 impl ReactorHandle {
     /// Sends a command to the reactor and wakes it.
     pub(crate) fn send(&self, cmd: Command) -> Result<(), SendError<Command>> {
+        self.stats.pending_commands.fetch_add(1, Ordering::Relaxed);
+
         let result = self.sender.send(cmd);
-        self.waker.wake();
+        if result.is_err() {
+            self.stats.pending_commands.fetch_sub(1, Ordering::Relaxed);
+        }
+
+        self.wake();
         result
     }
+
+    /// Deregisters `fd`, blocking until the reactor has done so.
+    ///
+    /// Returns right away if the reactor has stopped, in which case it
+    /// no longer uses any file descriptor either.
+    pub(crate) fn detach(&self, fd: RawFd) {
+        let (done, acknowledged) = channel();
+
+        if self.send(Command::Detach { fd, done }).is_ok() {
+            // Fails if the reactor stops, dropping the command.
+            let _ = acknowledged.recv();
+        }
+    }
+
+    /// Wakes the reactor so that it re-checks its commands and timers.
+    ///
+    /// The poller is only interrupted if the reactor is blocked in a poll
+    /// that no other handle woke yet; otherwise the reactor is bound to
+    /// look at its commands and timers before blocking again.
+    pub(crate) fn wake(&self) {
+        // Pairs with the reactor storing `polling` before it checks
+        // `notified`: either it sees the notification and does not block,
+        // or this sees it polling and wakes it.
+        self.signal.notified.store(true, Ordering::SeqCst);
+
+        if self.signal.polling.swap(false, Ordering::SeqCst) {
+            self.stats.wakeups.fetch_add(1, Ordering::Relaxed);
+            self.waker.wake();
+        }
+    }
+
+    /// Returns the time source of the reactor.
+    pub(crate) fn clock(&self) -> &Arc<dyn Clock> {
+        &self.clock
+    }
+
+    /// Returns why the reactor thread stopped, if it failed.
+    ///
+    /// Once the reactor has failed, no I/O event or timer is delivered
+    /// anymore, so tasks waiting on them never complete.
+    pub(crate) fn failure(&self) -> Option<&str> {
+        self.failure.get().map(String::as_str)
+    }
+
+    /// Returns a snapshot of the reactor statistics.
+    pub(crate) fn stats(&self) -> ReactorStats {
+        let mut stats = self.stats.snapshot();
+        stats.buffered_bytes = self.buffers.used();
+        stats
+    }
+
+    /// Returns the buffer budget shared by the streams of the reactor.
+    pub(crate) fn buffers(&self) -> &BufferBudget {
+        &self.buffers
+    }
+
+    /// Reserves a slot for a timer about to be armed.
+    ///
+    /// # Errors
+    ///
+    /// Returns `QuotaExceeded` if the runtime limit of armed timers is
+    /// reached.
+    pub(crate) fn timer_slot(&self) -> io::Result<TimerSlot> {
+        self.timers.acquire()
+    }
 }
 
 impl Reactor {
     /// Creates a new reactor instance.
-    fn new(receiver: Receiver<Command>, poller: Poller) -> Self {
+    fn new(
+        receiver: Receiver<Command>,
+        poller: Poller,
+        clock: Arc<dyn Clock>,
+        stats: Arc<ReactorCounters>,
+        signal: Arc<Signal>,
+        timer_granularity: Duration,
+        buffers: Arc<BufferBudget>,
+    ) -> Self {
         let events = Vec::with_capacity(64);
-        let timers = BinaryHeap::new();
+        let timers = TimerQueue::new(clock.now(), timer_granularity);
         let io = Slab::new(64);
+        let tokens = HashMap::new();
 
         Self {
             receiver,
@@ -79,62 +229,136 @@ impl Reactor {
             events,
             timers,
             io,
+            tokens,
+            buffers,
+            paused: Vec::new(),
+            stats,
+            clock,
+            signal,
         }
     }
 
     /// Starts the reactor thread and returns a handle to it.
-    pub(crate) fn start() -> ReactorHandle {
+    ///
+    /// Timers are fired according to `clock`, in groups of deadlines
+    /// `timer_granularity` wide, and at most `max_timers` of them can be
+    /// armed at once, if set. Reads from streams pause while their
+    /// buffers hold `max_buffer_bytes` in total, if set.
+    ///
+    /// If the event loop fails with an unrecoverable error or panics, the
+    /// reason is recorded and exposed through [`ReactorHandle::failure`]
+    /// instead of being lost with the thread.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the poller cannot be created, for instance when
+    /// the process is out of file descriptors, or if the reactor thread
+    /// cannot be spawned.
+    pub(crate) fn start(
+        clock: Arc<dyn Clock>,
+        max_timers: Option<usize>,
+        timer_granularity: Duration,
+        max_buffer_bytes: Option<usize>,
+    ) -> io::Result<ReactorHandle> {
         let (sender, rx) = channel();
-        let poller = Poller::new();
-        let waker = poller.waker();
-
-        thread::spawn(move || {
-            let mut reactor = Reactor::new(rx, poller);
-            reactor.run().unwrap();
-        });
 
-        ReactorHandle { sender, waker }
+        let poller = Poller::try_new()?;
+        let waker = poller.waker();
+        let failure = Arc::new(OnceLock::new());
+        let stats = Arc::new(ReactorCounters::default());
+        let signal = Arc::new(Signal::default());
+        let buffers = Arc::new(BufferBudget::new(max_buffer_bytes));
+
+        let reactor_clock = clock.clone();
+        let reactor_failure = failure.clone();
+        let reactor_stats = stats.clone();
+        let reactor_signal = signal.clone();
+        let reactor_buffers = buffers.clone();
+        thread::Builder::new().spawn(move || {
+            // Creating the reactor reads the clock, which may fail too.
+            let run = || {
+                Reactor::new(
+                    rx,
+                    poller,
+                    reactor_clock,
+                    reactor_stats,
+                    reactor_signal,
+                    timer_granularity,
+                    reactor_buffers,
+                )
+                .run()
+            };
+
+            let reason = match panic::catch_unwind(AssertUnwindSafe(run)) {
+                Ok(Ok(())) => return,
+                Ok(Err(e)) => format!("poll failed: {e}"),
+                Err(payload) => format!("panicked: {}", panic_message(&*payload)),
+            };
+
+            let _ = reactor_failure.set(reason);
+        })?;
+
+        Ok(ReactorHandle {
+            sender,
+            waker,
+            signal,
+            clock,
+            failure,
+            stats,
+            timers: Arc::new(TimerBudget::new(max_timers)),
+            buffers,
+        })
     }
 
     /// Main reactor event loop.
     ///
     /// The loop performs the following steps:
-    /// 1. Handle I/O events from the previous poll
+    /// 1. Handle I/O events from the previous poll and fire expired
+    ///    timers, waking their tasks in a single batch
     /// 2. Process pending commands
     /// 3. Poll the OS for new events (with timer-based timeout)
-    /// 4. Fire expired timers
     fn run(&mut self) -> io::Result<()> {
         loop {
-            // Handle previously collected I/O events
-            let events: Vec<Event> = self.events.drain(..).collect();
-            for event in events {
-                self.handle_event(event);
-            }
+            // Wake the tasks of the I/O events and expired timers of the
+            // previous poll, handing them to the workers in bulk.
+            let ((), handed) = wake_batched(|| {
+                let events: Vec<Event> = self.events.drain(..).collect();
+                for event in events {
+                    self.handle_event(event);
+                }
+
+                self.fire_timers();
+            });
+            self.stats.record_handoff(handed);
+
+            // Process incoming commands. Anything sent from now on
+            // notifies the reactor again.
+            self.signal.notified.store(false, Ordering::SeqCst);
 
-            // Process incoming commands
             while let Ok(cmd) = self.receiver.try_recv() {
+                self.stats.pending_commands.fetch_sub(1, Ordering::Relaxed);
+
                 match cmd {
                     Command::Register {
                         fd,
                         interest,
                         entry,
                     } => {
-                        let token = self.io.insert(entry);
-                        self.poller.register(fd, token, interest);
+                        self.register(fd, interest, entry);
                     }
                     Command::Deregister { fd } => {
-                        self.poller.deregister(fd);
+                        self.deregister(fd);
+                    }
+                    Command::Detach { fd, done } => {
+                        self.deregister(fd);
+                        let _ = done.send(());
                     }
                     Command::SetTimer {
                         deadline,
                         waker,
                         cancelled,
                     } => {
-                        self.timers.push(TimerEntry {
-                            deadline,
-                            waker,
-                            cancelled,
-                        });
+                        self.timers.push(deadline, TimerEntry { waker, cancelled });
                     }
                     Command::Shutdown => {
                         return Ok(());
@@ -142,30 +366,143 @@ impl Reactor {
                 }
             }
 
-            // Compute poll timeout from next timer
-            let timeout = self
-                .timers
-                .peek()
-                .map(|t| t.deadline.saturating_duration_since(Instant::now()));
+            if !self.paused.is_empty() && !self.buffers.exhausted() {
+                self.resume_reads();
+            }
+
+            self.publish_stats();
 
-            // Poll for I/O events
-            self.poller.poll(&mut self.events, timeout)?;
+            // Announce the poll before looking at the timers, so that a
+            // handle waking the reactor after this point interrupts it.
+            self.signal.polling.store(true, Ordering::SeqCst);
 
-            // Fire expired timers
-            let now = Instant::now();
-            while let Some(timer) = self.timers.peek() {
-                if timer.deadline > now {
-                    break;
-                }
+            // Compute poll timeout from next timer, or skip blocking when
+            // notified since the commands were drained.
+            let timeout = if self.signal.notified.load(Ordering::SeqCst) {
+                Some(Duration::ZERO)
+            } else {
+                self.timers
+                    .next_deadline()
+                    .map(|deadline| deadline.saturating_duration_since(self.clock.now()))
+            };
 
-                let timer = self.timers.pop().unwrap();
+            // Poll for I/O events, retrying when interrupted by a signal
+            let polled = self.poller.poll(&mut self.events, timeout);
+            self.signal.polling.store(false, Ordering::SeqCst);
 
-                if timer.cancelled.load(Ordering::Acquire) {
+            if let Err(e) = polled {
+                if e.kind() == io::ErrorKind::Interrupted {
                     continue;
                 }
 
-                timer.waker.wake();
+                return Err(e);
+            }
+
+            self.stats.record_poll(self.events.len());
+        }
+    }
+
+    /// Wakes the tasks of every expired timer.
+    fn fire_timers(&mut self) {
+        for timer in self.timers.expire(self.clock.now()) {
+            timer.waker.wake();
+        }
+    }
+
+    /// Resumes the reads paused for lack of buffer budget.
+    ///
+    /// Every paused stream is registered for reads again. Streams are
+    /// paused anew, without reading, if the budget runs out before their
+    /// turn.
+    fn resume_reads(&mut self) {
+        self.buffers.resume();
+
+        for (fd, token) in mem::take(&mut self.paused) {
+            // The stream may have been closed, and its token reused.
+            if self.tokens.get(&fd) != Some(&token) {
+                continue;
+            }
+
+            if let IoEntry::Stream(stream) = self.io.get_mut(token) {
+                let interest = {
+                    let mut stream = stream.lock().unwrap();
+                    stream.reads_paused = false;
+                    stream.interest()
+                };
+
+                self.poller.reregister(fd, token, interest);
+            }
+        }
+    }
+
+    /// Publishes the number of registrations and timers.
+    fn publish_stats(&self) {
+        self.stats
+            .registered_fds
+            .store(self.tokens.len(), Ordering::Relaxed);
+        self.stats
+            .timers
+            .store(self.timers.len(), Ordering::Relaxed);
+        self.stats
+            .timer_groups
+            .store(self.timers.groups(), Ordering::Relaxed);
+        self.stats
+            .paused_streams
+            .store(self.paused.len(), Ordering::Relaxed);
+    }
+
+    /// Registers `fd` with the poller.
+    ///
+    /// If `fd` is already registered, its entry and interest are updated
+    /// in place instead of taking a new slab slot, so that futures
+    /// waiting repeatedly on the same file descriptor do not accumulate
+    /// registrations. Tasks waiting on the replaced entry are woken so
+    /// that they can register again.
+    ///
+    /// A known `fd` is still registered afresh with the poller: it may
+    /// have been closed without being deregistered, which drops it from
+    /// the poller, and reused by a new socket since.
+    fn register(&mut self, fd: RawFd, interest: Interest, entry: IoEntry) {
+        // The directions waited for may have changed since the command was
+        // sent: watch the ones waiting now, if any.
+        let interest = match &entry {
+            IoEntry::Readiness(directions) => {
+                let interest = directions.interest();
+                if !interest.read && !interest.write {
+                    self.deregister(fd);
+                    return;
+                }
+                interest
+            }
+            _ => interest,
+        };
+
+        match self.tokens.get(&fd) {
+            Some(&token) => {
+                let previous = mem::replace(self.io.get_mut(token), entry);
+                self.poller.deregister(fd);
+                self.poller.register(fd, token, interest);
+
+                if !previous.same_waiter(self.io.get_mut(token)) {
+                    previous.wake_all();
+                }
             }
+            None => {
+                let token = self.io.insert(entry);
+                self.poller.register(fd, token, interest);
+                self.tokens.insert(fd, token);
+            }
+        }
+    }
+
+    /// Deregisters `fd` from the poller and releases its slab slot.
+    ///
+    /// Does nothing if `fd` is not registered, for instance because its
+    /// one-shot waiter already fired.
+    fn deregister(&mut self, fd: RawFd) {
+        if let Some(token) = self.tokens.remove(&fd) {
+            self.poller.deregister(fd);
+            drop(self.io.remove(token));
         }
     }
 
@@ -174,56 +511,129 @@ impl Reactor {
         let mut should_close = false;
         let mut fd = None;
         let mut new_interest = None;
+        let mut fired = None;
 
         {
             let entry = self.io.get_mut(event.token);
 
             match entry {
                 // One-shot waiter
-                IoEntry::Waiting(Waiting { waker, interest }) => {
+                IoEntry::Waiting(Waiting {
+                    fd: waiting_fd,
+                    waker,
+                    interest,
+                }) => {
                     let mut woke = false;
 
                     if event.readable && interest.read {
-                        waker.wake_by_ref();
+                        waker.wake();
                         woke = true;
                     }
 
                     if event.writable && interest.write {
-                        waker.wake_by_ref();
+                        waker.wake();
                         woke = true;
                     }
 
                     if woke {
-                        self.io.remove(event.token);
+                        fired = Some(*waiting_fd);
+                    }
+                }
+
+                // Independent reader and writer
+                IoEntry::Readiness(directions) => {
+                    if event.readable {
+                        directions.read.fire();
+                    }
+
+                    if event.writable {
+                        directions.write.fire();
+                    }
+
+                    // Keep watching the direction still waited for, if any.
+                    let interest = directions.interest();
+                    if interest.read || interest.write {
+                        fd = Some(directions.fd);
+                        new_interest = Some(interest);
+                    } else {
+                        fired = Some(directions.fd);
                     }
                 }
 
                 // Buffered stream
                 IoEntry::Stream(stream) => {
-                    let mut stream = stream.lock().unwrap();
+                    let mut guard = stream.lock().unwrap();
+                    let stream = &mut *guard;
                     fd = Some(stream.fd);
 
-                    if event.readable {
-                        if handle_read(stream.fd, &mut stream.in_buffer) {
-                            should_close = true;
+                    // Each direction gets a bounded turn per event, so that
+                    // a full-duplex exchange progresses both ways at once.
+                    if event.readable && !stream.eof {
+                        if self.buffers.exhausted() {
+                            // Past the buffer budget, input stays in the
+                            // socket until tasks drain the buffers.
+                            if !stream.reads_paused {
+                                stream.reads_paused = true;
+                                self.buffers.pause();
+                                self.paused.push((stream.fd, event.token));
+                            }
                         } else {
-                            stream.read_waiters.drain(..).for_each(|w| w.wake());
+                            match handle_read(stream, &self.stats.stream_reads) {
+                                Ok(eof) => {
+                                    // The peer may only have closed its write half:
+                                    // keep the stream registered so writes can proceed.
+                                    stream.eof = eof;
+                                    stream.read_waiters.drain(..).for_each(|w| w.wake());
+                                }
+                                Err(err) => {
+                                    stream.error = Some(err);
+                                    should_close = true;
+                                }
+                            }
                         }
                     }
 
                     if !should_close && event.writable {
-                        if handle_write(stream.fd, &mut stream.out_buffer) {
-                            should_close = true;
-                        } else if stream.out_buffer.is_empty() {
-                            stream.write_waiters.drain(..).for_each(|w| w.wake());
+                        let queued = stream.out_buffer.len();
+
+                        let result = handle_write(
+                            stream.fd,
+                            &mut stream.out_buffer,
+                            &mut stream.bytes_written,
+                        );
+
+                        // Writers queued behind others complete as soon as
+                        // their own bytes are out, before the buffer empties.
+                        let drained = queued - stream.out_buffer.len();
+                        stream.flushed += drained as u64;
+
+                        match result {
+                            Ok(()) if drained > 0 || stream.out_buffer.is_empty() => {
+                                stream.write_waiters.drain(..).for_each(|w| w.wake());
+                            }
+                            Ok(()) => {}
+                            Err(err) => {
+                                stream.error = Some(err);
+                                should_close = true;
+                            }
                         }
+
+                        stream.account();
                     }
 
+                    // Pending reads, writes and flushes are woken by the
+                    // cleanup below, and must then fail with the error
+                    // rather than wait.
                     new_interest = Some(stream.interest());
                 }
             }
         }
 
+        // One-shot waiters leave the poller once fired.
+        if let Some(fd) = fired {
+            self.deregister(fd);
+        }
+
         if let Some(fd) = fd {
             if should_close {
                 self.cleanup(event.token, fd);
@@ -235,27 +645,46 @@ impl Reactor {
 
     /// Cleans up a closed or errored I/O entry.
     fn cleanup(&mut self, token: usize, fd: RawFd) {
+        self.tokens.remove(&fd);
         self.poller.deregister(fd);
         self.io.remove(token).wake_all();
         sys_close(fd);
     }
 }
 
-/// Reads data from a file descriptor into a buffer.
+/// Reads data from a stream socket into its input buffer.
+///
+/// Reads are sized by the [`ReadSize`](super::io::ReadSize) of the
+/// stream, and counted in `reads`. Every byte read is added to the
+/// stream byte counter, and accounted in the runtime buffer budget.
 ///
-/// Returns `true` if the file descriptor should be closed.
-fn handle_read(fd: RawFd, buffer: &mut Vec<u8>) -> bool {
-    let mut temp = [0u8; 1024];
+/// Returns `Ok(true)` once the peer has closed its write half (EOF),
+/// `Ok(false)` if the file descriptor has been drained, or the
+/// [`IO_BUDGET`] or the buffer budget spent, and an error if the file
+/// descriptor should be closed.
+fn handle_read(stream: &mut Stream, reads: &AtomicU64) -> io::Result<bool> {
+    let mut budget = IO_BUDGET;
 
-    loop {
-        let n = sys_read(fd, &mut temp);
+    while budget > 0 && !stream.reactor.buffers().exhausted() {
+        // Read straight into the input buffer, then drop the bytes the
+        // read did not fill.
+        let len = stream.in_buffer.len();
+        stream.in_buffer.resize(len + stream.read_size.next(), 0);
+
+        let n = sys_read(stream.fd, &mut stream.in_buffer[len..]);
+
+        stream.in_buffer.truncate(len + n.max(0) as usize);
+        reads.fetch_add(1, Ordering::Relaxed);
 
         match n {
             (1..) => {
-                buffer.extend_from_slice(&temp[..n as usize]);
+                stream.read_size.record(n as usize);
+                stream.bytes_read += n as u64;
+                stream.account();
+                budget = budget.saturating_sub(n as usize);
             }
             0 => {
-                return true;
+                return Ok(true);
             }
             _ => {
                 let error = io::Error::last_os_error();
@@ -263,34 +692,55 @@ fn handle_read(fd: RawFd, buffer: &mut Vec<u8>) -> bool {
                 if error.kind() == io::ErrorKind::WouldBlock {
                     break;
                 } else {
-                    return true;
+                    return Err(error);
                 }
             }
         }
     }
 
-    false
+    Ok(false)
 }
 
 /// Writes buffered data to a file descriptor.
 ///
-/// Returns `true` if the file descriptor should be closed.
-fn handle_write(fd: RawFd, buffer: &mut Vec<u8>) -> bool {
-    while !buffer.is_empty() {
-        let n = sys_write(fd, buffer);
+/// Writes at most [`IO_BUDGET`] bytes per call, and every byte written is
+/// added to `total`. Writes go through [`sigpipe::send`], so a reset
+/// connection reports an error rather than raising `SIGPIPE`.
+///
+/// Returns an error if the file descriptor should be closed.
+fn handle_write(fd: RawFd, buffer: &mut Vec<u8>, total: &mut u64) -> io::Result<()> {
+    let mut written = 0;
+
+    while written < buffer.len() && written < IO_BUDGET {
+        let end = buffer.len().min(IO_BUDGET);
+        let n = sigpipe::send(fd, &buffer[written..end]);
 
         if n > 0 {
-            buffer.drain(..n as usize);
+            written += n as usize;
+            *total += n as u64;
         } else if n < 0 {
             let err = io::Error::last_os_error();
 
             if err.kind() == io::ErrorKind::WouldBlock {
                 break;
             } else {
-                return true;
+                buffer.drain(..written);
+                return Err(err);
             }
         }
     }
 
-    false
+    buffer.drain(..written);
+    Ok(())
+}
+
+/// Extracts the message of a panic payload.
+fn panic_message(payload: &(dyn Any + Send)) -> &str {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message
+    } else {
+        "unknown panic"
+    }
 }
@@ -1,16 +1,24 @@
+use crate::net::accept::accept;
 use crate::reactor::command::Command;
 use crate::reactor::io::{IoEntry, Stream, Waiting};
 use crate::runtime::context::CURRENT_REACTOR;
+use crate::sync::AtomicWaker;
+use crate::time::sleep::{Sleep, sleep};
 
 use nucleus::io::{RawFd, sys_read, sys_write};
 use nucleus::poll::Interest;
-use nucleus::socket::{EINPROGRESS, sys_accept, sys_connect, sys_get_socket_error};
+use nucleus::socket::{EINPROGRESS, sys_connect, sys_get_socket_error};
 use std::future::Future;
 use std::io;
 use std::net::SocketAddr;
 use std::pin::Pin;
 use std::sync::{Arc, Mutex};
 use std::task::{Context, Poll};
+use std::time::Duration;
+
+/// How long [`AcceptFuture`] waits before retrying once the process ran
+/// out of file descriptors.
+const ACCEPT_BACKOFF: Duration = Duration::from_millis(10);
 
 /// Asynchronous read operation on a raw file descriptor.
 ///
@@ -23,6 +31,9 @@ pub struct ReadFuture<'a> {
     fd: RawFd,
     buffer: &'a mut [u8],
     registered: bool,
+
+    /// Waker shared with the reactor entry, refreshed on every poll.
+    waker: Arc<AtomicWaker>,
 }
 
 impl<'a> ReadFuture<'a> {
@@ -32,6 +43,7 @@ impl<'a> ReadFuture<'a> {
             fd,
             buffer,
             registered: false,
+            waker: Arc::new(AtomicWaker::new()),
         }
     }
 }
@@ -58,6 +70,8 @@ impl<'a> Future for ReadFuture<'a> {
         let err = io::Error::last_os_error();
 
         if err.kind() == io::ErrorKind::WouldBlock {
+            this.waker.register(cx.waker());
+
             if !this.registered {
                 CURRENT_REACTOR.with(|cell| {
                     let binding = cell.borrow();
@@ -72,7 +86,8 @@ impl<'a> Future for ReadFuture<'a> {
                         fd: this.fd,
                         interest,
                         entry: IoEntry::Waiting(Waiting {
-                            waker: cx.waker().clone(),
+                            fd: this.fd,
+                            waker: this.waker.clone(),
                             interest,
                         }),
                     });
@@ -99,7 +114,13 @@ pub struct WriteFuture<'a> {
     fd: RawFd,
     buffer: &'a [u8],
     written: usize,
+
+    /// Whether interest was ever registered, and must be dropped once
+    /// the write completes.
     registered: bool,
+
+    /// Waker shared with the reactor entry, refreshed on every poll.
+    waker: Arc<AtomicWaker>,
 }
 
 impl<'a> WriteFuture<'a> {
@@ -110,6 +131,7 @@ impl<'a> WriteFuture<'a> {
             buffer,
             written: 0,
             registered: false,
+            waker: Arc::new(AtomicWaker::new()),
         }
     }
 }
@@ -136,28 +158,33 @@ impl<'a> Future for WriteFuture<'a> {
             let err = io::Error::last_os_error();
 
             if err.kind() == io::ErrorKind::WouldBlock {
-                if !this.registered {
-                    CURRENT_REACTOR.with(|cell| {
-                        let binding = cell.borrow();
-                        let reactor = binding.as_ref().expect("no reactor in context");
+                this.waker.register(cx.waker());
 
-                        let interest = Interest {
-                            read: false,
-                            write: true,
-                        };
+                // The reactor drops a waiter once it fires, and a large
+                // buffer can fill the descriptor many times over: interest
+                // is registered on every `WouldBlock`. A waiter that is
+                // still armed is updated in place.
+                CURRENT_REACTOR.with(|cell| {
+                    let binding = cell.borrow();
+                    let reactor = binding.as_ref().expect("no reactor in context");
 
-                        let _ = reactor.send(Command::Register {
+                    let interest = Interest {
+                        read: false,
+                        write: true,
+                    };
+
+                    let _ = reactor.send(Command::Register {
+                        fd: this.fd,
+                        interest,
+                        entry: IoEntry::Waiting(Waiting {
                             fd: this.fd,
+                            waker: this.waker.clone(),
                             interest,
-                            entry: IoEntry::Waiting(Waiting {
-                                waker: cx.waker().clone(),
-                                interest,
-                            }),
-                        });
+                        }),
                     });
+                });
 
-                    this.registered = true;
-                }
+                this.registered = true;
 
                 return Poll::Pending;
             }
@@ -179,9 +206,21 @@ impl<'a> Future for WriteFuture<'a> {
 ///
 /// Resolves with the newly accepted client file descriptor and
 /// its peer address.
+///
+/// When the process or the system runs out of file descriptors
+/// (`EMFILE` / `ENFILE`), the pending connection stays in the backlog:
+/// instead of failing, or spinning on a socket that stays readable, the
+/// future waits [`ACCEPT_BACKOFF`] before trying again, giving the
+/// application a chance to close some descriptors.
 pub struct AcceptFuture {
     fd: RawFd,
     registered: bool,
+
+    /// Waker shared with the reactor entry, refreshed on every poll.
+    waker: Arc<AtomicWaker>,
+
+    /// Timer delaying the next attempt after running out of descriptors.
+    backoff: Option<Sleep>,
 }
 
 impl AcceptFuture {
@@ -190,6 +229,8 @@ impl AcceptFuture {
         Self {
             fd,
             registered: false,
+            waker: Arc::new(AtomicWaker::new()),
+            backoff: None,
         }
     }
 }
@@ -200,13 +241,37 @@ impl Future for AcceptFuture {
     fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
         let this = self.get_mut();
 
-        match sys_accept(this.fd) {
+        if let Some(backoff) = &mut this.backoff {
+            match Pin::new(backoff).poll(cx) {
+                Poll::Ready(()) => this.backoff = None,
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+
+        match accept(this.fd) {
             Ok((client_fd, addr)) => {
                 deregister(this.fd, this.registered);
                 Poll::Ready(Ok((client_fd, addr)))
             }
 
+            Err(err) if is_fd_exhausted(&err) => {
+                // The socket stays readable while the connection waits in
+                // the backlog: stop listening for readiness and retry later.
+                deregister(this.fd, this.registered);
+                this.registered = false;
+
+                let mut backoff = sleep(ACCEPT_BACKOFF);
+                match Pin::new(&mut backoff).poll(cx) {
+                    Poll::Ready(()) => cx.waker().wake_by_ref(),
+                    Poll::Pending => this.backoff = Some(backoff),
+                }
+
+                Poll::Pending
+            }
+
             Err(err) if err.kind() == io::ErrorKind::WouldBlock => {
+                this.waker.register(cx.waker());
+
                 if !this.registered {
                     CURRENT_REACTOR.with(|cell| {
                         let binding = cell.borrow();
@@ -221,7 +286,8 @@ impl Future for AcceptFuture {
                             fd: this.fd,
                             interest,
                             entry: IoEntry::Waiting(Waiting {
-                                waker: cx.waker().clone(),
+                                fd: this.fd,
+                                waker: this.waker.clone(),
                                 interest,
                             }),
                         });
@@ -241,12 +307,27 @@ impl Future for AcceptFuture {
     }
 }
 
+/// Returns whether `err` reports that no file descriptor is available,
+/// either for the process (`EMFILE`) or for the whole system (`ENFILE`).
+fn is_fd_exhausted(err: &io::Error) -> bool {
+    #[cfg(unix)]
+    const CODES: [i32; 2] = [24, 23];
+
+    #[cfg(windows)]
+    const CODES: [i32; 1] = [10024];
+
+    err.raw_os_error().is_some_and(|code| CODES.contains(&code))
+}
+
 /// Asynchronous non-blocking connect operation.
 pub struct ConnectFuture {
     fd: RawFd,
     addr: SocketAddr,
     started: bool,
     registered: bool,
+
+    /// Waker shared with the reactor entry, refreshed on every poll.
+    waker: Arc<AtomicWaker>,
 }
 
 impl ConnectFuture {
@@ -257,6 +338,7 @@ impl ConnectFuture {
             addr,
             started: false,
             registered: false,
+            waker: Arc::new(AtomicWaker::new()),
         }
     }
 }
@@ -295,6 +377,8 @@ impl Future for ConnectFuture {
             {
                 this.started = true;
 
+                this.waker.register(cx.waker());
+
                 if !this.registered {
                     CURRENT_REACTOR.with(|cell| {
                         let binding = cell.borrow();
@@ -309,7 +393,8 @@ impl Future for ConnectFuture {
                             fd: this.fd,
                             interest,
                             entry: IoEntry::Waiting(Waiting {
-                                waker: cx.waker().clone(),
+                                fd: this.fd,
+                                waker: this.waker.clone(),
                                 interest,
                             }),
                         });
@@ -344,15 +429,29 @@ fn deregister(fd: RawFd, registered: bool) {
 ///
 /// Data is first read from the internal buffer filled by the reactor.
 /// If no data is available, the task is registered as a read waiter.
+///
+/// Once the peer has closed its write half and the buffer is drained,
+/// the read resolves with `Ok(0)`.
+///
+/// If the stream has a read timeout, a reactor timer is armed the first
+/// time the read has to wait, and the read fails with `TimedOut` if no
+/// data arrives before it fires.
 pub struct ReadFutureStream<'a> {
     stream: Arc<Mutex<Stream>>,
     buffer: &'a mut [u8],
+
+    /// Timer bounding the wait, armed on the first pending poll.
+    timer: Option<Sleep>,
 }
 
 impl<'a> ReadFutureStream<'a> {
     /// Creates a new stream read future.
     pub fn new(stream: Arc<Mutex<Stream>>, buffer: &'a mut [u8]) -> Self {
-        Self { stream, buffer }
+        Self {
+            stream,
+            buffer,
+            timer: None,
+        }
     }
 }
 
@@ -363,27 +462,66 @@ impl<'a> Future for ReadFutureStream<'a> {
         let this = self.get_mut();
         let mut stream = this.stream.lock().unwrap();
 
-        if !stream.in_buffer.is_empty() {
-            let n = std::cmp::min(this.buffer.len(), stream.in_buffer.len());
+        if let Poll::Ready(result) = stream.poll_read(cx, this.buffer) {
+            return Poll::Ready(result);
+        }
+
+        let timeout = stream.read_timeout;
+        drop(stream);
+
+        poll_timer(&mut this.timer, timeout, cx, "read timed out")
+    }
+}
+
+/// Asynchronous read operation on a buffered stream, into an owned buffer.
+///
+/// This behaves like [`ReadFutureStream`], but owns its buffer instead
+/// of borrowing it, so the future is `'static` and can be stored freely.
+/// The buffer is handed back alongside the number of bytes read.
+pub struct OwnedReadFuture {
+    /// The stream read from, filled by the reactor.
+    stream: Arc<Mutex<Stream>>,
+
+    /// The owned buffer, taken back on completion.
+    buffer: Option<Vec<u8>>,
 
-            this.buffer[..n].copy_from_slice(&stream.in_buffer[..n]);
-            stream.in_buffer.drain(..n);
+    /// Timer bounding the wait, armed on the first pending poll.
+    timer: Option<Sleep>,
+}
 
-            return Poll::Ready(Ok(n));
+impl OwnedReadFuture {
+    /// Creates a new owned stream read future.
+    pub fn new(stream: Arc<Mutex<Stream>>, buffer: Vec<u8>) -> Self {
+        Self {
+            stream,
+            buffer: Some(buffer),
+            timer: None,
         }
+    }
+}
 
-        stream.read_waiters.push(cx.waker().clone());
+impl Future for OwnedReadFuture {
+    type Output = io::Result<(Vec<u8>, usize)>;
 
-        if !stream.in_buffer.is_empty() {
-            let n = std::cmp::min(this.buffer.len(), stream.in_buffer.len());
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        let mut stream = this.stream.lock().unwrap();
 
-            this.buffer[..n].copy_from_slice(&stream.in_buffer[..n]);
-            stream.in_buffer.drain(..n);
+        let buffer = this
+            .buffer
+            .as_mut()
+            .expect("OwnedReadFuture polled after completion");
 
-            return Poll::Ready(Ok(n));
+        match stream.poll_read(cx, buffer) {
+            Poll::Ready(Ok(n)) => return Poll::Ready(Ok((this.buffer.take().unwrap(), n))),
+            Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
+            Poll::Pending => {}
         }
 
-        Poll::Pending
+        let timeout = stream.read_timeout;
+        drop(stream);
+
+        poll_timer(&mut this.timer, timeout, cx, "read timed out")
     }
 }
 
@@ -391,10 +529,25 @@ impl<'a> Future for ReadFutureStream<'a> {
 ///
 /// Data is appended to the stream output buffer and flushed by
 /// the reactor when the file descriptor becomes writable.
+///
+/// The write completes once its own bytes, and those queued before them,
+/// have been written: bytes queued after it by other writers sharing the
+/// stream do not hold it back, so writers complete in the order they
+/// queued their bytes.
+///
+/// If the stream has a write timeout, the write fails with `TimedOut`
+/// when its bytes are not flushed in time. The queued bytes are not
+/// withdrawn and may still reach the peer.
 pub struct WriteFutureStream<'a> {
     stream: Arc<Mutex<Stream>>,
     buffer: &'a [u8],
-    written: usize,
+
+    /// Offset in the output of the stream at which the buffer ends, set
+    /// once the buffer is queued.
+    end: Option<u64>,
+
+    /// Timer bounding the wait, armed on the first pending poll.
+    timer: Option<Sleep>,
 }
 
 impl<'a> WriteFutureStream<'a> {
@@ -403,7 +556,8 @@ impl<'a> WriteFutureStream<'a> {
         Self {
             stream,
             buffer,
-            written: 0,
+            end: None,
+            timer: None,
         }
     }
 }
@@ -415,17 +569,51 @@ impl<'a> Future for WriteFutureStream<'a> {
         let this = self.get_mut();
         let mut stream = this.stream.lock().unwrap();
 
-        if this.written == 0 && !this.buffer.is_empty() {
-            stream.out_buffer.extend_from_slice(this.buffer);
-            this.written = this.buffer.len();
-        }
+        let end = match this.end {
+            Some(end) => end,
+            None => {
+                stream.out_buffer.extend_from_slice(this.buffer);
+                stream.account();
+
+                let end = stream.flushed + stream.out_buffer.len() as u64;
+                *this.end.insert(end)
+            }
+        };
 
-        if stream.out_buffer.is_empty() {
-            return Poll::Ready(Ok(this.written));
+        if stream.flushed >= end {
+            return Poll::Ready(Ok(this.buffer.len()));
         }
 
+        stream.check_open()?;
         stream.write_waiters.push(cx.waker().clone());
 
-        Poll::Pending
+        let timeout = stream.write_timeout;
+        drop(stream);
+
+        poll_timer(&mut this.timer, timeout, cx, "write timed out")
+    }
+}
+
+/// Polls the timer bounding a pending stream operation.
+///
+/// The timer is armed with the reactor on the first call when a timeout
+/// is configured. Resolves with a `TimedOut` error once it fires, and
+/// stays pending otherwise.
+fn poll_timer<T>(
+    timer: &mut Option<Sleep>,
+    timeout: Option<Duration>,
+    cx: &mut Context<'_>,
+    message: &'static str,
+) -> Poll<io::Result<T>> {
+    if timer.is_none() {
+        *timer = timeout.map(sleep);
+    }
+
+    match timer {
+        Some(timer) => match Pin::new(timer).poll(cx) {
+            Poll::Ready(()) => Poll::Ready(Err(io::Error::new(io::ErrorKind::TimedOut, message))),
+            Poll::Pending => Poll::Pending,
+        },
+        None => Poll::Pending,
     }
 }
@@ -1,5 +1,11 @@
+use super::ReactorHandle;
+use crate::sync::AtomicWaker;
+
+use std::io;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::{Arc, Mutex};
-use std::task::Waker;
+use std::task::{Context, Poll, Waker};
+use std::time::Duration;
 
 use nucleus::io::RawFd;
 use nucleus::poll::Interest;
@@ -16,6 +22,9 @@ pub(crate) enum IoEntry {
     /// A single task waiting for an I/O event.
     Waiting(Waiting),
 
+    /// A reader and a writer waiting on a descriptor independently.
+    Readiness(Arc<Directions>),
+
     /// A stream with internal read/write buffers and multiple waiters.
     Stream(Arc<Mutex<Stream>>),
 }
@@ -24,12 +33,17 @@ impl IoEntry {
     /// Wakes all tasks associated with this I/O entry.
     ///
     /// - For [`Waiting`], wakes the single stored waker.
+    /// - For [`Directions`], wakes the waiters of both directions.
     /// - For [`Stream`], wakes all registered read and write waiters.
     pub(crate) fn wake_all(self) {
         match self {
             IoEntry::Waiting(waiting) => {
                 waiting.waker.wake();
             }
+            IoEntry::Readiness(directions) => {
+                directions.read.fire();
+                directions.write.fire();
+            }
             IoEntry::Stream(stream) => {
                 let mut stream = stream.lock().unwrap();
 
@@ -45,6 +59,17 @@ impl IoEntry {
             }
         }
     }
+
+    /// Returns `true` if both entries notify the same waiter, as when a
+    /// future registers its file descriptor again.
+    pub(crate) fn same_waiter(&self, other: &IoEntry) -> bool {
+        match (self, other) {
+            (IoEntry::Waiting(a), IoEntry::Waiting(b)) => Arc::ptr_eq(&a.waker, &b.waker),
+            (IoEntry::Readiness(a), IoEntry::Readiness(b)) => Arc::ptr_eq(a, b),
+            (IoEntry::Stream(a), IoEntry::Stream(b)) => Arc::ptr_eq(a, b),
+            _ => false,
+        }
+    }
 }
 
 /// A single I/O wait registration.
@@ -52,13 +77,74 @@ impl IoEntry {
 /// Used for simple futures that wait for a specific I/O interest
 /// (read or write) and only need to wake one task.
 pub(crate) struct Waiting {
+    /// File descriptor being waited on.
+    pub(crate) fd: RawFd,
+
     /// Waker to notify when the I/O event occurs.
-    pub(crate) waker: Waker,
+    ///
+    /// Shared with the waiting future, which refreshes it on every poll
+    /// so that the task currently polling it is the one woken.
+    pub(crate) waker: Arc<AtomicWaker>,
 
     /// I/O interest being waited on.
     pub(crate) interest: Interest,
 }
 
+/// Waiters on a descriptor, one per direction.
+///
+/// A task waiting to read and another waiting to write are woken
+/// independently, and the descriptor stays in the poller for as long as
+/// either of them waits.
+pub(crate) struct Directions {
+    /// File descriptor being waited on.
+    pub(crate) fd: RawFd,
+
+    /// Waiter for the descriptor to become readable.
+    pub(crate) read: Direction,
+
+    /// Waiter for the descriptor to become writable.
+    pub(crate) write: Direction,
+}
+
+impl Directions {
+    /// Creates the waiters of `fd`, none of them waiting yet.
+    pub(crate) fn new(fd: RawFd) -> Self {
+        Self {
+            fd,
+            read: Direction::default(),
+            write: Direction::default(),
+        }
+    }
+
+    /// Returns the interest of the directions still waited for.
+    pub(crate) fn interest(&self) -> Interest {
+        Interest {
+            read: self.read.waiting.load(Ordering::Acquire),
+            write: self.write.waiting.load(Ordering::Acquire),
+        }
+    }
+}
+
+/// The waiter of one direction of a [`Directions`].
+#[derive(Default)]
+pub(crate) struct Direction {
+    /// Waker of the task waiting in this direction, refreshed on every
+    /// poll.
+    pub(crate) waker: AtomicWaker,
+
+    /// Whether a task waits in this direction, cleared once woken.
+    pub(crate) waiting: AtomicBool,
+}
+
+impl Direction {
+    /// Wakes the waiting task, if any.
+    pub(crate) fn fire(&self) {
+        if self.waiting.swap(false, Ordering::AcqRel) {
+            self.waker.wake();
+        }
+    }
+}
+
 /// A stream registered with the reactor.
 ///
 /// `Stream` represents a file descriptor with buffered I/O and
@@ -78,16 +164,357 @@ pub struct Stream {
 
     /// Tasks waiting for the stream to become writable.
     pub(crate) write_waiters: Vec<Waker>,
+
+    /// Whether the peer has closed its write half (a read returned 0).
+    ///
+    /// Once set, reads drain the remaining buffered data and then
+    /// return 0, while writes keep flowing to the half-open peer.
+    pub(crate) eof: bool,
+
+    /// The I/O error after which the reactor closed the stream, if any.
+    ///
+    /// Output still buffered at that point is lost: writes and flushes
+    /// fail with this error instead of waiting for it to drain, and so
+    /// do reads once the buffered input is drained.
+    pub(crate) error: Option<io::Error>,
+
+    /// Maximum time a read may wait for data before failing.
+    pub(crate) read_timeout: Option<Duration>,
+
+    /// Maximum time a write may wait for its data to be flushed.
+    pub(crate) write_timeout: Option<Duration>,
+
+    /// Size of the next read from the socket, adapted to its traffic.
+    pub(crate) read_size: ReadSize,
+
+    /// Total number of bytes received from the socket.
+    pub(crate) bytes_read: u64,
+
+    /// Total number of bytes handed to the socket.
+    pub(crate) bytes_written: u64,
+
+    /// Total number of bytes of the output buffer written to the socket.
+    ///
+    /// Writes that queue their bytes behind others complete once this
+    /// passes the end of their own bytes, in the order they were queued.
+    pub(crate) flushed: u64,
+
+    /// Reactor of the stream, holding the runtime buffer budget.
+    pub(crate) reactor: ReactorHandle,
+
+    /// Bytes of both buffers accounted in the runtime buffer budget.
+    pub(crate) buffered: usize,
+
+    /// Whether the reactor stopped reading from the socket because the
+    /// runtime buffer budget is spent.
+    pub(crate) reads_paused: bool,
+}
+
+/// Bytes held in the buffers of every stream of a runtime, against the
+/// runtime limit.
+///
+/// Once the limit is reached, the reactor stops reading from the sockets
+/// it is notified about, and resumes them when tasks have drained enough
+/// buffered input: a slow consumer then leaves data in the kernel, where
+/// TCP flow control pushes back on the peer, rather than in memory.
+pub(crate) struct BufferBudget {
+    /// Maximum number of buffered bytes, if bounded.
+    limit: Option<usize>,
+
+    /// Number of bytes currently buffered.
+    used: AtomicUsize,
+
+    /// Whether the reactor paused reads for lack of budget.
+    paused: AtomicBool,
+}
+
+impl BufferBudget {
+    /// Creates a budget allowing `limit` buffered bytes, or any number.
+    pub(crate) fn new(limit: Option<usize>) -> Self {
+        Self {
+            limit,
+            used: AtomicUsize::new(0),
+            paused: AtomicBool::new(false),
+        }
+    }
+
+    /// Returns the number of bytes currently buffered.
+    pub(crate) fn used(&self) -> usize {
+        self.used.load(Ordering::Acquire)
+    }
+
+    /// Returns `true` if no more bytes should be read into the buffers.
+    pub(crate) fn exhausted(&self) -> bool {
+        self.limit.is_some_and(|limit| self.used() >= limit)
+    }
+
+    /// Records that the reactor paused reads on a stream.
+    pub(crate) fn pause(&self) {
+        self.paused.store(true, Ordering::Release);
+    }
+
+    /// Clears the paused flag once the reactor resumes every stream.
+    pub(crate) fn resume(&self) {
+        self.paused.store(false, Ordering::Release);
+    }
+
+    /// Accounts `n` more buffered bytes.
+    fn grow(&self, n: usize) {
+        self.used.fetch_add(n, Ordering::AcqRel);
+    }
+
+    /// Accounts `n` bytes leaving the buffers.
+    ///
+    /// Returns `true` if paused reads may now resume, so that the reactor
+    /// must be woken.
+    fn shrink(&self, n: usize) -> bool {
+        self.used.fetch_sub(n, Ordering::AcqRel);
+        self.paused.load(Ordering::Acquire) && !self.exhausted()
+    }
+}
+
+/// Adaptive size of the reads the reactor makes on a stream.
+///
+/// Bulk transfers are read in few large chunks, chatty connections in
+/// small ones: the size doubles whenever a read fills it, as more data is
+/// likely waiting, and halves after two reads in a row returned at most
+/// half of it.
+pub(crate) struct ReadSize {
+    /// Bytes asked for by the next read.
+    next: usize,
+
+    /// Whether the last read was small, so that another one shrinks the
+    /// size.
+    shrink: bool,
+}
+
+impl ReadSize {
+    /// Smallest read size.
+    const MIN: usize = 1024;
+
+    /// Read size of a new stream.
+    const INITIAL: usize = 4096;
+
+    /// Largest read size.
+    const MAX: usize = 64 * 1024;
+
+    /// Returns the size of a new stream.
+    pub(crate) fn new() -> Self {
+        Self {
+            next: Self::INITIAL,
+            shrink: false,
+        }
+    }
+
+    /// Returns the number of bytes the next read should ask for.
+    pub(crate) fn next(&self) -> usize {
+        self.next
+    }
+
+    /// Adapts the size to a read that returned `n` bytes.
+    pub(crate) fn record(&mut self, n: usize) {
+        if n >= self.next {
+            self.next = (self.next * 2).min(Self::MAX);
+            self.shrink = false;
+        } else if n <= self.next / 2 {
+            if self.shrink {
+                self.next = (self.next / 2).max(Self::MIN);
+            }
+            self.shrink = !self.shrink;
+        } else {
+            self.shrink = false;
+        }
+    }
 }
 
 impl Stream {
+    /// Creates the state of a stream over the socket `fd`, registered
+    /// with `reactor`.
+    pub(crate) fn new(fd: RawFd, reactor: ReactorHandle) -> Self {
+        Self {
+            fd,
+            in_buffer: Vec::new(),
+            out_buffer: Vec::new(),
+            read_waiters: Vec::new(),
+            write_waiters: Vec::new(),
+            eof: false,
+            error: None,
+            read_timeout: None,
+            write_timeout: None,
+            read_size: ReadSize::new(),
+            bytes_read: 0,
+            bytes_written: 0,
+            flushed: 0,
+            reactor,
+            buffered: 0,
+            reads_paused: false,
+        }
+    }
+
     /// Returns the I/O interests required for this stream.
     ///
-    /// Streams are always interested in both read and write readiness.
+    /// Streams are interested in write readiness for their whole
+    /// lifetime, and in read readiness until the peer reaches EOF, except
+    /// while reads are paused for lack of buffer budget.
     pub(crate) fn interest(&self) -> Interest {
         Interest {
-            read: true,
+            read: !self.eof && !self.reads_paused,
             write: true,
         }
     }
+
+    /// Accounts the bytes now held in the buffers in the runtime buffer
+    /// budget.
+    ///
+    /// Called after the buffers changed. Wakes the reactor when freeing
+    /// budget lets it resume paused reads.
+    pub(crate) fn account(&mut self) {
+        let held = self.in_buffer.len() + self.out_buffer.len();
+        let budget = self.reactor.buffers();
+
+        if held > self.buffered {
+            budget.grow(held - self.buffered);
+        } else if held < self.buffered && budget.shrink(self.buffered - held) {
+            self.reactor.wake();
+        }
+
+        self.buffered = held;
+    }
+
+    /// Reads buffered input into `buffer`.
+    ///
+    /// Returns `Ok(0)` once the peer has closed its write half and the
+    /// input buffer is drained. Otherwise, if no data is buffered, the
+    /// task is registered as a read waiter.
+    ///
+    /// Bytes leave the input buffer only in the call that copies them
+    /// into `buffer` and returns `Ready`: a read future dropped while
+    /// pending never takes any data with it.
+    pub(crate) fn poll_read(
+        &mut self,
+        cx: &mut Context<'_>,
+        buffer: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        match self.try_read(buffer) {
+            Err(e) if e.kind() == io::ErrorKind::WouldBlock => {
+                self.read_waiters.push(cx.waker().clone());
+                Poll::Pending
+            }
+            result => Poll::Ready(result),
+        }
+    }
+
+    /// Reads buffered input into `buffer` without waiting.
+    ///
+    /// Fails with `WouldBlock` if no data is buffered and the peer has
+    /// not closed its write half.
+    pub(crate) fn try_read(&mut self, buffer: &mut [u8]) -> io::Result<usize> {
+        if !self.in_buffer.is_empty() {
+            let n = std::cmp::min(buffer.len(), self.in_buffer.len());
+
+            buffer[..n].copy_from_slice(&self.in_buffer[..n]);
+            self.in_buffer.drain(..n);
+            self.account();
+
+            return Ok(n);
+        }
+
+        if self.eof {
+            return Ok(0);
+        }
+
+        self.check_open()?;
+
+        Err(io::ErrorKind::WouldBlock.into())
+    }
+
+    /// Resolves once a read would not wait: input is buffered, the peer
+    /// has closed its write half, or the stream was closed.
+    pub(crate) fn poll_read_ready(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        if !self.in_buffer.is_empty() || self.eof || self.error.is_some() {
+            return Poll::Ready(Ok(()));
+        }
+
+        self.read_waiters.push(cx.waker().clone());
+
+        Poll::Pending
+    }
+
+    /// Queues `buffer` for writing without waiting.
+    ///
+    /// Fails with `WouldBlock` while previously queued output is still
+    /// being flushed.
+    pub(crate) fn try_write(&mut self, buffer: &[u8]) -> io::Result<usize> {
+        self.check_open()?;
+
+        if !self.out_buffer.is_empty() {
+            return Err(io::ErrorKind::WouldBlock.into());
+        }
+
+        self.out_buffer.extend_from_slice(buffer);
+        self.account();
+
+        Ok(buffer.len())
+    }
+
+    /// Queues `buffer` for writing by the reactor.
+    ///
+    /// Bytes are only accepted once the previously queued output has been
+    /// flushed, which bounds the output buffer to a single write.
+    pub(crate) fn poll_write(
+        &mut self,
+        cx: &mut Context<'_>,
+        buffer: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        match self.try_write(buffer) {
+            Err(e) if e.kind() == io::ErrorKind::WouldBlock => {
+                self.write_waiters.push(cx.waker().clone());
+                Poll::Pending
+            }
+            result => Poll::Ready(result),
+        }
+    }
+
+    /// Resolves once a write would not wait: the output buffer is empty.
+    ///
+    /// This is the same condition as [`poll_flush`](Self::poll_flush).
+    pub(crate) fn poll_write_ready(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.poll_flush(cx)
+    }
+
+    /// Resolves once the reactor has written the whole output buffer.
+    ///
+    /// Fails with the error that closed the stream if it was closed
+    /// before that.
+    pub(crate) fn poll_flush(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        if self.out_buffer.is_empty() {
+            return Poll::Ready(Ok(()));
+        }
+
+        self.check_open()?;
+        self.write_waiters.push(cx.waker().clone());
+
+        Poll::Pending
+    }
+
+    /// Fails with `BrokenPipe` once the reactor closed the stream.
+    pub(crate) fn check_open(&self) -> io::Result<()> {
+        match &self.error {
+            // Every waiter gets its own copy of the error.
+            Some(err) => Err(match err.raw_os_error() {
+                Some(code) => io::Error::from_raw_os_error(code),
+                None => io::Error::new(err.kind(), err.to_string()),
+            }),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Drop for Stream {
+    /// Returns the bytes still buffered to the runtime buffer budget.
+    fn drop(&mut self) {
+        self.in_buffer.clear();
+        self.out_buffer.clear();
+        self.account();
+    }
 }
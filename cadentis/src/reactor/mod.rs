@@ -13,10 +13,12 @@
 //! it is an internal component used by higher-level async primitives.
 
 mod core;
-mod timer;
+pub(crate) mod timer;
 
 pub(crate) mod command;
 pub(crate) mod future;
 pub(crate) mod io;
+pub(crate) mod readiness;
+pub(crate) mod stats;
 
 pub(crate) use core::{Reactor, ReactorHandle};
@@ -0,0 +1,106 @@
+use crate::reactor::command::Command;
+use crate::reactor::io::{Directions, IoEntry};
+use crate::runtime::context::CURRENT_REACTOR;
+
+use nucleus::io::RawFd;
+use nucleus::poll::Interest;
+use std::io;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::task::{Context, Poll};
+
+/// Readiness tracking for a descriptor owned outside the reactor.
+///
+/// Types performing their own non-blocking calls on a descriptor (UDP
+/// sockets, pipes, ...) use `Readiness` to wait for the reactor whenever
+/// a call would block. The descriptor is deregistered when the
+/// `Readiness` is dropped, so it must be dropped before the descriptor
+/// is closed.
+///
+/// Each direction has its own waiter: a task reading the descriptor and
+/// another writing to it wait concurrently. Two tasks waiting in the
+/// same direction do not, the later one replacing the earlier.
+pub(crate) struct Readiness {
+    /// The tracked descriptor.
+    fd: RawFd,
+
+    /// Waiters shared with the reactor entry.
+    directions: Arc<Directions>,
+
+    /// Whether the descriptor has ever been registered with the reactor.
+    registered: AtomicBool,
+}
+
+impl Readiness {
+    /// Tracks the readiness of `fd`, which must be in non-blocking mode.
+    pub(crate) fn new(fd: RawFd) -> Self {
+        Self {
+            fd,
+            directions: Arc::new(Directions::new(fd)),
+            registered: AtomicBool::new(false),
+        }
+    }
+
+    /// Runs `op`, waiting for `interest` with the reactor if it would block.
+    ///
+    /// Waiting again in a direction replaces the waiter of that direction.
+    pub(crate) fn poll_io<R>(
+        &self,
+        cx: &mut Context<'_>,
+        interest: Interest,
+        mut op: impl FnMut() -> io::Result<R>,
+    ) -> Poll<io::Result<R>> {
+        match op() {
+            Err(err) if err.kind() == io::ErrorKind::WouldBlock => {}
+            result => return Poll::Ready(result),
+        }
+
+        let mut arm = false;
+
+        for (wanted, direction) in [
+            (interest.read, &self.directions.read),
+            (interest.write, &self.directions.write),
+        ] {
+            if wanted {
+                direction.waker.register(cx.waker());
+                arm |= !direction.waiting.swap(true, Ordering::AcqRel);
+            }
+        }
+
+        // A direction still waiting is still watched by the reactor, which
+        // only needs to hear about the ones it already woke.
+        if !arm {
+            return Poll::Pending;
+        }
+
+        CURRENT_REACTOR.with(|cell| {
+            let binding = cell.borrow();
+            let reactor = binding.as_ref().expect("no reactor in context");
+
+            let _ = reactor.send(Command::Register {
+                fd: self.fd,
+                interest: self.directions.interest(),
+                entry: IoEntry::Readiness(self.directions.clone()),
+            });
+        });
+
+        self.registered.store(true, Ordering::Release);
+
+        Poll::Pending
+    }
+}
+
+impl Drop for Readiness {
+    /// Removes the descriptor from the reactor.
+    fn drop(&mut self) {
+        if !self.registered.load(Ordering::Acquire) {
+            return;
+        }
+
+        CURRENT_REACTOR.with(|cell| {
+            if let Some(reactor) = cell.borrow().as_ref() {
+                let _ = reactor.send(Command::Deregister { fd: self.fd });
+            }
+        });
+    }
+}
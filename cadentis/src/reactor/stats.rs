@@ -0,0 +1,206 @@
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+
+/// A snapshot of the reactor statistics.
+///
+/// Obtained with [`RuntimeMetrics::reactor`](crate::RuntimeMetrics::reactor).
+/// These counters describe the reactor thread driving I/O and timers,
+/// and help telling whether latency comes from the scheduler or from
+/// the reactor: a growing command queue or long event batches point to
+/// the reactor.
+///
+/// Counters are published by the reactor thread once per loop
+/// iteration, so a snapshot may lag slightly behind the latest
+/// commands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ReactorStats {
+    /// Number of file descriptors registered with the poller.
+    pub(crate) registered_fds: usize,
+
+    /// Number of pending timers.
+    pub(crate) timers: usize,
+
+    /// Number of distinct deadlines the pending timers are grouped under.
+    pub(crate) timer_groups: usize,
+
+    /// Number of commands sent to the reactor and not handled yet.
+    pub(crate) pending_commands: usize,
+
+    /// Number of polls performed since the reactor started.
+    pub(crate) polls: u64,
+
+    /// Number of I/O events processed since the reactor started.
+    pub(crate) events: u64,
+
+    /// Number of I/O events returned by the last poll.
+    pub(crate) last_poll_events: usize,
+
+    /// Number of times a blocked poll was interrupted to handle commands.
+    pub(crate) wakeups: u64,
+
+    /// Number of times woken tasks were handed over to the workers.
+    pub(crate) handoffs: u64,
+
+    /// Number of woken tasks handed over to the workers.
+    pub(crate) handed_off_tasks: u64,
+
+    /// Number of reads made on stream sockets.
+    pub(crate) stream_reads: u64,
+
+    /// Number of bytes held in the buffers of the streams.
+    pub(crate) buffered_bytes: usize,
+
+    /// Number of streams whose reads are paused for lack of buffer budget.
+    pub(crate) paused_streams: usize,
+}
+
+impl ReactorStats {
+    /// Returns the number of file descriptors registered with the poller.
+    pub fn registered_fds(&self) -> usize {
+        self.registered_fds
+    }
+
+    /// Returns the number of pending timers.
+    ///
+    /// Timers cancelled before firing are counted until their deadline
+    /// is reached.
+    pub fn timers(&self) -> usize {
+        self.timers
+    }
+
+    /// Returns the number of distinct deadlines the pending timers are
+    /// grouped under.
+    ///
+    /// Timers due within the same
+    /// [granularity](crate::RuntimeBuilder::timer_granularity) share a
+    /// deadline, and the reactor wakes them together.
+    pub fn timer_groups(&self) -> usize {
+        self.timer_groups
+    }
+
+    /// Returns the number of commands waiting to be handled.
+    pub fn pending_commands(&self) -> usize {
+        self.pending_commands
+    }
+
+    /// Returns the number of polls performed since the reactor started.
+    pub fn polls(&self) -> u64 {
+        self.polls
+    }
+
+    /// Returns the number of I/O events processed since the reactor started.
+    pub fn events(&self) -> u64 {
+        self.events
+    }
+
+    /// Returns the number of I/O events returned by the last poll.
+    pub fn last_poll_events(&self) -> usize {
+        self.last_poll_events
+    }
+
+    /// Returns how many times the reactor was woken from a blocked poll
+    /// to handle new commands.
+    ///
+    /// Commands sent while the reactor is busy, or while a wake-up is
+    /// already on its way, do not wake it again, so a burst of
+    /// registrations usually costs a single wake-up.
+    pub fn wakeups(&self) -> u64 {
+        self.wakeups
+    }
+
+    /// Returns how many times the reactor handed the tasks it woke over
+    /// to the workers.
+    ///
+    /// The tasks woken by the events of one poll and by the timers
+    /// expiring with it are handed over together, so this grows with
+    /// the number of polls rather than with the number of tasks woken.
+    pub fn handoffs(&self) -> u64 {
+        self.handoffs
+    }
+
+    /// Returns the number of woken tasks the reactor handed over to the
+    /// workers.
+    pub fn handed_off_tasks(&self) -> u64 {
+        self.handed_off_tasks
+    }
+
+    /// Returns the number of reads the reactor made on stream sockets.
+    ///
+    /// Reads grow with the traffic of each stream, so a bulk transfer
+    /// costs far fewer reads than it moves kilobytes.
+    pub fn stream_reads(&self) -> u64 {
+        self.stream_reads
+    }
+
+    /// Returns the number of bytes held in the input and output buffers
+    /// of every stream.
+    ///
+    /// Unlike the other counters, this one is read live rather than
+    /// published by the reactor.
+    pub fn buffered_bytes(&self) -> usize {
+        self.buffered_bytes
+    }
+
+    /// Returns the number of streams the reactor stopped reading from
+    /// because the [buffer budget](crate::RuntimeBuilder::max_total_buffer_bytes)
+    /// is spent.
+    pub fn paused_streams(&self) -> usize {
+        self.paused_streams
+    }
+}
+
+/// Counters shared between the reactor thread and its handles.
+///
+/// Every counter is a relaxed atomic: they are only meant for
+/// observability and never synchronize anything.
+#[derive(Default)]
+pub(crate) struct ReactorCounters {
+    pub(crate) registered_fds: AtomicUsize,
+    pub(crate) timers: AtomicUsize,
+    pub(crate) timer_groups: AtomicUsize,
+    pub(crate) pending_commands: AtomicUsize,
+    pub(crate) polls: AtomicU64,
+    pub(crate) events: AtomicU64,
+    pub(crate) last_poll_events: AtomicUsize,
+    pub(crate) wakeups: AtomicU64,
+    pub(crate) handoffs: AtomicU64,
+    pub(crate) handed_off_tasks: AtomicU64,
+    pub(crate) stream_reads: AtomicU64,
+    pub(crate) paused_streams: AtomicUsize,
+}
+
+impl ReactorCounters {
+    /// Records a poll that returned `events` I/O events.
+    pub(crate) fn record_poll(&self, events: usize) {
+        self.polls.fetch_add(1, Ordering::Relaxed);
+        self.events.fetch_add(events as u64, Ordering::Relaxed);
+        self.last_poll_events.store(events, Ordering::Relaxed);
+    }
+
+    /// Records `tasks` woken tasks handed over to the workers at once.
+    pub(crate) fn record_handoff(&self, tasks: usize) {
+        if tasks > 0 {
+            self.handoffs.fetch_add(1, Ordering::Relaxed);
+            self.handed_off_tasks
+                .fetch_add(tasks as u64, Ordering::Relaxed);
+        }
+    }
+
+    /// Returns a snapshot of the counters.
+    pub(crate) fn snapshot(&self) -> ReactorStats {
+        ReactorStats {
+            registered_fds: self.registered_fds.load(Ordering::Relaxed),
+            timers: self.timers.load(Ordering::Relaxed),
+            timer_groups: self.timer_groups.load(Ordering::Relaxed),
+            pending_commands: self.pending_commands.load(Ordering::Relaxed),
+            polls: self.polls.load(Ordering::Relaxed),
+            events: self.events.load(Ordering::Relaxed),
+            last_poll_events: self.last_poll_events.load(Ordering::Relaxed),
+            wakeups: self.wakeups.load(Ordering::Relaxed),
+            handoffs: self.handoffs.load(Ordering::Relaxed),
+            handed_off_tasks: self.handed_off_tasks.load(Ordering::Relaxed),
+            stream_reads: self.stream_reads.load(Ordering::Relaxed),
+            buffered_bytes: 0,
+            paused_streams: self.paused_streams.load(Ordering::Relaxed),
+        }
+    }
+}
@@ -1,20 +1,24 @@
-use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::io;
 use std::sync::Arc;
-use std::sync::atomic::AtomicBool;
+use std::sync::atomic::{self, AtomicBool, AtomicUsize};
 use std::task::Waker;
-use std::time::Instant;
+use std::time::{Duration, Instant};
+
+/// Smallest timer queue worth pruning of its cancelled timers.
+const MIN_PRUNE_AT: usize = 1024;
+
+/// Default width of the timer groups: the resolution of the poller
+/// timeout.
+pub(crate) const DEFAULT_TIMER_GRANULARITY: Duration = Duration::from_millis(1);
 
 /// An entry in the reactor timer queue.
 ///
-/// `TimerEntry` represents a scheduled wake-up at a specific
-/// deadline. It is typically stored inside a priority queue
-/// (e.g. a binary heap) ordered by deadline.
+/// `TimerEntry` represents a scheduled wake-up, queued under the
+/// deadline of its group in a [`TimerQueue`].
 ///
 /// The entry may be cancelled before it fires.
 pub(crate) struct TimerEntry {
-    /// The time at which the timer should fire.
-    pub(crate) deadline: Instant,
-
     /// Waker to notify when the deadline is reached.
     pub(crate) waker: Waker,
 
@@ -22,29 +26,184 @@ pub(crate) struct TimerEntry {
     pub(crate) cancelled: Arc<AtomicBool>,
 }
 
-impl Eq for TimerEntry {}
+/// The pending timers of a reactor, grouped by deadline.
+///
+/// Deadlines are rounded up to the next multiple of the granularity,
+/// counted from the creation of the queue: timers armed at nearly the
+/// same moment for the same duration, such as thousands of connections
+/// arming their keep-alive, share a single group. The reactor then
+/// blocks until the group is due and wakes it as a whole, rather than
+/// handling each deadline on its own. A zero granularity only groups
+/// timers with equal deadlines.
+///
+/// Rounding up means a timer may fire up to one granularity late, never
+/// early.
+pub(crate) struct TimerQueue {
+    /// Timers by the deadline of their group, earliest first.
+    groups: BTreeMap<Instant, Vec<TimerEntry>>,
+
+    /// Number of queued timers, cancelled ones included.
+    len: usize,
+
+    /// Number of queued timers past which cancelled ones are pruned.
+    prune_at: usize,
+
+    /// Width of a group.
+    granularity: Duration,
+
+    /// Origin of the group deadlines.
+    epoch: Instant,
+}
+
+impl TimerQueue {
+    /// Creates an empty queue grouping deadlines by `granularity` from
+    /// `epoch`.
+    pub(crate) fn new(epoch: Instant, granularity: Duration) -> Self {
+        Self {
+            groups: BTreeMap::new(),
+            len: 0,
+            prune_at: MIN_PRUNE_AT,
+            granularity,
+            epoch,
+        }
+    }
+
+    /// Returns the number of queued timers, cancelled ones included.
+    pub(crate) fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns the number of groups the timers are queued in.
+    pub(crate) fn groups(&self) -> usize {
+        self.groups.len()
+    }
+
+    /// Returns the deadline of the earliest group.
+    pub(crate) fn next_deadline(&self) -> Option<Instant> {
+        self.groups.first_key_value().map(|(deadline, _)| *deadline)
+    }
+
+    /// Queues a timer due at `deadline`.
+    ///
+    /// Cancelled timers stay queued until their deadline. Once the queue
+    /// doubled since it was last pruned, they are dropped, so that its
+    /// size follows the number of armed timers rather than the number
+    /// of timers created.
+    pub(crate) fn push(&mut self, deadline: Instant, timer: TimerEntry) {
+        if self.len >= self.prune_at {
+            self.prune();
+        }
+
+        self.groups
+            .entry(self.group_of(deadline))
+            .or_default()
+            .push(timer);
+        self.len += 1;
+    }
+
+    /// Removes the groups due at `now`, returning their live timers.
+    pub(crate) fn expire(&mut self, now: Instant) -> Vec<TimerEntry> {
+        let mut expired = Vec::new();
+
+        while let Some(group) = self.groups.first_entry() {
+            if *group.key() > now {
+                break;
+            }
+
+            let timers = group.remove();
+            self.len -= timers.len();
+            expired.extend(
+                timers
+                    .into_iter()
+                    .filter(|timer| !timer.cancelled.load(atomic::Ordering::Acquire)),
+            );
+        }
+
+        expired
+    }
+
+    /// Drops the cancelled timers, and the groups left empty.
+    fn prune(&mut self) {
+        for timers in self.groups.values_mut() {
+            timers.retain(|timer| !timer.cancelled.load(atomic::Ordering::Acquire));
+        }
+        self.groups.retain(|_, timers| !timers.is_empty());
+
+        self.len = self.groups.values().map(Vec::len).sum();
+        self.prune_at = (2 * self.len).max(MIN_PRUNE_AT);
+    }
+
+    /// Returns the deadline of the group `deadline` falls in.
+    fn group_of(&self, deadline: Instant) -> Instant {
+        let granularity = self.granularity.as_nanos();
+
+        if granularity == 0 {
+            return deadline;
+        }
 
-impl PartialEq for TimerEntry {
-    /// Two timer entries are equal if their deadlines are equal.
-    fn eq(&self, other: &Self) -> bool {
-        self.deadline.eq(&other.deadline)
+        // Deadlines before the epoch are already due: keep them as is.
+        deadline
+            .checked_duration_since(self.epoch)
+            .and_then(|offset| {
+                let rounded = offset.as_nanos().div_ceil(granularity) * granularity;
+                self.epoch
+                    .checked_add(Duration::from_nanos(u64::try_from(rounded).ok()?))
+            })
+            .unwrap_or(deadline)
     }
 }
 
-impl Ord for TimerEntry {
-    /// Orders timer entries by deadline.
+/// Number of timers armed on a reactor, against an optional limit.
+///
+/// Each armed timer holds a [`TimerSlot`], released when its sleep
+/// future completes or is dropped.
+pub(crate) struct TimerBudget {
+    /// Maximum number of armed timers, if bounded.
+    limit: Option<usize>,
+
+    /// Number of slots currently held.
+    armed: AtomicUsize,
+}
+
+impl TimerBudget {
+    /// Creates a budget allowing `limit` armed timers, or any number.
+    pub(crate) fn new(limit: Option<usize>) -> Self {
+        Self {
+            limit,
+            armed: AtomicUsize::new(0),
+        }
+    }
+
+    /// Reserves a slot for one more timer.
+    ///
+    /// # Errors
     ///
-    /// Note that the comparison is **reversed** so that a
-    /// `BinaryHeap<TimerEntry>` behaves as a min-heap,
-    /// where the earliest deadline is popped first.
-    fn cmp(&self, other: &Self) -> Ordering {
-        other.deadline.cmp(&self.deadline)
+    /// Returns `QuotaExceeded` if the limit is reached.
+    pub(crate) fn acquire(self: &Arc<Self>) -> io::Result<TimerSlot> {
+        let limit = self.limit.unwrap_or(usize::MAX);
+
+        self.armed
+            .fetch_update(
+                atomic::Ordering::AcqRel,
+                atomic::Ordering::Acquire,
+                |armed| (armed < limit).then_some(armed + 1),
+            )
+            .map_err(|_| {
+                io::Error::new(
+                    io::ErrorKind::QuotaExceeded,
+                    format!("the runtime limit of {limit} armed timers is reached"),
+                )
+            })?;
+
+        Ok(TimerSlot(self.clone()))
     }
 }
 
-impl PartialOrd for TimerEntry {
-    /// Partial ordering consistent with [`Ord`].
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
+/// A slot of a [`TimerBudget`], released on drop.
+pub(crate) struct TimerSlot(Arc<TimerBudget>);
+
+impl Drop for TimerSlot {
+    fn drop(&mut self) {
+        self.0.armed.fetch_sub(1, atomic::Ordering::AcqRel);
     }
 }
@@ -0,0 +1,169 @@
+//! Pool of threads running blocking work on behalf of async tasks.
+//!
+//! Some operations have no non-blocking form, such as name resolution
+//! through `getaddrinfo`. Running them on a worker thread would stall
+//! every task queued on it, so they are handed to this pool instead and
+//! awaited through a [`Blocking`] future.
+//!
+//! Threads are started on demand, up to [`MAX_THREADS`], and exit after
+//! staying idle for [`KEEP_ALIVE`]. The pool is shared by every runtime
+//! of the process.
+
+use crate::sync::AtomicWaker;
+
+use std::collections::VecDeque;
+use std::future::Future;
+use std::panic::{self, AssertUnwindSafe};
+use std::pin::Pin;
+use std::sync::{Arc, Condvar, Mutex as Mutex_std, OnceLock};
+use std::task::{Context, Poll};
+use std::thread;
+use std::time::Duration;
+
+/// Maximum number of threads of the pool.
+const MAX_THREADS: usize = 64;
+
+/// How long an idle thread waits for a job before exiting.
+const KEEP_ALIVE: Duration = Duration::from_secs(10);
+
+/// A unit of blocking work.
+type Job = Box<dyn FnOnce() + Send>;
+
+/// The process-wide blocking pool, started on first use.
+static POOL: OnceLock<Pool> = OnceLock::new();
+
+/// Shared state of the blocking pool.
+struct Pool {
+    /// Pending jobs and thread accounting.
+    state: Mutex_std<State>,
+
+    /// Signals idle threads that a job was queued.
+    condvar: Condvar,
+}
+
+/// Internal state of a [`Pool`].
+struct State {
+    /// Jobs waiting for a thread, in submission order.
+    jobs: VecDeque<Job>,
+
+    /// Number of running threads.
+    threads: usize,
+
+    /// Number of threads waiting for a job.
+    idle: usize,
+}
+
+/// Runs `f` on the blocking pool and returns a future resolving to its
+/// output.
+///
+/// The closure starts running even if the returned future is never
+/// polled. A panic raised by `f` is resumed when the future is polled.
+pub(crate) fn spawn_blocking<F, T>(f: F) -> Blocking<T>
+where
+    F: FnOnce() -> T + Send + 'static,
+    T: Send + 'static,
+{
+    let shared = Arc::new(Shared {
+        output: Mutex_std::new(None),
+        waker: AtomicWaker::new(),
+    });
+
+    let completion = shared.clone();
+    let job = Box::new(move || {
+        let output = panic::catch_unwind(AssertUnwindSafe(f));
+
+        *completion.output.lock().unwrap() = Some(output);
+        completion.waker.wake();
+    });
+
+    POOL.get_or_init(Pool::new).submit(job);
+
+    Blocking { shared }
+}
+
+impl Pool {
+    fn new() -> Self {
+        Self {
+            state: Mutex_std::new(State {
+                jobs: VecDeque::new(),
+                threads: 0,
+                idle: 0,
+            }),
+            condvar: Condvar::new(),
+        }
+    }
+
+    /// Queues `job`, starting a new thread if every idle one already has
+    /// a job to pick up.
+    fn submit(&'static self, job: Job) {
+        let mut state = self.state.lock().unwrap();
+        state.jobs.push_back(job);
+
+        if state.jobs.len() <= state.idle {
+            self.condvar.notify_one();
+            return;
+        }
+
+        if state.threads < MAX_THREADS {
+            state.threads += 1;
+            drop(state);
+
+            thread::Builder::new()
+                .name("cadentis-blocking".into())
+                .spawn(move || self.run())
+                .expect("failed to spawn a blocking pool thread");
+        }
+    }
+
+    /// Runs jobs until the thread stays idle for [`KEEP_ALIVE`].
+    fn run(&self) {
+        let mut state = self.state.lock().unwrap();
+
+        loop {
+            if let Some(job) = state.jobs.pop_front() {
+                drop(state);
+                job();
+                state = self.state.lock().unwrap();
+                continue;
+            }
+
+            state.idle += 1;
+            let (next, timeout) = self.condvar.wait_timeout(state, KEEP_ALIVE).unwrap();
+            state = next;
+            state.idle -= 1;
+
+            if timeout.timed_out() && state.jobs.is_empty() {
+                state.threads -= 1;
+                return;
+            }
+        }
+    }
+}
+
+/// State shared between a [`Blocking`] future and its job.
+struct Shared<T> {
+    /// Output of the job, or its panic payload, once it finished.
+    output: Mutex_std<Option<thread::Result<T>>>,
+
+    /// Waker of the task awaiting the job.
+    waker: AtomicWaker,
+}
+
+/// Future returned by [`spawn_blocking`].
+pub(crate) struct Blocking<T> {
+    shared: Arc<Shared<T>>,
+}
+
+impl<T> Future for Blocking<T> {
+    type Output = T;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<T> {
+        self.shared.waker.register(cx.waker());
+
+        match self.shared.output.lock().unwrap().take() {
+            Some(Ok(output)) => Poll::Ready(output),
+            Some(Err(payload)) => panic::resume_unwind(payload),
+            None => Poll::Pending,
+        }
+    }
+}
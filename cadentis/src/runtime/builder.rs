@@ -1,36 +1,124 @@
 use super::Runtime;
+use super::current_thread::CurrentThreadRuntime;
+use super::executor::affinity::available_cores;
+use super::task::hooks::{BusyLoopAction, TaskHooks};
+use super::task::{TaskId, Tracer};
+use super::work_stealing::injector::{DEFAULT_TIME_SLICE, Injector};
+use super::work_stealing::queue::{DEFAULT_LOCAL_QUEUE_CAPACITY, StealStrategy};
+use crate::reactor::Reactor;
+use crate::reactor::timer::DEFAULT_TIMER_GRANULARITY;
+use crate::time::{Clock, SystemClock};
 
+use std::io;
+use std::sync::Arc;
 use std::thread;
+use std::time::Duration;
 
 /// Builder for configuring and creating a runtime.
 ///
 /// `RuntimeBuilder` allows customizing runtime parameters before
 /// constructing the runtime. Currently, it supports configuring
-/// the number of worker threads used by the executor.
+/// the number of worker threads used by the executor, the capacity
+/// of each worker's local task queue, the number of tasks taken per
+/// steal, the time slice of self-waking tasks, the clock driving timers,
+/// the CPU affinity of worker threads, a deterministic scheduling mode
+/// for tests, and instrumentation hooks run around task spawns and polls.
 ///
 /// # Examples
 ///
 /// ```rust,ignore
 /// let runtime = RuntimeBuilder::new()
 ///     .worker_threads(4)
-///     .build();
+///     .build()?;
 /// ```
 pub struct RuntimeBuilder {
     /// Number of worker threads in the executor.
     worker_threads: usize,
+
+    /// Maximum number of tasks held by each worker's local queue.
+    local_queue_capacity: usize,
+
+    /// Time source driving the runtime timers.
+    clock: Arc<dyn Clock>,
+
+    /// Maximum number of timers armed at once, if bounded.
+    max_timers: Option<usize>,
+
+    /// Maximum number of bytes buffered by all streams, if bounded.
+    max_buffer_bytes: Option<usize>,
+
+    /// Width of the groups timers are fired in.
+    timer_granularity: Duration,
+
+    /// Whether worker threads are pinned to CPU cores.
+    pin_workers: bool,
+
+    /// Explicit cores to pin workers to (`None` for every allowed core).
+    core_ids: Option<Vec<usize>>,
+
+    /// Seed of the deterministic scheduler, if enabled.
+    seed: Option<u64>,
+
+    /// How many tasks an idle worker takes from another per steal.
+    steal: StealStrategy,
+
+    /// How long a task may keep waking itself before yielding its turn.
+    time_slice: Duration,
+
+    /// Instrumentation callbacks run around task spawns and polls.
+    hooks: TaskHooks,
 }
 
 impl RuntimeBuilder {
     /// Creates a new `RuntimeBuilder` with default configuration.
     ///
     /// By default, the number of worker threads is set to the number
-    /// of available logical CPUs, falling back to `1` if unavailable.
+    /// of available logical CPUs, falling back to `1` if unavailable,
+    /// each local queue holds up to 256 tasks, and timers follow the
+    /// wall clock.
     pub fn new() -> Self {
         let worker_threads = thread::available_parallelism()
             .map(|n| n.get())
             .unwrap_or(1);
 
-        Self { worker_threads }
+        Self {
+            worker_threads,
+            local_queue_capacity: DEFAULT_LOCAL_QUEUE_CAPACITY,
+            clock: Arc::new(SystemClock),
+            max_timers: None,
+            max_buffer_bytes: None,
+            timer_granularity: DEFAULT_TIMER_GRANULARITY,
+            pin_workers: false,
+            core_ids: None,
+            seed: None,
+            steal: StealStrategy::One,
+            time_slice: DEFAULT_TIME_SLICE,
+            hooks: TaskHooks::default(),
+        }
+    }
+
+    /// Creates a builder for a runtime running on the calling thread.
+    ///
+    /// The resulting runtime has no worker threads: spawned tasks and the
+    /// future given to `block_on` are all polled by the thread calling
+    /// `block_on`, which lets that future hold values that are not
+    /// `Send`, such as `Rc`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// let runtime = RuntimeBuilder::current_thread().build()?;
+    ///
+    /// let counter = Rc::new(Cell::new(0));
+    /// runtime.block_on(async {
+    ///     yield_now().await;
+    ///     counter.set(counter.get() + 1);
+    /// });
+    /// ```
+    pub fn current_thread() -> CurrentThreadBuilder {
+        CurrentThreadBuilder {
+            clock: Arc::new(SystemClock),
+        }
     }
 
     /// Sets the number of worker threads used by the runtime.
@@ -52,11 +140,450 @@ impl RuntimeBuilder {
         self
     }
 
+    /// Sets the capacity of each worker's local task queue.
+    ///
+    /// Tasks spawned from a worker thread are pushed to that worker's
+    /// local queue. Once the queue is full, further tasks overflow to
+    /// the global injector, where any worker can pick them up; spawning
+    /// never blocks and never drops a task.
+    ///
+    /// Idle workers steal from other workers one task at a time by
+    /// default (see [`steal_batch`](Self::steal_batch)). A larger capacity
+    /// favors locality for bursty spawn patterns; a smaller one spreads
+    /// work through the injector sooner.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n == 0`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// let builder = RuntimeBuilder::new()
+    ///     .local_queue_capacity(1024);
+    /// ```
+    pub fn local_queue_capacity(mut self, n: usize) -> Self {
+        assert!(n > 0, "local_queue_capacity must be > 0");
+
+        self.local_queue_capacity = n;
+        self
+    }
+
+    /// Sets how many tasks an idle worker takes from another worker's
+    /// queue per steal.
+    ///
+    /// [`StealStrategy::One`], the default, moves as few tasks as
+    /// possible away from the worker that spawned them, which favors
+    /// cache locality. [`StealStrategy::Half`] takes half of the victim's
+    /// queue at once, which spreads a burst of tasks spawned on one
+    /// worker, such as a fan-out of nested spawns, across idle workers
+    /// in far fewer steals.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// let runtime = RuntimeBuilder::new()
+    ///     .steal_batch(StealStrategy::Half)
+    ///     .build()?;
+    /// ```
+    pub fn steal_batch(mut self, strategy: StealStrategy) -> Self {
+        self.steal = strategy;
+        self
+    }
+
+    /// Sets the clock driving the runtime timers.
+    ///
+    /// Every [`sleep`](crate::time::sleep) and
+    /// [`timeout`](crate::time::timeout) of the runtime is measured
+    /// against this clock. Installing a
+    /// [`PausedClock`](crate::time::test::PausedClock) makes timers
+    /// deterministic in tests.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// let clock = PausedClock::new();
+    /// let runtime = RuntimeBuilder::new()
+    ///     .clock(clock.clone())
+    ///     .build()?;
+    /// ```
+    pub fn clock<C: Clock>(mut self, clock: C) -> Self {
+        self.clock = Arc::new(clock);
+        self
+    }
+
+    /// Pins each worker thread to a CPU core.
+    ///
+    /// Workers are assigned round-robin to the cores the process is
+    /// allowed to run on, or to the cores given by
+    /// [`core_ids`](Self::core_ids). Pinning is supported on Linux
+    /// (`sched_setaffinity`) and Windows (`SetThreadAffinityMask`), and
+    /// is a no-op on other platforms. It is best-effort: a worker that
+    /// cannot be pinned keeps running unpinned.
+    ///
+    /// This is an advanced setting. It can reduce cache thrashing for
+    /// NUMA-aware or latency-sensitive deployments, but it can also
+    /// hurt badly when misconfigured, for instance when several pinned
+    /// runtimes or other busy threads share the same cores.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// let runtime = RuntimeBuilder::new()
+    ///     .worker_threads(4)
+    ///     .pin_workers(true)
+    ///     .build()?;
+    /// ```
+    pub fn pin_workers(mut self, pin: bool) -> Self {
+        self.pin_workers = pin;
+        self
+    }
+
+    /// Pins worker threads to the given CPU cores.
+    ///
+    /// Worker `i` is pinned to `core_ids[i % core_ids.len()]`. This
+    /// implies [`pin_workers(true)`](Self::pin_workers); see it for the
+    /// caveats.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `core_ids` is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// // Keep the workers on the second socket.
+    /// let runtime = RuntimeBuilder::new()
+    ///     .worker_threads(4)
+    ///     .core_ids(vec![8, 9, 10, 11])
+    ///     .build()?;
+    /// ```
+    pub fn core_ids(mut self, core_ids: Vec<usize>) -> Self {
+        assert!(!core_ids.is_empty(), "core_ids must not be empty");
+
+        self.pin_workers = true;
+        self.core_ids = Some(core_ids);
+        self
+    }
+
+    /// Runs every task on a single worker, in an order driven by `seed`.
+    ///
+    /// Instead of following queue order, the worker draws the next task
+    /// to poll among all ready tasks with a pseudo-random generator
+    /// seeded by `seed`. Two runs with the same seed poll tasks in the
+    /// same order, which makes interleaving bugs reproducible; trying
+    /// several seeds explores different interleavings.
+    ///
+    /// This overrides [`worker_threads`](Self::worker_threads). Wakeups
+    /// coming from outside the worker, such as I/O readiness or timers
+    /// following the wall clock, still arrive at arbitrary times:
+    /// combine this mode with a
+    /// [`PausedClock`](crate::time::test::PausedClock) and avoid real
+    /// I/O for fully reproducible runs.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// let runtime = RuntimeBuilder::new()
+    ///     .deterministic(42)
+    ///     .clock(PausedClock::new())
+    ///     .build()?;
+    /// ```
+    pub fn deterministic(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Sets how long a task may keep waking itself before it yields
+    /// its turn to the other tasks.
+    ///
+    /// A task that wakes itself while being polled is normally
+    /// rescheduled through its usual queue, so a high-priority task
+    /// spinning that way would be picked again before any normal task.
+    /// Once such a task has been re-notified continuously for longer
+    /// than the time slice, it is pushed to the tail of the global
+    /// queue instead, letting every queued task run before it is
+    /// polled again. The slice restarts whenever the task really waits.
+    ///
+    /// Tasks pinned with [`spawn_on`](crate::task::spawn_on) always
+    /// stay on their worker. The default slice is 10 milliseconds.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// let runtime = RuntimeBuilder::new()
+    ///     .time_slice(Duration::from_millis(2))
+    ///     .build()?;
+    /// ```
+    pub fn time_slice(mut self, slice: Duration) -> Self {
+        self.time_slice = slice;
+        self
+    }
+
+    /// Sets a callback invoked once for each task spawned on the runtime.
+    ///
+    /// The callback receives the [`TaskId`] of the new task, the same one
+    /// returned by [`JoinHandle::id`](crate::task::JoinHandle::id), and
+    /// runs on the spawning thread before the task is first scheduled.
+    /// Together with [`on_task_poll`](Self::on_task_poll), it allows
+    /// building task dashboards or bridging to a tracing library without
+    /// touching the runtime. Leaving it unset costs nothing.
+    ///
+    /// The callback runs on the hot path of every spawn: keep it short
+    /// and do not spawn from it.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// let runtime = RuntimeBuilder::new()
+    ///     .on_task_spawn(|id| println!("spawned task {id}"))
+    ///     .build()?;
+    /// ```
+    pub fn on_task_spawn<F>(mut self, f: F) -> Self
+    where
+        F: Fn(TaskId) + Send + Sync + 'static,
+    {
+        self.hooks.on_spawn = Some(Arc::new(f));
+        self
+    }
+
+    /// Sets a callback invoked on the worker thread before each poll of a
+    /// task.
+    ///
+    /// The callback receives the [`TaskId`] of the task about to be
+    /// polled. See [`on_task_spawn`](Self::on_task_spawn).
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// let polls = Arc::new(AtomicUsize::new(0));
+    /// let counter = polls.clone();
+    /// let runtime = RuntimeBuilder::new()
+    ///     .on_task_poll(move |_| {
+    ///         counter.fetch_add(1, Ordering::Relaxed);
+    ///     })
+    ///     .build()?;
+    /// ```
+    pub fn on_task_poll<F>(mut self, f: F) -> Self
+    where
+        F: Fn(TaskId) + Send + Sync + 'static,
+    {
+        self.hooks.on_poll = Some(Arc::new(f));
+        self
+    }
+
+    /// Sets a callback invoked when a single poll of a task takes longer
+    /// than `threshold`.
+    ///
+    /// A long poll means the task did blocking or heavy synchronous work
+    /// without yielding, stalling every other task queued on its worker.
+    /// The callback runs on the worker thread right after the poll, with
+    /// the [`TaskId`] of the task and the duration of the poll.
+    ///
+    /// Debug builds warn on standard error about polls longer than 50ms
+    /// by default; this replaces that warning. Release builds measure
+    /// polls only when a callback is set.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// let runtime = RuntimeBuilder::new()
+    ///     .on_slow_poll(Duration::from_millis(10), |id, elapsed| {
+    ///         tracing::warn!(%id, ?elapsed, "task blocked the executor");
+    ///     })
+    ///     .build()?;
+    /// ```
+    pub fn on_slow_poll<F>(mut self, threshold: Duration, f: F) -> Self
+    where
+        F: Fn(TaskId, Duration) + Send + Sync + 'static,
+    {
+        self.hooks.on_slow_poll = Some((threshold, Arc::new(f)));
+        self
+    }
+
+    /// Sets the tracer told about the spans of traced tasks.
+    ///
+    /// Around each poll of a task spawned with
+    /// [`spawn_traced`](crate::task::spawn_traced), the tracer is called
+    /// on the worker thread to enter the span of the task, then to exit
+    /// it. This keeps the runtime independent of any tracing library:
+    /// the bridge to one implements [`Tracer`]. Tasks without a span
+    /// never reach the tracer.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// struct Bridge;
+    ///
+    /// impl Tracer for Bridge {
+    ///     fn enter(&self, span: SpanId) { telemetry::enter(span.as_u64()) }
+    ///     fn exit(&self, span: SpanId) { telemetry::exit(span.as_u64()) }
+    /// }
+    ///
+    /// let runtime = RuntimeBuilder::new().tracer(Bridge).build()?;
+    /// ```
+    pub fn tracer<T>(mut self, tracer: T) -> Self
+    where
+        T: Tracer + 'static,
+    {
+        self.hooks.tracer = Some(Arc::new(tracer));
+        self
+    }
+
+    /// Caps the number of timers armed at once on the runtime.
+    ///
+    /// Every pending [`sleep`](crate::time::sleep),
+    /// [`timeout`](crate::time::timeout), interval tick or I/O timeout
+    /// holds one of the `max` slots until it completes or is dropped. This
+    /// bounds the memory of the reactor, so that a bug creating timers in
+    /// a loop fails the offending task instead of taking the whole process
+    /// down.
+    ///
+    /// Once every slot is taken, [`try_sleep`](crate::time::try_sleep) and
+    /// [`try_timeout`](crate::time::try_timeout) return a `QuotaExceeded`
+    /// error, while the infallible forms panic in the task polling them.
+    /// Timers are unbounded by default.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// let runtime = RuntimeBuilder::new()
+    ///     .max_timers(100_000)
+    ///     .build()?;
+    /// ```
+    pub fn max_timers(mut self, max: usize) -> Self {
+        self.max_timers = Some(max);
+        self
+    }
+
+    /// Caps the bytes buffered by all the streams of the runtime.
+    ///
+    /// The reactor reads incoming data into the input buffer of each
+    /// stream ahead of the tasks, and writes queue into its output buffer.
+    /// Across many connections whose tasks consume slowly, or a peer doing
+    /// so on purpose, these buffers can grow the process out of memory.
+    /// Once they hold `bytes` in total, the reactor stops reading from the
+    /// sockets: incoming data stays in the kernel, whose flow control
+    /// slows the peers down, until tasks drain enough of the buffers.
+    ///
+    /// The cap may be exceeded by up to one read. Buffers are unbounded by
+    /// default; [`ReactorStats::buffered_bytes`](crate::ReactorStats::buffered_bytes)
+    /// reports their total either way.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// let runtime = RuntimeBuilder::new()
+    ///     .max_total_buffer_bytes(256 * 1024 * 1024)
+    ///     .build()?;
+    /// ```
+    pub fn max_total_buffer_bytes(mut self, bytes: usize) -> Self {
+        self.max_buffer_bytes = Some(bytes);
+        self
+    }
+
+    /// Sets how close deadlines must be for their timers to fire together.
+    ///
+    /// Timer deadlines are rounded up to a multiple of `granularity`, and
+    /// the reactor wakes all the timers sharing a deadline at once. When
+    /// many tasks arm the same timeout at nearly the same moment, such as
+    /// thousands of connections arming an idle timeout, a coarser
+    /// granularity groups them under a few deadlines instead of one each,
+    /// at the cost of timers firing up to `granularity` late. A zero
+    /// granularity only groups equal deadlines.
+    ///
+    /// Defaults to 1 millisecond, the resolution of the poller timeout.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// // Keep-alive timeouts don't need to fire to the millisecond.
+    /// let runtime = RuntimeBuilder::new()
+    ///     .timer_granularity(Duration::from_millis(50))
+    ///     .build()?;
+    /// ```
+    pub fn timer_granularity(mut self, granularity: Duration) -> Self {
+        self.timer_granularity = granularity;
+        self
+    }
+
+    /// Guards against tasks busy-looping the scheduler.
+    ///
+    /// A future that wakes itself and returns `Poll::Pending` on every
+    /// poll, without ever waiting on an external event, keeps a worker at
+    /// 100% CPU while making no progress. Once a task has done so for more
+    /// than `limit` polls in a row, the runtime applies `action`: it either
+    /// warns on standard error or aborts the task, whose
+    /// [`JoinHandle`](crate::task::JoinHandle) then reports a panic
+    /// describing the loop. The streak restarts whenever the task really
+    /// waits.
+    ///
+    /// Wake-ups from other threads landing while the task is being polled
+    /// count as self-wakes as well, so keep `limit` well above the number
+    /// of polls a legitimate task may chain, such as a long
+    /// [`yield_now`](crate::yield_now) loop. The guard is off by default.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// let runtime = RuntimeBuilder::new()
+    ///     .busy_loop_limit(100_000, BusyLoopAction::Abort)
+    ///     .build()?;
+    /// ```
+    pub fn busy_loop_limit(mut self, limit: u32, action: BusyLoopAction) -> Self {
+        self.hooks.busy_loop = Some((limit, action));
+        self
+    }
+
     /// Builds the runtime with the configured options.
     ///
     /// This starts the reactor and initializes the executor.
-    pub fn build(self) -> Runtime {
-        Runtime::new(self.worker_threads)
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the reactor or a worker thread cannot be
+    /// started, for instance when the process is out of file descriptors
+    /// or threads. Nothing is left running in that case.
+    pub fn build(self) -> io::Result<Runtime> {
+        let core_ids = self
+            .pin_workers
+            .then(|| self.core_ids.unwrap_or_else(available_cores));
+
+        let worker_threads = match self.seed {
+            Some(_) => 1,
+            None => self.worker_threads,
+        };
+
+        Runtime::new(
+            Reactor::start(
+                self.clock,
+                self.max_timers,
+                self.timer_granularity,
+                self.max_buffer_bytes,
+            )?,
+            worker_threads,
+            self.local_queue_capacity,
+            core_ids,
+            self.seed,
+            self.steal,
+            Arc::new(Injector::with_time_slice(self.time_slice).with_hooks(self.hooks)),
+        )
+    }
+
+    /// Builds the runtime, panicking if it cannot be started.
+    ///
+    /// Meant for `main` functions and tests, such as the ones generated
+    /// by `#[cadentis::main]` and `#[cadentis::test]`, which have no
+    /// better way to handle the error than [`build`](Self::build) would.
+    ///
+    /// # Panics
+    ///
+    /// Panics if [`build`](Self::build) returns an error.
+    pub fn build_unwrap(self) -> Runtime {
+        match self.build() {
+            Ok(runtime) => runtime,
+            Err(err) => panic!("failed to build the Cadentis runtime: {err}"),
+        }
     }
 }
 
@@ -66,3 +593,33 @@ impl Default for RuntimeBuilder {
         Self::new()
     }
 }
+
+/// Builder for a runtime running on the calling thread.
+///
+/// Created with [`RuntimeBuilder::current_thread`].
+pub struct CurrentThreadBuilder {
+    /// Time source driving the runtime timers.
+    clock: Arc<dyn Clock>,
+}
+
+impl CurrentThreadBuilder {
+    /// Sets the clock driving the runtime timers.
+    ///
+    /// See [`RuntimeBuilder::clock`].
+    pub fn clock<C: Clock>(mut self, clock: C) -> Self {
+        self.clock = Arc::new(clock);
+        self
+    }
+
+    /// Builds the runtime with the configured options.
+    ///
+    /// This starts the reactor; tasks only run while `block_on` is
+    /// running.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the reactor cannot be started.
+    pub fn build(self) -> io::Result<CurrentThreadRuntime> {
+        CurrentThreadRuntime::new(self.clock)
+    }
+}
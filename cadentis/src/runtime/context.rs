@@ -2,8 +2,9 @@ use crate::reactor::ReactorHandle;
 use crate::runtime::work_stealing::injector::InjectorHandle;
 use crate::runtime::work_stealing::queue::LocalQueue;
 
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
 use std::sync::Arc;
+use std::time::Instant;
 
 thread_local! {
     /// Thread-local handle to the current reactor.
@@ -31,13 +32,20 @@ thread_local! {
     /// synchronization.
     pub(crate) static CURRENT_LOCALS: RefCell<Option<Arc<Vec<Arc<LocalQueue>>>>> =
         const { RefCell::new(None) };
+
+    /// Deadline of the innermost `with_deadline` being polled.
+    ///
+    /// Installed only for the duration of a poll, so it behaves as a
+    /// task-local value for the wrapped future.
+    pub(crate) static CURRENT_DEADLINE: Cell<Option<Instant>> = const { Cell::new(None) };
 }
 
 /// Enters the runtime execution context for the current thread.
 ///
 /// This function temporarily installs thread-local runtime state
 /// (reactor and injector handles) for the duration of the closure `f`.
-/// After the closure completes, the previous context is restored.
+/// After the closure completes, or unwinds, the previous context is
+/// restored.
 ///
 /// This mechanism allows deeply nested runtime components to access
 /// shared execution state without passing handles through every API.
@@ -60,17 +68,23 @@ pub(crate) fn enter_context<R>(
     injector: InjectorHandle,
     f: impl FnOnce() -> R,
 ) -> R {
-    CURRENT_REACTOR.with(|r| {
-        CURRENT_INJECTOR.with(|i| {
-            let prev_r = r.replace(Some(reactor));
-            let prev_i = i.replace(Some(injector));
+    /// Restores the previous context, even if `f` panics.
+    struct Restore {
+        reactor: Option<ReactorHandle>,
+        injector: Option<InjectorHandle>,
+    }
 
-            let out = f();
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            CURRENT_INJECTOR.with(|i| i.replace(self.injector.take()));
+            CURRENT_REACTOR.with(|r| r.replace(self.reactor.take()));
+        }
+    }
 
-            i.replace(prev_i);
-            r.replace(prev_r);
+    let _restore = Restore {
+        reactor: CURRENT_REACTOR.with(|r| r.replace(Some(reactor))),
+        injector: CURRENT_INJECTOR.with(|i| i.replace(Some(injector))),
+    };
 
-            out
-        })
-    })
+    f()
 }
@@ -1,9 +1,23 @@
 use std::future::Future;
-use std::sync::mpsc;
+use std::io;
+use std::panic;
+use std::sync::mpsc::RecvTimeoutError;
+use std::sync::{Arc, mpsc};
+use std::time::Duration;
 
 use super::executor::core::Executor;
+use super::handle::Handle;
+use super::metrics::RuntimeMetrics;
+use super::work_stealing::injector::Injector;
+use super::work_stealing::queue::StealStrategy;
+use crate::reactor::ReactorHandle;
 use crate::reactor::command::Command;
-use crate::reactor::{Reactor, ReactorHandle};
+use crate::runtime::context::CURRENT_WORKER_ID;
+use crate::runtime::task::JoinHandle;
+use crate::runtime::wake_batched;
+
+/// How often [`Runtime::block_on`] checks whether the reactor failed.
+const REACTOR_CHECK_INTERVAL: Duration = Duration::from_millis(100);
 
 /// The main runtime handle.
 ///
@@ -28,22 +42,52 @@ impl Runtime {
     ///
     /// # Arguments
     ///
+    /// * `reactor_handle` - Handle to the started reactor, shut down if
+    ///   the executor cannot be started.
     /// * `worker_threads` - Number of worker threads used by the executor.
-    ///
-    /// The reactor is started automatically.
-    pub(crate) fn new(worker_threads: usize) -> Self {
-        let reactor_handle = Reactor::start();
-        let executor = Executor::new(reactor_handle.clone(), worker_threads);
+    /// * `local_queue_capacity` - Maximum number of tasks per worker queue.
+    /// * `core_ids` - CPU cores to pin the workers to, if any.
+    /// * `seed` - Seed of the deterministic scheduler, if enabled.
+    /// * `steal` - How many tasks a worker takes from another per steal.
+    /// * `injector` - Global injector, carrying the time slice and task
+    ///   hooks.
+    pub(crate) fn new(
+        reactor_handle: ReactorHandle,
+        worker_threads: usize,
+        local_queue_capacity: usize,
+        core_ids: Option<Vec<usize>>,
+        seed: Option<u64>,
+        steal: StealStrategy,
+        injector: Arc<Injector>,
+    ) -> io::Result<Self> {
+        let executor = Executor::new(
+            reactor_handle.clone(),
+            worker_threads,
+            local_queue_capacity,
+            core_ids,
+            seed,
+            steal,
+            injector,
+        )
+        .inspect_err(|_| {
+            let _ = reactor_handle.send(Command::Shutdown);
+        })?;
 
-        Self {
+        Ok(Self {
             executor,
             reactor_handle,
-        }
+        })
     }
 
     /// Spawns a future onto the runtime.
     ///
-    /// The future is executed asynchronously and runs until completion.
+    /// The future is pushed to the global injector and executed by the
+    /// worker threads, whether or not [`block_on`](Self::block_on) is
+    /// running. This lets setup code seed tasks or start background
+    /// services from the owning thread, outside any root future.
+    ///
+    /// The returned [`JoinHandle`] can be awaited, for instance from
+    /// within `block_on`, to retrieve the output of the future.
     ///
     /// # Requirements
     ///
@@ -53,15 +97,41 @@ impl Runtime {
     /// # Examples
     ///
     /// ```rust,ignore
-    /// runtime.spawn(async {
+    /// let handle = runtime.spawn(async {
     ///     // background task
+    ///     42
     /// });
+    ///
+    /// let value = runtime.block_on(async move { handle.await.unwrap() });
     /// ```
-    pub fn spawn<F>(&self, future: F)
+    pub fn spawn<F, T>(&self, future: F) -> JoinHandle<T>
     where
-        F: Future<Output = ()> + Send + 'static,
+        T: Send + 'static,
+        F: Future<Output = T> + Send + 'static,
     {
-        self.executor.spawn(future);
+        self.executor.spawn(future)
+    }
+
+    /// Returns a handle to spawn tasks onto this runtime from anywhere.
+    ///
+    /// See [`set_global`](super::set_global) to make it the default
+    /// runtime of threads running outside of any runtime.
+    pub fn handle(&self) -> Handle {
+        Handle::new(self.executor.injector())
+    }
+
+    /// Returns a snapshot of the runtime metrics.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// let workers = runtime.metrics().num_workers();
+    /// ```
+    pub fn metrics(&self) -> RuntimeMetrics {
+        RuntimeMetrics {
+            num_workers: self.executor.num_workers(),
+            reactor: self.reactor_handle.stats(),
+        }
     }
 
     /// Runs a future to completion, blocking the current thread.
@@ -76,6 +146,18 @@ impl Runtime {
     ///
     /// Panics if the runtime shuts down before the future completes.
     ///
+    /// If the future panics, the panic is propagated to the caller with
+    /// its original payload.
+    ///
+    /// Panics if the reactor thread fails (an unrecoverable poll error or
+    /// a panic) while the future is pending. I/O and timers stop being
+    /// driven at that point, so waiting any longer could hang forever.
+    ///
+    /// Panics if called from a runtime worker thread (for example from
+    /// inside a spawned task). Blocking a worker on a future that needs
+    /// workers to make progress would otherwise deadlock silently; use
+    /// `.await` instead.
+    ///
     /// # Examples
     ///
     /// ```rust,ignore
@@ -89,14 +171,42 @@ impl Runtime {
         F: Future + Send + 'static,
         F::Output: Send + 'static,
     {
+        let in_worker = CURRENT_WORKER_ID.with(|id| id.borrow().is_some());
+
+        assert!(
+            !in_worker,
+            "block_on cannot be called from within a runtime worker thread; \
+             await the future instead"
+        );
+
         let (transmitter, receiver) = mpsc::channel();
 
-        self.spawn(async move {
-            let result = future.await;
-            let _ = transmitter.send(result);
+        // Both tasks are queued at once, so that a worker never picks the
+        // future without its forwarder, which would make the order of a
+        // deterministic runtime depend on timing.
+        wake_batched(|| {
+            let handle = self.spawn(future);
+            self.spawn(async move {
+                let _ = transmitter.send(handle.await);
+            });
         });
 
-        receiver.recv().expect("block_on failed")
+        loop {
+            match receiver.recv_timeout(REACTOR_CHECK_INTERVAL) {
+                Ok(Ok(output)) => return output,
+                // Re-raise a panic of the future on the calling thread.
+                Ok(Err(error)) => match error.try_into_panic() {
+                    Ok(payload) => panic::resume_unwind(payload),
+                    Err(error) => panic!("block_on failed: {error}"),
+                },
+                Err(RecvTimeoutError::Timeout) => {
+                    if let Some(reason) = self.reactor_handle.failure() {
+                        panic!("block_on failed: runtime reactor stopped ({reason})");
+                    }
+                }
+                Err(RecvTimeoutError::Disconnected) => panic!("block_on failed"),
+            }
+        }
     }
 }
 
@@ -0,0 +1,205 @@
+use std::future::Future;
+use std::io;
+use std::pin::pin;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::task::{Context, Poll, Wake, Waker};
+
+use super::context::{CURRENT_WORKER_ID, enter_context};
+use super::metrics::RuntimeMetrics;
+use super::task::{JoinHandle, Priority, Task, coop};
+use super::work_stealing::batch;
+use super::work_stealing::injector::Injector;
+use crate::reactor::command::Command;
+use crate::reactor::timer::DEFAULT_TIMER_GRANULARITY;
+use crate::reactor::{Reactor, ReactorHandle};
+use crate::time::Clock;
+
+/// A runtime running every task on the thread calling `block_on`.
+///
+/// Unlike the multi-threaded [`Runtime`](super::Runtime), this runtime
+/// has no worker threads: [`block_on`](Self::block_on) polls the root
+/// future and the spawned tasks in turn on the calling thread, and
+/// returns once the root future completes. Tasks spawned with
+/// [`spawn`](crate::task::spawn) only make progress while `block_on`
+/// is running; tasks spawned with [`spawn`](Self::spawn) before it is
+/// called are kept and start as soon as it is.
+///
+/// Since the root future never leaves the calling thread, it does not
+/// need to be `Send`: it may hold `Rc` or `RefCell` values across
+/// awaits. Spawned tasks still need to be `Send`.
+///
+/// Created with [`RuntimeBuilder::current_thread`](crate::RuntimeBuilder::current_thread).
+pub struct CurrentThreadRuntime {
+    /// Queue of spawned tasks, drained by `block_on`.
+    injector: Arc<Injector>,
+
+    /// Handle to the reactor thread.
+    reactor_handle: ReactorHandle,
+}
+
+/// Waker of the root future of [`CurrentThreadRuntime::block_on`].
+struct RootWaker {
+    /// Set when the root future must be polled again.
+    woken: AtomicBool,
+
+    /// Injector the calling thread parks on.
+    injector: Arc<Injector>,
+}
+
+impl Wake for RootWaker {
+    fn wake(self: Arc<Self>) {
+        self.wake_by_ref();
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        self.woken.store(true, Ordering::Release);
+        self.injector.notify();
+    }
+}
+
+impl CurrentThreadRuntime {
+    /// Creates a new current-thread runtime whose timers follow `clock`.
+    ///
+    /// The reactor is started automatically.
+    pub(crate) fn new(clock: Arc<dyn Clock>) -> io::Result<Self> {
+        Ok(Self {
+            injector: Arc::new(Injector::new()),
+            reactor_handle: Reactor::start(clock, None, DEFAULT_TIMER_GRANULARITY, None)?,
+        })
+    }
+
+    /// Returns a snapshot of the runtime metrics.
+    ///
+    /// The calling thread counts as the single worker of the runtime.
+    pub fn metrics(&self) -> RuntimeMetrics {
+        RuntimeMetrics {
+            num_workers: 1,
+            reactor: self.reactor_handle.stats(),
+        }
+    }
+
+    /// Spawns a future onto the runtime.
+    ///
+    /// The task is queued right away, but only runs on the thread calling
+    /// [`block_on`](Self::block_on): spawned before it, the task waits
+    /// for the next call, and starts before the root future is first
+    /// polled.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// let runtime = RuntimeBuilder::current_thread().build()?;
+    ///
+    /// let handle = runtime.spawn(async { 42 });
+    /// let value = runtime.block_on(async move { handle.await.unwrap() });
+    /// ```
+    pub fn spawn<F, T>(&self, future: F) -> JoinHandle<T>
+    where
+        T: Send + 'static,
+        F: Future<Output = T> + Send + 'static,
+    {
+        let task = Task::new(future, self.injector.clone());
+        batch::push(&self.injector, task.clone(), Priority::Normal);
+
+        JoinHandle { task }
+    }
+
+    /// Runs a future to completion on the current thread.
+    ///
+    /// Spawned tasks are run in between polls of `future`, on the same
+    /// thread. The tasks queued before the call run first, so that they
+    /// are not left behind by a future completing at its first poll. The
+    /// future does not need to be `Send` nor `'static`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the reactor thread fails while the future is pending.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// let runtime = RuntimeBuilder::current_thread().build()?;
+    ///
+    /// let shared = Rc::new(RefCell::new(0));
+    /// runtime.block_on(async {
+    ///     yield_now().await;
+    ///     *shared.borrow_mut() += 1;
+    /// });
+    /// ```
+    pub fn block_on<F: Future>(&self, future: F) -> F::Output {
+        let root = Arc::new(RootWaker {
+            woken: AtomicBool::new(true),
+            injector: self.injector.clone(),
+        });
+        let waker = Waker::from(root.clone());
+        let mut cx = Context::from_waker(&waker);
+        let mut future = pin!(future);
+
+        // Called from a worker of another runtime, the tasks spawned here
+        // must not land in the local queue of that worker.
+        let _worker = WorkerGuard(CURRENT_WORKER_ID.with(|id| id.replace(None)));
+
+        enter_context(self.reactor_handle.clone(), self.injector.clone(), || {
+            // Tasks woken again meanwhile wait for the loop below.
+            for _ in 0..self.injector.len() {
+                match self.injector.steal_high().or_else(|| self.injector.steal()) {
+                    Some(task) => task.run(),
+                    None => break,
+                }
+            }
+
+            loop {
+                if root.woken.swap(false, Ordering::AcqRel) {
+                    let _budget = coop::start(self.injector.time_slice());
+
+                    if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+                        return output;
+                    }
+                }
+
+                // Run the tasks queued so far, then give the root future
+                // a chance to make progress before running the next ones.
+                let mut ran = false;
+                while let Some(task) = self.injector.steal_high().or_else(|| self.injector.steal())
+                {
+                    task.run();
+                    ran = true;
+
+                    if root.woken.load(Ordering::Acquire) {
+                        break;
+                    }
+                }
+
+                if !ran && !root.woken.load(Ordering::Acquire) {
+                    if let Some(reason) = self.reactor_handle.failure() {
+                        panic!("block_on failed: runtime reactor stopped ({reason})");
+                    }
+
+                    self.injector.park();
+                }
+            }
+        })
+    }
+}
+
+/// Restores the worker id of the calling thread once `block_on` returns,
+/// or unwinds.
+struct WorkerGuard(Option<usize>);
+
+impl Drop for WorkerGuard {
+    fn drop(&mut self) {
+        CURRENT_WORKER_ID.with(|id| *id.borrow_mut() = self.0);
+    }
+}
+
+impl Drop for CurrentThreadRuntime {
+    /// Shuts down the reactor.
+    ///
+    /// Tasks that did not complete are dropped without being polled again.
+    fn drop(&mut self) {
+        self.injector.shutdown();
+
+        let _ = self.reactor_handle.send(Command::Shutdown);
+    }
+}
@@ -0,0 +1,132 @@
+//! Worker thread CPU affinity.
+//!
+//! Pinning is implemented on Linux (`sched_setaffinity`) and Windows
+//! (`SetThreadAffinityMask`). On other platforms it is a no-op.
+
+use std::io;
+
+/// Returns the CPU cores the current process is allowed to run on.
+///
+/// Returns an empty list on platforms without affinity support.
+pub(crate) fn available_cores() -> Vec<usize> {
+    sys::available_cores().unwrap_or_default()
+}
+
+/// Pins the calling thread to the given CPU core.
+///
+/// # Errors
+///
+/// Returns the OS error if the core does not exist or is not allowed
+/// for this process.
+pub(crate) fn pin_current_thread(core: usize) -> io::Result<()> {
+    sys::pin_current_thread(core)
+}
+
+#[cfg(target_os = "linux")]
+mod sys {
+    use crate::sys::{CPU_SETSIZE, CpuSet, sched_getaffinity, sched_setaffinity};
+
+    use std::io;
+    use std::mem;
+
+    /// Bits per word of a [`CpuSet`].
+    const WORD_BITS: usize = u64::BITS as usize;
+
+    pub(super) fn available_cores() -> io::Result<Vec<usize>> {
+        let mut set = CpuSet {
+            bits: [0; CPU_SETSIZE / WORD_BITS],
+        };
+
+        // SAFETY: `set` is a valid, writable `cpu_set_t` of the given size,
+        // and pid 0 designates the calling thread.
+        if unsafe { sched_getaffinity(0, mem::size_of::<CpuSet>(), &mut set) } != 0 {
+            return Err(io::Error::last_os_error());
+        }
+
+        Ok((0..CPU_SETSIZE)
+            .filter(|&core| set.bits[core / WORD_BITS] & (1 << (core % WORD_BITS)) != 0)
+            .collect())
+    }
+
+    pub(super) fn pin_current_thread(core: usize) -> io::Result<()> {
+        if core >= CPU_SETSIZE {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "core id out of range",
+            ));
+        }
+
+        let mut set = CpuSet {
+            bits: [0; CPU_SETSIZE / WORD_BITS],
+        };
+        set.bits[core / WORD_BITS] |= 1 << (core % WORD_BITS);
+
+        // SAFETY: `set` is a valid `cpu_set_t` of the given size, and pid 0
+        // designates the calling thread.
+        if unsafe { sched_setaffinity(0, mem::size_of::<CpuSet>(), &set) } != 0 {
+            return Err(io::Error::last_os_error());
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(windows)]
+mod sys {
+    use crate::sys::{
+        GetCurrentProcess, GetCurrentThread, GetProcessAffinityMask, SetThreadAffinityMask,
+    };
+
+    use std::io;
+
+    /// Bits in a Windows affinity mask.
+    const MASK_BITS: usize = usize::BITS as usize;
+
+    pub(super) fn available_cores() -> io::Result<Vec<usize>> {
+        let mut process_mask = 0;
+        let mut system_mask = 0;
+
+        // SAFETY: both out-pointers are valid for writes, and the pseudo
+        // handle of the current process needs no cleanup.
+        let ok = unsafe {
+            GetProcessAffinityMask(GetCurrentProcess(), &mut process_mask, &mut system_mask)
+        };
+
+        if ok == 0 {
+            return Err(io::Error::last_os_error());
+        }
+
+        Ok((0..MASK_BITS)
+            .filter(|&core| process_mask & (1 << core) != 0)
+            .collect())
+    }
+
+    pub(super) fn pin_current_thread(core: usize) -> io::Result<()> {
+        if core >= MASK_BITS {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "core id out of range",
+            ));
+        }
+
+        // SAFETY: the pseudo handle of the current thread needs no cleanup.
+        if unsafe { SetThreadAffinityMask(GetCurrentThread(), 1 << core) } == 0 {
+            return Err(io::Error::last_os_error());
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(not(any(target_os = "linux", windows)))]
+mod sys {
+    use std::io;
+
+    pub(super) fn available_cores() -> io::Result<Vec<usize>> {
+        Ok(Vec::new())
+    }
+
+    pub(super) fn pin_current_thread(_core: usize) -> io::Result<()> {
+        Ok(())
+    }
+}
@@ -1,13 +1,16 @@
 use crate::reactor::ReactorHandle;
 use crate::runtime::context::enter_context;
+use crate::runtime::executor::affinity::pin_current_thread;
 use crate::runtime::executor::worker::Worker;
-use crate::runtime::task::Task;
+use crate::runtime::task::{JoinHandle, Priority, Task};
+use crate::runtime::work_stealing::batch;
 use crate::runtime::work_stealing::injector::Injector;
-use crate::runtime::work_stealing::queue::LocalQueue;
+use crate::runtime::work_stealing::queue::{LocalQueue, StealStrategy};
 
+use std::io;
 use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, Ordering};
-use std::thread::{self, JoinHandle};
+use std::thread;
 
 /// Multi-threaded task executor.
 ///
@@ -23,10 +26,13 @@ pub(crate) struct Executor {
     injector: Arc<Injector>,
 
     /// Join handles for worker threads.
-    handles: Vec<JoinHandle<()>>,
+    handles: Vec<thread::JoinHandle<()>>,
 
     /// Shutdown flag shared with all workers.
     shutdown: Arc<AtomicBool>,
+
+    /// Number of worker threads.
+    num_workers: usize,
 }
 
 impl Executor {
@@ -42,40 +48,76 @@ impl Executor {
     ///
     /// * `reactor_handle` - Handle to the runtime reactor
     /// * `threads` - Number of worker threads
-    pub(crate) fn new(reactor_handle: ReactorHandle, threads: usize) -> Self {
-        let injector = Arc::new(Injector::new());
+    /// * `local_queue_capacity` - Maximum number of tasks per local queue
+    /// * `core_ids` - CPU cores to pin workers to, assigned round-robin
+    ///   (`None` or an empty list leaves workers unpinned)
+    /// * `seed` - Seed of the deterministic scheduler (`None` to disable)
+    /// * `steal` - How many tasks a worker takes from another per steal
+    /// * `injector` - Global injector, carrying the time slice and task
+    ///   hooks of the runtime
+    pub(crate) fn new(
+        reactor_handle: ReactorHandle,
+        threads: usize,
+        local_queue_capacity: usize,
+        core_ids: Option<Vec<usize>>,
+        seed: Option<u64>,
+        steal: StealStrategy,
+        injector: Arc<Injector>,
+    ) -> io::Result<Self> {
         let shutdown = Arc::new(AtomicBool::new(false));
 
-        let mut handles = Vec::with_capacity(threads);
+        let handles = Vec::with_capacity(threads);
 
         let mut locals = Vec::with_capacity(threads);
         for _ in 0..threads {
-            locals.push(Arc::new(LocalQueue::new()));
+            locals.push(Arc::new(LocalQueue::new(local_queue_capacity)));
         }
 
         let locals = Arc::new(locals);
 
+        let mut executor = Self {
+            injector,
+            handles,
+            shutdown,
+            num_workers: threads,
+        };
+
         for id in 0..threads {
-            let worker = Worker::new(id, locals.clone(), injector.clone());
+            let worker = Worker::new(id, locals.clone(), executor.injector.clone(), seed, steal);
 
             let reactor = reactor_handle.clone();
-            let sd = shutdown.clone();
-            let injector = injector.clone();
+            let sd = executor.shutdown.clone();
+            let injector = executor.injector.clone();
+
+            let core = core_ids
+                .as_ref()
+                .filter(|cores| !cores.is_empty())
+                .map(|cores| cores[id % cores.len()]);
+
+            let spawned = thread::Builder::new().spawn(move || {
+                if let Some(core) = core {
+                    // Pinning is best-effort: an unusable core leaves the
+                    // worker free to run anywhere.
+                    let _ = pin_current_thread(core);
+                }
 
-            let handle = thread::spawn(move || {
                 enter_context(reactor.clone(), injector.clone(), || {
                     worker.run(sd, reactor);
                 });
             });
 
-            handles.push(handle);
+            match spawned {
+                Ok(handle) => executor.handles.push(handle),
+                Err(e) => {
+                    // Stop the workers already running before giving up.
+                    executor.shutdown();
+                    executor.join();
+                    return Err(e);
+                }
+            }
         }
 
-        Self {
-            injector,
-            handles,
-            shutdown,
-        }
+        Ok(executor)
     }
 
     /// Signals all workers to shut down.
@@ -88,19 +130,32 @@ impl Executor {
         self.injector.shutdown();
     }
 
+    /// Returns the number of worker threads.
+    pub(crate) fn num_workers(&self) -> usize {
+        self.num_workers
+    }
+
+    /// Returns the global injector queue.
+    pub(crate) fn injector(&self) -> Arc<Injector> {
+        self.injector.clone()
+    }
+
     /// Spawns a new asynchronous task onto the executor.
     ///
-    /// Tasks spawned after shutdown has begun are silently ignored.
-    pub(crate) fn spawn<F>(&self, future: F)
+    /// The task is pushed to the global injector. Tasks spawned after
+    /// shutdown has begun are never scheduled.
+    pub(crate) fn spawn<F, T>(&self, future: F) -> JoinHandle<T>
     where
-        F: Future<Output = ()> + Send + 'static,
+        T: Send + 'static,
+        F: Future<Output = T> + Send + 'static,
     {
-        if self.shutdown.load(Ordering::Acquire) {
-            return;
+        let task = Task::new(future, self.injector.clone());
+
+        if !self.shutdown.load(Ordering::Acquire) {
+            batch::push(&self.injector, task.clone(), Priority::Normal);
         }
 
-        let task = Arc::new(Task::new(future, self.injector.clone()));
-        self.injector.push(task);
+        JoinHandle { task }
     }
 
     /// Waits for all worker threads to terminate.
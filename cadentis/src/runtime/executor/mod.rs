@@ -5,10 +5,14 @@
 //!
 //! It is composed of:
 //! - [`core`]: the main executor logic and lifecycle management,
-//! - [`worker`]: worker threads that run tasks using work-stealing.
+//! - [`worker`]: worker threads that run tasks using work-stealing,
+//! - [`affinity`]: optional pinning of worker threads to CPU cores,
+//! - [`rng`]: the seeded generator driving the deterministic mode.
 //!
 //! Together, these components implement a scalable, multi-threaded
 //! executor integrated with the runtime reactor.
 
+pub(crate) mod affinity;
 pub(crate) mod core;
+pub(crate) mod rng;
 pub(crate) mod worker;
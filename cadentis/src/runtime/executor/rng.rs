@@ -0,0 +1,36 @@
+/// A small, seedable pseudo-random number generator (SplitMix64).
+///
+/// Used where the scheduler needs choices that are cheap and
+/// reproducible from a seed. It is not suitable for cryptography.
+pub(crate) struct Rng {
+    /// Current generator state.
+    state: u64,
+}
+
+impl Rng {
+    /// Creates a generator producing the sequence of `seed`.
+    pub(crate) fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    /// Returns the next pseudo-random value.
+    pub(crate) fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Returns a pseudo-random index in `0..n`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n == 0`.
+    pub(crate) fn below(&mut self, n: usize) -> usize {
+        assert!(n > 0, "Rng::below called with n == 0");
+
+        (self.next_u64() % n as u64) as usize
+    }
+}
@@ -1,7 +1,8 @@
 use crate::reactor::ReactorHandle;
-use crate::runtime::context::{CURRENT_WORKER_ID, enter_context};
+use crate::runtime::context::{CURRENT_LOCALS, CURRENT_WORKER_ID, enter_context};
+use crate::runtime::executor::rng::Rng;
 use crate::runtime::work_stealing::injector::InjectorHandle;
-use crate::runtime::work_stealing::queue::LocalQueue;
+use crate::runtime::work_stealing::queue::{LocalQueue, StealStrategy};
 use crate::task::Runnable;
 
 use std::sync::Arc;
@@ -14,10 +15,15 @@ use std::sync::atomic::{AtomicBool, Ordering};
 /// cooperates with other workers to balance load.
 ///
 /// The execution order is:
-/// 1. Pop from the local queue
-/// 2. Steal from the global injector
-/// 3. Steal from other workers
-/// 4. Park if no work is available
+/// 1. Take a high-priority task from the injector
+/// 2. Pop from the local queue, then from the tasks pinned to this worker
+/// 3. Steal from the global injector
+/// 4. Steal from other workers
+/// 5. Park if no work is available
+///
+/// In deterministic mode (a single worker with a seed), the worker
+/// instead picks the next task among every ready one with a generator
+/// seeded by that seed, so a given seed always yields the same order.
 pub(crate) struct Worker {
     /// Unique identifier of the worker.
     id: usize,
@@ -29,6 +35,12 @@ pub(crate) struct Worker {
 
     /// Handle to the global injector queue.
     injector: InjectorHandle,
+
+    /// Seed of the deterministic scheduler, if enabled.
+    seed: Option<u64>,
+
+    /// How many tasks are taken from another worker per steal.
+    steal: StealStrategy,
 }
 
 impl Worker {
@@ -39,15 +51,21 @@ impl Worker {
     /// * `id` - Worker identifier
     /// * `locals` - Shared vector of all local queues
     /// * `injector` - Handle to the global injector
+    /// * `seed` - Seed of the deterministic scheduler (`None` to disable)
+    /// * `steal` - How many tasks to take from another worker per steal
     pub(crate) fn new(
         id: usize,
         locals: Arc<Vec<Arc<LocalQueue>>>,
         injector: InjectorHandle,
+        seed: Option<u64>,
+        steal: StealStrategy,
     ) -> Self {
         Self {
             id,
             locals,
             injector,
+            seed,
+            steal,
         }
     }
 
@@ -59,18 +77,31 @@ impl Worker {
     ///
     /// # Execution loop
     ///
+    /// - Execute a high-priority task if one is queued
     /// - Execute from the local queue if possible
     /// - Otherwise, steal from the global injector
     /// - Otherwise, steal from another worker
     /// - Otherwise, park until work becomes available
     pub(crate) fn run(&self, shutdown: Arc<AtomicBool>, reactor: ReactorHandle) {
         CURRENT_WORKER_ID.with(|id| *id.borrow_mut() = Some(self.id));
+        CURRENT_LOCALS.with(|locals| *locals.borrow_mut() = Some(self.locals.clone()));
+
+        if let Some(seed) = self.seed {
+            return self.run_deterministic(seed, shutdown, reactor);
+        }
 
         loop {
             if shutdown.load(Ordering::Acquire) {
                 break;
             }
 
+            if let Some(task) = self.injector.steal_high() {
+                enter_context(reactor.clone(), self.injector.clone(), || {
+                    task.run();
+                });
+                continue;
+            }
+
             if let Some(task) = self.locals[self.id].pop() {
                 enter_context(reactor.clone(), self.injector.clone(), || {
                     task.run();
@@ -78,6 +109,13 @@ impl Worker {
                 continue;
             }
 
+            if let Some(task) = self.locals[self.id].pop_pinned() {
+                enter_context(reactor.clone(), self.injector.clone(), || {
+                    task.run();
+                });
+                continue;
+            }
+
             if let Some(task) = self.injector.steal() {
                 enter_context(reactor.clone(), self.injector.clone(), || {
                     task.run();
@@ -96,10 +134,66 @@ impl Worker {
         }
     }
 
+    /// Runs the deterministic event loop.
+    ///
+    /// Every ready task is collected in arrival order, then the next one
+    /// to run is drawn with a generator seeded by `seed`. High-priority
+    /// tasks still run first, in FIFO order.
+    fn run_deterministic(&self, seed: u64, shutdown: Arc<AtomicBool>, reactor: ReactorHandle) {
+        let mut rng = Rng::new(seed);
+        let mut ready: Vec<Arc<dyn Runnable>> = Vec::new();
+
+        loop {
+            if shutdown.load(Ordering::Acquire) {
+                break;
+            }
+
+            let task = match self.injector.steal_high() {
+                Some(task) => task,
+                None => {
+                    self.collect_ready(&mut ready);
+
+                    if ready.is_empty() {
+                        self.injector.park();
+                        continue;
+                    }
+
+                    let index = rng.below(ready.len());
+                    ready.swap_remove(index)
+                }
+            };
+
+            enter_context(reactor.clone(), self.injector.clone(), || {
+                task.run();
+            });
+        }
+    }
+
+    /// Moves every queued task of this worker into `ready`.
+    ///
+    /// Tasks are appended in arrival order: local queue, pinned tasks,
+    /// then the global injector.
+    fn collect_ready(&self, ready: &mut Vec<Arc<dyn Runnable>>) {
+        let local = &self.locals[self.id];
+
+        while let Some(task) = local.steal() {
+            ready.push(task);
+        }
+
+        while let Some(task) = local.pop_pinned() {
+            ready.push(task);
+        }
+
+        while let Some(task) = self.injector.steal() {
+            ready.push(task);
+        }
+    }
+
     /// Attempts to steal a task from another worker's local queue.
     ///
     /// Workers are visited in a round-robin fashion to avoid
-    /// starvation and distribute load evenly.
+    /// starvation and distribute load evenly. Depending on the steal
+    /// strategy, more tasks may be moved to this worker's local queue.
     fn try_steal(&self) -> Option<Arc<dyn Runnable>> {
         let len = self.locals.len();
 
@@ -110,7 +204,7 @@ impl Worker {
         for i in 0..len {
             let victim = (self.id + i + 1) % len;
 
-            if let Some(task) = self.locals[victim].steal() {
+            if let Some(task) = self.locals[victim].steal_into(&self.locals[self.id], self.steal) {
                 return Some(task);
             }
         }
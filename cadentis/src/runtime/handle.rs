@@ -0,0 +1,115 @@
+use crate::runtime::context::CURRENT_INJECTOR;
+use crate::runtime::task::JoinHandle;
+use crate::runtime::task::Task;
+use crate::runtime::work_stealing::injector::InjectorHandle;
+
+use std::future::Future;
+use std::sync::Mutex as Mutex_std;
+
+/// Process-wide default handle, installed by [`set_global`].
+static GLOBAL: Mutex_std<Option<Handle>> = Mutex_std::new(None);
+
+/// A handle to a runtime, used to spawn tasks onto it from anywhere.
+///
+/// A `Handle` is cheap to clone and can be sent to other threads,
+/// including threads that are not managed by the runtime. It does not
+/// keep the runtime running: tasks spawned after the runtime has been
+/// dropped are never polled.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// let handle = runtime.handle();
+///
+/// std::thread::spawn(move || {
+///     handle.spawn(async { background_work().await });
+/// });
+/// ```
+#[derive(Clone)]
+pub struct Handle {
+    /// Global queue of the runtime.
+    injector: InjectorHandle,
+}
+
+impl Handle {
+    /// Creates a handle pushing tasks to `injector`.
+    pub(crate) fn new(injector: InjectorHandle) -> Self {
+        Self { injector }
+    }
+
+    /// Returns a handle to the runtime running the current thread.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called outside the context of a runtime.
+    pub fn current() -> Self {
+        Self::try_current().expect("Handle::current must be called within the context of a runtime")
+    }
+
+    /// Returns a handle to the runtime running the current thread, or
+    /// `None` outside of a runtime.
+    ///
+    /// The global handle installed by [`set_global`] is not considered.
+    pub fn try_current() -> Option<Self> {
+        CURRENT_INJECTOR.with(|cell| cell.borrow().clone().map(Self::new))
+    }
+
+    /// Spawns a future as a task onto the runtime.
+    ///
+    /// The task is pushed to the global queue of the runtime and picked
+    /// up by the next idle worker.
+    pub fn spawn<F, T>(&self, future: F) -> JoinHandle<T>
+    where
+        T: Send + 'static,
+        F: Future<Output = T> + Send + 'static,
+    {
+        let task = Task::new(future, self.injector.clone());
+        self.injector.push(task.clone());
+
+        JoinHandle { task }
+    }
+
+    /// Returns the global queue of the runtime.
+    pub(crate) fn injector(&self) -> &InjectorHandle {
+        &self.injector
+    }
+}
+
+/// Installs `handle` as the process-wide default runtime.
+///
+/// Once installed, [`task::spawn`](crate::task::spawn) called outside of
+/// any runtime context (from a thread spawned with [`std::thread`], a
+/// callback of a foreign library, ...) spawns onto this runtime instead
+/// of panicking. Code running inside a runtime keeps spawning onto its
+/// own runtime.
+///
+/// Returns the previously installed handle, if any. This is meant for
+/// applications running a single runtime; a library should take a
+/// [`Handle`] explicitly instead.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// let runtime = RuntimeBuilder::new().build()?;
+/// runtime::set_global(runtime.handle());
+///
+/// std::thread::spawn(|| {
+///     task::spawn(async { flush_metrics().await });
+/// });
+/// ```
+pub fn set_global(handle: Handle) -> Option<Handle> {
+    GLOBAL.lock().unwrap().replace(handle)
+}
+
+/// Removes the process-wide default runtime, returning it.
+///
+/// Spawning outside of a runtime context panics again afterwards.
+pub fn clear_global() -> Option<Handle> {
+    GLOBAL.lock().unwrap().take()
+}
+
+/// Returns the handle of the current runtime, falling back to the
+/// process-wide default one.
+pub(crate) fn current_or_global() -> Option<Handle> {
+    Handle::try_current().or_else(|| GLOBAL.lock().unwrap().clone())
+}
@@ -0,0 +1,46 @@
+use crate::reactor::stats::ReactorStats;
+
+/// A snapshot of runtime metrics.
+///
+/// Obtained with `Runtime::metrics`. Metrics describe the runtime
+/// configuration and can be used, for instance, to shard work across
+/// workers with [`spawn_on`](crate::task::spawn_on).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RuntimeMetrics {
+    /// Number of worker threads of the executor.
+    pub(crate) num_workers: usize,
+
+    /// Statistics of the reactor.
+    pub(crate) reactor: ReactorStats,
+}
+
+impl RuntimeMetrics {
+    /// Returns the number of worker threads of the runtime.
+    ///
+    /// Valid worker ids range from `0` to `num_workers() - 1`.
+    pub fn num_workers(&self) -> usize {
+        self.num_workers
+    }
+
+    /// Returns the number of file descriptors registered with the reactor.
+    ///
+    /// Streams stay registered for their whole lifetime, while one-shot
+    /// waits such as `accept` are registered only until they fire.
+    /// Waiting again on a file descriptor that is still registered
+    /// updates the existing registration instead of adding one.
+    pub fn io_registrations(&self) -> usize {
+        self.reactor.registered_fds
+    }
+
+    /// Returns the statistics of the reactor driving I/O and timers.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// let stats = runtime.metrics().reactor();
+    /// println!("{} commands queued", stats.pending_commands());
+    /// ```
+    pub fn reactor(&self) -> ReactorStats {
+        self.reactor
+    }
+}
@@ -16,10 +16,19 @@ mod core;
 mod executor;
 mod work_stealing;
 
+pub(crate) mod blocking;
 pub(crate) mod builder;
 pub(crate) mod context;
+pub(crate) mod current_thread;
+pub(crate) mod handle;
+pub(crate) mod metrics;
 pub(crate) mod yield_now;
 
 pub mod task;
 
-use core::Runtime;
+pub use core::Runtime;
+pub use handle::{Handle, clear_global, set_global};
+pub use task::hooks::BusyLoopAction;
+pub use work_stealing::queue::StealStrategy;
+
+pub(crate) use work_stealing::batch::wake_batched;
@@ -0,0 +1,92 @@
+//! Worker-local cache of task allocations.
+//!
+//! Spawning allocates the shared `Arc<Task<T, F>>` holding the state of
+//! the task along with its future. Once a finished task is only referenced
+//! by the thread dropping it, its allocation is kept in a small per-worker
+//! free list instead of being returned to the allocator, and the next spawn
+//! of a task with the same future type on that worker refills it in place.
+//!
+//! Only tasks whose future and result were already dropped are cached, so
+//! the cache never extends the lifetime of user values.
+
+use super::Task;
+use crate::runtime::context::CURRENT_WORKER_ID;
+
+use std::any::{Any, TypeId};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Maximum number of idle tasks kept per future type on each worker.
+const CAPACITY: usize = 64;
+
+thread_local! {
+    /// Idle task allocations of the current worker, by task type.
+    static CACHE: RefCell<HashMap<TypeId, Vec<Arc<dyn Any + Send + Sync>>>> =
+        RefCell::new(HashMap::new());
+}
+
+/// Takes an idle task with future type `F` from the current worker.
+///
+/// The returned task is uniquely owned and ready to be refilled. Returns
+/// `None` outside a worker thread or when no such task is cached.
+pub(crate) fn take<T, F>() -> Option<Arc<Task<T, F>>>
+where
+    T: Send + 'static,
+    F: Future<Output = T> + Send + 'static,
+{
+    if !on_worker() {
+        return None;
+    }
+
+    let task = CACHE
+        .try_with(|cache| {
+            cache
+                .borrow_mut()
+                .get_mut(&TypeId::of::<Task<T, F>>())?
+                .pop()
+        })
+        .ok()
+        .flatten()?;
+
+    let mut task = task.downcast::<Task<T, F>>().ok()?;
+
+    // Tasks are cached while their last other reference is being dropped,
+    // so this only fails if that reference somehow outlived the cache.
+    Arc::get_mut(&mut task)?;
+
+    Some(task)
+}
+
+/// Hands a finished task to the cache of the current worker.
+///
+/// `task` must have dropped its future and result, and must not be
+/// referenced by anything but the caller and, at most, the one reference
+/// the caller is about to drop.
+pub(crate) fn release<T, F>(task: Arc<Task<T, F>>)
+where
+    T: Send + 'static,
+    F: Future<Output = T> + Send + 'static,
+{
+    if !on_worker() {
+        return;
+    }
+
+    // `try_with` fails while the thread is being torn down, in which
+    // case the task is simply freed.
+    let _ = CACHE.try_with(|cache| {
+        let mut cache = cache.borrow_mut();
+        let tasks = cache.entry(TypeId::of::<Task<T, F>>()).or_default();
+
+        if tasks.len() < CAPACITY {
+            tasks.push(task);
+        }
+    });
+}
+
+/// Returns `true` on a runtime worker thread.
+fn on_worker() -> bool {
+    CURRENT_WORKER_ID
+        .try_with(|id| id.borrow().is_some())
+        .unwrap_or(false)
+}
@@ -0,0 +1,67 @@
+//! Cooperative scheduling budget.
+//!
+//! Each poll of a task may run for one runtime
+//! [time slice](crate::RuntimeBuilder::time_slice) before it is expected
+//! to give its worker back. The runtime cannot interrupt a poll, so code
+//! running long loops of its own, such as a decoder draining a large
+//! buffer, checks [`has_budget`] along the way and yields with
+//! [`coop_yield`] once it is spent.
+
+use crate::yield_now;
+
+use std::cell::Cell;
+use std::time::{Duration, Instant};
+
+thread_local! {
+    /// Start and length of the budget of the poll running on this thread.
+    static BUDGET: Cell<Option<(Instant, Duration)>> = const { Cell::new(None) };
+}
+
+/// Returns `true` if the current task may keep running before yielding.
+///
+/// The budget of a task is spent once its current poll has run for the
+/// runtime time slice. Outside of a task, the budget is never spent.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// while let Some(frame) = decoder.decode(&mut buffer)? {
+///     frames.push(frame);
+///
+///     if !task::has_budget() {
+///         task::coop_yield().await;
+///     }
+/// }
+/// ```
+pub fn has_budget() -> bool {
+    BUDGET.with(|budget| match budget.get() {
+        Some((start, slice)) => start.elapsed() < slice,
+        None => true,
+    })
+}
+
+/// Yields to the scheduler if the budget of the current task is spent.
+///
+/// Resolves right away while [`has_budget`] returns `true`, and behaves
+/// like [`yield_now`] otherwise, so it can be awaited on every iteration
+/// of a loop at little cost.
+pub async fn coop_yield() {
+    if !has_budget() {
+        yield_now().await;
+    }
+}
+
+/// Starts the budget of a poll lasting `slice`, until the returned guard
+/// is dropped.
+pub(crate) fn start(slice: Duration) -> BudgetGuard {
+    BudgetGuard(BUDGET.replace(Some((Instant::now(), slice))))
+}
+
+/// Restores the budget of the enclosing poll, if any, on drop.
+pub(crate) struct BudgetGuard(Option<(Instant, Duration)>);
+
+impl Drop for BudgetGuard {
+    fn drop(&mut self) {
+        BUDGET.set(self.0);
+    }
+}
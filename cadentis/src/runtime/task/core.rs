@@ -1,14 +1,27 @@
 use super::JoinHandle;
+use super::cache;
+use super::coop;
+use super::id::TaskId;
+use super::priority::Priority;
+use super::span::{self, SpanId};
 use super::state::{CANCELLED, COMPLETED, IDLE, NOTIFIED, QUEUED, RUNNING};
 use crate::runtime::context::{CURRENT_INJECTOR, CURRENT_LOCALS, CURRENT_WORKER_ID};
+use crate::runtime::handle::current_or_global;
 use crate::runtime::task::waker::make_waker;
+use crate::runtime::work_stealing::batch;
 use crate::runtime::work_stealing::injector::Injector;
+use crate::runtime::work_stealing::queue::LocalQueue;
 
+use std::any::Any;
 use std::cell::UnsafeCell;
+use std::mem::{self, ManuallyDrop};
+use std::panic::{self, AssertUnwindSafe};
 use std::pin::Pin;
 use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::{Arc, Mutex};
 use std::task::{Context, Poll, Waker};
+use std::thread;
+use std::time::Instant;
 
 /// A runnable unit of work that can be executed by the scheduler.
 ///
@@ -25,44 +38,141 @@ pub(crate) trait Runnable: Send + Sync {
 /// A `Task` acts as the container for a `Future`. It coordinates the lifecycle
 /// of that future, including its execution state, waker registration,
 /// and result storage.
-pub(crate) struct Task<T> {
-    /// The underlying future.
-    ///
-    /// Wrapped in `UnsafeCell` for interior mutability during `poll`, and
-    /// `Pin<Box<...>>` to ensure the future remains pinned in memory.
-    future: UnsafeCell<Pin<Box<dyn Future<Output = T> + Send>>>,
-
+///
+/// The future is stored inline, so spawning makes a single allocation.
+/// Spawning creates a `Task<T, F>` for the concrete future type `F`,
+/// which `JoinHandle<T>` sees as a `Task<T>` with the future type erased.
+pub(crate) struct Task<T, F: ?Sized = dyn Future<Output = T> + Send> {
     /// Storage for the result produced by the future upon completion.
-    pub(crate) result: UnsafeCell<Option<T>>,
+    ///
+    /// Holds the panic payload instead if polling the future panicked.
+    pub(crate) result: UnsafeCell<Option<thread::Result<T>>>,
 
     /// The current lifecycle state of the task (IDLE, RUNNING, etc.).
     pub(crate) state: AtomicUsize,
 
+    /// Unique identifier of the task.
+    pub(crate) id: TaskId,
+
     /// Reference to the global injector queue for rescheduling.
     injector: Arc<Injector>,
 
+    /// Local queue of the worker this task is pinned to, if any.
+    ///
+    /// A pinned task is always rescheduled on that worker and is never
+    /// stolen by another one.
+    home: Option<Arc<LocalQueue>>,
+
+    /// Scheduling priority of the task.
+    priority: Priority,
+
+    /// Tracing span entered around each poll, if any.
+    span: Option<SpanId>,
+
+    /// When the task started being re-notified on every poll, if it is.
+    ///
+    /// Only accessed by the thread holding the task in the `RUNNING` state.
+    slice_start: UnsafeCell<Option<Instant>>,
+
+    /// Number of polls in a row during which the task woke itself.
+    ///
+    /// Only accessed by the thread holding the task in the `RUNNING` state.
+    self_wakes: UnsafeCell<u32>,
+
     /// A list of wakers belonging to `JoinHandle`s awaiting this task.
     pub(crate) waiters: Mutex<Vec<Waker>>,
+
+    /// Offers the task to the worker-local cache when its `JoinHandle`
+    /// is dropped.
+    ///
+    /// Stored as a function pointer because `JoinHandle<T>` places no
+    /// bound on `T`, while the cache needs `T: 'static`.
+    pub(crate) release: fn(&Arc<Task<T>>),
+
+    /// Whether the future was already dropped.
+    ///
+    /// Only accessed by the thread holding the task in the `RUNNING` state,
+    /// or through a unique reference.
+    future_dropped: UnsafeCell<bool>,
+
+    /// The underlying future.
+    ///
+    /// Wrapped in `UnsafeCell` for interior mutability during `poll`. It is
+    /// pinned by the allocation of the task, and dropped as soon as it
+    /// completes rather than with the task, hence the `ManuallyDrop`. It
+    /// comes last so that the future type can be erased.
+    future: UnsafeCell<ManuallyDrop<F>>,
 }
 
-unsafe impl<T> Send for Task<T> {}
-unsafe impl<T> Sync for Task<T> {}
+unsafe impl<T, F: ?Sized> Send for Task<T, F> {}
+unsafe impl<T, F: ?Sized> Sync for Task<T, F> {}
+
+impl<T, F: ?Sized> Drop for Task<T, F> {
+    fn drop(&mut self) {
+        if !*self.future_dropped.get_mut() {
+            // Safety: The future was not dropped yet, and never is again.
+            unsafe { ManuallyDrop::drop(self.future.get_mut()) }
+        }
+    }
+}
 
-impl<T: Send + 'static> Task<T> {
+impl<T, F> Task<T, F>
+where
+    T: Send + 'static,
+    F: Future<Output = T> + Send + 'static,
+{
     /// Creates a new task instance from a future.
     ///
     /// The task is initialized in the `QUEUED` state, indicating it is ready
     /// to be processed by the scheduler.
-    pub(crate) fn new<F>(future: F, injector: Arc<Injector>) -> Self
-    where
-        F: Future<Output = T> + Send + 'static,
-    {
-        Self {
-            future: UnsafeCell::new(Box::pin(future)),
+    pub(crate) fn new(future: F, injector: Arc<Injector>) -> Arc<Self> {
+        Self::with_options(future, injector, None, Priority::Normal, None)
+    }
+
+    /// Creates a new task with explicit scheduling options.
+    ///
+    /// A `home` queue pins the task to the worker owning it; passing
+    /// `None` creates a regular, stealable task. A `span` is entered
+    /// around each poll of the future.
+    ///
+    /// On a worker thread, the allocation of a finished task with the same
+    /// future type is reused when one is cached.
+    pub(crate) fn with_options(
+        future: F,
+        injector: Arc<Injector>,
+        home: Option<Arc<LocalQueue>>,
+        priority: Priority,
+        span: Option<SpanId>,
+    ) -> Arc<Self> {
+        let id = TaskId::next();
+        injector.hooks().spawned(id);
+
+        let task = Self {
             result: UnsafeCell::new(None),
             state: AtomicUsize::new(QUEUED),
+            id,
             injector,
+            home,
+            priority,
+            span,
+            slice_start: UnsafeCell::new(None),
+            self_wakes: UnsafeCell::new(0),
             waiters: Mutex::new(Vec::new()),
+            release: Self::release_handle,
+            future_dropped: UnsafeCell::new(false),
+            future: UnsafeCell::new(ManuallyDrop::new(future)),
+        };
+
+        match cache::take::<T, F>() {
+            Some(mut cached) => {
+                // `take` only returns uniquely owned tasks.
+                if let Some(slot) = Arc::get_mut(&mut cached) {
+                    *slot = task;
+                    return cached;
+                }
+                Arc::new(task)
+            }
+            None => Arc::new(task),
         }
     }
 
@@ -72,6 +182,10 @@ impl<T: Send + 'static> Task<T> {
     /// and handles the resulting `Poll` state:
     /// - `Poll::Pending`: Transitions back to `IDLE` or re-queues if notified.
     /// - `Poll::Ready`: Stores the result and notifies all `JoinHandle` waiters.
+    ///
+    /// A panic raised while polling is caught: the future is dropped and
+    /// the payload is stored as the result of the task, so the worker
+    /// thread survives and the `JoinHandle` reports the panic.
     pub(crate) fn run(self: Arc<Self>) {
         let current = self.state.load(Ordering::Acquire);
 
@@ -92,36 +206,144 @@ impl<T: Send + 'static> Task<T> {
         let waker = make_waker(self.clone());
         let mut cx = Context::from_waker(&waker);
 
-        // Safety: The RUNNING state guarantees that no other thread is polling this future.
-        let poll = unsafe { (&mut *self.future.get()).as_mut().poll(&mut cx) };
+        let poll_start = self.injector.hooks().polling(self.id);
+        let budget = coop::start(self.injector.time_slice());
+        let span = span::enter(self.span, self.injector.hooks().tracer.as_ref());
+
+        // Safety: The RUNNING state guarantees that no other thread is polling
+        // this future, which never moves out of the allocation of the task.
+        let poll = panic::catch_unwind(AssertUnwindSafe(|| unsafe {
+            Pin::new_unchecked(&mut **self.future.get()).poll(&mut cx)
+        }));
+
+        drop(span);
+        drop(budget);
+
+        self.injector.hooks().polled(self.id, poll_start);
+
+        let result = match poll {
+            Ok(Poll::Pending) => {
+                // Safety: The RUNNING state is still held. The slice and the
+                // self-wake streak are taken now because leaving that state
+                // hands the task to other threads. Both restart whenever the
+                // task really waits. The streak is only counted when the busy
+                // loop guard is on.
+                let slice_start = unsafe { (*self.slice_start.get()).take() };
+                let self_wakes = match self.injector.hooks().busy_loop {
+                    Some(_) => unsafe { mem::take(&mut *self.self_wakes.get()) }.saturating_add(1),
+                    None => 0,
+                };
 
-        match poll {
-            Poll::Pending => {
                 // Return to IDLE state unless a wake-up occurred during execution (NOTIFIED).
-                if self
-                    .state
-                    .compare_exchange(RUNNING, IDLE, Ordering::AcqRel, Ordering::Acquire)
-                    .is_err()
-                {
-                    // Task was notified while running; move back to QUEUED and reschedule.
-                    self.state.store(QUEUED, Ordering::Release);
-                    self.injector.push(self.clone());
-                }
-            }
-            Poll::Ready(val) => {
-                // Store the result and finalize the task state.
-                unsafe {
-                    *self.result.get() = Some(val);
-                }
-                self.state.store(COMPLETED, Ordering::Release);
+                let Err(state) =
+                    self.state
+                        .compare_exchange(RUNNING, IDLE, Ordering::AcqRel, Ordering::Acquire)
+                else {
+                    return;
+                };
 
-                // Wake all handles awaiting the result of this task.
-                let waiters = self.waiters.lock().unwrap();
-                for w in waiters.iter() {
-                    w.wake_by_ref();
+                // An abort during the poll must not be undone by rescheduling.
+                if state != NOTIFIED {
+                    return;
                 }
+
+                let Some(message) = self.injector.hooks().self_woken(self.id, self_wakes) else {
+                    if self
+                        .state
+                        .compare_exchange(NOTIFIED, QUEUED, Ordering::AcqRel, Ordering::Acquire)
+                        .is_ok()
+                    {
+                        // Safety: The task is QUEUED but not pushed yet, so no
+                        // other thread can run it.
+                        unsafe {
+                            *self.self_wakes.get() = self_wakes;
+                        }
+
+                        // Task was notified while running; reschedule it.
+                        self.reschedule(slice_start);
+                    }
+                    return;
+                };
+
+                // The busy loop guard stops the task as if it had panicked.
+                unsafe { self.drop_future() };
+                Err(Box::new(message) as Box<dyn Any + Send>)
+            }
+            Ok(Poll::Ready(val)) => {
+                // Release what the finished future still holds right away
+                // rather than with the last reference to the task.
+                unsafe { self.drop_future() };
+                Ok(val)
+            }
+            Err(payload) => {
+                // The future may be left in an inconsistent state: drop it
+                // now rather than with the last reference to the task.
+                unsafe { self.drop_future() };
+                Err(payload)
             }
+        };
+
+        // Store the result and finalize the task state.
+        unsafe {
+            *self.result.get() = Some(result);
+        }
+        self.state.store(COMPLETED, Ordering::Release);
+
+        // Wake all handles awaiting the result of this task.
+        for w in self.waiters.lock().unwrap().iter() {
+            w.wake_by_ref();
         }
+
+        drop(waker);
+        self.release_detached();
+    }
+
+    /// Drops the future, which is never polled again.
+    ///
+    /// # Safety
+    ///
+    /// The caller must hold the task in the `RUNNING` state, and the future
+    /// must not have been dropped yet.
+    unsafe fn drop_future(&self) {
+        unsafe {
+            *self.future_dropped.get() = true;
+            ManuallyDrop::drop(&mut *self.future.get());
+        }
+    }
+
+    /// Caches a finished task whose `JoinHandle` is already gone.
+    ///
+    /// Nobody can read the result anymore, so it is dropped here, as it
+    /// would have been with the task.
+    fn release_detached(mut self: Arc<Self>) {
+        let Some(task) = Arc::get_mut(&mut self) else {
+            return;
+        };
+
+        task.result.get_mut().take();
+        cache::release(self);
+    }
+
+    /// Caches a finished task when its `JoinHandle`, the last other
+    /// reference to it, is dropped.
+    ///
+    /// Only a completed task whose result was consumed is cached: a
+    /// cancelled task may still hold its future.
+    fn release_handle(task: &Arc<Task<T>>) {
+        if Arc::strong_count(task) != 1 || task.state.load(Ordering::Acquire) != COMPLETED {
+            return;
+        }
+
+        // Safety: The handle holds the only reference, so nothing else
+        // can access the result.
+        if unsafe { (*task.result.get()).is_some() } {
+            return;
+        }
+
+        // Safety: `release` is only ever set to this function on a task
+        // allocated with the future type `F`.
+        let task = unsafe { Arc::from_raw(Arc::into_raw(task.clone()) as *const Self) };
+        cache::release(task);
     }
 
     /// Signals the task to be rescheduled.
@@ -140,7 +362,7 @@ impl<T: Send + 'static> Task<T> {
                         .compare_exchange(IDLE, QUEUED, Ordering::AcqRel, Ordering::Acquire)
                         .is_ok()
                     {
-                        self.injector.push(self.clone());
+                        self.schedule();
                         return;
                     }
                 }
@@ -160,6 +382,52 @@ impl<T: Send + 'static> Task<T> {
         }
     }
 
+    /// Reschedules a task that was notified while it was being polled.
+    ///
+    /// Once the task has been re-notified continuously for longer than
+    /// the runtime time slice, it goes to the tail of the global injector
+    /// instead of its usual queue, so that a task spinning on its own
+    /// waker cannot keep every other task from running. Pinned tasks
+    /// always return to their worker.
+    fn reschedule(self: &Arc<Self>, slice_start: Option<Instant>) {
+        let started = slice_start.unwrap_or_else(Instant::now);
+
+        if self.home.is_none() && started.elapsed() >= self.injector.time_slice() {
+            self.injector.push(self.clone());
+            return;
+        }
+
+        // Safety: The task is QUEUED but not pushed yet, so no other
+        // thread can run it before it is scheduled below.
+        unsafe {
+            *self.slice_start.get() = Some(started);
+        }
+        self.schedule();
+    }
+
+    /// Pushes the task back to the scheduler.
+    ///
+    /// Pinned tasks return to their worker's queue, high-priority tasks
+    /// to the high-priority queue, and other tasks go through the global
+    /// injector, in bulk when woken by the reactor.
+    fn schedule(self: &Arc<Self>) {
+        match &self.home {
+            Some(home) => {
+                home.push_pinned(self.clone());
+
+                // Notified after the queue lock is released, as by the
+                // pushes of the injector: workers check their queues
+                // without the lock of the condition variable and park
+                // for a bounded time, so holding the queue lock here
+                // would not make the wake-up any more reliable.
+                self.injector.notify();
+            }
+            None => batch::push(&self.injector, self.clone(), self.priority),
+        }
+    }
+}
+
+impl<T, F: ?Sized> Task<T, F> {
     /// Aborts the task execution.
     ///
     /// Transitions the task to the `CANCELLED` state. If the task transitions
@@ -189,33 +457,89 @@ impl<T: Send + 'static> Task<T> {
     }
 }
 
-impl<T: Send + 'static> Runnable for Task<T> {
+impl<T, F> Runnable for Task<T, F>
+where
+    T: Send + 'static,
+    F: Future<Output = T> + Send + 'static,
+{
     fn run(self: Arc<Self>) {
         Task::run(self)
     }
 }
 
+/// Returns the injector of the current runtime, or of the global one.
+///
+/// # Panics
+///
+/// Panics if called outside the context of a runtime while no global
+/// runtime is installed.
+fn current_injector() -> Arc<Injector> {
+    current_or_global()
+        .expect(
+            "spawn must be called within the context of a runtime, \
+             or after installing one with runtime::set_global",
+        )
+        .injector()
+        .clone()
+}
+
 /// Spawns a future as a task onto the current runtime.
 ///
 /// The task is first attempted to be pushed to the local worker's queue
-/// for better cache locality. If called from outside the runtime, it is
-/// pushed to the global injector queue.
+/// for better cache locality. If called from outside the runtime, or if
+/// the local queue is full, it is pushed to the global injector queue.
+///
+/// Outside of any runtime context, the task is spawned onto the global
+/// runtime installed with [`set_global`](crate::runtime::set_global).
 ///
 /// # Panics
-/// Panics if called outside the context of a running runtime.
+/// Panics if called outside the context of a running runtime while no
+/// global runtime is installed.
 pub fn spawn<F, T>(future: F) -> JoinHandle<T>
 where
     T: Send + 'static,
     F: Future<Output = T> + Send + 'static,
 {
-    let injector = CURRENT_INJECTOR.with(|cell| {
-        cell.borrow()
-            .as_ref()
-            .expect("spawn must be called within the context of a runtime")
-            .clone()
-    });
+    spawn_in_span(future, None)
+}
 
-    let task = Arc::new(Task::new(future, injector.clone()));
+/// Spawns a future as a task carrying the tracing span `span`.
+///
+/// Behaves like [`spawn`], except that `span` is entered around every
+/// poll of the future: [`TaskContext::current_span`](super::TaskContext::current_span)
+/// returns it from within the task, across all of its awaits, and the
+/// [`Tracer`](super::Tracer) of the runtime is told when the span is
+/// entered and exited. Tasks spawned from within the task do not
+/// inherit the span.
+///
+/// # Panics
+/// Panics if called outside the context of a running runtime while no
+/// global runtime is installed.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// let span = SpanId::new(request.trace_id());
+/// task::spawn_traced(span, async move { handle(request).await });
+/// ```
+pub fn spawn_traced<F, T>(span: SpanId, future: F) -> JoinHandle<T>
+where
+    T: Send + 'static,
+    F: Future<Output = T> + Send + 'static,
+{
+    spawn_in_span(future, Some(span))
+}
+
+/// Spawns a future as a task carrying `span`, preferring the local queue
+/// of the current worker.
+fn spawn_in_span<F, T>(future: F, span: Option<SpanId>) -> JoinHandle<T>
+where
+    T: Send + 'static,
+    F: Future<Output = T> + Send + 'static,
+{
+    let injector = current_injector();
+
+    let task = Task::with_options(future, injector.clone(), None, Priority::Normal, span);
 
     // Try local queue injection for performance.
     let pushed_locally = CURRENT_WORKER_ID.with(|id_cell| {
@@ -223,8 +547,9 @@ where
         if let Some(id) = id {
             CURRENT_LOCALS.with(|locals_cell| {
                 if let Some(locals) = locals_cell.borrow().as_ref() {
-                    locals[id].push(task.clone());
-                    return true;
+                    // A full local queue hands the task back: overflow
+                    // to the injector below.
+                    return locals[id].push(task.clone()).is_ok();
                 }
                 false
             })
@@ -240,3 +565,104 @@ where
 
     JoinHandle { task }
 }
+
+/// Spawns a future as a task with the given scheduling priority.
+///
+/// [`Priority::High`] tasks are queued on a dedicated queue that every
+/// worker drains before any normal-priority work, and they keep their
+/// priority each time they are woken. [`Priority::Normal`] behaves
+/// exactly like [`spawn`], including its fallback to the global runtime.
+///
+/// # Panics
+/// Panics if called outside the context of a running runtime while no
+/// global runtime is installed.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// task::spawn_with_priority(Priority::High, async move {
+///     loop {
+///         send_heartbeat().await;
+///         sleep(Duration::from_secs(1)).await;
+///     }
+/// });
+/// ```
+pub fn spawn_with_priority<F, T>(priority: Priority, future: F) -> JoinHandle<T>
+where
+    T: Send + 'static,
+    F: Future<Output = T> + Send + 'static,
+{
+    if priority == Priority::Normal {
+        return spawn(future);
+    }
+
+    let injector = current_injector();
+
+    let task = Task::with_options(future, injector, None, priority, None);
+    task.schedule();
+
+    JoinHandle { task }
+}
+
+/// Spawns a future as a task pinned to the worker `worker_id`.
+///
+/// The task is pushed directly to that worker's queue and is never
+/// stolen: every poll of the future, including the ones following a
+/// wake-up, happens on the same worker thread. This is useful for
+/// sharded state that should stay on the core owning its data.
+///
+/// Workers are numbered from `0` to the number of worker threads
+/// (see [`RuntimeMetrics::num_workers`](crate::RuntimeMetrics::num_workers)).
+/// A pinned task cannot be load-balanced, so a busy worker delays its
+/// pinned tasks even if other workers are idle.
+///
+/// # Panics
+///
+/// Panics if called outside a runtime worker thread, or if `worker_id`
+/// is not a valid worker index.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// let shard = key % workers;
+/// let handle = task::spawn_on(shard, async move { handle_shard(key).await });
+/// ```
+pub fn spawn_on<F, T>(worker_id: usize, future: F) -> JoinHandle<T>
+where
+    T: Send + 'static,
+    F: Future<Output = T> + Send + 'static,
+{
+    let injector = CURRENT_INJECTOR.with(|cell| {
+        cell.borrow()
+            .as_ref()
+            .expect("spawn_on must be called within the context of a runtime")
+            .clone()
+    });
+
+    let home = CURRENT_LOCALS.with(|cell| {
+        let locals = cell.borrow();
+        let locals = locals
+            .as_ref()
+            .expect("spawn_on must be called from a runtime worker thread");
+
+        assert!(
+            worker_id < locals.len(),
+            "spawn_on: worker id {worker_id} out of range (runtime has {} workers)",
+            locals.len()
+        );
+
+        locals[worker_id].clone()
+    });
+
+    let task = Task::with_options(future, injector, Some(home), Priority::Normal, None);
+    task.schedule();
+
+    JoinHandle { task }
+}
+
+/// Returns the index of the worker thread running the current task.
+///
+/// Returns `None` when called outside a runtime worker thread.
+pub fn current_worker_id() -> Option<usize> {
+    CURRENT_WORKER_ID.with(|id| *id.borrow())
+}
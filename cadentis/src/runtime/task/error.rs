@@ -0,0 +1,117 @@
+use std::any::Any;
+use std::error::Error;
+use std::fmt;
+
+/// Error returned by a [`JoinHandle`](super::JoinHandle) whose task did
+/// not complete.
+///
+/// A task fails to complete either because it was aborted
+/// ([`is_cancelled`](Self::is_cancelled)) or because its future panicked
+/// ([`is_panic`](Self::is_panic)). In the latter case, the panic payload
+/// is captured and can be recovered with [`into_panic`](Self::into_panic),
+/// for instance to re-raise it with [`std::panic::resume_unwind`].
+pub struct JoinError {
+    repr: Repr,
+}
+
+/// Cause of a [`JoinError`].
+enum Repr {
+    /// The task was aborted before completing.
+    Cancelled,
+
+    /// The task panicked; holds the panic payload.
+    Panic(Box<dyn Any + Send + 'static>),
+}
+
+impl JoinError {
+    /// Creates an error for an aborted task.
+    pub(crate) fn cancelled() -> Self {
+        Self {
+            repr: Repr::Cancelled,
+        }
+    }
+
+    /// Creates an error for a task that panicked with `payload`.
+    pub(crate) fn panic(payload: Box<dyn Any + Send + 'static>) -> Self {
+        Self {
+            repr: Repr::Panic(payload),
+        }
+    }
+
+    /// Returns `true` if the task was aborted.
+    pub fn is_cancelled(&self) -> bool {
+        matches!(self.repr, Repr::Cancelled)
+    }
+
+    /// Returns `true` if the task panicked.
+    pub fn is_panic(&self) -> bool {
+        matches!(self.repr, Repr::Panic(_))
+    }
+
+    /// Consumes the error, returning the payload of the panic.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the task was cancelled rather than panicking; use
+    /// [`try_into_panic`](Self::try_into_panic) to handle both cases.
+    pub fn into_panic(self) -> Box<dyn Any + Send + 'static> {
+        self.try_into_panic()
+            .expect("JoinError::into_panic called on a cancelled task")
+    }
+
+    /// Consumes the error, returning the payload of the panic, or the
+    /// error itself if the task was cancelled.
+    pub fn try_into_panic(self) -> Result<Box<dyn Any + Send + 'static>, JoinError> {
+        match self.repr {
+            Repr::Panic(payload) => Ok(payload),
+            Repr::Cancelled => Err(self),
+        }
+    }
+}
+
+impl fmt::Debug for JoinError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.repr {
+            Repr::Cancelled => f.write_str("JoinError::Cancelled"),
+            Repr::Panic(payload) => match panic_message(&**payload) {
+                Some(message) => write!(f, "JoinError::Panic({message:?})"),
+                None => f.write_str("JoinError::Panic(..)"),
+            },
+        }
+    }
+}
+
+impl fmt::Display for JoinError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.repr {
+            Repr::Cancelled => f.write_str("task was cancelled"),
+            Repr::Panic(payload) => match panic_message(&**payload) {
+                Some(message) => write!(f, "task panicked with message {message:?}"),
+                None => f.write_str("task panicked"),
+            },
+        }
+    }
+}
+
+impl Error for JoinError {}
+
+impl From<JoinError> for std::io::Error {
+    /// Converts the error into an `Other` I/O error carrying its message,
+    /// so that tasks returning [`io::Result`](std::io::Result) can be
+    /// joined with
+    /// [`JoinSet::join_all_or_first_err`](super::JoinSet::join_all_or_first_err).
+    ///
+    /// The panic payload is not `Sync` and is therefore dropped.
+    fn from(err: JoinError) -> Self {
+        std::io::Error::other(err.to_string())
+    }
+}
+
+/// Extracts the message of a panic payload, if it is a string.
+fn panic_message(payload: &(dyn Any + Send)) -> Option<&str> {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        Some(message)
+    } else {
+        payload.downcast_ref::<String>().map(String::as_str)
+    }
+}
@@ -1,7 +1,9 @@
-use crate::task::Task;
+use crate::task::error::JoinError;
 use crate::task::set::SetHandle;
 use crate::task::state::{CANCELLED, COMPLETED};
+use crate::task::{Task, TaskId};
 
+use std::any::Any;
 use std::pin::Pin;
 use std::sync::Arc;
 use std::sync::atomic::Ordering;
@@ -13,18 +15,57 @@ use std::task::{Context, Poll};
 /// handle to a task running in the background. It implements [`Future`],
 /// allowing you to `.await` the task's return value.
 ///
+/// Awaiting the handle resolves to `Ok(value)` once the task completes,
+/// or to a [`JoinError`] if the task was aborted or panicked.
+///
 /// # Panics
 ///
-/// Polling a `JoinHandle` after the task has been aborted will result in a panic.
-/// Additionally, attempting to poll a `JoinHandle` after it has already returned
+/// Attempting to poll a `JoinHandle` after it has already returned
 /// [`Poll::Ready`] will panic, as the task result is consumed upon completion.
 pub struct JoinHandle<T> {
     /// A shared reference to the underlying task and its state.
     pub(crate) task: Arc<Task<T>>,
 }
 
+impl<T: Send + 'static> JoinHandle<T> {
+    /// Aborts the task.
+    ///
+    /// A task that has not completed yet is never polled again, and
+    /// awaiting its handle resolves to a [`JoinError`] for which
+    /// [`is_cancelled`](JoinError::is_cancelled) returns `true`. Aborting
+    /// a completed task has no effect.
+    pub fn abort(&self) {
+        self.task.abort();
+    }
+}
+
+impl<T> JoinHandle<T> {
+    /// Returns the identifier of the task.
+    pub fn id(&self) -> TaskId {
+        self.task.id
+    }
+
+    /// Takes the result stored by the completed task.
+    fn take_result(&self) -> Result<T, JoinError> {
+        let result = unsafe {
+            (*self.task.result.get())
+                .take()
+                .expect("task result was already consumed; JoinHandle cannot be polled twice")
+        };
+
+        result.map_err(JoinError::panic)
+    }
+}
+
+impl<T> Drop for JoinHandle<T> {
+    /// Detaches the task, recycling its allocation if it already finished.
+    fn drop(&mut self) {
+        (self.task.release)(&self.task);
+    }
+}
+
 impl<T> Future for JoinHandle<T> {
-    type Output = T;
+    type Output = Result<T, JoinError>;
 
     /// Polls the task for completion, returning the result if ready.
     ///
@@ -33,7 +74,8 @@ impl<T> Future for JoinHandle<T> {
     /// registration.
     ///
     /// ### State Machine Logic:
-    /// 1. **Initial Check**: If the task is `COMPLETED`, the result is taken and returned.
+    /// 1. **Initial Check**: If the task is `COMPLETED`, the result is taken and returned;
+    ///    if it is `CANCELLED`, a cancellation error is returned.
     /// 2. **Waker Registration**: If not ready, the current [`Waker`](std::task::Waker)
     ///    is added to the task's waiter list.
     /// 3. **Secondary Check**: The state is checked again. This handles the race
@@ -44,16 +86,11 @@ impl<T> Future for JoinHandle<T> {
         let state = self.task.state.load(Ordering::Acquire);
 
         if state == COMPLETED {
-            let value = unsafe {
-                (*self.task.result.get())
-                    .take()
-                    .expect("task result was already consumed; JoinHandle cannot be polled twice")
-            };
-            return Poll::Ready(value);
+            return Poll::Ready(self.take_result());
         }
 
         if state == CANCELLED {
-            panic!("JoinHandle polled after task was aborted");
+            return Poll::Ready(Err(JoinError::cancelled()));
         }
 
         // --- Phase 2: Register interest in task completion ---
@@ -66,16 +103,11 @@ impl<T> Future for JoinHandle<T> {
         // `run` method wouldn't have seen our waker. We check again to be sure.
         let state_after = self.task.state.load(Ordering::Acquire);
         if state_after == COMPLETED {
-            let value = unsafe {
-                (*self.task.result.get())
-                    .take()
-                    .expect("task result was already consumed")
-            };
-            return Poll::Ready(value);
+            return Poll::Ready(self.take_result());
         }
 
         if state_after == CANCELLED {
-            panic!("JoinHandle polled after task was aborted");
+            return Poll::Ready(Err(JoinError::cancelled()));
         }
 
         Poll::Pending
@@ -95,6 +127,19 @@ impl<T: Send + 'static> SetHandle for JoinHandle<T> {
         }
     }
 
+    /// Polls the handle, boxing the output of the task on completion.
+    fn poll_output(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<Box<dyn Any + Send>, JoinError>> {
+        match Future::poll(self, cx) {
+            Poll::Ready(result) => {
+                Poll::Ready(result.map(|value| Box::new(value) as Box<dyn Any + Send>))
+            }
+            Poll::Pending => Poll::Pending,
+        }
+    }
+
     /// Triggers the abort logic on the underlying task.
     ///
     /// This will transition the task state to `CANCELLED` and notify any
@@ -0,0 +1,134 @@
+use super::TaskId;
+use super::span::Tracer;
+
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// A callback receiving the identifier of a task.
+pub(crate) type Hook = Arc<dyn Fn(TaskId) + Send + Sync>;
+
+/// A callback receiving the identifier of a task and the duration of one
+/// of its polls.
+pub(crate) type SlowPollHook = Arc<dyn Fn(TaskId, Duration) + Send + Sync>;
+
+/// Poll duration above which debug builds warn by default.
+pub(crate) const DEFAULT_SLOW_POLL_THRESHOLD: Duration = Duration::from_millis(50);
+
+/// What the runtime does with a task that keeps waking itself.
+///
+/// Set with [`RuntimeBuilder::busy_loop_limit`](crate::RuntimeBuilder::busy_loop_limit).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BusyLoopAction {
+    /// Print a warning on standard error, once per streak of self-wakes,
+    /// and keep polling the task.
+    Warn,
+
+    /// Stop the task: its future is dropped and its
+    /// [`JoinHandle`](crate::task::JoinHandle) reports a panic describing
+    /// the busy loop.
+    Abort,
+}
+
+/// Instrumentation callbacks invoked by the scheduler.
+///
+/// Unset hooks cost a single branch.
+#[derive(Clone)]
+pub(crate) struct TaskHooks {
+    /// Called once for each spawned task, before it is first scheduled.
+    pub(crate) on_spawn: Option<Hook>,
+
+    /// Called before each poll of a task.
+    pub(crate) on_poll: Option<Hook>,
+
+    /// Called after a poll lasting longer than the threshold.
+    pub(crate) on_slow_poll: Option<(Duration, SlowPollHook)>,
+
+    /// Consecutive self-wakes tolerated, and what to do past them.
+    pub(crate) busy_loop: Option<(u32, BusyLoopAction)>,
+
+    /// Told about the spans entered around polls of traced tasks.
+    pub(crate) tracer: Option<Arc<dyn Tracer>>,
+}
+
+impl Default for TaskHooks {
+    /// Returns hooks that do nothing, except in debug builds where polls
+    /// longer than [`DEFAULT_SLOW_POLL_THRESHOLD`] print a warning.
+    fn default() -> Self {
+        let on_slow_poll = cfg!(debug_assertions).then(|| {
+            let warn: SlowPollHook = Arc::new(|id, elapsed| {
+                eprintln!(
+                    "cadentis: task {id} blocked its worker for {elapsed:?} in a single poll"
+                );
+            });
+            (DEFAULT_SLOW_POLL_THRESHOLD, warn)
+        });
+
+        Self {
+            on_spawn: None,
+            on_poll: None,
+            on_slow_poll,
+            busy_loop: None,
+            tracer: None,
+        }
+    }
+}
+
+impl TaskHooks {
+    /// Runs the spawn hook, if any, for the task `id`.
+    pub(crate) fn spawned(&self, id: TaskId) {
+        if let Some(hook) = &self.on_spawn {
+            hook(id);
+        }
+    }
+
+    /// Runs the poll hook, if any, for the task `id`.
+    ///
+    /// Returns the instant the poll starts when slow polls are watched.
+    pub(crate) fn polling(&self, id: TaskId) -> Option<Instant> {
+        if let Some(hook) = &self.on_poll {
+            hook(id);
+        }
+
+        self.on_slow_poll.as_ref().map(|_| Instant::now())
+    }
+
+    /// Runs the slow poll hook if the poll of the task `id`, started at
+    /// the instant returned by [`polling`](Self::polling), was too long.
+    pub(crate) fn polled(&self, id: TaskId, started: Option<Instant>) {
+        if let (Some((threshold, hook)), Some(started)) = (&self.on_slow_poll, started) {
+            let elapsed = started.elapsed();
+
+            if elapsed > *threshold {
+                hook(id, elapsed);
+            }
+        }
+    }
+
+    /// Applies the busy loop guard to the task `id`, which just woke
+    /// itself for the `wakes`-th consecutive time.
+    ///
+    /// Returns the message to abort the task with, if it must be.
+    pub(crate) fn self_woken(&self, id: TaskId, wakes: u32) -> Option<String> {
+        let (limit, action) = self.busy_loop?;
+
+        if wakes <= limit {
+            return None;
+        }
+
+        let message =
+            format!("task {id} woke itself {wakes} times in a row without waiting on anything");
+
+        match action {
+            BusyLoopAction::Warn => {
+                if wakes == limit + 1 {
+                    eprintln!("cadentis: {message}: it is likely busy-looping");
+                }
+                None
+            }
+            BusyLoopAction::Abort => {
+                eprintln!("cadentis: {message}: aborting it");
+                Some(message)
+            }
+        }
+    }
+}
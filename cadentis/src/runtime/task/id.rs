@@ -0,0 +1,33 @@
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// An opaque identifier of a spawned task.
+///
+/// Identifiers are unique among the tasks spawned by the process, and
+/// stay unique after the task finishes. They are passed to the
+/// instrumentation hooks of
+/// [`RuntimeBuilder`](crate::RuntimeBuilder::on_task_spawn) and returned
+/// by [`JoinHandle::id`](super::JoinHandle::id), which lets the two be
+/// correlated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TaskId(u64);
+
+impl TaskId {
+    /// Returns a new, never used identifier.
+    pub(crate) fn next() -> Self {
+        static NEXT: AtomicU64 = AtomicU64::new(1);
+
+        Self(NEXT.fetch_add(1, Ordering::Relaxed))
+    }
+
+    /// Returns the identifier as an integer.
+    pub fn as_u64(self) -> u64 {
+        self.0
+    }
+}
+
+impl fmt::Display for TaskId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
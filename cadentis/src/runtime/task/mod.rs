@@ -7,21 +7,43 @@
 //! - **Task state management**: Atomic state transitions (IDLE, RUNNING, etc.).
 //! - **Task & Runnable**: The core unit of work executed by the scheduler.
 //! - **JoinHandle**: A handle to await the result of a single spawned task.
+//! - **JoinError**: The error reported when a task is aborted or panics.
+//! - **TaskId**: The unique identifier of a spawned task.
 //! - **JoinSet**: A collection of tasks that allows awaiting their completion
 //!   collectively or managing their lifecycle (e.g., mass cancellation).
+//! - **scope**: Structured concurrency, awaiting every child task before
+//!   returning.
+//! - **has_budget / coop_yield**: Cooperative yielding for code running
+//!   long loops inside a single poll.
+//! - **SpanId / Tracer**: Tracing spans carried by tasks and entered
+//!   around each of their polls.
 //!
 //! Most users will interact with this module through [`spawn`] to launch
 //! individual tasks or [`JoinSet`] to manage multiple concurrent tasks.
 
+pub(crate) mod cache;
+pub(crate) mod coop;
+pub(crate) mod error;
 pub(crate) mod handle;
+pub(crate) mod hooks;
+pub(crate) mod id;
+pub(crate) mod priority;
+pub(crate) mod scope;
 pub(crate) mod set;
+pub(crate) mod span;
 pub(crate) mod state;
 pub(crate) mod waker;
 
 pub(crate) use core::{Runnable, Task};
-pub(crate) use handle::JoinHandle;
 
 pub mod core;
 
-pub use core::spawn;
+pub use coop::{coop_yield, has_budget};
+pub use core::{current_worker_id, spawn, spawn_on, spawn_traced, spawn_with_priority};
+pub use error::JoinError;
+pub use handle::JoinHandle;
+pub use id::TaskId;
+pub use priority::Priority;
+pub use scope::{Scope, scope};
 pub use set::JoinSet;
+pub use span::{SpanId, TaskContext, Tracer};
@@ -0,0 +1,16 @@
+/// Scheduling priority of a task.
+///
+/// The executor uses a simple two-tier design: every worker drains the
+/// high-priority queue before looking at its local queue, the global
+/// injector, or other workers. High priority is meant for a few
+/// latency-critical tasks (heartbeats, control messages); flooding the
+/// runtime with high-priority tasks starves normal ones.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Priority {
+    /// Runs before any normal-priority task that is ready.
+    High,
+
+    /// The default priority, used by [`spawn`](super::spawn).
+    #[default]
+    Normal,
+}
@@ -0,0 +1,259 @@
+//! Structured concurrency.
+//!
+//! [`scope`] runs an async body that spawns child tasks through a
+//! [`Scope`], and only resolves once every one of them has finished. If
+//! the scope future is dropped early, the children still running are
+//! aborted instead, so no child ever outlives its scope.
+//!
+//! Children spawned with [`Scope::spawn`] are regular `'static` tasks
+//! running on any worker. Those spawned with [`Scope::spawn_borrowed`]
+//! may borrow from the caller, and are polled by the scope future itself.
+
+use crate::task::JoinSet;
+
+use std::future::{Future, poll_fn};
+use std::mem;
+use std::panic::{self, AssertUnwindSafe};
+use std::pin::{Pin, pin};
+use std::sync::{Arc, Mutex as Mutex_std};
+use std::task::{Context, Poll, Waker};
+
+/// A child future that may borrow data living for `'env`.
+type Borrowed<'env> = Pin<Box<dyn Future<Output = ()> + Send + 'env>>;
+
+/// Runs `body` and waits for every task it spawned on the scope.
+///
+/// `body` receives a [`Scope`] to spawn child tasks with. Once the future
+/// it returns completes, `scope` keeps waiting until all the children,
+/// including those spawned by other children, have finished, and then
+/// resolves to the output of `body`.
+///
+/// Dropping the returned future aborts every child still running. A
+/// child that panics or is aborted does not affect its siblings.
+///
+/// Children spawned with [`Scope::spawn`] must be `'static`, as with
+/// [`spawn`](crate::task::spawn): the scope future can be forgotten
+/// instead of dropped, so it cannot lend borrowed data to tasks running
+/// elsewhere. Use [`Scope::spawn_borrowed`] for children that borrow.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// let total = Arc::new(AtomicUsize::new(0));
+///
+/// task::scope(|s| async move {
+///     for shard in shards {
+///         let total = total.clone();
+///         s.spawn(async move {
+///             total.fetch_add(shard.compact().await, Ordering::Relaxed);
+///         });
+///     }
+/// })
+/// .await;
+///
+/// // Every shard has been compacted here.
+/// println!("reclaimed {} bytes", total.load(Ordering::Relaxed));
+/// ```
+pub async fn scope<'env, F, Fut, R>(body: F) -> R
+where
+    F: FnOnce(Scope<'env>) -> Fut,
+    Fut: Future<Output = R>,
+{
+    let scope = Scope {
+        children: Arc::new(Mutex_std::new(Children {
+            set: JoinSet::new(),
+            borrowed: Vec::new(),
+            waker: None,
+            closed: false,
+        })),
+    };
+
+    let _guard = CloseGuard(scope.children.clone());
+    let mut body = pin!(body(scope.clone()));
+    let mut output = None;
+    let mut joining: Option<Pin<Box<dyn Future<Output = ()> + Send>>> = None;
+
+    poll_fn(|cx| {
+        if output.is_none()
+            && let Poll::Ready(value) = body.as_mut().poll(cx)
+        {
+            output = Some(value);
+        }
+
+        scope.poll_borrowed(cx);
+
+        if output.is_none() {
+            return Poll::Pending;
+        }
+
+        loop {
+            if let Some(join) = &mut joining {
+                if join.as_mut().poll(cx).is_pending() {
+                    return Poll::Pending;
+                }
+                joining = None;
+            }
+
+            let mut children = scope.children.lock().unwrap();
+
+            // Children may spawn more children while others are awaited,
+            // so the set is drained until it stays empty.
+            if !children.set.is_empty() {
+                let mut set = mem::take(&mut children.set);
+                joining = Some(Box::pin(async move { set.join_all().await }));
+                continue;
+            }
+
+            if !children.borrowed.is_empty() {
+                return Poll::Pending;
+            }
+
+            children.closed = true;
+            return Poll::Ready(output.take().unwrap());
+        }
+    })
+    .await
+}
+
+/// Handle used to spawn tasks tied to a [`scope`].
+///
+/// Cloning the handle is cheap, and clones can be moved into child tasks
+/// so that they spawn tasks of their own.
+///
+/// `'env` is the lifetime of the data that children spawned with
+/// [`spawn_borrowed`](Self::spawn_borrowed) may borrow.
+#[derive(Clone)]
+pub struct Scope<'env> {
+    children: Arc<Mutex_std<Children<'env>>>,
+}
+
+impl<'env> Scope<'env> {
+    /// Spawns a task that the scope waits for before returning.
+    ///
+    /// If the scope already returned, or was dropped, `future` is dropped
+    /// without being run.
+    pub fn spawn<F, T>(&self, future: F)
+    where
+        F: Future<Output = T> + Send + 'static,
+        T: Send + 'static,
+    {
+        let mut children = self.children.lock().unwrap();
+
+        if !children.closed {
+            children.set.spawn(future);
+        }
+    }
+
+    /// Spawns a child that may borrow data outliving the scope.
+    ///
+    /// Unlike [`spawn`](Self::spawn), the child is not handed to the
+    /// executor: the scope future polls it along with the body, so borrowed
+    /// children run concurrently with each other but on the worker driving
+    /// the scope. Handing borrowed data to other workers would be unsound,
+    /// as the scope future can be forgotten instead of dropped.
+    ///
+    /// If the scope already returned, or was dropped, `future` is dropped
+    /// without being run.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// let pages = fetch_index().await?;
+    /// let sizes = Mutex::new(Vec::new());
+    /// let (pages, sizes) = (&pages, &sizes);
+    ///
+    /// task::scope(|s| async move {
+    ///     for page in pages {
+    ///         s.spawn_borrowed(async move {
+    ///             let size = page.download().await.len();
+    ///             sizes.lock().await.push(size);
+    ///         });
+    ///     }
+    /// })
+    /// .await;
+    /// ```
+    pub fn spawn_borrowed<F>(&self, future: F)
+    where
+        F: Future + Send + 'env,
+    {
+        let waker = {
+            let mut children = self.children.lock().unwrap();
+
+            if children.closed {
+                return;
+            }
+
+            children.borrowed.push(Box::pin(async move {
+                future.await;
+            }));
+            children.waker.clone()
+        };
+
+        // The scope may be waiting on other children: let it poll this one.
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }
+
+    /// Polls the borrowed children, including those they spawn meanwhile,
+    /// and keeps the ones still pending.
+    fn poll_borrowed(&self, cx: &mut Context<'_>) {
+        let mut pending = Vec::new();
+
+        loop {
+            let batch = {
+                let mut children = self.children.lock().unwrap();
+
+                if children.borrowed.is_empty() {
+                    children.borrowed = pending;
+                    children.waker = Some(cx.waker().clone());
+                    return;
+                }
+
+                mem::take(&mut children.borrowed)
+            };
+
+            for mut child in batch {
+                // A panicking child is dropped, like an aborted task.
+                match panic::catch_unwind(AssertUnwindSafe(|| child.as_mut().poll(cx))) {
+                    Ok(Poll::Pending) => pending.push(child),
+                    Ok(Poll::Ready(())) | Err(_) => {}
+                }
+            }
+        }
+    }
+}
+
+/// Children of a [`Scope`] not yet awaited.
+struct Children<'env> {
+    set: JoinSet,
+
+    /// Children polled by the scope future itself.
+    borrowed: Vec<Borrowed<'env>>,
+
+    /// Waker of the task driving the scope.
+    waker: Option<Waker>,
+
+    /// Set once the scope returned or was dropped.
+    closed: bool,
+}
+
+/// Closes the scope when its future completes or is dropped, aborting
+/// the children still running.
+struct CloseGuard<'env>(Arc<Mutex_std<Children<'env>>>);
+
+impl Drop for CloseGuard<'_> {
+    fn drop(&mut self) {
+        let borrowed = {
+            let mut children = self.0.lock().unwrap();
+
+            children.closed = true;
+            children.set.abort_all();
+            children.waker = None;
+            mem::take(&mut children.borrowed)
+        };
+
+        // Dropped outside the lock, as a child may hold a scope handle.
+        drop(borrowed);
+    }
+}
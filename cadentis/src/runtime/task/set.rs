@@ -1,8 +1,11 @@
+use std::any::Any;
 use std::future::{Future, poll_fn};
+use std::panic;
 use std::pin::Pin;
 use std::task::{Context, Poll};
 
 use crate::task;
+use crate::task::JoinError;
 
 /// A collection of tasks that allows awaiting their completion
 /// collectively or managing their lifecycle as a group.
@@ -16,7 +19,22 @@ pub struct JoinSet {
     /// Internal collection of task handles stored as pinned trait objects.
     /// Handles are stored as `dyn SetHandle` to allow the set to manage tasks
     /// returning different types `T` internally.
-    pub(crate) handles: Vec<Pin<Box<dyn SetHandle>>>,
+    pub(crate) handles: Vec<Member>,
+
+    /// Place in the spawn order given to the next task.
+    next_order: u64,
+}
+
+/// A task of a [`JoinSet`].
+pub(crate) struct Member {
+    /// Place of the task in the spawn order of the set.
+    ///
+    /// Kept with the handle because [`JoinSet::join_next`] reorders the
+    /// handles as it removes them.
+    order: u64,
+
+    /// Handle of the task.
+    handle: Pin<Box<dyn SetHandle>>,
 }
 
 impl JoinSet {
@@ -26,6 +44,7 @@ impl JoinSet {
     pub fn new() -> Self {
         Self {
             handles: Vec::new(),
+            next_order: 0,
         }
     }
 
@@ -42,7 +61,12 @@ impl JoinSet {
         T: Send + 'static,
     {
         let handle = task::spawn(fut);
-        self.handles.push(Box::pin(handle));
+
+        self.handles.push(Member {
+            order: self.next_order,
+            handle: Box::pin(handle),
+        });
+        self.next_order += 1;
     }
 
     /// Returns the number of tasks currently managed by the set.
@@ -78,7 +102,7 @@ impl JoinSet {
             let mut i = 0;
 
             while i < self.handles.len() {
-                match self.handles[i].as_mut().poll_completed(cx) {
+                match self.handles[i].handle.as_mut().poll_completed(cx) {
                     Poll::Ready(()) => {
                         // O(1) removal by swapping with the last element.
                         // Order is not preserved, but efficiency is maximized.
@@ -101,8 +125,8 @@ impl JoinSet {
     /// is cleared immediately. Any results from tasks that had not yet
     /// been joined are discarded.
     pub fn abort_all(&mut self) {
-        for handle in &self.handles {
-            handle.abort();
+        for member in &self.handles {
+            member.handle.abort();
         }
         self.handles.clear();
     }
@@ -148,6 +172,147 @@ impl JoinSet {
     pub async fn join_all(&mut self) {
         while self.join_next().await.is_some() {}
     }
+
+    /// Waits for every task to succeed, failing fast on the first error.
+    ///
+    /// Every task in the set must return a `Result<T, E>`. The successful
+    /// values are returned in spawn order. As soon as one task returns an
+    /// error, panics or is cancelled, every remaining task is aborted and
+    /// the error is returned; panics and cancellations are converted with
+    /// `E::from(JoinError)`.
+    ///
+    /// The set is empty once this method returns.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a task in the set does not return a `Result<T, E>`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// let mut set = JoinSet::new();
+    ///
+    /// for url in urls {
+    ///     set.spawn(async move { fetch(url).await });
+    /// }
+    ///
+    /// let pages: Vec<Page> = set.join_all_or_first_err::<_, io::Error>().await?;
+    /// ```
+    pub async fn join_all_or_first_err<T, E>(&mut self) -> Result<Vec<T>, E>
+    where
+        T: 'static,
+        E: From<JoinError> + 'static,
+    {
+        let result = self
+            .collect_in_spawn_order(|output| match output {
+                Ok(output) => *output.downcast::<Result<T, E>>().unwrap_or_else(|_| {
+                    panic!("join_all_or_first_err: task returned an unexpected type")
+                }),
+                Err(err) => Err(E::from(err)),
+            })
+            .await;
+
+        if result.is_err() {
+            self.abort_all();
+        }
+        result
+    }
+
+    /// Waits for every task to complete and returns their outputs in
+    /// spawn order.
+    ///
+    /// Outputs are buffered as tasks complete, whatever the order, so the
+    /// returned vector lines up with the order in which the tasks were
+    /// spawned. Use it when the results must match an input list.
+    ///
+    /// The set is empty once this method returns.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a task in the set does not return a `T`. If a task
+    /// panicked, every remaining task is aborted and its panic is resumed;
+    /// an aborted task panics as well.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// let mut set = JoinSet::new();
+    ///
+    /// for path in &paths {
+    ///     let path = path.clone();
+    ///     set.spawn(async move { checksum(&path).await });
+    /// }
+    ///
+    /// for (path, sum) in paths.iter().zip(set.join_all_ordered::<u64>().await) {
+    ///     println!("{sum:016x}  {path}");
+    /// }
+    /// ```
+    pub async fn join_all_ordered<T: 'static>(&mut self) -> Vec<T> {
+        let result = self
+            .collect_in_spawn_order(|output| {
+                output.map(|output| {
+                    *output.downcast::<T>().unwrap_or_else(|_| {
+                        panic!("join_all_ordered: task returned an unexpected type")
+                    })
+                })
+            })
+            .await;
+
+        match result {
+            Ok(values) => values,
+            Err(err) => {
+                self.abort_all();
+
+                match err.try_into_panic() {
+                    Ok(payload) => panic::resume_unwind(payload),
+                    Err(_) => panic!("join_all_ordered: a task of the set was aborted"),
+                }
+            }
+        }
+    }
+
+    /// Waits for every task to complete, turning each output into a value
+    /// with `convert` as it arrives, and returns the values in spawn order.
+    ///
+    /// Stops at the first error `convert` returns, leaving the tasks that
+    /// are still running in the set.
+    async fn collect_in_spawn_order<T, E>(
+        &mut self,
+        mut convert: impl FnMut(Result<Box<dyn Any + Send>, JoinError>) -> Result<T, E>,
+    ) -> Result<Vec<T>, E> {
+        let mut values = Vec::with_capacity(self.handles.len());
+
+        poll_fn(|cx| {
+            let mut i = 0;
+
+            while i < self.handles.len() {
+                let output = match self.handles[i].handle.as_mut().poll_output(cx) {
+                    Poll::Ready(output) => output,
+                    Poll::Pending => {
+                        i += 1;
+                        continue;
+                    }
+                };
+
+                let member = self.handles.swap_remove(i);
+
+                match convert(output) {
+                    Ok(value) => values.push((member.order, value)),
+                    Err(err) => return Poll::Ready(Err(err)),
+                }
+            }
+
+            if self.handles.is_empty() {
+                Poll::Ready(Ok(()))
+            } else {
+                Poll::Pending
+            }
+        })
+        .await?;
+
+        values.sort_unstable_by_key(|&(order, _)| order);
+        Ok(values.into_iter().map(|(_, value)| value).collect())
+    }
 }
 
 impl Default for JoinSet {
@@ -176,6 +341,12 @@ pub(crate) trait SetHandle: Send {
     /// Polled by the `JoinSet` to check for task completion.
     fn poll_completed(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()>;
 
+    /// Polled by the `JoinSet` to take the type-erased output of the task.
+    fn poll_output(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<Box<dyn Any + Send>, JoinError>>;
+
     /// Signals the task to stop execution.
     fn abort(&self);
 }
@@ -0,0 +1,118 @@
+//! Tracing spans carried by tasks.
+//!
+//! A task spawned with [`spawn_traced`](super::spawn_traced) carries a
+//! [`SpanId`], entered around every poll of its future: code running in
+//! the task reads it with [`TaskContext::current_span`], and the
+//! [`Tracer`] installed with
+//! [`RuntimeBuilder::tracer`](crate::RuntimeBuilder::tracer) is told when
+//! the span is entered and exited. The runtime attaches no meaning to the
+//! identifier, which is left to the tracing library.
+
+use std::cell::Cell;
+use std::fmt;
+use std::sync::Arc;
+
+thread_local! {
+    /// Span of the task being polled on this thread.
+    static CURRENT_SPAN: Cell<Option<SpanId>> = const { Cell::new(None) };
+}
+
+/// An opaque identifier of a tracing span.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SpanId(u64);
+
+impl SpanId {
+    /// Creates a span identifier from an integer.
+    pub const fn new(id: u64) -> Self {
+        Self(id)
+    }
+
+    /// Returns the identifier as an integer.
+    pub fn as_u64(self) -> u64 {
+        self.0
+    }
+}
+
+impl From<u64> for SpanId {
+    fn from(id: u64) -> Self {
+        Self(id)
+    }
+}
+
+impl fmt::Display for SpanId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+/// Receives the spans entered and exited by the runtime.
+///
+/// Implemented by the bridge to a tracing library. Both methods run on
+/// the worker thread, around every poll of a traced task: keep them
+/// short.
+pub trait Tracer: Send + Sync {
+    /// Called before a task carrying `span` is polled.
+    fn enter(&self, span: SpanId);
+
+    /// Called after a task carrying `span` was polled.
+    fn exit(&self, span: SpanId);
+}
+
+/// Accessors to the context of the task being polled.
+pub struct TaskContext(());
+
+impl TaskContext {
+    /// Returns the span carried by the current task.
+    ///
+    /// Returns `None` outside of a task, or in a task spawned without a
+    /// span.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// task::spawn_traced(SpanId::new(request.trace_id), async move {
+    ///     let reply = handle(request).await;
+    ///     log::info!("span {:?}: replied", TaskContext::current_span());
+    ///     reply
+    /// });
+    /// ```
+    pub fn current_span() -> Option<SpanId> {
+        CURRENT_SPAN.get()
+    }
+}
+
+/// Enters `span` for the duration of a poll, until the returned guard is
+/// dropped.
+///
+/// A task without a span clears the current one, so that it does not
+/// observe the span of an enclosing poll.
+pub(crate) fn enter(span: Option<SpanId>, tracer: Option<&Arc<dyn Tracer>>) -> SpanGuard<'_> {
+    let tracer = tracer.filter(|_| span.is_some());
+
+    if let (Some(span), Some(tracer)) = (span, tracer) {
+        tracer.enter(span);
+    }
+
+    SpanGuard {
+        span,
+        tracer,
+        previous: CURRENT_SPAN.replace(span),
+    }
+}
+
+/// Exits the span of a poll and restores the enclosing one on drop.
+pub(crate) struct SpanGuard<'a> {
+    span: Option<SpanId>,
+    tracer: Option<&'a Arc<dyn Tracer>>,
+    previous: Option<SpanId>,
+}
+
+impl Drop for SpanGuard<'_> {
+    fn drop(&mut self) {
+        CURRENT_SPAN.set(self.previous);
+
+        if let (Some(span), Some(tracer)) = (self.span, self.tracer) {
+            tracer.exit(span);
+        }
+    }
+}
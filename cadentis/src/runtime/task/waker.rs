@@ -4,7 +4,7 @@ use std::mem;
 use std::sync::Arc;
 use std::task::{RawWaker, RawWakerVTable, Waker};
 
-/// Returns the `RawWakerVTable` for a task of type `T`.
+/// Returns the `RawWakerVTable` for a task of type `Task<T, F>`.
 ///
 /// The vtable defines how the executor interacts with the task when:
 /// - cloning the waker,
@@ -18,12 +18,16 @@ use std::task::{RawWaker, RawWakerVTable, Waker};
 /// by [`RawWaker`], in particular:
 /// - reference counts must be correctly managed,
 /// - the task must remain valid for the lifetime of the waker.
-fn vtable<T: Send + 'static>() -> &'static RawWakerVTable {
+fn vtable<T, F>() -> &'static RawWakerVTable
+where
+    T: Send + 'static,
+    F: Future<Output = T> + Send + 'static,
+{
     &RawWakerVTable::new(
-        clone_raw::<T>,
-        wake_raw::<T>,
-        wake_by_ref_raw::<T>,
-        drop_raw::<T>,
+        clone_raw::<T, F>,
+        wake_raw::<T, F>,
+        wake_by_ref_raw::<T, F>,
+        drop_raw::<T, F>,
     )
 }
 
@@ -34,56 +38,76 @@ fn vtable<T: Send + 'static>() -> &'static RawWakerVTable {
 /// # Safety
 ///
 /// This function relies on a custom `RawWaker` implementation backed
-/// by an `Arc<Task<T>>`. The pointer stored inside the `RawWaker`
+/// by an `Arc<Task<T, F>>`. The pointer stored inside the `RawWaker`
 /// must originate from `Arc::into_raw` and follow proper reference
 /// counting semantics.
 ///
 /// This function is safe to call as long as the `Task` correctly
 /// implements the wake logic.
-pub(crate) fn make_waker<T: Send + 'static>(task: Arc<Task<T>>) -> Waker {
+pub(crate) fn make_waker<T, F>(task: Arc<Task<T, F>>) -> Waker
+where
+    T: Send + 'static,
+    F: Future<Output = T> + Send + 'static,
+{
     unsafe {
         Waker::from_raw(RawWaker::new(
             Arc::into_raw(task) as *const (),
-            vtable::<T>(),
+            vtable::<T, F>(),
         ))
     }
 }
 
 /// Clones the raw waker.
 ///
-/// This increments the reference count of the underlying `Arc<Task<T>>`
+/// This increments the reference count of the underlying `Arc<Task<T, F>>`
 /// and returns a new `RawWaker` pointing to the same task.
-fn clone_raw<T: Send + 'static>(ptr: *const ()) -> RawWaker {
-    let arc = unsafe { Arc::<Task<T>>::from_raw(ptr as *const Task<T>) };
+fn clone_raw<T, F>(ptr: *const ()) -> RawWaker
+where
+    T: Send + 'static,
+    F: Future<Output = T> + Send + 'static,
+{
+    let arc = unsafe { Arc::<Task<T, F>>::from_raw(ptr as *const Task<T, F>) };
     let cloned = arc.clone();
     mem::forget(arc);
 
-    RawWaker::new(Arc::into_raw(cloned) as *const (), vtable::<T>())
+    RawWaker::new(Arc::into_raw(cloned) as *const (), vtable::<T, F>())
 }
 
 /// Wakes the task and consumes the waker.
 ///
-/// This transfers ownership of the `Arc<Task<T>>` and calls
+/// This transfers ownership of the `Arc<Task<T, F>>` and calls
 /// [`Task::wake`], potentially scheduling the task for execution.
-fn wake_raw<T: Send + 'static>(ptr: *const ()) {
-    let arc = unsafe { Arc::<Task<T>>::from_raw(ptr as *const Task<T>) };
+fn wake_raw<T, F>(ptr: *const ())
+where
+    T: Send + 'static,
+    F: Future<Output = T> + Send + 'static,
+{
+    let arc = unsafe { Arc::<Task<T, F>>::from_raw(ptr as *const Task<T, F>) };
     arc.wake();
 }
 
 /// Wakes the task without consuming the waker.
 ///
-/// The underlying `Arc<Task<T>>` is cloned to preserve the original
+/// The underlying `Arc<Task<T, F>>` is cloned to preserve the original
 /// reference count.
-fn wake_by_ref_raw<T: Send + 'static>(ptr: *const ()) {
-    let arc = unsafe { Arc::<Task<T>>::from_raw(ptr as *const Task<T>) };
+fn wake_by_ref_raw<T, F>(ptr: *const ())
+where
+    T: Send + 'static,
+    F: Future<Output = T> + Send + 'static,
+{
+    let arc = unsafe { Arc::<Task<T, F>>::from_raw(ptr as *const Task<T, F>) };
     arc.clone().wake();
     mem::forget(arc);
 }
 
 /// Drops the raw waker.
 ///
-/// This decrements the reference count of the underlying `Arc<Task<T>>`.
+/// This decrements the reference count of the underlying `Arc<Task<T, F>>`.
 /// No other action is performed.
-fn drop_raw<T: Send + 'static>(ptr: *const ()) {
-    unsafe { Arc::<Task<T>>::from_raw(ptr as *const Task<T>) };
+fn drop_raw<T, F>(ptr: *const ())
+where
+    T: Send + 'static,
+    F: Future<Output = T> + Send + 'static,
+{
+    unsafe { Arc::<Task<T, F>>::from_raw(ptr as *const Task<T, F>) };
 }
@@ -0,0 +1,127 @@
+//! Bulk hand-off of woken tasks to the injector.
+//!
+//! The reactor thread wakes every task whose I/O became ready or whose
+//! timer expired. Pushing each of them to the injector on its own takes
+//! the queue lock and wakes every parked worker once per task, which
+//! makes the reactor thread a scheduling bottleneck when many sockets
+//! become ready at once.
+//!
+//! Inside [`wake_batched`], tasks woken by the current thread are
+//! collected instead, and handed to their injector in a single push once
+//! the closure returns.
+
+use super::injector::Injector;
+use crate::runtime::task::Priority;
+use crate::runtime::task::Runnable;
+
+use std::cell::RefCell;
+use std::mem;
+use std::sync::Arc;
+
+thread_local! {
+    /// Tasks woken by the current thread during [`wake_batched`].
+    static BATCH: RefCell<Option<Vec<Pending>>> = const { RefCell::new(None) };
+}
+
+/// Tasks waiting to be pushed to one injector.
+struct Pending {
+    /// Injector the tasks belong to.
+    injector: Arc<Injector>,
+
+    /// Woken normal-priority tasks, in wake-up order.
+    normal: Vec<Arc<dyn Runnable>>,
+
+    /// Woken high-priority tasks, in wake-up order.
+    high: Vec<Arc<dyn Runnable>>,
+}
+
+/// Runs `f`, handing the tasks it wakes to the scheduler in bulk once it
+/// returns, or unwinds.
+///
+/// Returns the output of `f` along with the number of tasks handed over.
+/// Nested calls join the outermost batch, and hand over nothing
+/// themselves.
+pub(crate) fn wake_batched<R>(f: impl FnOnce() -> R) -> (R, usize) {
+    let started = BATCH.with(|batch| {
+        let mut batch = batch.borrow_mut();
+        let started = batch.is_none();
+
+        if started {
+            *batch = Some(Vec::new());
+        }
+        started
+    });
+
+    if !started {
+        return (f(), 0);
+    }
+
+    /// Flushes the batch even if `f` panics, so that no task is lost.
+    struct Flush;
+
+    impl Drop for Flush {
+        fn drop(&mut self) {
+            flush();
+        }
+    }
+
+    let guard = Flush;
+    let output = f();
+    mem::forget(guard);
+
+    (output, flush())
+}
+
+/// Pushes the tasks of the current batch to their injectors and ends the
+/// batch, returning the number of tasks pushed.
+fn flush() -> usize {
+    let pending = BATCH.with(|batch| batch.borrow_mut().take());
+    let mut handed = 0;
+
+    for pending in pending.into_iter().flatten() {
+        handed += pending.high.len() + pending.normal.len();
+
+        pending.injector.push_high_batch(pending.high);
+        pending.injector.push_batch(pending.normal);
+    }
+    handed
+}
+
+/// Queues `task` on `injector`, deferring the push to the current batch
+/// if the thread is batching wake-ups.
+pub(crate) fn push(injector: &Arc<Injector>, task: Arc<dyn Runnable>, priority: Priority) {
+    let task = BATCH.with(|batch| {
+        let mut batch = batch.borrow_mut();
+        let Some(batch) = batch.as_mut() else {
+            return Some(task);
+        };
+
+        let index = match batch
+            .iter()
+            .position(|pending| Arc::ptr_eq(&pending.injector, injector))
+        {
+            Some(index) => index,
+            None => {
+                batch.push(Pending {
+                    injector: injector.clone(),
+                    normal: Vec::new(),
+                    high: Vec::new(),
+                });
+                batch.len() - 1
+            }
+        };
+
+        match priority {
+            Priority::High => batch[index].high.push(task),
+            Priority::Normal => batch[index].normal.push(task),
+        }
+        None
+    });
+
+    if let Some(task) = task {
+        match priority {
+            Priority::High => injector.push_high(task),
+            Priority::Normal => injector.push(task),
+        }
+    }
+}
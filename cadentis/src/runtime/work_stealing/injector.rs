@@ -1,10 +1,14 @@
 use crate::runtime::task::Runnable;
+use crate::runtime::task::hooks::TaskHooks;
 
 use std::collections::VecDeque;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Condvar, Mutex};
 use std::time::Duration;
 
+/// Default time slice of a task that keeps waking itself.
+pub(crate) const DEFAULT_TIME_SLICE: Duration = Duration::from_millis(10);
+
 /// Shared handle to the global task injector.
 pub(crate) type InjectorHandle = Arc<Injector>;
 
@@ -19,6 +23,9 @@ pub(crate) struct Injector {
     /// Queue holding globally injected tasks.
     queue: Mutex<VecDeque<Arc<dyn Runnable>>>,
 
+    /// Queue holding high-priority tasks, drained before any other.
+    high: Mutex<VecDeque<Arc<dyn Runnable>>>,
+
     /// Number of parked worker threads.
     parked: Mutex<usize>,
 
@@ -27,19 +34,50 @@ pub(crate) struct Injector {
 
     /// Indicates whether the executor is shutting down.
     shutdown: AtomicBool,
+
+    /// How long a task may keep waking itself before it yields its turn.
+    time_slice: Duration,
+
+    /// Instrumentation callbacks run around task spawns and polls.
+    hooks: TaskHooks,
 }
 
 impl Injector {
-    /// Creates a new empty injector.
+    /// Creates a new empty injector with the default time slice.
     pub(crate) fn new() -> Self {
+        Self::with_time_slice(DEFAULT_TIME_SLICE)
+    }
+
+    /// Creates a new empty injector with the given time slice.
+    pub(crate) fn with_time_slice(time_slice: Duration) -> Self {
         Injector {
             queue: Mutex::new(VecDeque::new()),
+            high: Mutex::new(VecDeque::new()),
             parked: Mutex::new(0),
             condvar: Condvar::new(),
             shutdown: AtomicBool::new(false),
+            time_slice,
+            hooks: TaskHooks::default(),
         }
     }
 
+    /// Installs the instrumentation callbacks of the tasks.
+    pub(crate) fn with_hooks(mut self, hooks: TaskHooks) -> Self {
+        self.hooks = hooks;
+        self
+    }
+
+    /// Returns how long a task may keep waking itself before it is
+    /// moved to the back of the global queue.
+    pub(crate) fn time_slice(&self) -> Duration {
+        self.time_slice
+    }
+
+    /// Returns the instrumentation callbacks of the tasks.
+    pub(crate) fn hooks(&self) -> &TaskHooks {
+        &self.hooks
+    }
+
     /// Signals shutdown and wakes all parked workers.
     ///
     /// After shutdown is initiated, workers should stop parking
@@ -57,17 +95,54 @@ impl Injector {
         self.condvar.notify_all();
     }
 
+    /// Pushes several tasks at once, in order.
+    ///
+    /// This takes the queue lock and wakes the parked workers only once.
+    pub(crate) fn push_batch(&self, tasks: Vec<Arc<dyn Runnable>>) {
+        if tasks.is_empty() {
+            return;
+        }
+
+        self.queue.lock().unwrap().extend(tasks);
+        self.condvar.notify_all();
+    }
+
+    /// Pushes several high-priority tasks at once, in order.
+    pub(crate) fn push_high_batch(&self, tasks: Vec<Arc<dyn Runnable>>) {
+        if tasks.is_empty() {
+            return;
+        }
+
+        self.high.lock().unwrap().extend(tasks);
+        self.condvar.notify_all();
+    }
+
+    /// Pushes a high-priority task.
+    ///
+    /// This wakes any parked worker threads.
+    pub(crate) fn push_high(&self, task: Arc<dyn Runnable>) {
+        self.high.lock().unwrap().push_back(task);
+        self.condvar.notify_all();
+    }
+
+    /// Wakes every parked worker without queuing a task.
+    ///
+    /// Used when work is pushed to a specific worker's queue.
+    pub(crate) fn notify(&self) {
+        self.condvar.notify_all();
+    }
+
     /// Parks the current worker thread until work becomes available
     /// or a shutdown signal is received.
     ///
-    /// Workers only park if the injector queue is empty.
+    /// Workers only park if the injector queues are empty.
     /// The park operation uses a timed wait to ensure periodic wakeups.
     pub(crate) fn park(&self) {
         if self.shutdown.load(Ordering::Acquire) {
             return;
         }
 
-        if !self.queue.lock().unwrap().is_empty() {
+        if !self.queue.lock().unwrap().is_empty() || !self.high.lock().unwrap().is_empty() {
             return;
         }
 
@@ -80,6 +155,11 @@ impl Injector {
             .unwrap();
     }
 
+    /// Returns the number of tasks queued, of any priority.
+    pub(crate) fn len(&self) -> usize {
+        self.queue.lock().unwrap().len() + self.high.lock().unwrap().len()
+    }
+
     /// Steals a task from the global injector.
     ///
     /// Tasks are taken from the front of the queue.
@@ -87,4 +167,11 @@ impl Injector {
     pub(crate) fn steal(&self) -> Option<Arc<dyn Runnable>> {
         self.queue.lock().unwrap().pop_front()
     }
+
+    /// Steals the oldest high-priority task.
+    ///
+    /// Returns `None` if no high-priority task is queued.
+    pub(crate) fn steal_high(&self) -> Option<Arc<dyn Runnable>> {
+        self.high.lock().unwrap().pop_front()
+    }
 }
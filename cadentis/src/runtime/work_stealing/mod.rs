@@ -5,6 +5,7 @@
 //! a work-stealing strategy.
 //!
 //! It consists of:
+//! - [`batch`]: bulk hand-off of the tasks woken by the reactor,
 //! - [`injector`]: a global queue for newly spawned tasks,
 //! - [`queue`]: per-worker local queues used for fast local execution
 //!   and task stealing.
@@ -12,5 +13,6 @@
 //! This design minimizes contention while maintaining good load
 //! balancing across threads.
 
+pub(crate) mod batch;
 pub(crate) mod injector;
 pub(crate) mod queue;
@@ -1,8 +1,41 @@
 use crate::runtime::task::Runnable;
 
+use std::cell::UnsafeCell;
 use std::collections::VecDeque;
+use std::mem::{self, MaybeUninit};
+use std::ptr;
+use std::sync::atomic::{AtomicIsize, Ordering, fence};
 use std::sync::{Arc, Mutex};
 
+/// Default capacity of a worker's local queue, in tasks.
+pub(crate) const DEFAULT_LOCAL_QUEUE_CAPACITY: usize = 256;
+
+/// How many tasks an idle worker takes from another worker's queue.
+///
+/// Stealing one task at a time keeps tasks close to the worker that
+/// spawned them, which favors cache locality: the victim keeps the rest
+/// of its queue, likely to touch the same data. Stealing half of the
+/// victim's queue balances a burst of tasks spawned on one worker across
+/// idle workers in far fewer steals, at the cost of moving more tasks
+/// away from the data they were spawned next to.
+///
+/// Set with [`RuntimeBuilder::steal_batch`](crate::RuntimeBuilder::steal_batch).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum StealStrategy {
+    /// Steal a single task per steal.
+    #[default]
+    One,
+
+    /// Steal half of the victim's queue, rounded up.
+    ///
+    /// The thief runs the first stolen task and queues the others
+    /// locally, where they can in turn be stolen by other workers.
+    Half,
+}
+
+/// A slot of the ring buffer of a [`LocalQueue`].
+type Slot = UnsafeCell<MaybeUninit<Arc<dyn Runnable>>>;
+
 /// A per-worker local task queue.
 ///
 /// `LocalQueue` stores runnable tasks local to a worker thread.
@@ -11,41 +44,246 @@ use std::sync::{Arc, Mutex};
 ///
 /// Other workers may steal tasks from the front of the queue (FIFO),
 /// enabling work-stealing and load balancing across the executor.
+///
+/// The queue is bounded: once it holds `capacity` tasks, further pushes
+/// are rejected and the caller spills the task to the global injector.
+///
+/// Stealable tasks live in a lock-free Chase-Lev deque over a fixed ring
+/// buffer: only the owning worker pushes and pops at the back, while any
+/// worker steals from the front by advancing `top` with a compare and
+/// swap. Neither side ever takes a lock, so thieves do not contend with
+/// the owner on its hot path.
+///
+/// Tasks pinned to the worker (see [`spawn_on`](crate::task::spawn_on))
+/// are kept in a separate, unbounded FIFO that is never stolen from.
 pub(crate) struct LocalQueue {
-    /// Inner deque protected by a mutex.
-    inner: Mutex<VecDeque<Arc<dyn Runnable>>>,
+    /// Index of the oldest task, advanced by thieves and by the owner
+    /// taking the last task.
+    top: AtomicIsize,
+
+    /// Index one past the newest task, only written by the owner.
+    bottom: AtomicIsize,
+
+    /// Ring buffer of tasks, indexed modulo its power-of-two length.
+    ///
+    /// The slots between `top` and `bottom` hold initialized tasks.
+    buffer: Box<[Slot]>,
+
+    /// Tasks pinned to the owning worker, in scheduling order.
+    pinned: Mutex<VecDeque<Arc<dyn Runnable>>>,
+
+    /// Maximum number of tasks held by the queue.
+    capacity: usize,
 }
 
+// Safety: tasks are `Send + Sync`, and each slot is only accessed by the
+// owner or by the thread that won it through `top`, as described on
+// `LocalQueue`.
+unsafe impl Send for LocalQueue {}
+unsafe impl Sync for LocalQueue {}
+
 impl LocalQueue {
-    /// Creates an empty local task queue.
-    pub(crate) fn new() -> Self {
+    /// Creates an empty local task queue holding at most `capacity` tasks.
+    pub(crate) fn new(capacity: usize) -> Self {
+        let buffer = (0..capacity.next_power_of_two())
+            .map(|_| UnsafeCell::new(MaybeUninit::uninit()))
+            .collect();
+
         Self {
-            inner: Mutex::new(VecDeque::new()),
+            top: AtomicIsize::new(0),
+            bottom: AtomicIsize::new(0),
+            buffer,
+            pinned: Mutex::new(VecDeque::new()),
+            capacity,
         }
     }
 
+    /// Returns a pointer to the slot of the task at `index`.
+    fn slot(&self, index: isize) -> *mut MaybeUninit<Arc<dyn Runnable>> {
+        self.buffer[index as usize & (self.buffer.len() - 1)].get()
+    }
+
+    /// Returns the number of stealable tasks in the queue.
+    fn len(&self) -> usize {
+        let top = self.top.load(Ordering::Acquire);
+        let bottom = self.bottom.load(Ordering::Acquire);
+
+        (bottom - top).max(0) as usize
+    }
+
     /// Pushes a runnable task onto the local queue.
     ///
-    /// Tasks are pushed to the back of the queue.
-    pub(crate) fn push(&self, task: Arc<dyn Runnable>) {
-        self.inner.lock().unwrap().push_back(task);
+    /// Tasks are pushed to the back of the queue. Must only be called by
+    /// the worker owning the queue.
+    ///
+    /// # Errors
+    ///
+    /// Returns the task back if the queue is full, so that it can be
+    /// pushed to the global injector instead.
+    pub(crate) fn push(&self, task: Arc<dyn Runnable>) -> Result<(), Arc<dyn Runnable>> {
+        let bottom = self.bottom.load(Ordering::Relaxed);
+        let top = self.top.load(Ordering::Acquire);
+
+        if (bottom - top) as usize >= self.capacity {
+            return Err(task);
+        }
+
+        // Safety: the slot is outside `top..bottom`, so no other thread
+        // takes it until `bottom` is published below.
+        unsafe {
+            (*self.slot(bottom)).write(task);
+        }
+        self.bottom.store(bottom + 1, Ordering::Release);
+
+        Ok(())
     }
 
     /// Pops a runnable task from the local queue.
     ///
-    /// Tasks are popped from the back of the queue.
+    /// Tasks are popped from the back of the queue. Must only be called
+    /// by the worker owning the queue.
     /// Returns `None` if the queue is empty.
     pub(crate) fn pop(&self) -> Option<Arc<dyn Runnable>> {
-        self.inner.lock().unwrap().pop_back()
+        let bottom = self.bottom.load(Ordering::Relaxed) - 1;
+
+        // Reserve the newest task before looking at `top`, so that a
+        // thief either sees it reserved or has already advanced `top`.
+        self.bottom.store(bottom, Ordering::Relaxed);
+        fence(Ordering::SeqCst);
+        let top = self.top.load(Ordering::Relaxed);
+
+        if top > bottom {
+            // Empty: undo the reservation.
+            self.bottom.store(bottom + 1, Ordering::Relaxed);
+            return None;
+        }
+
+        // Safety: the slot is in `top..=bottom`, so it is initialized.
+        let task = unsafe { ptr::read(self.slot(bottom)).assume_init() };
+
+        if top < bottom {
+            return Some(task);
+        }
+
+        // Last task: race thieves for it through `top`.
+        let won = self
+            .top
+            .compare_exchange(top, top + 1, Ordering::SeqCst, Ordering::Relaxed)
+            .is_ok();
+        self.bottom.store(bottom + 1, Ordering::Relaxed);
+
+        if won {
+            Some(task)
+        } else {
+            // A thief took it: ours is only a copy.
+            mem::forget(task);
+            None
+        }
+    }
+
+    /// Pushes a task pinned to the owning worker.
+    ///
+    /// Pinned tasks are not subject to the queue capacity, since they
+    /// cannot overflow to the injector.
+    pub(crate) fn push_pinned(&self, task: Arc<dyn Runnable>) {
+        self.pinned.lock().unwrap().push_back(task);
+    }
+
+    /// Pops the oldest task pinned to the owning worker.
+    ///
+    /// Returns `None` if no pinned task is queued.
+    pub(crate) fn pop_pinned(&self) -> Option<Arc<dyn Runnable>> {
+        self.pinned.lock().unwrap().pop_front()
     }
 
     /// Steals a runnable task from the local queue.
     ///
     /// Stealing removes a task from the front of the queue and is
-    /// intended to be used by other worker threads.
+    /// intended to be used by other worker threads. A single task is
+    /// taken, leaving the rest of the queue to its owner; see
+    /// [`steal_into`](Self::steal_into) for larger batches. Pinned tasks
+    /// are never stolen.
     ///
     /// Returns `None` if the queue is empty.
     pub(crate) fn steal(&self) -> Option<Arc<dyn Runnable>> {
-        self.inner.lock().unwrap().pop_front()
+        loop {
+            let top = self.top.load(Ordering::Acquire);
+            fence(Ordering::SeqCst);
+            let bottom = self.bottom.load(Ordering::Acquire);
+
+            if top >= bottom {
+                return None;
+            }
+
+            // Safety: the slot was initialized when `bottom` was read. The
+            // owner may overwrite it once another thief advanced `top`, in
+            // which case the exchange below fails and the copy is
+            // discarded without being used.
+            let task = unsafe { ptr::read_volatile(self.slot(top)) };
+
+            if self
+                .top
+                .compare_exchange(top, top + 1, Ordering::SeqCst, Ordering::Relaxed)
+                .is_ok()
+            {
+                // Safety: winning the exchange makes the copy ours.
+                return Some(unsafe { task.assume_init() });
+            }
+
+            // Lost the task to another thief or the owner: try the next.
+        }
+    }
+
+    /// Steals tasks from this queue on behalf of the owner of `thief`.
+    ///
+    /// With [`StealStrategy::One`], this behaves like [`steal`](Self::steal).
+    /// With [`StealStrategy::Half`], half of the queue is taken from the
+    /// front: the first task is returned and the others are pushed to
+    /// `thief`, within its free capacity.
+    ///
+    /// Must be called by the worker owning `thief`, the only thread
+    /// pushing to it. Returns `None` if the queue is empty.
+    pub(crate) fn steal_into(
+        &self,
+        thief: &LocalQueue,
+        strategy: StealStrategy,
+    ) -> Option<Arc<dyn Runnable>> {
+        let first = self.steal()?;
+
+        if strategy == StealStrategy::One {
+            return Some(first);
+        }
+
+        // Only the caller pushes to `thief`, so its room cannot shrink.
+        let room = thief.capacity - thief.len();
+        let count = (self.len() + 1).div_ceil(2).min(room + 1);
+
+        for _ in 1..count {
+            let Some(task) = self.steal() else {
+                break;
+            };
+
+            if thief.push(task).is_err() {
+                unreachable!("stole more tasks than the thief has room for");
+            }
+        }
+
+        Some(first)
+    }
+}
+
+impl Drop for LocalQueue {
+    /// Drops the tasks still queued.
+    fn drop(&mut self) {
+        let top = *self.top.get_mut();
+        let bottom = *self.bottom.get_mut();
+
+        for index in top..bottom {
+            // Safety: the slots in `top..bottom` are initialized, and
+            // `&mut self` rules out any concurrent access.
+            unsafe {
+                (*self.slot(index)).assume_init_drop();
+            }
+        }
     }
 }
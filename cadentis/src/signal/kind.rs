@@ -0,0 +1,65 @@
+/// A Unix signal number.
+///
+/// Common signals have named constructors; any other one can be built
+/// with [`from_raw`](Self::from_raw).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SignalKind(i32);
+
+impl SignalKind {
+    /// Builds a kind from a raw signal number.
+    pub const fn from_raw(signum: i32) -> Self {
+        Self(signum)
+    }
+
+    /// Returns the raw signal number.
+    pub const fn as_raw(self) -> i32 {
+        self.0
+    }
+
+    /// `SIGHUP`: the controlling terminal was closed.
+    pub const fn hangup() -> Self {
+        Self(1)
+    }
+
+    /// `SIGINT`: interrupt from the keyboard (Ctrl-C).
+    pub const fn interrupt() -> Self {
+        Self(2)
+    }
+
+    /// `SIGQUIT`: quit from the keyboard.
+    pub const fn quit() -> Self {
+        Self(3)
+    }
+
+    /// `SIGTERM`: termination request.
+    pub const fn terminate() -> Self {
+        Self(15)
+    }
+
+    /// `SIGUSR1`: user-defined signal 1.
+    pub const fn user_defined1() -> Self {
+        #[cfg(any(target_os = "linux", target_os = "android"))]
+        return Self(10);
+
+        #[cfg(not(any(target_os = "linux", target_os = "android")))]
+        return Self(30);
+    }
+
+    /// `SIGUSR2`: user-defined signal 2.
+    pub const fn user_defined2() -> Self {
+        #[cfg(any(target_os = "linux", target_os = "android"))]
+        return Self(12);
+
+        #[cfg(not(any(target_os = "linux", target_os = "android")))]
+        return Self(31);
+    }
+
+    /// `SIGCHLD`: a child process stopped or exited.
+    pub const fn child() -> Self {
+        #[cfg(any(target_os = "linux", target_os = "android"))]
+        return Self(17);
+
+        #[cfg(not(any(target_os = "linux", target_os = "android")))]
+        return Self(20);
+    }
+}
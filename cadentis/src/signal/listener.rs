@@ -0,0 +1,89 @@
+use super::SignalKind;
+use super::registry::{self, Globals};
+use crate::stream::Stream;
+
+use std::future::poll_fn;
+use std::io;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+/// A stream of deliveries of one signal, created by [`unix`](super::unix).
+///
+/// Deliveries received while nobody polls the stream are coalesced: the
+/// next [`recv`](Self::recv) completes once, however many signals
+/// arrived in between.
+pub struct Signal {
+    kind: SignalKind,
+
+    /// Deliveries already reported.
+    seen: usize,
+
+    /// The self-pipe shared by every listener.
+    globals: &'static Globals,
+}
+
+impl Signal {
+    /// Installs the handler for `kind` and starts listening.
+    pub(super) fn new(kind: SignalKind) -> io::Result<Self> {
+        let globals = registry::install(kind)?;
+
+        Ok(Self {
+            kind,
+            seen: registry::deliveries(kind),
+            globals,
+        })
+    }
+
+    /// Returns the signal this stream listens to.
+    pub fn kind(&self) -> SignalKind {
+        self.kind
+    }
+
+    /// Waits for the next delivery of the signal.
+    ///
+    /// Returns `None` if the self-pipe backing the listeners fails,
+    /// which only happens if the process runs out of resources.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// let mut hangup = signal::unix(SignalKind::hangup())?;
+    ///
+    /// while hangup.recv().await.is_some() {
+    ///     reload_config();
+    /// }
+    /// ```
+    pub async fn recv(&mut self) -> Option<()> {
+        poll_fn(|cx| self.poll_recv(cx)).await
+    }
+
+    /// Attempts to receive the next delivery of the signal.
+    ///
+    /// Returns `Poll::Pending` and schedules a wake-up of the current
+    /// task if the signal has not been delivered since the last call.
+    pub fn poll_recv(&mut self, cx: &mut Context<'_>) -> Poll<Option<()>> {
+        loop {
+            let deliveries = registry::deliveries(self.kind);
+
+            if deliveries != self.seen {
+                self.seen = deliveries;
+                return Poll::Ready(Some(()));
+            }
+
+            // A drained pipe may hold our delivery: check again.
+            match self.globals.poll_delivery(cx) {
+                Poll::Ready(Ok(())) => continue,
+                Poll::Ready(Err(_)) => return Poll::Ready(None),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
+
+impl Stream for Signal {
+    type Item = ();
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<()>> {
+        self.get_mut().poll_recv(cx)
+    }
+}
@@ -0,0 +1,70 @@
+//! Asynchronous signal handling.
+//!
+//! This module lets tasks wait for Unix signals, typically to trigger a
+//! graceful shutdown:
+//! - [`ctrl_c`] completes on the next `SIGINT`,
+//! - [`unix`] returns a [`Signal`] stream yielding once per delivery of
+//!   any [`SignalKind`].
+//!
+//! Signal handlers only record the delivery and write a byte to a
+//! self-pipe registered with the reactor, so listening tasks are woken by
+//! the reactor like for any other I/O readiness.
+//!
+//! Once a signal is listened to, its handler stays installed for the
+//! lifetime of the process: for instance, `SIGINT` no longer terminates
+//! the process after a first call to [`ctrl_c`].
+//!
+//! This module is only available on unix platforms.
+//!
+//! # Examples
+//!
+//! ```rust,ignore
+//! let mut terminate = signal::unix(SignalKind::terminate())?;
+//!
+//! terminate.recv().await;
+//! println!("shutting down");
+//! ```
+
+mod kind;
+mod listener;
+mod registry;
+
+pub use kind::SignalKind;
+pub use listener::Signal;
+
+use std::io;
+
+/// Listens for deliveries of `kind`.
+///
+/// Only deliveries received after this call are reported.
+///
+/// # Errors
+///
+/// Returns `InvalidInput` for signals that cannot be handled (such as
+/// `SIGKILL` or `SIGSEGV`), or the OS error if the handler cannot be
+/// installed.
+///
+/// # Panics
+///
+/// Polling the returned stream panics outside of a runtime.
+pub fn unix(kind: SignalKind) -> io::Result<Signal> {
+    Signal::new(kind)
+}
+
+/// Completes on the next `SIGINT` (Ctrl-C).
+///
+/// # Errors
+///
+/// Returns an error if the `SIGINT` handler cannot be installed.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// signal::ctrl_c().await?;
+/// println!("interrupted");
+/// ```
+pub async fn ctrl_c() -> io::Result<()> {
+    unix(SignalKind::interrupt())?.recv().await;
+
+    Ok(())
+}
@@ -0,0 +1,204 @@
+//! Process-wide signal handlers and the self-pipe waking listeners.
+
+use super::SignalKind;
+use crate::reactor::readiness::Readiness;
+use crate::sys;
+
+use nucleus::poll::Interest;
+use std::io::{self, Read};
+use std::os::fd::AsRawFd;
+use std::os::unix::net::UnixStream;
+use std::sync::atomic::{AtomicBool, AtomicI32, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex as Mutex_std, OnceLock};
+use std::task::{Context, Poll, Wake, Waker};
+
+/// Number of signal slots; covers every standard and real-time signal.
+const MAX_SIGNAL: usize = 65;
+
+/// Signals that can never be handled, or only by crashing.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+const FORBIDDEN: [i32; 5] = [4, 8, 9, 11, 19];
+#[cfg(not(any(target_os = "linux", target_os = "android")))]
+const FORBIDDEN: [i32; 5] = [4, 8, 9, 11, 17];
+
+/// Number of deliveries of each signal since the process started.
+static DELIVERIES: [AtomicUsize; MAX_SIGNAL] = [const { AtomicUsize::new(0) }; MAX_SIGNAL];
+
+/// Whether the handler of each signal has been installed.
+static INSTALLED: [AtomicBool; MAX_SIGNAL] = [const { AtomicBool::new(false) }; MAX_SIGNAL];
+
+/// Write end of the self-pipe, `-1` until it is created.
+static SENDER: AtomicI32 = AtomicI32::new(-1);
+
+/// The self-pipe and the tasks waiting on it.
+static GLOBALS: OnceLock<Globals> = OnceLock::new();
+
+/// Shared state behind every [`Signal`](super::Signal).
+pub(super) struct Globals {
+    /// Readiness of the read end, registered with the reactor.
+    readiness: Readiness,
+
+    /// Read end of the self-pipe.
+    receiver: UnixStream,
+
+    /// Write end of the self-pipe, kept open for the process lifetime.
+    sender: UnixStream,
+
+    /// Tasks waiting for any signal.
+    waiters: Arc<Waiters>,
+
+    /// Waker handed to the reactor: wakes every waiting task.
+    waker: Waker,
+}
+
+/// Tasks woken together whenever the self-pipe becomes readable.
+struct Waiters {
+    wakers: Mutex_std<Vec<Waker>>,
+}
+
+impl Wake for Waiters {
+    fn wake(self: Arc<Self>) {
+        self.wake_by_ref();
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        let wakers = std::mem::take(&mut *self.wakers.lock().unwrap());
+
+        for waker in wakers {
+            waker.wake();
+        }
+    }
+}
+
+/// Records a delivery and wakes the reactor.
+///
+/// Only async-signal-safe operations are allowed here: an atomic
+/// increment and a `write`. The `errno` of the interrupted code is
+/// restored on return, as `write` may overwrite it.
+extern "C" fn handle(signum: i32) {
+    let errno = sys::errno();
+
+    if let Some(deliveries) = DELIVERIES.get(signum as usize) {
+        deliveries.fetch_add(1, Ordering::Release);
+    }
+
+    let fd = SENDER.load(Ordering::Acquire);
+
+    if fd >= 0 {
+        // A full pipe already guarantees a wake-up: the result is ignored.
+        // SAFETY: the buffer is a valid one-byte slice and `fd` stays open
+        // for the lifetime of the process.
+        unsafe { sys::write(fd, [1u8].as_ptr(), 1) };
+    }
+
+    sys::set_errno(errno);
+}
+
+/// Installs the handler for `kind` and returns the shared state.
+pub(super) fn install(kind: SignalKind) -> io::Result<&'static Globals> {
+    let signum = kind.as_raw();
+
+    if signum <= 0 || signum as usize >= MAX_SIGNAL || FORBIDDEN.contains(&signum) {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("signal {signum} cannot be handled"),
+        ));
+    }
+
+    let globals = globals()?;
+
+    if !INSTALLED[signum as usize].swap(true, Ordering::AcqRel) {
+        // `handle` only performs async-signal-safe operations.
+        if let Err(err) = sys::set_signal_handler(signum, handle) {
+            INSTALLED[signum as usize].store(false, Ordering::Release);
+            return Err(err);
+        }
+    }
+
+    Ok(globals)
+}
+
+/// Returns the number of deliveries of `kind` so far.
+pub(super) fn deliveries(kind: SignalKind) -> usize {
+    DELIVERIES[kind.as_raw() as usize].load(Ordering::Acquire)
+}
+
+/// Creates the self-pipe on first use.
+fn globals() -> io::Result<&'static Globals> {
+    if let Some(globals) = GLOBALS.get() {
+        return Ok(globals);
+    }
+
+    let (receiver, sender) = UnixStream::pair()?;
+    receiver.set_nonblocking(true)?;
+    sender.set_nonblocking(true)?;
+
+    let waiters = Arc::new(Waiters {
+        wakers: Mutex_std::new(Vec::new()),
+    });
+
+    let globals = Globals {
+        readiness: Readiness::new(receiver.as_raw_fd()),
+        waker: Waker::from(waiters.clone()),
+        waiters,
+        receiver,
+        sender,
+    };
+
+    // A concurrent first call may have won the race: its pipe is used
+    // and ours is closed.
+    let globals = GLOBALS.get_or_init(|| globals);
+    SENDER.store(globals.sender.as_raw_fd(), Ordering::Release);
+
+    Ok(globals)
+}
+
+impl Globals {
+    /// Waits for the self-pipe to receive a byte.
+    ///
+    /// Returns `Poll::Ready` once the pipe has been drained, after waking
+    /// every other waiting task; otherwise schedules a wake-up of the
+    /// current task for the next delivery of any signal.
+    pub(super) fn poll_delivery(&self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        {
+            let mut wakers = self.waiters.wakers.lock().unwrap();
+
+            if !wakers.iter().any(|w| w.will_wake(cx.waker())) {
+                wakers.push(cx.waker().clone());
+            }
+        }
+
+        let interest = Interest {
+            read: true,
+            write: false,
+        };
+
+        // The reactor wakes every waiter, not only the task polling last,
+        // which may be gone by the time a signal arrives.
+        let mut broadcast = Context::from_waker(&self.waker);
+        let result = self
+            .readiness
+            .poll_io(&mut broadcast, interest, || self.drain());
+
+        if result.is_ready() {
+            self.waker.wake_by_ref();
+        }
+
+        result
+    }
+
+    /// Empties the self-pipe, failing with `WouldBlock` if it was empty.
+    fn drain(&self) -> io::Result<()> {
+        let mut buffer = [0u8; 64];
+        let mut drained = false;
+
+        loop {
+            match (&self.receiver).read(&mut buffer) {
+                Ok(0) => return Ok(()),
+                Ok(_) => drained = true,
+                Err(e) if e.kind() == io::ErrorKind::WouldBlock && drained => return Ok(()),
+                Err(e) => return Err(e),
+            }
+        }
+    }
+}
@@ -0,0 +1,52 @@
+use std::ops::DerefMut;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+/// A sequence of values produced asynchronously.
+///
+/// `Stream` is the asynchronous equivalent of [`Iterator`]: instead of
+/// returning the next value immediately, [`poll_next`](Self::poll_next)
+/// may return [`Poll::Pending`] and wake the task once a value is ready.
+///
+/// A stream signals its end by returning `Poll::Ready(None)`.
+pub trait Stream {
+    /// The type of values yielded by the stream.
+    type Item;
+
+    /// Attempts to pull the next value out of the stream.
+    ///
+    /// Returns:
+    /// - `Poll::Pending` if no value is ready yet (the current task is
+    ///   woken once one is),
+    /// - `Poll::Ready(Some(value))` when a value is available,
+    /// - `Poll::Ready(None)` once the stream is exhausted.
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>>;
+}
+
+impl<S: Stream + Unpin + ?Sized> Stream for &mut S {
+    type Item = S::Item;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        Pin::new(&mut **self).poll_next(cx)
+    }
+}
+
+impl<S: Stream + Unpin + ?Sized> Stream for Box<S> {
+    type Item = S::Item;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        Pin::new(&mut **self).poll_next(cx)
+    }
+}
+
+impl<P> Stream for Pin<P>
+where
+    P: DerefMut + Unpin,
+    P::Target: Stream,
+{
+    type Item = <P::Target as Stream>::Item;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.get_mut().as_mut().poll_next(cx)
+    }
+}
@@ -0,0 +1,237 @@
+use super::Stream;
+
+use std::future::Future;
+use std::mem;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+/// Extension trait providing combinators for [`Stream`]s.
+///
+/// This trait is implemented for every type implementing [`Stream`],
+/// so bringing it into scope is enough to use its methods.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// use cadentis::stream::StreamExt;
+///
+/// while let Some(msg) = rx.next().await {
+///     println!("{msg}");
+/// }
+/// ```
+pub trait StreamExt: Stream {
+    /// Returns a future resolving to the next item of the stream.
+    ///
+    /// Resolves to `None` once the stream is exhausted.
+    fn next(&mut self) -> Next<'_, Self>
+    where
+        Self: Unpin,
+    {
+        Next { stream: self }
+    }
+
+    /// Transforms each item of the stream with the closure `f`.
+    fn map<U, F>(self, f: F) -> Map<Self, F>
+    where
+        F: FnMut(Self::Item) -> U,
+        Self: Sized,
+    {
+        Map { stream: self, f }
+    }
+
+    /// Yields only the items for which the predicate returns `true`.
+    fn filter<F>(self, predicate: F) -> Filter<Self, F>
+    where
+        F: FnMut(&Self::Item) -> bool,
+        Self: Sized,
+    {
+        Filter {
+            stream: self,
+            predicate,
+        }
+    }
+
+    /// Runs the closure `f` on every item until the stream is exhausted.
+    ///
+    /// The returned future resolves once the stream yields `None`.
+    fn for_each<F>(self, f: F) -> ForEach<Self, F>
+    where
+        F: FnMut(Self::Item),
+        Self: Sized,
+    {
+        ForEach { stream: self, f }
+    }
+
+    /// Collects every item of the stream into a collection.
+    ///
+    /// The returned future resolves once the stream yields `None`.
+    fn collect<C>(self) -> Collect<Self, C>
+    where
+        C: Default + Extend<Self::Item>,
+        Self: Sized,
+    {
+        Collect {
+            stream: self,
+            collection: C::default(),
+        }
+    }
+}
+
+impl<S: Stream + ?Sized> StreamExt for S {}
+
+/// Future returned by [`StreamExt::next`].
+pub struct Next<'a, S: ?Sized> {
+    stream: &'a mut S,
+}
+
+impl<S: Stream + Unpin + ?Sized> Future for Next<'_, S> {
+    type Output = Option<S::Item>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        Pin::new(&mut *self.stream).poll_next(cx)
+    }
+}
+
+/// Stream returned by [`StreamExt::map`].
+pub struct Map<S, F> {
+    /// The underlying stream.
+    stream: S,
+
+    /// Closure applied to each item.
+    f: F,
+}
+
+impl<S, F, U> Stream for Map<S, F>
+where
+    S: Stream,
+    F: FnMut(S::Item) -> U,
+{
+    type Item = U;
+
+    /// Polls the underlying stream and maps the produced item.
+    ///
+    /// # Safety
+    ///
+    /// This implementation uses `unsafe` pin projections but is sound
+    /// because the wrapped stream is never moved after being pinned.
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = unsafe { self.get_unchecked_mut() };
+        let stream = unsafe { Pin::new_unchecked(&mut this.stream) };
+
+        stream.poll_next(cx).map(|item| item.map(&mut this.f))
+    }
+}
+
+/// Stream returned by [`StreamExt::filter`].
+pub struct Filter<S, F> {
+    /// The underlying stream.
+    stream: S,
+
+    /// Predicate deciding which items are yielded.
+    predicate: F,
+}
+
+impl<S, F> Stream for Filter<S, F>
+where
+    S: Stream,
+    F: FnMut(&S::Item) -> bool,
+{
+    type Item = S::Item;
+
+    /// Polls the underlying stream until an item matches the predicate.
+    ///
+    /// # Safety
+    ///
+    /// This implementation uses `unsafe` pin projections but is sound
+    /// because the wrapped stream is never moved after being pinned.
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = unsafe { self.get_unchecked_mut() };
+
+        loop {
+            let stream = unsafe { Pin::new_unchecked(&mut this.stream) };
+
+            match stream.poll_next(cx) {
+                Poll::Ready(Some(item)) => {
+                    if (this.predicate)(&item) {
+                        return Poll::Ready(Some(item));
+                    }
+                }
+                other => return other,
+            }
+        }
+    }
+}
+
+/// Future returned by [`StreamExt::for_each`].
+pub struct ForEach<S, F> {
+    /// The underlying stream.
+    stream: S,
+
+    /// Closure run on each item.
+    f: F,
+}
+
+impl<S, F> Future for ForEach<S, F>
+where
+    S: Stream,
+    F: FnMut(S::Item),
+{
+    type Output = ();
+
+    /// Drives the stream, running the closure on every ready item.
+    ///
+    /// # Safety
+    ///
+    /// This implementation uses `unsafe` pin projections but is sound
+    /// because the wrapped stream is never moved after being pinned.
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = unsafe { self.get_unchecked_mut() };
+
+        loop {
+            let stream = unsafe { Pin::new_unchecked(&mut this.stream) };
+
+            match stream.poll_next(cx) {
+                Poll::Ready(Some(item)) => (this.f)(item),
+                Poll::Ready(None) => return Poll::Ready(()),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
+
+/// Future returned by [`StreamExt::collect`].
+pub struct Collect<S, C> {
+    /// The underlying stream.
+    stream: S,
+
+    /// Collection accumulating the items.
+    collection: C,
+}
+
+impl<S, C> Future for Collect<S, C>
+where
+    S: Stream,
+    C: Default + Extend<S::Item>,
+{
+    type Output = C;
+
+    /// Drives the stream, accumulating every item into the collection.
+    ///
+    /// # Safety
+    ///
+    /// This implementation uses `unsafe` pin projections but is sound
+    /// because the wrapped stream is never moved after being pinned.
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = unsafe { self.get_unchecked_mut() };
+
+        loop {
+            let stream = unsafe { Pin::new_unchecked(&mut this.stream) };
+
+            match stream.poll_next(cx) {
+                Poll::Ready(Some(item)) => this.collection.extend(Some(item)),
+                Poll::Ready(None) => return Poll::Ready(mem::take(&mut this.collection)),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
@@ -0,0 +1,20 @@
+//! Asynchronous streams.
+//!
+//! This module defines the [`Stream`] trait, the asynchronous counterpart
+//! of [`Iterator`], along with the [`StreamExt`] extension trait providing
+//! common combinators.
+//!
+//! It includes:
+//! - [`Stream`] for types producing a sequence of values asynchronously,
+//! - [`StreamExt`] for adapters like `next`, `map`, `filter`, `for_each`
+//!   and `collect`.
+//!
+//! Runtime primitives producing multiple values over time (such as the
+//! [`mpsc::Receiver`](crate::sync::mpsc::Receiver)) implement [`Stream`] so
+//! they compose with these combinators.
+
+mod core;
+mod ext;
+
+pub use core::Stream;
+pub use ext::{Collect, Filter, ForEach, Map, Next, StreamExt};
@@ -0,0 +1,158 @@
+use std::cell::UnsafeCell;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::task::Waker;
+
+/// No registration or wakeup is in progress.
+const WAITING: usize = 0;
+
+/// A task is storing its waker.
+const REGISTERING: usize = 0b01;
+
+/// A wakeup is taking the stored waker.
+const WAKING: usize = 0b10;
+
+/// A slot holding the waker of a single consumer task.
+///
+/// `AtomicWaker` is a low-level building block for futures that are
+/// woken by another thread: the consumer stores its waker with
+/// [`register`](Self::register) on every poll, and the producer calls
+/// [`wake`](Self::wake) once the awaited event happened.
+///
+/// Registration and wakeup coordinate through an atomic state instead of
+/// a lock, and no wakeup is lost: a `wake` racing with a `register`
+/// either wakes the newly registered waker or makes `register` wake it
+/// itself.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+///     // Register before checking the condition, so that an event
+///     // happening in between is not missed.
+///     self.waker.register(cx.waker());
+///
+///     if self.ready.load(Ordering::Acquire) {
+///         return Poll::Ready(());
+///     }
+///
+///     Poll::Pending
+/// }
+/// ```
+pub struct AtomicWaker {
+    /// Combination of the `REGISTERING` and `WAKING` flags.
+    state: AtomicUsize,
+
+    /// The registered waker.
+    ///
+    /// Only accessed by the thread that moved `state` out of `WAITING`.
+    waker: UnsafeCell<Option<Waker>>,
+}
+
+// SAFETY: access to `waker` is serialized by `state`, and `Waker` itself
+// is `Send + Sync`.
+unsafe impl Send for AtomicWaker {}
+unsafe impl Sync for AtomicWaker {}
+
+impl AtomicWaker {
+    /// Creates an empty `AtomicWaker`.
+    pub fn new() -> Self {
+        Self {
+            state: AtomicUsize::new(WAITING),
+            waker: UnsafeCell::new(None),
+        }
+    }
+
+    /// Registers `waker` to be woken by the next call to [`wake`](Self::wake).
+    ///
+    /// A previously registered waker is replaced, unless it would wake
+    /// the same task. If a wakeup happens concurrently, `waker` is woken
+    /// immediately.
+    ///
+    /// Only one task should register at a time; concurrent registrations
+    /// are not lost but only one of them is kept.
+    pub fn register(&self, waker: &Waker) {
+        match self.state.compare_exchange(
+            WAITING,
+            REGISTERING,
+            Ordering::Acquire,
+            Ordering::Acquire,
+        ) {
+            Ok(_) => {
+                // SAFETY: moving the state to `REGISTERING` grants exclusive
+                // access to the slot until the state is released.
+                unsafe {
+                    let slot = &mut *self.waker.get();
+
+                    match slot {
+                        Some(old) if old.will_wake(waker) => {}
+                        _ => *slot = Some(waker.clone()),
+                    }
+                }
+
+                if let Err(actual) = self.state.compare_exchange(
+                    REGISTERING,
+                    WAITING,
+                    Ordering::AcqRel,
+                    Ordering::Acquire,
+                ) {
+                    // A wakeup arrived while registering and could not take
+                    // the waker: deliver it on its behalf.
+                    debug_assert_eq!(actual, REGISTERING | WAKING);
+
+                    // SAFETY: `wake` leaves the slot alone while the
+                    // `REGISTERING` flag is set, so access is still exclusive.
+                    let waker = unsafe { (*self.waker.get()).take() };
+
+                    self.state.swap(WAITING, Ordering::AcqRel);
+
+                    if let Some(waker) = waker {
+                        waker.wake();
+                    }
+                }
+            }
+            Err(WAKING) => {
+                // A wakeup is in progress and may miss this waker.
+                waker.wake_by_ref();
+            }
+            Err(_) => {
+                // A concurrent registration is in progress and wins.
+            }
+        }
+    }
+
+    /// Wakes the registered waker, if any.
+    ///
+    /// The waker is consumed: a new wakeup requires a new registration.
+    pub fn wake(&self) {
+        if let Some(waker) = self.take() {
+            waker.wake();
+        }
+    }
+
+    /// Takes the registered waker out of the slot, if any.
+    pub fn take(&self) -> Option<Waker> {
+        match self.state.fetch_or(WAKING, Ordering::AcqRel) {
+            WAITING => {
+                // SAFETY: setting `WAKING` from `WAITING` grants exclusive
+                // access to the slot until the flag is cleared.
+                let waker = unsafe { (*self.waker.get()).take() };
+
+                self.state.fetch_and(!WAKING, Ordering::Release);
+
+                waker
+            }
+            _ => {
+                // A registration is in progress and will observe `WAKING`,
+                // or another wakeup is already taking the waker.
+                None
+            }
+        }
+    }
+}
+
+impl Default for AtomicWaker {
+    /// Returns an empty [`AtomicWaker`].
+    fn default() -> Self {
+        Self::new()
+    }
+}
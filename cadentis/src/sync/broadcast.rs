@@ -0,0 +1,437 @@
+//! Multi-producer, multi-consumer broadcast channels.
+//!
+//! Every value sent on a broadcast channel is delivered to every
+//! [`Receiver`] subscribed at the time it was sent. A channel is created
+//! with [`channel`] or [`channel_with_overflow`]; more receivers are
+//! created with [`Sender::subscribe`].
+//!
+//! A channel buffers at most `capacity` values not yet seen by every
+//! receiver. What happens once a slow receiver lets the buffer fill up
+//! is chosen at creation with [`Overflow`]:
+//! - [`Overflow::Lag`] (the default) overwrites the oldest value; the
+//!   slow receiver skips it and is told how many values it missed with
+//!   [`RecvError::Lagged`],
+//! - [`Overflow::Wait`] suspends senders until the slowest receiver has
+//!   made room, so that every receiver sees every value.
+
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex as Mutex_std};
+use std::task::{Context, Poll, Waker};
+
+/// Behavior of a full broadcast channel.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Overflow {
+    /// Sending never waits: the oldest buffered value is dropped, and
+    /// receivers that had not seen it report [`RecvError::Lagged`].
+    #[default]
+    Lag,
+
+    /// Sending waits until every receiver has seen the oldest buffered
+    /// value: no value is ever lost, at the cost of the fastest
+    /// receivers progressing at the pace of the slowest one.
+    Wait,
+}
+
+/// Creates a broadcast channel buffering up to `capacity` values, which
+/// overwrites the oldest value when full ([`Overflow::Lag`]).
+///
+/// # Panics
+///
+/// Panics if `capacity == 0`.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// let (tx, mut rx1) = broadcast::channel(16);
+/// let mut rx2 = tx.subscribe();
+///
+/// tx.send("hello").await.unwrap();
+/// assert_eq!(rx1.recv().await, Ok("hello"));
+/// assert_eq!(rx2.recv().await, Ok("hello"));
+/// ```
+pub fn channel<T: Clone>(capacity: usize) -> (Sender<T>, Receiver<T>) {
+    channel_with_overflow(capacity, Overflow::Lag)
+}
+
+/// Creates a broadcast channel buffering up to `capacity` values, with
+/// the given behavior when full.
+///
+/// # Panics
+///
+/// Panics if `capacity == 0`.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// // Every subscriber must see every event.
+/// let (tx, rx) = broadcast::channel_with_overflow(64, Overflow::Wait);
+/// ```
+pub fn channel_with_overflow<T: Clone>(
+    capacity: usize,
+    overflow: Overflow,
+) -> (Sender<T>, Receiver<T>) {
+    assert!(capacity > 0, "channel capacity must be > 0");
+
+    let shared = Arc::new(Shared {
+        state: Mutex_std::new(State {
+            buffer: VecDeque::with_capacity(capacity),
+            head: 0,
+            capacity,
+            overflow,
+            receivers: HashMap::new(),
+            next_id: 0,
+            senders: 1,
+            send_waiters: Vec::new(),
+        }),
+    });
+
+    let sender = Sender { shared };
+    let receiver = sender.subscribe();
+
+    (sender, receiver)
+}
+
+/// State shared between all endpoints of a channel.
+struct Shared<T> {
+    /// Channel state protected by a standard blocking mutex.
+    ///
+    /// Critical sections are short and never span an await point.
+    state: Mutex_std<State<T>>,
+}
+
+/// Mutable channel state.
+struct State<T> {
+    /// Values not yet seen by every receiver, oldest first.
+    buffer: VecDeque<T>,
+
+    /// Position of the first value of `buffer` in the channel history.
+    head: u64,
+
+    /// Maximum number of buffered values.
+    capacity: usize,
+
+    /// Behavior once the buffer is full.
+    overflow: Overflow,
+
+    /// Position of the next value of each receiver, by receiver id.
+    receivers: HashMap<u64, Cursor>,
+
+    /// Identifier given to the next receiver.
+    next_id: u64,
+
+    /// Number of live senders.
+    senders: usize,
+
+    /// Wakers of senders waiting for room ([`Overflow::Wait`] only).
+    send_waiters: Vec<Waker>,
+}
+
+/// Progress of one receiver.
+struct Cursor {
+    /// Position of the next value to receive.
+    next: u64,
+
+    /// Waker of the receiver waiting for a value.
+    waker: Option<Waker>,
+}
+
+impl<T> State<T> {
+    /// Returns the position the next sent value will take.
+    fn tail(&self) -> u64 {
+        self.head + self.buffer.len() as u64
+    }
+
+    /// Drops the values every receiver has seen, waking waiting senders
+    /// if room was made.
+    fn prune(&mut self) {
+        let oldest = self
+            .receivers
+            .values()
+            .map(|cursor| cursor.next)
+            .min()
+            .unwrap_or_else(|| self.tail());
+
+        let mut pruned = false;
+
+        while self.head < oldest {
+            self.buffer.pop_front();
+            self.head += 1;
+            pruned = true;
+        }
+
+        if pruned {
+            for waker in self.send_waiters.drain(..) {
+                waker.wake();
+            }
+        }
+    }
+
+    /// Wakes every receiver waiting for a value.
+    fn wake_receivers(&mut self) {
+        for cursor in self.receivers.values_mut() {
+            if let Some(waker) = cursor.waker.take() {
+                waker.wake();
+            }
+        }
+    }
+}
+
+/// Error returned when sending on a channel without receivers.
+///
+/// The unsent value is handed back to the caller.
+#[derive(PartialEq, Eq)]
+pub struct SendError<T>(pub T);
+
+impl<T> fmt::Debug for SendError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("SendError(..)")
+    }
+}
+
+impl<T> fmt::Display for SendError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("channel closed")
+    }
+}
+
+impl<T> std::error::Error for SendError<T> {}
+
+/// Error returned by [`Receiver::recv`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecvError {
+    /// The receiver fell behind and this many values were overwritten
+    /// before it could see them ([`Overflow::Lag`] only).
+    ///
+    /// The next call receives the oldest value still buffered.
+    Lagged(u64),
+
+    /// Every sender was dropped and every buffered value was received.
+    Closed,
+}
+
+impl fmt::Display for RecvError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RecvError::Lagged(n) => write!(f, "receiver lagged behind by {n} values"),
+            RecvError::Closed => f.write_str("channel closed"),
+        }
+    }
+}
+
+impl std::error::Error for RecvError {}
+
+/// The sending half of a broadcast channel.
+///
+/// Senders can be cloned; the channel is closed once every sender has
+/// been dropped.
+pub struct Sender<T> {
+    shared: Arc<Shared<T>>,
+}
+
+impl<T> Sender<T> {
+    /// Sends a value to every current receiver.
+    ///
+    /// With [`Overflow::Lag`], the returned future completes immediately.
+    /// With [`Overflow::Wait`], it waits while the buffer is full until
+    /// the slowest receiver has made room.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SendError`] with the value if there is no receiver.
+    pub fn send(&self, value: T) -> SendFuture<'_, T> {
+        SendFuture {
+            sender: self,
+            value: Some(value),
+        }
+    }
+
+    /// Creates a new receiver, which receives the values sent from now on.
+    pub fn subscribe(&self) -> Receiver<T> {
+        let mut state = self.shared.state.lock().unwrap();
+
+        let id = state.next_id;
+        state.next_id += 1;
+
+        let next = state.tail();
+        state.receivers.insert(id, Cursor { next, waker: None });
+
+        Receiver {
+            shared: self.shared.clone(),
+            id,
+        }
+    }
+
+    /// Returns the number of live receivers.
+    pub fn receiver_count(&self) -> usize {
+        self.shared.state.lock().unwrap().receivers.len()
+    }
+
+    /// Returns the behavior of the channel when full.
+    pub fn overflow(&self) -> Overflow {
+        self.shared.state.lock().unwrap().overflow
+    }
+}
+
+impl<T> Clone for Sender<T> {
+    /// Creates another sender for the same channel.
+    fn clone(&self) -> Self {
+        self.shared.state.lock().unwrap().senders += 1;
+
+        Self {
+            shared: self.shared.clone(),
+        }
+    }
+}
+
+impl<T> Drop for Sender<T> {
+    /// Releases the sender, waking every receiver if it was the last one.
+    fn drop(&mut self) {
+        let mut state = self.shared.state.lock().unwrap();
+        state.senders -= 1;
+
+        if state.senders == 0 {
+            state.wake_receivers();
+        }
+    }
+}
+
+/// Future returned by [`Sender::send`].
+pub struct SendFuture<'a, T> {
+    sender: &'a Sender<T>,
+    value: Option<T>,
+}
+
+impl<T> Unpin for SendFuture<'_, T> {}
+
+impl<T> Future for SendFuture<'_, T> {
+    type Output = Result<(), SendError<T>>;
+
+    /// Attempts to append the value to the channel.
+    ///
+    /// A full [`Overflow::Wait`] channel queues the task until a receiver
+    /// makes room; a full [`Overflow::Lag`] channel drops its oldest value.
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        let mut state = this.sender.shared.state.lock().unwrap();
+
+        let value = this
+            .value
+            .take()
+            .expect("SendFuture polled after completion");
+
+        if state.receivers.is_empty() {
+            return Poll::Ready(Err(SendError(value)));
+        }
+
+        if state.buffer.len() >= state.capacity {
+            match state.overflow {
+                Overflow::Lag => {
+                    state.buffer.pop_front();
+                    state.head += 1;
+                }
+                Overflow::Wait => {
+                    this.value = Some(value);
+                    state.send_waiters.push(cx.waker().clone());
+
+                    return Poll::Pending;
+                }
+            }
+        }
+
+        state.buffer.push_back(value);
+        state.wake_receivers();
+
+        Poll::Ready(Ok(()))
+    }
+}
+
+/// The receiving half of a broadcast channel.
+///
+/// Each receiver sees every value sent after its creation, in sending
+/// order, unless it lags behind a [`Overflow::Lag`] channel.
+pub struct Receiver<T> {
+    shared: Arc<Shared<T>>,
+
+    /// Key of the receiver's cursor in the channel state.
+    id: u64,
+}
+
+impl<T: Clone> Receiver<T> {
+    /// Receives the next value, waiting until one is available.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RecvError::Lagged`] if values were overwritten before
+    /// this receiver could see them, or [`RecvError::Closed`] once every
+    /// sender has been dropped and every value has been received.
+    pub fn recv(&mut self) -> RecvFuture<'_, T> {
+        RecvFuture { receiver: self }
+    }
+
+    /// Polls for the next value.
+    ///
+    /// Registers the current task to be woken when a value is sent or
+    /// the last sender is dropped.
+    fn poll_recv(&self, cx: &mut Context<'_>) -> Poll<Result<T, RecvError>> {
+        let mut state = self.shared.state.lock().unwrap();
+        let head = state.head;
+        let tail = state.tail();
+        let senders = state.senders;
+
+        let cursor = state
+            .receivers
+            .get_mut(&self.id)
+            .expect("receiver cursor missing");
+
+        if cursor.next < head {
+            let missed = head - cursor.next;
+            cursor.next = head;
+
+            return Poll::Ready(Err(RecvError::Lagged(missed)));
+        }
+
+        if cursor.next == tail {
+            if senders == 0 {
+                return Poll::Ready(Err(RecvError::Closed));
+            }
+
+            cursor.waker = Some(cx.waker().clone());
+
+            return Poll::Pending;
+        }
+
+        let index = (cursor.next - head) as usize;
+        cursor.next += 1;
+
+        let value = state.buffer[index].clone();
+        state.prune();
+
+        Poll::Ready(Ok(value))
+    }
+}
+
+impl<T> Drop for Receiver<T> {
+    /// Unsubscribes, releasing the values only this receiver had not seen.
+    fn drop(&mut self) {
+        let mut state = self.shared.state.lock().unwrap();
+
+        state.receivers.remove(&self.id);
+        state.prune();
+    }
+}
+
+/// Future returned by [`Receiver::recv`].
+pub struct RecvFuture<'a, T> {
+    receiver: &'a mut Receiver<T>,
+}
+
+impl<T: Clone> Future for RecvFuture<'_, T> {
+    type Output = Result<T, RecvError>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.receiver.poll_recv(cx)
+    }
+}
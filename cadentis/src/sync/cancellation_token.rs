@@ -0,0 +1,232 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex as Mutex_std, Weak};
+use std::task::{Context, Poll, Waker};
+
+/// A token signaling cancellation to a tree of tasks.
+///
+/// Tasks hold a clone of the token and stop their work once
+/// [`cancel`](Self::cancel) is called on any clone, either by polling
+/// [`is_cancelled`](Self::is_cancelled) or by awaiting
+/// [`cancelled`](Self::cancelled), typically in a `select!` branch.
+///
+/// [`child_token`](Self::child_token) derives a token that is cancelled
+/// along with its parent, but whose own cancellation does not reach the
+/// parent. This lets a subtree of tasks be stopped on its own while a
+/// global shutdown still stops everything.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// let token = CancellationToken::new();
+/// let child = token.child_token();
+///
+/// task::spawn(async move {
+///     select! {
+///         child.cancelled() => |_| println!("stopping"),
+///         serve() => |_| {},
+///     }
+/// });
+///
+/// token.cancel();
+/// ```
+#[derive(Clone)]
+pub struct CancellationToken {
+    node: Arc<Node>,
+}
+
+/// Shared state of a token and all of its clones.
+struct Node {
+    /// Whether the token was cancelled, readable without the lock.
+    cancelled: AtomicBool,
+
+    /// Waiters and children, cleared once the token is cancelled.
+    state: Mutex_std<State>,
+
+    /// Node this one was derived from, if any.
+    ///
+    /// Held strongly, so that a token dropped in the middle of the tree
+    /// still links its parent to the children derived from it.
+    _parent: Option<Arc<Node>>,
+}
+
+/// Internal state of a [`Node`].
+struct State {
+    /// Wakers of the pending [`Cancelled`] futures, by identifier.
+    waiters: HashMap<u64, Waker>,
+
+    /// Identifier given to the next waiter.
+    next_id: u64,
+
+    /// Tokens derived with [`CancellationToken::child_token`].
+    ///
+    /// Held weakly so that dropped children do not keep their state
+    /// alive; dead entries are pruned when a new child is added. A child
+    /// that is still alive keeps this node alive in turn.
+    children: Vec<Weak<Node>>,
+}
+
+impl CancellationToken {
+    /// Creates a new token that is not cancelled.
+    pub fn new() -> Self {
+        Self {
+            node: Arc::new(Node::new(None)),
+        }
+    }
+
+    /// Returns a token cancelled whenever this one is.
+    ///
+    /// Cancelling the child does not cancel this token. If this token is
+    /// already cancelled, the child is returned cancelled.
+    pub fn child_token(&self) -> CancellationToken {
+        let child = Arc::new(Node::new(Some(self.node.clone())));
+        let mut state = self.node.state.lock().unwrap();
+
+        if self.is_cancelled() {
+            drop(state);
+            child.cancel();
+        } else {
+            state.children.retain(|child| child.strong_count() > 0);
+            state.children.push(Arc::downgrade(&child));
+        }
+
+        CancellationToken { node: child }
+    }
+
+    /// Cancels this token and every token derived from it.
+    ///
+    /// All tasks awaiting [`cancelled`](Self::cancelled) are woken.
+    /// Cancelling an already cancelled token does nothing.
+    pub fn cancel(&self) {
+        self.node.cancel();
+    }
+
+    /// Returns `true` if this token, or one of its ancestors, was
+    /// cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        self.node.cancelled.load(Ordering::Acquire)
+    }
+
+    /// Returns a future that completes once the token is cancelled.
+    ///
+    /// The future completes immediately if the token is already
+    /// cancelled.
+    ///
+    /// # Example
+    /// ```rust,ignore
+    /// token.cancelled().await;
+    /// ```
+    pub fn cancelled(&self) -> Cancelled<'_> {
+        Cancelled {
+            token: self,
+            id: None,
+        }
+    }
+}
+
+impl Default for CancellationToken {
+    /// Returns a new token that is not cancelled.
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for CancellationToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CancellationToken")
+            .field("cancelled", &self.is_cancelled())
+            .finish()
+    }
+}
+
+impl Node {
+    fn new(parent: Option<Arc<Node>>) -> Self {
+        Self {
+            cancelled: AtomicBool::new(false),
+            state: Mutex_std::new(State {
+                waiters: HashMap::new(),
+                next_id: 0,
+                children: Vec::new(),
+            }),
+            _parent: parent,
+        }
+    }
+
+    /// Marks the node cancelled, wakes its waiters and cancels its children.
+    fn cancel(&self) {
+        let (waiters, children) = {
+            let mut state = self.state.lock().unwrap();
+
+            if self.cancelled.swap(true, Ordering::AcqRel) {
+                return;
+            }
+
+            (
+                std::mem::take(&mut state.waiters),
+                std::mem::take(&mut state.children),
+            )
+        };
+
+        for waker in waiters.into_values() {
+            waker.wake();
+        }
+
+        for child in children.iter().filter_map(Weak::upgrade) {
+            child.cancel();
+        }
+    }
+}
+
+/// Future returned by [`CancellationToken::cancelled`].
+///
+/// Dropping the future before completion unregisters its waker.
+pub struct Cancelled<'a> {
+    token: &'a CancellationToken,
+
+    /// Identifier of the registered waker, if any.
+    id: Option<u64>,
+}
+
+impl Future for Cancelled<'_> {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+
+        if this.token.is_cancelled() {
+            this.id = None;
+            return Poll::Ready(());
+        }
+
+        let mut state = this.token.node.state.lock().unwrap();
+
+        // Cancellation takes the lock, so checking again under it cannot
+        // miss a concurrent `cancel`.
+        if this.token.is_cancelled() {
+            this.id = None;
+            return Poll::Ready(());
+        }
+
+        let id = *this.id.get_or_insert_with(|| {
+            let id = state.next_id;
+            state.next_id += 1;
+            id
+        });
+
+        state.waiters.insert(id, cx.waker().clone());
+
+        Poll::Pending
+    }
+}
+
+impl Drop for Cancelled<'_> {
+    /// Unregisters the waker if the future is dropped while waiting.
+    fn drop(&mut self) {
+        if let Some(id) = self.id.take() {
+            self.token.node.state.lock().unwrap().waiters.remove(&id);
+        }
+    }
+}
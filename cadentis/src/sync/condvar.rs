@@ -0,0 +1,228 @@
+use super::MutexGuard;
+
+use std::collections::VecDeque;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex as Mutex_std};
+use std::task::{Context, Poll, Waker};
+
+/// The waiter has not been notified yet.
+const WAITING: usize = 0;
+
+/// The waiter was selected by [`Condvar::notify_one`].
+const NOTIFIED_ONE: usize = 1;
+
+/// The waiter was woken by [`Condvar::notify_all`].
+const NOTIFIED_ALL: usize = 2;
+
+/// An asynchronous condition variable.
+///
+/// Tasks holding the guard of a [`Mutex`](super::Mutex) wait on a
+/// `Condvar` for the protected state to change: [`wait`](Self::wait)
+/// releases the lock while the task is suspended, and acquires it again
+/// once another task calls [`notify_one`](Self::notify_one) or
+/// [`notify_all`](Self::notify_all).
+///
+/// A task is queued before the lock is released, so that a notification
+/// sent right after cannot be lost. Waiters are notified in FIFO order.
+/// Like any condition variable, a woken task must check its condition
+/// again, as the state may have changed before it acquired the lock:
+/// [`wait_while`](Self::wait_while) does so.
+pub struct Condvar {
+    /// Tasks waiting for a notification, in arrival order.
+    ///
+    /// Protected by a standard blocking `Mutex` because critical
+    /// sections are short and never span an await point.
+    waiters: Mutex_std<VecDeque<Waiter>>,
+}
+
+/// A task queued on a [`Condvar`].
+struct Waiter {
+    /// Waker of the waiting task, once it was polled.
+    waker: Option<Waker>,
+
+    /// Notification state shared with the [`Waiting`] future.
+    notified: Arc<AtomicUsize>,
+}
+
+impl Condvar {
+    /// Creates a new condition variable with no waiters.
+    pub fn new() -> Self {
+        Self {
+            waiters: Mutex_std::new(VecDeque::new()),
+        }
+    }
+
+    /// Releases the lock held by `guard` until the task is notified, then
+    /// acquires it again.
+    ///
+    /// The task may be woken while the state it waits for does not hold,
+    /// either because another task changed it first, or because the
+    /// notification was meant for another condition: the condition must
+    /// be checked again, in a loop.
+    ///
+    /// # Example
+    /// ```rust, ignore
+    /// let mut queue = state.lock().await;
+    /// while queue.is_empty() {
+    ///     queue = condvar.wait(queue).await;
+    /// }
+    /// ```
+    pub async fn wait<'a, T>(&self, guard: MutexGuard<'a, T>) -> MutexGuard<'a, T> {
+        let mutex = MutexGuard::mutex(&guard);
+        let waiting = self.enqueue();
+
+        drop(guard);
+        waiting.await;
+
+        mutex.lock().await
+    }
+
+    /// Waits until `condition` returns `false` for the state protected by
+    /// `guard`, and returns the guard then.
+    ///
+    /// The condition is checked first, with the lock held, so that the
+    /// task only waits when it does not already hold. The lock is then
+    /// released while waiting, and the condition checked again after each
+    /// notification.
+    ///
+    /// # Example
+    /// ```rust, ignore
+    /// let mut queue = condvar
+    ///     .wait_while(state.lock().await, |queue| queue.is_empty())
+    ///     .await;
+    /// let job = queue.pop_front().unwrap();
+    /// ```
+    pub async fn wait_while<'a, T, F>(
+        &self,
+        mut guard: MutexGuard<'a, T>,
+        mut condition: F,
+    ) -> MutexGuard<'a, T>
+    where
+        F: FnMut(&T) -> bool,
+    {
+        while condition(&guard) {
+            guard = self.wait(guard).await;
+        }
+
+        guard
+    }
+
+    /// Wakes the task that has been waiting the longest.
+    ///
+    /// Unlike [`Notify`](super::Notify), nothing is stored if no task is
+    /// waiting: the state protected by the mutex records what happened.
+    pub fn notify_one(&self) {
+        let mut waiters = self.waiters.lock().unwrap();
+
+        if let Some(waiter) = waiters.pop_front() {
+            waiter.notified.store(NOTIFIED_ONE, Ordering::Release);
+
+            if let Some(waker) = waiter.waker {
+                waker.wake();
+            }
+        }
+    }
+
+    /// Wakes every task currently waiting.
+    pub fn notify_all(&self) {
+        let mut waiters = self.waiters.lock().unwrap();
+
+        for waiter in waiters.drain(..) {
+            waiter.notified.store(NOTIFIED_ALL, Ordering::Release);
+
+            if let Some(waker) = waiter.waker {
+                waker.wake();
+            }
+        }
+    }
+
+    /// Queues a waiter and returns the future completing once it is
+    /// notified.
+    fn enqueue(&self) -> Waiting<'_> {
+        let notified = Arc::new(AtomicUsize::new(WAITING));
+
+        self.waiters.lock().unwrap().push_back(Waiter {
+            waker: None,
+            notified: notified.clone(),
+        });
+
+        Waiting {
+            condvar: self,
+            notified: Some(notified),
+        }
+    }
+}
+
+impl Default for Condvar {
+    /// Returns a new [`Condvar`] with no waiters.
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Future completing once a queued waiter is notified.
+///
+/// Dropping the future before completion removes the task from the
+/// queue. A `notify_one` notification received but never observed is
+/// forwarded to the next waiter, so it is never lost.
+struct Waiting<'a> {
+    condvar: &'a Condvar,
+
+    /// Notification state shared with the queued waiter, until observed.
+    notified: Option<Arc<AtomicUsize>>,
+}
+
+impl Future for Waiting<'_> {
+    type Output = ();
+
+    /// Polls the future to check for a notification.
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        let Some(notified) = &this.notified else {
+            return Poll::Ready(());
+        };
+
+        let mut waiters = this.condvar.waiters.lock().unwrap();
+
+        if notified.load(Ordering::Acquire) != WAITING {
+            this.notified = None;
+            return Poll::Ready(());
+        }
+
+        // Still queued: refresh the waker in place to keep our position.
+        if let Some(waiter) = waiters
+            .iter_mut()
+            .find(|w| Arc::ptr_eq(&w.notified, notified))
+        {
+            waiter.waker = Some(cx.waker().clone());
+        }
+
+        Poll::Pending
+    }
+}
+
+impl Drop for Waiting<'_> {
+    /// Leaves the waiters queue if the future is dropped while waiting.
+    fn drop(&mut self) {
+        let Some(notified) = self.notified.take() else {
+            return;
+        };
+
+        // Notifications are delivered under the lock, so checking the
+        // state while holding it cannot race with `notify_one`.
+        let mut waiters = self.condvar.waiters.lock().unwrap();
+
+        match notified.load(Ordering::Acquire) {
+            WAITING => {
+                waiters.retain(|w| !Arc::ptr_eq(&w.notified, &notified));
+            }
+            NOTIFIED_ONE => {
+                drop(waiters);
+                self.condvar.notify_one();
+            }
+            _ => {}
+        }
+    }
+}
@@ -6,6 +6,16 @@
 //!
 //! The current primitives include:
 //! - [`Mutex`] — an asynchronous mutual exclusion primitive.
+//! - [`RwLock`] — an asynchronous reader-writer lock, preferring either
+//!   readers or writers under contention.
+//! - [`Semaphore`] — a counting semaphore granting permits in FIFO order.
+//! - [`Condvar`] — a condition variable waiting on [`Mutex`]-protected
+//!   state.
+//! - [`Notify`] — a signaling primitive waking waiters in FIFO order.
+//! - [`mpsc`] — multi-producer, single-consumer channels.
+//! - [`broadcast`] — multi-producer, multi-consumer broadcast channels.
+//! - [`AtomicWaker`] — a lock-free slot for the waker of a single task.
+//! - [`CancellationToken`] — a tree of tokens signaling cancellation.
 //!
 //! ## Design notes
 //!
@@ -17,6 +27,23 @@
 //! Most runtime users will use these primitives indirectly when sharing
 //! state between tasks; advanced users can use them directly for custom data structures.
 
+mod atomic_waker;
+mod cancellation_token;
+mod condvar;
 mod mutex;
+mod notify;
+mod rwlock;
+mod semaphore;
 
-pub use mutex::Mutex;
+pub mod broadcast;
+pub mod mpsc;
+
+pub use atomic_waker::AtomicWaker;
+pub use cancellation_token::{CancellationToken, Cancelled};
+pub use condvar::Condvar;
+pub use mutex::{MappedMutexGuard, Mutex, MutexGuard};
+pub use notify::{Notified, Notify};
+pub use rwlock::{
+    ReadFuture, RwLock, RwLockPolicy, RwLockReadGuard, RwLockWriteGuard, WriteFuture,
+};
+pub use semaphore::{Acquire, OwnedSemaphorePermit, Semaphore, SemaphorePermit};
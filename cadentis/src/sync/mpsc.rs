@@ -0,0 +1,520 @@
+//! Multi-producer, single-consumer channels.
+//!
+//! A channel is created with [`channel`] (bounded) or [`unbounded_channel`]
+//! and returns a ([`Sender`], [`Receiver`]) pair. Senders can be cloned
+//! and moved across tasks; the single receiver consumes values in the
+//! order they were sent.
+//!
+//! The [`Receiver`] implements [`Stream`], so it composes with the
+//! combinators of [`StreamExt`](crate::stream::StreamExt).
+//!
+//! [`Sender::send_timeout`] and [`Receiver::recv_timeout`] bound the
+//! wait of a single operation; a timed-out send hands its value back.
+
+use crate::stream::Stream;
+use crate::time::sleep;
+
+use std::collections::VecDeque;
+use std::fmt;
+use std::future::{Future, poll_fn};
+use std::pin::{Pin, pin};
+use std::sync::{Arc, Mutex as Mutex_std};
+use std::task::{Context, Poll, Waker};
+use std::time::Duration;
+
+/// Creates a bounded channel holding at most `capacity` buffered values.
+///
+/// Sending on a full channel suspends the sender until the receiver
+/// makes room.
+///
+/// # Panics
+///
+/// Panics if `capacity == 0`.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// let (tx, mut rx) = mpsc::channel(16);
+///
+/// tx.send(1).await.unwrap();
+/// assert_eq!(rx.recv().await, Some(1));
+/// ```
+pub fn channel<T>(capacity: usize) -> (Sender<T>, Receiver<T>) {
+    assert!(capacity > 0, "channel capacity must be > 0");
+
+    new_channel(Some(capacity))
+}
+
+/// Creates an unbounded channel.
+///
+/// Sending never suspends; values are buffered until received.
+pub fn unbounded_channel<T>() -> (Sender<T>, Receiver<T>) {
+    new_channel(None)
+}
+
+/// Builds the shared state and both channel endpoints.
+fn new_channel<T>(capacity: Option<usize>) -> (Sender<T>, Receiver<T>) {
+    let shared = Arc::new(Shared {
+        state: Mutex_std::new(State {
+            queue: VecDeque::new(),
+            capacity,
+            senders: 1,
+            receiver_alive: true,
+            recv_waker: None,
+            send_waiters: VecDeque::new(),
+            next_id: 0,
+        }),
+    });
+
+    (
+        Sender {
+            shared: shared.clone(),
+        },
+        Receiver { shared },
+    )
+}
+
+/// State shared between all endpoints of a channel.
+struct Shared<T> {
+    /// Channel state protected by a standard blocking mutex.
+    ///
+    /// Critical sections are short and never span an await point.
+    state: Mutex_std<State<T>>,
+}
+
+/// Mutable channel state.
+struct State<T> {
+    /// Buffered values, in sending order.
+    queue: VecDeque<T>,
+
+    /// Maximum number of buffered values (`None` for unbounded).
+    capacity: Option<usize>,
+
+    /// Number of live senders.
+    senders: usize,
+
+    /// Whether the receiver is still alive.
+    receiver_alive: bool,
+
+    /// Waker of the receiver waiting for a value.
+    recv_waker: Option<Waker>,
+
+    /// Senders waiting for room, in arrival order, with the identifier
+    /// of their [`SendFuture`].
+    send_waiters: VecDeque<(u64, Waker)>,
+
+    /// Identifier handed to the next waiting sender.
+    next_id: u64,
+}
+
+impl<T> State<T> {
+    /// Returns `true` if no more values can be buffered.
+    fn is_full(&self) -> bool {
+        self.capacity.is_some_and(|cap| self.queue.len() >= cap)
+    }
+
+    /// Removes the waiting sender `id` from the queue.
+    ///
+    /// Returns `false` if it was no longer queued, that is, if it was
+    /// woken to retry.
+    fn leave_send_queue(&mut self, id: u64) -> bool {
+        let len = self.send_waiters.len();
+        self.send_waiters.retain(|(waiter, _)| *waiter != id);
+
+        self.send_waiters.len() != len
+    }
+}
+
+/// Error returned when sending on a channel whose receiver was dropped.
+///
+/// The unsent value is handed back to the caller.
+#[derive(PartialEq, Eq)]
+pub struct SendError<T>(pub T);
+
+impl<T> fmt::Debug for SendError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("SendError(..)")
+    }
+}
+
+impl<T> fmt::Display for SendError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("channel closed")
+    }
+}
+
+impl<T> std::error::Error for SendError<T> {}
+
+/// Error returned by [`Sender::send_timeout`].
+///
+/// Both variants hand the unsent value back to the caller.
+#[derive(PartialEq, Eq)]
+pub enum SendTimeoutError<T> {
+    /// The channel stayed full for the whole duration.
+    Timeout(T),
+
+    /// The receiver was dropped.
+    Closed(T),
+}
+
+impl<T> SendTimeoutError<T> {
+    /// Returns the unsent value.
+    pub fn into_inner(self) -> T {
+        match self {
+            SendTimeoutError::Timeout(value) | SendTimeoutError::Closed(value) => value,
+        }
+    }
+}
+
+impl<T> fmt::Debug for SendTimeoutError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SendTimeoutError::Timeout(_) => f.write_str("Timeout(..)"),
+            SendTimeoutError::Closed(_) => f.write_str("Closed(..)"),
+        }
+    }
+}
+
+impl<T> fmt::Display for SendTimeoutError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SendTimeoutError::Timeout(_) => f.write_str("timed out waiting on send"),
+            SendTimeoutError::Closed(_) => f.write_str("channel closed"),
+        }
+    }
+}
+
+impl<T> std::error::Error for SendTimeoutError<T> {}
+
+/// Error returned by [`Receiver::recv_timeout`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecvTimeoutError {
+    /// No value arrived for the whole duration.
+    Timeout,
+
+    /// Every sender was dropped and the buffer is empty.
+    Closed,
+}
+
+impl fmt::Display for RecvTimeoutError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RecvTimeoutError::Timeout => f.write_str("timed out waiting on receive"),
+            RecvTimeoutError::Closed => f.write_str("channel closed"),
+        }
+    }
+}
+
+impl std::error::Error for RecvTimeoutError {}
+
+/// The sending half of a channel.
+///
+/// Senders can be cloned; the channel is closed once every sender
+/// has been dropped.
+pub struct Sender<T> {
+    shared: Arc<Shared<T>>,
+}
+
+impl<T> Sender<T> {
+    /// Sends a value, waiting for room if the channel is full.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SendError`] with the value if the receiver was dropped.
+    pub fn send(&self, value: T) -> SendFuture<'_, T> {
+        SendFuture {
+            sender: self,
+            value: Some(value),
+            id: None,
+        }
+    }
+
+    /// Sends a value, waiting at most `duration` for room in the channel.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SendTimeoutError::Timeout`] if the channel is still full
+    /// once `duration` has elapsed, or [`SendTimeoutError::Closed`] if the
+    /// receiver was dropped. Either way, the value is handed back.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// match tx.send_timeout(job, Duration::from_millis(50)).await {
+    ///     Ok(()) => {}
+    ///     Err(SendTimeoutError::Timeout(job)) => shed(job),
+    ///     Err(SendTimeoutError::Closed(_)) => return,
+    /// }
+    /// ```
+    pub async fn send_timeout(
+        &self,
+        value: T,
+        duration: Duration,
+    ) -> Result<(), SendTimeoutError<T>> {
+        let mut send = self.send(value);
+        let mut timer = pin!(sleep(duration));
+
+        poll_fn(|cx| {
+            match Pin::new(&mut send).poll(cx) {
+                Poll::Ready(Ok(())) => return Poll::Ready(Ok(())),
+                Poll::Ready(Err(SendError(value))) => {
+                    return Poll::Ready(Err(SendTimeoutError::Closed(value)));
+                }
+                Poll::Pending => {}
+            }
+
+            // Dropping `send` afterwards leaves the waiters queue.
+            match timer.as_mut().poll(cx) {
+                Poll::Ready(()) => {
+                    let value = send.value.take().unwrap();
+                    Poll::Ready(Err(SendTimeoutError::Timeout(value)))
+                }
+                Poll::Pending => Poll::Pending,
+            }
+        })
+        .await
+    }
+
+    /// Returns `true` if the receiver has been dropped.
+    pub fn is_closed(&self) -> bool {
+        !self.shared.state.lock().unwrap().receiver_alive
+    }
+}
+
+impl<T> Clone for Sender<T> {
+    /// Creates another sender for the same channel.
+    fn clone(&self) -> Self {
+        self.shared.state.lock().unwrap().senders += 1;
+
+        Self {
+            shared: self.shared.clone(),
+        }
+    }
+}
+
+impl<T> Drop for Sender<T> {
+    /// Releases the sender, waking the receiver if it was the last one.
+    fn drop(&mut self) {
+        let mut state = self.shared.state.lock().unwrap();
+        state.senders -= 1;
+
+        if state.senders == 0
+            && let Some(waker) = state.recv_waker.take()
+        {
+            waker.wake();
+        }
+    }
+}
+
+/// Future returned by [`Sender::send`].
+///
+/// Dropping the future while it waits for room removes the task from
+/// the queue. A wakeup received but never observed is forwarded to the
+/// next waiting sender, so it is never lost.
+pub struct SendFuture<'a, T> {
+    sender: &'a Sender<T>,
+    value: Option<T>,
+
+    /// Queue identifier, set while the sender is waiting.
+    id: Option<u64>,
+}
+
+impl<T> Unpin for SendFuture<'_, T> {}
+
+impl<T> Future for SendFuture<'_, T> {
+    type Output = Result<(), SendError<T>>;
+
+    /// Attempts to push the value into the channel.
+    ///
+    /// If the channel is full, the task is queued behind the other
+    /// waiting senders and woken once a value has been received. A
+    /// queued sender keeps its position across polls.
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        let mut state = this.sender.shared.state.lock().unwrap();
+
+        let value = this
+            .value
+            .take()
+            .expect("SendFuture polled after completion");
+
+        if !state.receiver_alive {
+            if let Some(id) = this.id.take() {
+                state.leave_send_queue(id);
+            }
+
+            return Poll::Ready(Err(SendError(value)));
+        }
+
+        if state.is_full() {
+            this.value = Some(value);
+
+            // Refresh the waker in place, or queue again at the back if
+            // woken only to find the channel full again.
+            let queued = this.id.and_then(|id| {
+                state
+                    .send_waiters
+                    .iter_mut()
+                    .find(|(waiter, _)| *waiter == id)
+            });
+
+            match queued {
+                Some((_, waker)) => *waker = cx.waker().clone(),
+                None => {
+                    let id = state.next_id;
+                    state.next_id += 1;
+                    state.send_waiters.push_back((id, cx.waker().clone()));
+                    this.id = Some(id);
+                }
+            }
+
+            return Poll::Pending;
+        }
+
+        if let Some(id) = this.id.take() {
+            state.leave_send_queue(id);
+        }
+
+        state.queue.push_back(value);
+
+        if let Some(waker) = state.recv_waker.take() {
+            waker.wake();
+        }
+
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<T> Drop for SendFuture<'_, T> {
+    /// Leaves the waiters queue if the future is dropped while waiting.
+    ///
+    /// If room was made for this sender in the meantime, the wakeup it
+    /// consumed is forwarded to the next waiting sender.
+    fn drop(&mut self) {
+        let Some(id) = self.id.take() else {
+            return;
+        };
+
+        let mut state = self.sender.shared.state.lock().unwrap();
+
+        if !state.leave_send_queue(id)
+            && !state.is_full()
+            && let Some((_, next)) = state.send_waiters.pop_front()
+        {
+            next.wake();
+        }
+    }
+}
+
+/// The receiving half of a channel.
+///
+/// Values are received in the order they were sent. Once every sender
+/// has been dropped, the remaining buffered values are still delivered
+/// before the channel reports its end with `None`.
+pub struct Receiver<T> {
+    shared: Arc<Shared<T>>,
+}
+
+impl<T> Receiver<T> {
+    /// Receives the next value, waiting until one is available.
+    ///
+    /// Returns `None` once every sender has been dropped and the buffer
+    /// is empty.
+    pub fn recv(&mut self) -> RecvFuture<'_, T> {
+        RecvFuture { receiver: self }
+    }
+
+    /// Receives the next value, waiting at most `duration` for one.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RecvTimeoutError::Timeout`] if no value arrived within
+    /// `duration`, or [`RecvTimeoutError::Closed`] once every sender has
+    /// been dropped and the buffer is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// match rx.recv_timeout(Duration::from_secs(1)).await {
+    ///     Ok(event) => handle(event),
+    ///     Err(RecvTimeoutError::Timeout) => heartbeat(),
+    ///     Err(RecvTimeoutError::Closed) => break,
+    /// }
+    /// ```
+    pub async fn recv_timeout(&mut self, duration: Duration) -> Result<T, RecvTimeoutError> {
+        let mut timer = pin!(sleep(duration));
+
+        poll_fn(|cx| match self.poll_recv(cx) {
+            Poll::Ready(Some(value)) => Poll::Ready(Ok(value)),
+            Poll::Ready(None) => Poll::Ready(Err(RecvTimeoutError::Closed)),
+            Poll::Pending => match timer.as_mut().poll(cx) {
+                Poll::Ready(()) => Poll::Ready(Err(RecvTimeoutError::Timeout)),
+                Poll::Pending => Poll::Pending,
+            },
+        })
+        .await
+    }
+
+    /// Polls for the next value.
+    ///
+    /// Registers the current task to be woken when a value is sent or
+    /// the last sender is dropped.
+    fn poll_recv(&self, cx: &mut Context<'_>) -> Poll<Option<T>> {
+        let mut state = self.shared.state.lock().unwrap();
+
+        if let Some(value) = state.queue.pop_front() {
+            // Queued senders remove themselves when dropped, so the first
+            // one is always waiting.
+            if let Some((_, waker)) = state.send_waiters.pop_front() {
+                waker.wake();
+            }
+
+            return Poll::Ready(Some(value));
+        }
+
+        // Only checked once the buffer is empty, so that values sent before
+        // the last sender was dropped are never lost.
+        if state.senders == 0 {
+            return Poll::Ready(None);
+        }
+
+        state.recv_waker = Some(cx.waker().clone());
+
+        Poll::Pending
+    }
+}
+
+impl<T> Drop for Receiver<T> {
+    /// Closes the channel, waking every sender waiting for room.
+    fn drop(&mut self) {
+        let mut state = self.shared.state.lock().unwrap();
+        state.receiver_alive = false;
+
+        for (_, waker) in state.send_waiters.drain(..) {
+            waker.wake();
+        }
+    }
+}
+
+impl<T> Stream for Receiver<T> {
+    type Item = T;
+
+    /// Yields each received value, then `None` once the channel is
+    /// closed and drained.
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.poll_recv(cx)
+    }
+}
+
+/// Future returned by [`Receiver::recv`].
+pub struct RecvFuture<'a, T> {
+    receiver: &'a mut Receiver<T>,
+}
+
+impl<T> Future for RecvFuture<'_, T> {
+    type Output = Option<T>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.receiver.poll_recv(cx)
+    }
+}
@@ -1,5 +1,7 @@
 use std::cell::UnsafeCell;
 use std::future::Future;
+use std::marker::PhantomData;
+use std::mem;
 use std::ops::{Deref, DerefMut};
 use std::pin::Pin;
 use std::sync::Mutex as Mutex_std;
@@ -79,6 +81,20 @@ impl<T> Mutex<T> {
     }
 }
 
+/// Releases a mutex given its lock flag and waiters queue.
+///
+/// Shared by [`MutexGuard`] and [`MappedMutexGuard`], which no longer
+/// knows the type protected by the mutex.
+fn unlock(locked: &AtomicBool, waiters: &Mutex_std<Vec<Waker>>) {
+    // Release the lock.
+    locked.store(false, Ordering::Release);
+
+    // Wake the next waiting task.
+    if let Some(waker) = waiters.lock().unwrap().pop() {
+        waker.wake();
+    }
+}
+
 /// Future returned by `Mutex::lock`.
 ///
 /// The future resolves to a `MutexGuard` once the lock is acquired.
@@ -116,16 +132,55 @@ pub struct MutexGuard<'a, T> {
     mutex: &'a Mutex<T>,
 }
 
+impl<'a, T> MutexGuard<'a, T> {
+    /// Returns the mutex held by the guard.
+    ///
+    /// Used by [`Condvar`](super::Condvar) to acquire the lock again
+    /// after releasing the guard.
+    pub(crate) fn mutex(this: &Self) -> &'a Mutex<T> {
+        this.mutex
+    }
+
+    /// Projects the guard to a part of the protected data.
+    ///
+    /// The returned guard only gives access to the value returned by `f`,
+    /// typically a field, but still holds the whole mutex: it is released
+    /// when the mapped guard is dropped. If `f` panics, the mutex is
+    /// released.
+    ///
+    /// This is an associated function, called as `MutexGuard::map(guard, f)`,
+    /// so that it does not shadow a `map` method of the protected data.
+    ///
+    /// # Example
+    /// ```rust, ignore
+    /// let guard = state.lock().await;
+    /// let mut peers = MutexGuard::map(guard, |state| &mut state.peers);
+    /// peers.push(peer);
+    /// ```
+    pub fn map<U: ?Sized, F>(this: Self, f: F) -> MappedMutexGuard<'a, U>
+    where
+        F: FnOnce(&mut T) -> &mut U,
+    {
+        // SAFETY: the guard grants exclusive access to the data for `'a`.
+        let data = f(unsafe { &mut *this.mutex.data.get() }) as *mut U;
+        let mutex = this.mutex;
+
+        // The mapped guard takes over releasing the lock.
+        mem::forget(this);
+
+        MappedMutexGuard {
+            locked: &mutex.locked,
+            waiters: &mutex.waiters,
+            data,
+            marker: PhantomData,
+        }
+    }
+}
+
 impl<'a, T> Drop for MutexGuard<'a, T> {
     /// Unlocks the mutex and wakes one waiting task (if any).
     fn drop(&mut self) {
-        // Release the lock.
-        self.mutex.locked.store(false, Ordering::Release);
-
-        // Wake the next waiting task.
-        if let Some(waker) = self.mutex.waiters.lock().unwrap().pop() {
-            waker.wake();
-        }
+        unlock(&self.mutex.locked, &self.mutex.waiters);
     }
 }
 
@@ -144,3 +199,73 @@ impl<'a, T> DerefMut for MutexGuard<'a, T> {
         unsafe { &mut *self.mutex.data.get() }
     }
 }
+
+/// Guard to a part of the data protected by a [`Mutex`].
+///
+/// Created by [`MutexGuard::map`]. Releases the whole mutex when dropped.
+pub struct MappedMutexGuard<'a, U: ?Sized> {
+    /// Lock flag of the mutex.
+    locked: &'a AtomicBool,
+
+    /// Waiters queue of the mutex.
+    waiters: &'a Mutex_std<Vec<Waker>>,
+
+    /// The projected data, valid while the lock is held.
+    data: *mut U,
+
+    /// The guard borrows the projected data mutably for `'a`.
+    marker: PhantomData<&'a mut U>,
+}
+
+// Safety: the guard owns exclusive access to `U` until dropped, like a
+// `&mut U`, and releasing the lock is thread-safe.
+unsafe impl<U: ?Sized + Send> Send for MappedMutexGuard<'_, U> {}
+// Safety: a shared guard only hands out `&U`.
+unsafe impl<U: ?Sized + Sync> Sync for MappedMutexGuard<'_, U> {}
+
+impl<'a, U: ?Sized> MappedMutexGuard<'a, U> {
+    /// Projects the guard further, to a part of the projected data.
+    ///
+    /// See [`MutexGuard::map`].
+    pub fn map<V: ?Sized, F>(this: Self, f: F) -> MappedMutexGuard<'a, V>
+    where
+        F: FnOnce(&mut U) -> &mut V,
+    {
+        // SAFETY: the guard grants exclusive access to the data for `'a`.
+        let data = f(unsafe { &mut *this.data }) as *mut V;
+        let (locked, waiters) = (this.locked, this.waiters);
+
+        // The new guard takes over releasing the lock.
+        mem::forget(this);
+
+        MappedMutexGuard {
+            locked,
+            waiters,
+            data,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<U: ?Sized> Drop for MappedMutexGuard<'_, U> {
+    /// Unlocks the mutex and wakes one waiting task (if any).
+    fn drop(&mut self) {
+        unlock(self.locked, self.waiters);
+    }
+}
+
+impl<U: ?Sized> Deref for MappedMutexGuard<'_, U> {
+    type Target = U;
+
+    /// Provides immutable access to the projected data.
+    fn deref(&self) -> &Self::Target {
+        unsafe { &*self.data }
+    }
+}
+
+impl<U: ?Sized> DerefMut for MappedMutexGuard<'_, U> {
+    /// Provides mutable access to the projected data.
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        unsafe { &mut *self.data }
+    }
+}
@@ -0,0 +1,201 @@
+use std::collections::VecDeque;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex as Mutex_std};
+use std::task::{Context, Poll, Waker};
+
+/// The waiter has not been notified yet.
+const WAITING: usize = 0;
+
+/// The waiter was selected by [`Notify::notify_one`].
+const NOTIFIED_ONE: usize = 1;
+
+/// The waiter was woken by [`Notify::notify_waiters`].
+const NOTIFIED_ALL: usize = 2;
+
+/// Notifies one or all waiting tasks.
+///
+/// `Notify` is a minimal signaling primitive: tasks wait with
+/// [`notified`](Self::notified) and are woken by
+/// [`notify_one`](Self::notify_one) or [`notify_waiters`](Self::notify_waiters).
+///
+/// Waiters are notified in **FIFO order**: `notify_one` always wakes the
+/// task that has been waiting the longest.
+///
+/// If `notify_one` is called while no task is waiting, a single permit
+/// is stored and the next call to `notified().await` completes
+/// immediately.
+pub struct Notify {
+    /// Stored permit and waiters queue.
+    ///
+    /// Protected by a standard blocking `Mutex` because critical
+    /// sections are short and never span an await point.
+    state: Mutex_std<State>,
+}
+
+/// Internal state of a [`Notify`].
+struct State {
+    /// Whether a `notify_one` call is pending with no waiter to consume it.
+    permit: bool,
+
+    /// Tasks waiting for a notification, in arrival order.
+    waiters: VecDeque<Waiter>,
+}
+
+/// A task queued on a [`Notify`].
+struct Waiter {
+    /// Waker of the waiting task.
+    waker: Waker,
+
+    /// Notification state shared with the [`Notified`] future.
+    notified: Arc<AtomicUsize>,
+}
+
+impl Notify {
+    /// Creates a new `Notify` with no stored permit.
+    pub fn new() -> Self {
+        Self {
+            state: Mutex_std::new(State {
+                permit: false,
+                waiters: VecDeque::new(),
+            }),
+        }
+    }
+
+    /// Returns a future that completes once the task is notified.
+    ///
+    /// # Example
+    /// ```rust, ignore
+    /// notify.notified().await;
+    /// ```
+    pub fn notified(&self) -> Notified<'_> {
+        Notified {
+            notify: self,
+            notified: None,
+        }
+    }
+
+    /// Wakes the task that has been waiting the longest.
+    ///
+    /// If no task is waiting, a permit is stored so that the next call
+    /// to [`notified`](Self::notified) completes immediately.
+    pub fn notify_one(&self) {
+        let mut state = self.state.lock().unwrap();
+
+        match state.waiters.pop_front() {
+            Some(waiter) => {
+                waiter.notified.store(NOTIFIED_ONE, Ordering::Release);
+                waiter.waker.wake();
+            }
+            None => {
+                state.permit = true;
+            }
+        }
+    }
+
+    /// Wakes every task currently waiting.
+    ///
+    /// Unlike [`notify_one`](Self::notify_one), no permit is stored if
+    /// no task is waiting.
+    pub fn notify_waiters(&self) {
+        let mut state = self.state.lock().unwrap();
+
+        for waiter in state.waiters.drain(..) {
+            waiter.notified.store(NOTIFIED_ALL, Ordering::Release);
+            waiter.waker.wake();
+        }
+    }
+}
+
+impl Default for Notify {
+    /// Returns a new [`Notify`] with no stored permit.
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Future returned by [`Notify::notified`].
+///
+/// Dropping the future before completion removes the task from the
+/// queue. A `notify_one` notification received but never observed is
+/// forwarded to the next waiter, so it is never lost.
+pub struct Notified<'a> {
+    notify: &'a Notify,
+
+    /// Notification state shared with the queued waiter, if any.
+    notified: Option<Arc<AtomicUsize>>,
+}
+
+impl Future for Notified<'_> {
+    type Output = ();
+
+    /// Polls the future to check for a notification.
+    ///
+    /// On the first poll, a stored permit is consumed if present;
+    /// otherwise the task is queued behind the existing waiters.
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        let mut state = this.notify.state.lock().unwrap();
+
+        if let Some(notified) = &this.notified {
+            if notified.load(Ordering::Acquire) != WAITING {
+                this.notified = None;
+                return Poll::Ready(());
+            }
+
+            // Still queued: refresh the waker in place to keep our position.
+            if let Some(waiter) = state
+                .waiters
+                .iter_mut()
+                .find(|w| Arc::ptr_eq(&w.notified, notified))
+            {
+                waiter.waker = cx.waker().clone();
+            }
+
+            return Poll::Pending;
+        }
+
+        if state.permit {
+            state.permit = false;
+            return Poll::Ready(());
+        }
+
+        let notified = Arc::new(AtomicUsize::new(WAITING));
+
+        state.waiters.push_back(Waiter {
+            waker: cx.waker().clone(),
+            notified: notified.clone(),
+        });
+
+        this.notified = Some(notified);
+
+        Poll::Pending
+    }
+}
+
+impl Drop for Notified<'_> {
+    /// Leaves the waiters queue if the future is dropped while waiting.
+    fn drop(&mut self) {
+        let Some(notified) = self.notified.take() else {
+            return;
+        };
+
+        // Notifications are delivered under the lock, so checking the
+        // state while holding it cannot race with `notify_one`.
+        let mut state = self.notify.state.lock().unwrap();
+
+        match notified.load(Ordering::Acquire) {
+            WAITING => {
+                state
+                    .waiters
+                    .retain(|w| !Arc::ptr_eq(&w.notified, &notified));
+            }
+            NOTIFIED_ONE => {
+                drop(state);
+                self.notify.notify_one();
+            }
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,319 @@
+use std::cell::UnsafeCell;
+use std::collections::VecDeque;
+use std::future::Future;
+use std::ops::{Deref, DerefMut};
+use std::pin::Pin;
+use std::sync::Mutex as Mutex_std;
+use std::task::{Context, Poll, Waker};
+
+/// Which side of an [`RwLock`] wins when readers and writers contend.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RwLockPolicy {
+    /// New readers join the current read group even while a writer is
+    /// waiting. Maximizes read throughput, but a steady stream of
+    /// readers can starve writers.
+    #[default]
+    ReaderPreference,
+
+    /// New readers queue behind any waiting writer, which acquires the
+    /// lock as soon as the current read group is done.
+    WriterPreference,
+}
+
+/// An asynchronous reader-writer lock.
+///
+/// Any number of tasks may hold the lock for reading at once, while a
+/// writer holds it alone. Tasks that cannot acquire the lock are
+/// suspended and woken once it may be available.
+///
+/// How contention is arbitrated is chosen at construction with
+/// [`RwLockPolicy`].
+pub struct RwLock<T> {
+    /// Arbitration between readers and waiting writers.
+    policy: RwLockPolicy,
+
+    /// Holders and waiters of the lock.
+    ///
+    /// Protected by a standard blocking `Mutex` because critical
+    /// sections are short and never span an await point.
+    state: Mutex_std<State>,
+
+    /// The data protected by the lock.
+    data: UnsafeCell<T>,
+}
+
+// Safety: `RwLock<T>` can be sent across threads if `T` is Send.
+unsafe impl<T: Send> Send for RwLock<T> {}
+// Safety: readers on several threads share `&T`, so `T` must also be
+// Sync; access is otherwise synchronized by `state`.
+unsafe impl<T: Send + Sync> Sync for RwLock<T> {}
+
+/// Internal state of an [`RwLock`].
+struct State {
+    /// Number of tasks holding the lock for reading.
+    readers: usize,
+
+    /// Set while a task holds the lock for writing.
+    writer: bool,
+
+    /// Number of writers waiting for the lock, queued or just woken.
+    waiting_writers: usize,
+
+    /// Readers waiting for the lock, all woken at once.
+    read_waiters: Vec<Waker>,
+
+    /// Writers waiting for the lock, in arrival order.
+    write_waiters: VecDeque<(u64, Waker)>,
+
+    /// Identifier handed to the next waiting writer.
+    next_id: u64,
+}
+
+impl State {
+    /// Returns `true` if a new reader may acquire the lock.
+    fn admits_reader(&self, policy: RwLockPolicy) -> bool {
+        !self.writer && (policy == RwLockPolicy::ReaderPreference || self.waiting_writers == 0)
+    }
+
+    /// Returns `true` if a writer may acquire the lock.
+    fn admits_writer(&self) -> bool {
+        !self.writer && self.readers == 0
+    }
+
+    /// Wakes the waiters which may now acquire the lock.
+    fn wake(&mut self, policy: RwLockPolicy) {
+        if self.admits_writer()
+            && let Some((_, waker)) = self.write_waiters.pop_front()
+        {
+            waker.wake();
+        }
+
+        if self.admits_reader(policy) {
+            for waker in self.read_waiters.drain(..) {
+                waker.wake();
+            }
+        }
+    }
+}
+
+impl<T> RwLock<T> {
+    /// Creates a new reader-preferring lock wrapping the given value.
+    ///
+    /// # Example
+    /// ```rust, ignore
+    /// let lock = RwLock::new(Config::default());
+    /// ```
+    pub fn new(value: T) -> RwLock<T> {
+        Self::with_policy(value, RwLockPolicy::default())
+    }
+
+    /// Creates a new lock wrapping the given value, arbitrating
+    /// contention according to `policy`.
+    ///
+    /// # Example
+    /// ```rust, ignore
+    /// // Configuration reloads must not wait behind every request.
+    /// let lock = RwLock::with_policy(config, RwLockPolicy::WriterPreference);
+    /// ```
+    pub fn with_policy(value: T, policy: RwLockPolicy) -> RwLock<T> {
+        Self {
+            policy,
+            state: Mutex_std::new(State {
+                readers: 0,
+                writer: false,
+                waiting_writers: 0,
+                read_waiters: Vec::new(),
+                write_waiters: VecDeque::new(),
+                next_id: 0,
+            }),
+            data: UnsafeCell::new(value),
+        }
+    }
+
+    /// Returns the policy of this lock.
+    pub fn policy(&self) -> RwLockPolicy {
+        self.policy
+    }
+
+    /// Returns a future that resolves to a shared guard once the lock is
+    /// acquired for reading.
+    ///
+    /// # Example
+    /// ```rust, ignore
+    /// let config = lock.read().await;
+    /// ```
+    pub fn read(&self) -> ReadFuture<'_, T> {
+        ReadFuture { lock: self }
+    }
+
+    /// Returns a future that resolves to an exclusive guard once the lock
+    /// is acquired for writing.
+    ///
+    /// # Example
+    /// ```rust, ignore
+    /// *lock.write().await = reloaded;
+    /// ```
+    pub fn write(&self) -> WriteFuture<'_, T> {
+        WriteFuture {
+            lock: self,
+            id: None,
+        }
+    }
+}
+
+/// Future returned by [`RwLock::read`].
+pub struct ReadFuture<'a, T> {
+    lock: &'a RwLock<T>,
+}
+
+impl<'a, T> Future for ReadFuture<'a, T> {
+    type Output = RwLockReadGuard<'a, T>;
+
+    /// Polls the future to acquire the lock for reading.
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let lock = self.lock;
+        let mut state = lock.state.lock().unwrap();
+
+        if state.admits_reader(lock.policy) {
+            state.readers += 1;
+
+            return Poll::Ready(RwLockReadGuard { lock });
+        }
+
+        if !state.read_waiters.iter().any(|w| w.will_wake(cx.waker())) {
+            state.read_waiters.push(cx.waker().clone());
+        }
+
+        Poll::Pending
+    }
+}
+
+/// Future returned by [`RwLock::write`].
+///
+/// A writer counts as waiting from its first unsuccessful poll until it
+/// acquires the lock or the future is dropped.
+pub struct WriteFuture<'a, T> {
+    lock: &'a RwLock<T>,
+
+    /// Queue identifier, set while the writer is waiting.
+    id: Option<u64>,
+}
+
+impl<'a, T> Future for WriteFuture<'a, T> {
+    type Output = RwLockWriteGuard<'a, T>;
+
+    /// Polls the future to acquire the lock for writing.
+    ///
+    /// A waiting writer keeps its position in the queue across polls.
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        let lock = this.lock;
+        let mut state = lock.state.lock().unwrap();
+
+        if state.admits_writer() {
+            state.writer = true;
+
+            if let Some(id) = this.id.take() {
+                state.waiting_writers -= 1;
+                state.write_waiters.retain(|(waiter, _)| *waiter != id);
+            }
+
+            return Poll::Ready(RwLockWriteGuard { lock });
+        }
+
+        let id = match this.id {
+            Some(id) => id,
+            None => {
+                let id = state.next_id;
+                state.next_id += 1;
+                state.waiting_writers += 1;
+                this.id = Some(id);
+                id
+            }
+        };
+
+        match state
+            .write_waiters
+            .iter_mut()
+            .find(|(waiter, _)| *waiter == id)
+        {
+            Some((_, waker)) => *waker = cx.waker().clone(),
+            None => state.write_waiters.push_back((id, cx.waker().clone())),
+        }
+
+        Poll::Pending
+    }
+}
+
+impl<T> Drop for WriteFuture<'_, T> {
+    /// Leaves the waiters queue if the future is dropped while waiting.
+    ///
+    /// The readers or writer this one was holding back are woken.
+    fn drop(&mut self) {
+        let Some(id) = self.id.take() else {
+            return;
+        };
+
+        let mut state = self.lock.state.lock().unwrap();
+        state.waiting_writers -= 1;
+        state.write_waiters.retain(|(waiter, _)| *waiter != id);
+        state.wake(self.lock.policy);
+    }
+}
+
+/// Shared guard returned by [`RwLock::read`].
+///
+/// Releases the read lock when dropped.
+pub struct RwLockReadGuard<'a, T> {
+    lock: &'a RwLock<T>,
+}
+
+impl<T> Drop for RwLockReadGuard<'_, T> {
+    /// Leaves the read group, waking a writer if it was the last reader.
+    fn drop(&mut self) {
+        let mut state = self.lock.state.lock().unwrap();
+        state.readers -= 1;
+        state.wake(self.lock.policy);
+    }
+}
+
+impl<T> Deref for RwLockReadGuard<'_, T> {
+    type Target = T;
+
+    /// Provides immutable access to the protected data.
+    fn deref(&self) -> &Self::Target {
+        unsafe { &*self.lock.data.get() }
+    }
+}
+
+/// Exclusive guard returned by [`RwLock::write`].
+///
+/// Releases the write lock when dropped.
+pub struct RwLockWriteGuard<'a, T> {
+    lock: &'a RwLock<T>,
+}
+
+impl<T> Drop for RwLockWriteGuard<'_, T> {
+    /// Releases the lock, waking the waiters which may now acquire it.
+    fn drop(&mut self) {
+        let mut state = self.lock.state.lock().unwrap();
+        state.writer = false;
+        state.wake(self.lock.policy);
+    }
+}
+
+impl<T> Deref for RwLockWriteGuard<'_, T> {
+    type Target = T;
+
+    /// Provides immutable access to the protected data.
+    fn deref(&self) -> &Self::Target {
+        unsafe { &*self.lock.data.get() }
+    }
+}
+
+impl<T> DerefMut for RwLockWriteGuard<'_, T> {
+    /// Provides mutable access to the protected data.
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        unsafe { &mut *self.lock.data.get() }
+    }
+}
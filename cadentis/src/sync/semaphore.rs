@@ -0,0 +1,293 @@
+use std::collections::VecDeque;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex as Mutex_std};
+use std::task::{Context, Poll, Waker};
+
+/// An asynchronous counting semaphore.
+///
+/// A `Semaphore` maintains a number of permits. Tasks acquire permits
+/// with [`acquire`](Self::acquire) and release them by dropping the
+/// returned [`SemaphorePermit`].
+///
+/// Permits are granted in **FIFO order**: once a task is queued, no task
+/// arriving later can acquire permits before it. Released permits are
+/// handed off directly to the waiters at the front of the queue, which
+/// bounds the waiting time of every task under contention.
+pub struct Semaphore {
+    /// Permit count and waiters queue.
+    ///
+    /// Protected by a standard blocking `Mutex` because critical
+    /// sections are short and never span an await point.
+    state: Mutex_std<State>,
+}
+
+/// Internal state of a [`Semaphore`].
+struct State {
+    /// Number of permits currently available.
+    permits: usize,
+
+    /// Tasks waiting for permits, in arrival order.
+    waiters: VecDeque<Waiter>,
+}
+
+/// A task queued on the semaphore.
+struct Waiter {
+    /// Number of permits requested.
+    permits: usize,
+
+    /// Waker of the waiting task.
+    waker: Waker,
+
+    /// Set once the permits have been handed off to this waiter.
+    granted: Arc<AtomicBool>,
+}
+
+impl Semaphore {
+    /// Creates a new semaphore with the given number of permits.
+    ///
+    /// # Example
+    /// ```rust, ignore
+    /// let semaphore = Semaphore::new(3);
+    /// ```
+    pub fn new(permits: usize) -> Self {
+        Self {
+            state: Mutex_std::new(State {
+                permits,
+                waiters: VecDeque::new(),
+            }),
+        }
+    }
+
+    /// Returns the number of permits currently available.
+    pub fn available_permits(&self) -> usize {
+        self.state.lock().unwrap().permits
+    }
+
+    /// Returns a future that resolves to a permit once one is available.
+    ///
+    /// # Example
+    /// ```rust, ignore
+    /// let permit = semaphore.acquire().await;
+    /// // The permit is released when dropped.
+    /// ```
+    pub fn acquire(&self) -> Acquire<'_> {
+        self.acquire_many(1)
+    }
+
+    /// Returns a future that resolves once `permits` permits are acquired.
+    ///
+    /// The permits are acquired atomically: a waiter at the front of the
+    /// queue holds back later waiters until its whole request fits.
+    pub fn acquire_many(&self, permits: usize) -> Acquire<'_> {
+        Acquire {
+            semaphore: self,
+            permits,
+            granted: None,
+        }
+    }
+
+    /// Acquires a permit that keeps the semaphore alive.
+    ///
+    /// Unlike [`acquire`](Self::acquire), the returned permit does not
+    /// borrow the semaphore, so it can be moved into a spawned task and
+    /// released whenever that task is done.
+    ///
+    /// # Example
+    /// ```rust, ignore
+    /// let permit = semaphore.clone().acquire_owned().await;
+    /// task::spawn(async move {
+    ///     work().await;
+    ///     drop(permit);
+    /// });
+    /// ```
+    pub async fn acquire_owned(self: Arc<Self>) -> OwnedSemaphorePermit {
+        self.acquire().await.forget();
+
+        OwnedSemaphorePermit {
+            semaphore: self,
+            permits: 1,
+        }
+    }
+
+    /// Attempts to acquire a permit without waiting.
+    ///
+    /// Returns `None` if no permit is available or if other tasks are
+    /// already waiting.
+    pub fn try_acquire(&self) -> Option<SemaphorePermit<'_>> {
+        let mut state = self.state.lock().unwrap();
+
+        if state.waiters.is_empty() && state.permits >= 1 {
+            state.permits -= 1;
+
+            return Some(SemaphorePermit {
+                semaphore: self,
+                permits: 1,
+            });
+        }
+
+        None
+    }
+
+    /// Adds `permits` new permits to the semaphore.
+    ///
+    /// Waiting tasks are served in FIFO order.
+    pub fn add_permits(&self, permits: usize) {
+        self.release(permits);
+    }
+
+    /// Returns `permits` permits to the semaphore.
+    ///
+    /// Released permits are granted to the waiters at the front of the
+    /// queue, exactly as many as the released count allows.
+    fn release(&self, permits: usize) {
+        let mut state = self.state.lock().unwrap();
+        state.permits += permits;
+
+        while let Some(waiter) = state.waiters.front() {
+            if waiter.permits > state.permits {
+                break;
+            }
+
+            let waiter = state.waiters.pop_front().unwrap();
+            state.permits -= waiter.permits;
+
+            waiter.granted.store(true, Ordering::Release);
+            waiter.waker.wake();
+        }
+    }
+}
+
+/// Future returned by [`Semaphore::acquire`] and [`Semaphore::acquire_many`].
+///
+/// Dropping the future before completion removes the task from the
+/// queue, returning any permits that had already been handed off to it.
+pub struct Acquire<'a> {
+    semaphore: &'a Semaphore,
+    permits: usize,
+
+    /// Grant flag shared with the queued waiter, if any.
+    granted: Option<Arc<AtomicBool>>,
+}
+
+impl<'a> Future for Acquire<'a> {
+    type Output = SemaphorePermit<'a>;
+
+    /// Polls the future to acquire the permits.
+    ///
+    /// The permits are taken immediately only if no other task is
+    /// waiting; otherwise the task is queued behind them.
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        let mut state = this.semaphore.state.lock().unwrap();
+
+        if let Some(granted) = &this.granted {
+            if granted.load(Ordering::Acquire) {
+                this.granted = None;
+
+                return Poll::Ready(SemaphorePermit {
+                    semaphore: this.semaphore,
+                    permits: this.permits,
+                });
+            }
+
+            // Still queued: refresh the waker in place to keep our position.
+            if let Some(waiter) = state
+                .waiters
+                .iter_mut()
+                .find(|w| Arc::ptr_eq(&w.granted, granted))
+            {
+                waiter.waker = cx.waker().clone();
+            }
+
+            return Poll::Pending;
+        }
+
+        if state.waiters.is_empty() && state.permits >= this.permits {
+            state.permits -= this.permits;
+
+            return Poll::Ready(SemaphorePermit {
+                semaphore: this.semaphore,
+                permits: this.permits,
+            });
+        }
+
+        let granted = Arc::new(AtomicBool::new(false));
+
+        state.waiters.push_back(Waiter {
+            permits: this.permits,
+            waker: cx.waker().clone(),
+            granted: granted.clone(),
+        });
+
+        this.granted = Some(granted);
+
+        Poll::Pending
+    }
+}
+
+impl Drop for Acquire<'_> {
+    /// Leaves the waiters queue if the future is dropped while waiting.
+    fn drop(&mut self) {
+        let Some(granted) = self.granted.take() else {
+            return;
+        };
+
+        let mut state = self.semaphore.state.lock().unwrap();
+
+        if granted.load(Ordering::Acquire) {
+            // Permits were handed off but never observed: give them back.
+            drop(state);
+            self.semaphore.release(self.permits);
+        } else {
+            state.waiters.retain(|w| !Arc::ptr_eq(&w.granted, &granted));
+
+            // Leaving the queue may unblock the waiters queued behind us.
+            drop(state);
+            self.semaphore.release(0);
+        }
+    }
+}
+
+/// A permit acquired from a [`Semaphore`].
+///
+/// The permits are returned to the semaphore when dropped.
+pub struct SemaphorePermit<'a> {
+    semaphore: &'a Semaphore,
+    permits: usize,
+}
+
+impl SemaphorePermit<'_> {
+    /// Forgets the permit without returning it to the semaphore.
+    ///
+    /// This permanently reduces the number of available permits.
+    pub fn forget(mut self) {
+        self.permits = 0;
+    }
+}
+
+impl Drop for SemaphorePermit<'_> {
+    /// Releases the permits, waking the next waiters in FIFO order.
+    fn drop(&mut self) {
+        if self.permits > 0 {
+            self.semaphore.release(self.permits);
+        }
+    }
+}
+
+/// A permit acquired from a shared [`Semaphore`] with
+/// [`acquire_owned`](Semaphore::acquire_owned).
+///
+/// The permit is returned to the semaphore when dropped.
+pub struct OwnedSemaphorePermit {
+    semaphore: Arc<Semaphore>,
+    permits: usize,
+}
+
+impl Drop for OwnedSemaphorePermit {
+    /// Releases the permit, waking the next waiters in FIFO order.
+    fn drop(&mut self) {
+        self.semaphore.release(self.permits);
+    }
+}
@@ -0,0 +1,16 @@
+//! Raw system calls.
+//!
+//! The crate does not depend on `libc`: the few calls it needs beyond
+//! those `nucleus` provides are declared here, once, along with the
+//! types and constants they take. The modules using them keep the
+//! platform logic.
+
+#[cfg(unix)]
+mod unix;
+#[cfg(windows)]
+mod windows;
+
+#[cfg(unix)]
+pub(crate) use unix::*;
+#[cfg(windows)]
+pub(crate) use windows::*;
@@ -0,0 +1,359 @@
+//! Raw system calls of unix platforms.
+
+use nucleus::io::RawFd;
+#[cfg(any(target_os = "linux", target_os = "android"))]
+use std::ffi::c_long;
+use std::ffi::c_void;
+use std::io;
+
+#[cfg(not(target_os = "haiku"))]
+mod consts {
+    #[cfg(not(any(target_os = "linux", target_os = "android")))]
+    pub(super) const F_SETFD: i32 = 2;
+    pub(super) const F_GETFL: i32 = 3;
+    pub(super) const F_SETFL: i32 = 4;
+}
+
+#[cfg(target_os = "haiku")]
+mod consts {
+    pub(super) const F_SETFD: i32 = 0x4;
+    pub(super) const F_GETFL: i32 = 0x8;
+    pub(super) const F_SETFL: i32 = 0x10;
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "android")))]
+use consts::F_SETFD;
+use consts::{F_GETFL, F_SETFL};
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+const O_NONBLOCK: i32 = 0o4000;
+#[cfg(any(target_os = "solaris", target_os = "illumos", target_os = "haiku"))]
+const O_NONBLOCK: i32 = 0x80;
+#[cfg(not(any(
+    target_os = "linux",
+    target_os = "android",
+    target_os = "solaris",
+    target_os = "illumos",
+    target_os = "haiku"
+)))]
+const O_NONBLOCK: i32 = 0x4;
+
+/// Number of CPUs representable in a [`CpuSet`] (glibc default).
+#[cfg(target_os = "linux")]
+pub(crate) const CPU_SETSIZE: usize = 1024;
+
+/// Mirror of the C `cpu_set_t` bit mask.
+#[cfg(target_os = "linux")]
+#[repr(C)]
+pub(crate) struct CpuSet {
+    pub(crate) bits: [u64; CPU_SETSIZE / u64::BITS as usize],
+}
+
+unsafe extern "C" {
+    fn fcntl(fd: i32, cmd: i32, ...) -> i32;
+
+    pub(crate) fn write(fd: i32, buffer: *const u8, len: usize) -> isize;
+
+    pub(crate) fn setsockopt(fd: i32, level: i32, name: i32, value: *const c_void, len: u32)
+    -> i32;
+    pub(crate) fn getsockopt(
+        fd: i32,
+        level: i32,
+        name: i32,
+        value: *mut c_void,
+        len: *mut u32,
+    ) -> i32;
+
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    pub(crate) fn send(fd: i32, buffer: *const c_void, len: usize, flags: i32) -> isize;
+
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    pub(crate) fn accept4(fd: i32, address: *mut c_void, len: *mut u32, flags: i32) -> i32;
+    #[cfg(any(
+        target_os = "macos",
+        target_os = "ios",
+        target_os = "freebsd",
+        target_os = "dragonfly",
+        target_os = "openbsd",
+        target_os = "netbsd"
+    ))]
+    pub(crate) fn accept(fd: i32, address: *mut c_void, len: *mut u32) -> i32;
+
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    pub(crate) fn socket(domain: i32, kind: i32, protocol: i32) -> i32;
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    pub(crate) fn socketpair(domain: i32, kind: i32, protocol: i32, fds: *mut i32) -> i32;
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    pub(crate) fn bind(fd: i32, address: *const c_void, len: u32) -> i32;
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    pub(crate) fn listen(fd: i32, backlog: i32) -> i32;
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    pub(crate) fn connect(fd: i32, address: *const c_void, len: u32) -> i32;
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    pub(crate) fn recv(fd: i32, buffer: *mut c_void, len: usize, flags: i32) -> isize;
+
+    #[cfg(all(
+        any(target_os = "linux", target_os = "android"),
+        target_pointer_width = "64"
+    ))]
+    pub(crate) fn sendfile(out_fd: i32, in_fd: i32, offset: *mut i64, count: usize) -> isize;
+    #[cfg(target_os = "macos")]
+    pub(crate) fn sendfile(
+        fd: i32,
+        s: i32,
+        offset: i64,
+        len: *mut i64,
+        hdtr: *mut c_void,
+        flags: i32,
+    ) -> i32;
+
+    #[cfg(target_os = "linux")]
+    pub(crate) fn sched_setaffinity(pid: i32, size: usize, mask: *const CpuSet) -> i32;
+    #[cfg(target_os = "linux")]
+    pub(crate) fn sched_getaffinity(pid: i32, size: usize, mask: *mut CpuSet) -> i32;
+
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    pub(crate) fn syscall(number: c_long, ...) -> c_long;
+}
+
+/// Switches `fd` to non-blocking mode.
+pub(crate) fn set_nonblocking(fd: RawFd) -> io::Result<()> {
+    // SAFETY: `fd` is a valid open descriptor for the whole call.
+    let flags = unsafe { fcntl(fd, F_GETFL) };
+    if flags < 0 {
+        return Err(io::Error::last_os_error());
+    }
+
+    // SAFETY: as above.
+    if unsafe { fcntl(fd, F_SETFL, flags | O_NONBLOCK) } < 0 {
+        return Err(io::Error::last_os_error());
+    }
+
+    Ok(())
+}
+
+/// Closes `fd` in the child processes the program spawns.
+///
+/// Linux and Android create descriptors close-on-exec atomically instead.
+#[cfg(not(any(target_os = "linux", target_os = "android")))]
+pub(crate) fn set_cloexec(fd: RawFd) -> io::Result<()> {
+    const FD_CLOEXEC: i32 = 1;
+
+    // SAFETY: `fd` is a valid open descriptor for the whole call.
+    if unsafe { fcntl(fd, F_SETFD, FD_CLOEXEC) } < 0 {
+        return Err(io::Error::last_os_error());
+    }
+
+    Ok(())
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+const SA_RESTART: i32 = 0x1000_0000;
+#[cfg(any(target_os = "solaris", target_os = "illumos"))]
+const SA_RESTART: i32 = 0x4;
+#[cfg(target_os = "haiku")]
+const SA_RESTART: i32 = 0x10;
+#[cfg(not(any(
+    target_os = "linux",
+    target_os = "android",
+    target_os = "solaris",
+    target_os = "illumos",
+    target_os = "haiku"
+)))]
+const SA_RESTART: i32 = 0x2;
+
+/// The `struct sigaction` of the platform, the handler as an address.
+///
+/// Each layout is checked against the C one at compile time below.
+#[cfg(all(
+    target_os = "linux",
+    not(any(target_arch = "mips", target_arch = "mips64"))
+))]
+#[repr(C)]
+struct SigAction {
+    handler: usize,
+    mask: [usize; 1024 / usize::BITS as usize],
+    flags: i32,
+    restorer: usize,
+}
+
+#[cfg(all(target_os = "linux", any(target_arch = "mips", target_arch = "mips64")))]
+#[repr(C)]
+struct SigAction {
+    flags: i32,
+    handler: usize,
+    mask: [usize; 1024 / usize::BITS as usize],
+    restorer: usize,
+    #[cfg(target_pointer_width = "32")]
+    resv: i32,
+}
+
+#[cfg(all(target_os = "android", target_pointer_width = "64"))]
+#[repr(C)]
+struct SigAction {
+    flags: i32,
+    handler: usize,
+    mask: u64,
+    restorer: usize,
+}
+
+#[cfg(all(target_os = "android", target_pointer_width = "32"))]
+#[repr(C)]
+struct SigAction {
+    handler: usize,
+    mask: u32,
+    flags: i32,
+    restorer: usize,
+}
+
+#[cfg(any(target_os = "macos", target_os = "ios", target_os = "openbsd"))]
+#[repr(C)]
+struct SigAction {
+    handler: usize,
+    mask: u32,
+    flags: i32,
+}
+
+#[cfg(any(target_os = "freebsd", target_os = "dragonfly"))]
+#[repr(C)]
+struct SigAction {
+    handler: usize,
+    flags: i32,
+    mask: [u32; 4],
+}
+
+#[cfg(target_os = "netbsd")]
+#[repr(C)]
+struct SigAction {
+    handler: usize,
+    mask: [u32; 4],
+    flags: i32,
+}
+
+#[cfg(any(target_os = "solaris", target_os = "illumos"))]
+#[repr(C)]
+struct SigAction {
+    flags: i32,
+    handler: usize,
+    mask: [u32; 4],
+    #[cfg(target_pointer_width = "32")]
+    resv: [i32; 2],
+}
+
+#[cfg(target_os = "haiku")]
+#[repr(C)]
+struct SigAction {
+    handler: usize,
+    mask: u64,
+    flags: i32,
+    user_data: usize,
+}
+
+/// Asserts the size of [`SigAction`] and the offsets of its handler, mask
+/// and flags.
+macro_rules! assert_sigaction_layout {
+    ($size:expr, $handler:expr, $mask:expr, $flags:expr) => {
+        const _: () = {
+            assert!(size_of::<SigAction>() == $size);
+            assert!(std::mem::offset_of!(SigAction, handler) == $handler);
+            assert!(std::mem::offset_of!(SigAction, mask) == $mask);
+            assert!(std::mem::offset_of!(SigAction, flags) == $flags);
+        };
+    };
+}
+
+#[cfg(all(
+    target_os = "linux",
+    not(any(target_arch = "mips", target_arch = "mips64")),
+    target_pointer_width = "64"
+))]
+assert_sigaction_layout!(152, 0, 8, 136);
+#[cfg(all(
+    target_os = "linux",
+    not(any(target_arch = "mips", target_arch = "mips64")),
+    target_pointer_width = "32"
+))]
+assert_sigaction_layout!(140, 0, 4, 132);
+#[cfg(all(target_os = "linux", target_arch = "mips64"))]
+assert_sigaction_layout!(152, 8, 16, 0);
+#[cfg(all(target_os = "linux", target_arch = "mips"))]
+assert_sigaction_layout!(144, 4, 8, 0);
+#[cfg(all(target_os = "android", target_pointer_width = "64"))]
+assert_sigaction_layout!(32, 8, 16, 0);
+#[cfg(all(target_os = "android", target_pointer_width = "32"))]
+assert_sigaction_layout!(16, 0, 4, 8);
+#[cfg(all(
+    any(target_os = "macos", target_os = "ios", target_os = "openbsd"),
+    target_pointer_width = "64"
+))]
+assert_sigaction_layout!(16, 0, 8, 12);
+#[cfg(all(
+    any(target_os = "freebsd", target_os = "dragonfly"),
+    target_pointer_width = "64"
+))]
+assert_sigaction_layout!(32, 0, 12, 8);
+#[cfg(all(target_os = "netbsd", target_pointer_width = "64"))]
+assert_sigaction_layout!(32, 0, 8, 24);
+#[cfg(all(
+    any(target_os = "solaris", target_os = "illumos"),
+    target_pointer_width = "64"
+))]
+assert_sigaction_layout!(32, 8, 16, 0);
+#[cfg(all(target_os = "haiku", target_pointer_width = "64"))]
+assert_sigaction_layout!(32, 0, 8, 16);
+
+unsafe extern "C" {
+    #[cfg_attr(target_os = "netbsd", link_name = "__sigaction14")]
+    fn sigaction(signum: i32, action: *const SigAction, previous: *mut SigAction) -> i32;
+
+    #[cfg_attr(target_os = "linux", link_name = "__errno_location")]
+    #[cfg_attr(
+        any(target_os = "android", target_os = "openbsd", target_os = "netbsd"),
+        link_name = "__errno"
+    )]
+    #[cfg_attr(
+        any(
+            target_os = "macos",
+            target_os = "ios",
+            target_os = "freebsd",
+            target_os = "dragonfly"
+        ),
+        link_name = "__error"
+    )]
+    #[cfg_attr(
+        any(target_os = "solaris", target_os = "illumos"),
+        link_name = "___errno"
+    )]
+    #[cfg_attr(target_os = "haiku", link_name = "_errnop")]
+    fn errno_location() -> *mut i32;
+}
+
+/// Installs `handler` for signal `signum`, restarting the system calls
+/// it interrupts.
+pub(crate) fn set_signal_handler(signum: i32, handler: extern "C" fn(i32)) -> io::Result<()> {
+    // SAFETY: `struct sigaction` is plain data, and an all-zero mask is
+    // an empty signal set.
+    let mut action: SigAction = unsafe { std::mem::zeroed() };
+    action.handler = handler as usize;
+    action.flags = SA_RESTART;
+
+    // SAFETY: `action` is a valid `struct sigaction`, and the previous
+    // action is not asked for.
+    if unsafe { sigaction(signum, &action, std::ptr::null_mut()) } < 0 {
+        return Err(io::Error::last_os_error());
+    }
+
+    Ok(())
+}
+
+/// Returns the `errno` of the calling thread.
+pub(crate) fn errno() -> i32 {
+    // SAFETY: the location of `errno` is valid for the calling thread.
+    unsafe { *errno_location() }
+}
+
+/// Sets the `errno` of the calling thread.
+pub(crate) fn set_errno(errno: i32) {
+    // SAFETY: the location of `errno` is valid for the calling thread.
+    unsafe { *errno_location() = errno }
+}
@@ -0,0 +1,34 @@
+//! Raw system calls of Windows.
+
+use std::ffi::c_void;
+
+#[link(name = "kernel32")]
+unsafe extern "system" {
+    pub(crate) fn GetCurrentThread() -> *mut c_void;
+    pub(crate) fn GetCurrentProcess() -> *mut c_void;
+    pub(crate) fn SetThreadAffinityMask(thread: *mut c_void, mask: usize) -> usize;
+    pub(crate) fn GetProcessAffinityMask(
+        process: *mut c_void,
+        process_mask: *mut usize,
+        system_mask: *mut usize,
+    ) -> i32;
+    pub(crate) fn CloseHandle(handle: *mut c_void) -> i32;
+}
+
+#[link(name = "ws2_32")]
+unsafe extern "system" {
+    pub(crate) fn setsockopt(
+        socket: usize,
+        level: i32,
+        name: i32,
+        value: *const u8,
+        len: i32,
+    ) -> i32;
+    pub(crate) fn getsockopt(
+        socket: usize,
+        level: i32,
+        name: i32,
+        value: *mut u8,
+        len: *mut i32,
+    ) -> i32;
+}
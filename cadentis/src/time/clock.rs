@@ -0,0 +1,62 @@
+use crate::runtime::context::CURRENT_REACTOR;
+use crate::time::sleep::Sleep;
+
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// A source of time for the runtime.
+///
+/// Every timer of a runtime ([`sleep`](super::sleep),
+/// [`timeout`](super::timeout), ...) reads the current time from its
+/// clock, and the reactor fires timers according to it. The default
+/// clock is [`SystemClock`]; tests can install a
+/// [`PausedClock`](super::test::PausedClock) with
+/// [`RuntimeBuilder::clock`](crate::RuntimeBuilder::clock) to control
+/// time explicitly.
+pub trait Clock: Send + Sync + 'static {
+    /// Returns the current instant according to this clock.
+    fn now(&self) -> Instant;
+
+    /// Returns a future that completes once `duration` has elapsed.
+    ///
+    /// The deadline is computed from this clock, while the sleep is
+    /// driven by the clock of the current runtime.
+    fn sleep(&self, duration: Duration) -> Sleep {
+        Sleep::until(self.now() + duration)
+    }
+}
+
+/// The wall clock, backed by [`Instant::now`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+}
+
+/// Returns the current instant according to the clock of the current
+/// runtime.
+///
+/// Outside of a runtime, the wall clock is used.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// let start = time::now();
+/// sleep(Duration::from_secs(1)).await;
+/// assert!(time::now() - start >= Duration::from_secs(1));
+/// ```
+pub fn now() -> Instant {
+    current_clock().now()
+}
+
+/// Returns the clock of the current runtime, or the wall clock when
+/// called outside of a runtime.
+pub(crate) fn current_clock() -> Arc<dyn Clock> {
+    CURRENT_REACTOR.with(|cell| match cell.borrow().as_ref() {
+        Some(reactor) => reactor.clock().clone(),
+        None => Arc::new(SystemClock),
+    })
+}
@@ -0,0 +1,145 @@
+use crate::runtime::context::CURRENT_DEADLINE;
+use crate::time::clock::now;
+use crate::time::sleep::Sleep;
+
+use std::error::Error;
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+
+/// Runs `future` with an overall deadline.
+///
+/// Unlike [`timeout`](super::timeout), which bounds a single operation,
+/// `with_deadline` sets a budget for a whole multi-step task: the
+/// wrapped future is dropped at the first await point after `deadline`
+/// passes, and the call resolves to `Err(Elapsed)`.
+///
+/// The deadline is propagated to everything polled inside the future,
+/// so nested calls can check the remaining budget with [`deadline`] and
+/// [`remaining`]. Nesting `with_deadline` never extends the enclosing
+/// deadline: the earliest one applies.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// let response = with_deadline(Instant::now() + Duration::from_secs(2), async {
+///     let user = fetch_user().await?;
+///
+///     // Skip the optional step if the budget is almost spent.
+///     if time::remaining().unwrap() > Duration::from_millis(200) {
+///         enrich(&user).await?;
+///     }
+///
+///     Ok(user)
+/// })
+/// .await?;
+/// ```
+pub fn with_deadline<F>(deadline: Instant, future: F) -> WithDeadline<F>
+where
+    F: Future,
+{
+    WithDeadline {
+        future,
+        deadline,
+        sleep: Sleep::until(deadline),
+    }
+}
+
+/// Returns the deadline of the enclosing [`with_deadline`], if any.
+///
+/// When several deadlines are nested, the earliest one is returned.
+pub fn deadline() -> Option<Instant> {
+    CURRENT_DEADLINE.with(|current| current.get())
+}
+
+/// Returns the time left before the enclosing deadline, if any.
+///
+/// Returns `Some(Duration::ZERO)` once the deadline has passed.
+pub fn remaining() -> Option<Duration> {
+    deadline().map(|deadline| deadline.saturating_duration_since(now()))
+}
+
+/// Error returned when a [`with_deadline`] deadline passes before the
+/// wrapped future completes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Elapsed;
+
+impl fmt::Display for Elapsed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("deadline has elapsed")
+    }
+}
+
+impl Error for Elapsed {}
+
+/// A future running another future under a deadline.
+///
+/// Created by [`with_deadline`].
+pub struct WithDeadline<F> {
+    /// The wrapped future.
+    future: F,
+
+    /// Absolute deadline of the wrapped future.
+    deadline: Instant,
+
+    /// Timer waking the task once the deadline passes.
+    sleep: Sleep,
+}
+
+impl<F> Future for WithDeadline<F>
+where
+    F: Future,
+{
+    /// Returns `Ok(output)` if the future completes before the deadline,
+    /// or `Err(Elapsed)` otherwise.
+    type Output = Result<F::Output, Elapsed>;
+
+    /// Polls the wrapped future with its deadline installed.
+    ///
+    /// The wrapped future is not polled anymore once the deadline has
+    /// passed.
+    ///
+    /// # Safety
+    ///
+    /// This implementation uses `unsafe` pin projections but is sound
+    /// because:
+    /// - `future` is never moved after being pinned
+    /// - `sleep` is never moved after being pinned
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = unsafe { self.get_unchecked_mut() };
+
+        let sleep = unsafe { Pin::new_unchecked(&mut this.sleep) };
+        if let Poll::Ready(()) = sleep.poll(cx) {
+            return Poll::Ready(Err(Elapsed));
+        }
+
+        let effective = match deadline() {
+            Some(outer) => outer.min(this.deadline),
+            None => this.deadline,
+        };
+
+        let guard = DeadlineGuard(CURRENT_DEADLINE.replace(Some(effective)));
+
+        let future = unsafe { Pin::new_unchecked(&mut this.future) };
+        let result = future.poll(cx);
+
+        drop(guard);
+
+        match result {
+            Poll::Ready(output) => Poll::Ready(Ok(output)),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+/// Restores the deadline of the enclosing future on drop, even if the
+/// wrapped future panics.
+struct DeadlineGuard(Option<Instant>);
+
+impl Drop for DeadlineGuard {
+    fn drop(&mut self) {
+        CURRENT_DEADLINE.set(self.0);
+    }
+}
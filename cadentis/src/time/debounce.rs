@@ -0,0 +1,126 @@
+use crate::stream::Stream;
+use crate::time::clock::{Clock, current_clock};
+use crate::time::sleep::Sleep;
+
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+
+/// Debounces a stream of triggers.
+///
+/// The returned stream yields the latest item of `stream` once it has
+/// stayed silent for `quiet_period`: a burst of triggers closer together
+/// than the quiet period produces a single item, emitted one quiet period
+/// after the last of them. When `stream` ends, an item still waiting is
+/// yielded right away, and the debounced stream ends after it.
+///
+/// Silence is measured against the runtime [`Clock`].
+///
+/// # Panics
+///
+/// Panics if polled outside of a running runtime.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// let mut changes = time::debounce(watcher.events(), Duration::from_millis(200));
+///
+/// // An editor saving a file fires several events: reload once.
+/// while let Some(event) = changes.next().await {
+///     config.reload(event.path()).await?;
+/// }
+/// ```
+pub fn debounce<S: Stream>(stream: S, quiet_period: Duration) -> Debounce<S> {
+    Debounce {
+        stream,
+        quiet_period,
+        pending: None,
+        deadline: None,
+        timer: None,
+        done: false,
+        clock: current_clock(),
+    }
+}
+
+/// Stream returned by [`debounce`].
+pub struct Debounce<S: Stream> {
+    /// The stream of triggers.
+    stream: S,
+
+    /// Silence required before an item is emitted.
+    quiet_period: Duration,
+
+    /// Latest trigger, waiting for the quiet period to pass.
+    pending: Option<S::Item>,
+
+    /// When the pending trigger is due, pushed back by each new one.
+    deadline: Option<Instant>,
+
+    /// Timer waking the task at the deadline, with the deadline it was
+    /// armed for.
+    timer: Option<(Instant, Sleep)>,
+
+    /// Set once the stream of triggers ended.
+    done: bool,
+
+    /// Clock the silence is measured against.
+    clock: Arc<dyn Clock>,
+}
+
+impl<S: Stream> Stream for Debounce<S> {
+    type Item = S::Item;
+
+    /// Drains the ready triggers, then yields the latest one if the quiet
+    /// period has passed since it arrived.
+    ///
+    /// # Safety
+    ///
+    /// This implementation uses `unsafe` pin projections but is sound
+    /// because the wrapped stream is never moved after being pinned.
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = unsafe { self.get_unchecked_mut() };
+
+        while !this.done {
+            let stream = unsafe { Pin::new_unchecked(&mut this.stream) };
+
+            match stream.poll_next(cx) {
+                Poll::Ready(Some(item)) => {
+                    this.pending = Some(item);
+                    this.deadline = Some(this.clock.now() + this.quiet_period);
+                }
+                Poll::Ready(None) => this.done = true,
+                Poll::Pending => break,
+            }
+        }
+
+        if this.done {
+            this.timer = None;
+            return Poll::Ready(this.pending.take());
+        }
+
+        let Some(deadline) = this.deadline else {
+            return Poll::Pending;
+        };
+
+        loop {
+            if this.clock.now() >= deadline {
+                this.deadline = None;
+                this.timer = None;
+                return Poll::Ready(this.pending.take());
+            }
+
+            // A trigger pushed the deadline back since the timer was
+            // armed: re-arm it.
+            if this.timer.as_ref().map(|(armed, _)| *armed) != Some(deadline) {
+                this.timer = Some((deadline, Sleep::until(deadline)));
+            }
+
+            let (_, sleep) = this.timer.as_mut().expect("timer armed above");
+            if Pin::new(sleep).poll(cx).is_pending() {
+                return Poll::Pending;
+            }
+        }
+    }
+}
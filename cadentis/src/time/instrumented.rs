@@ -1,5 +1,7 @@
 use std::future::Future;
 use std::pin::Pin;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::task::{Context, Poll};
 use std::time::{Duration, Instant};
 
@@ -9,7 +11,10 @@ use std::time::{Duration, Instant};
 /// - the output of the wrapped future,
 /// - the elapsed time since the first poll.
 ///
-/// Timing starts on the **first poll**, not at construction time.
+/// Timing starts on the **first poll**, not at construction time. A
+/// finer breakdown (poll count, busy and pending time) is available
+/// through [`Instrumented::stats`], and [`Instrumented::with_ewma`] keeps
+/// a running average of the latency of repeated operations.
 ///
 /// # Examples
 ///
@@ -31,6 +36,44 @@ pub struct Instrumented<F> {
 
     /// Instant marking the first poll.
     start: Option<Instant>,
+
+    /// Instant marking the end of the last poll.
+    last_poll_end: Option<Instant>,
+
+    /// Observations collected so far.
+    stats: InstrumentStats,
+
+    /// Average updated with the elapsed time on completion.
+    ewma: Option<LatencyEwma>,
+}
+
+/// Observations collected by an [`Instrumented`] future.
+///
+/// The time between the first poll and completion splits into busy
+/// time, spent inside the wrapped future's `poll`, and pending time,
+/// spent waiting between two polls.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct InstrumentStats {
+    polls: u64,
+    busy: Duration,
+    pending: Duration,
+}
+
+impl InstrumentStats {
+    /// Returns the number of times the wrapped future was polled.
+    pub fn polls(&self) -> u64 {
+        self.polls
+    }
+
+    /// Returns the total time spent polling the wrapped future.
+    pub fn busy_time(&self) -> Duration {
+        self.busy
+    }
+
+    /// Returns the total time spent waiting between two polls.
+    pub fn pending_time(&self) -> Duration {
+        self.pending
+    }
 }
 
 impl<F> Instrumented<F> {
@@ -39,8 +82,76 @@ impl<F> Instrumented<F> {
         Self {
             future,
             start: None,
+            last_poll_end: None,
+            stats: InstrumentStats::default(),
+            ewma: None,
         }
     }
+
+    /// Records the completion latency of the future into `ewma`.
+    ///
+    /// Sharing one [`LatencyEwma`] between every call of an operation
+    /// keeps a running average of its latency.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// let latency = LatencyEwma::new(0.2);
+    ///
+    /// // Calls fail while the backend is slow on average, so that the
+    /// // breaker trips before requests start timing out.
+    /// let response = breaker
+    ///     .call(|| async {
+    ///         let (response, _) = instrumented(backend.call(request))
+    ///             .with_ewma(&latency)
+    ///             .await;
+    ///
+    ///         match latency.get() {
+    ///             Some(average) if average > SLOW => Err(Error::Slow),
+    ///             _ => response,
+    ///         }
+    ///     })
+    ///     .await?;
+    /// ```
+    pub fn with_ewma(mut self, ewma: &LatencyEwma) -> Self {
+        self.ewma = Some(ewma.clone());
+        self
+    }
+
+    /// Returns the observations collected so far.
+    ///
+    /// To read them once the future has completed, await it by
+    /// reference rather than by value.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// let mut future = pin!(instrumented(handle(request)));
+    /// let (response, elapsed) = (&mut future).await;
+    ///
+    /// let stats = future.stats();
+    /// println!("{} polls, {:?} busy", stats.polls(), stats.busy_time());
+    /// ```
+    pub fn stats(&self) -> InstrumentStats {
+        self.stats
+    }
+
+    /// Returns a reference to the wrapped future.
+    pub fn get_ref(&self) -> &F {
+        &self.future
+    }
+
+    /// Returns a mutable reference to the wrapped future.
+    pub fn get_mut(&mut self) -> &mut F {
+        &mut self.future
+    }
+
+    /// Consumes the wrapper, returning the wrapped future.
+    ///
+    /// The collected observations are discarded.
+    pub fn into_inner(self) -> F {
+        self.future
+    }
 }
 
 impl<F: Future> Future for Instrumented<F> {
@@ -60,16 +171,101 @@ impl<F: Future> Future for Instrumented<F> {
     fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
         let this = unsafe { self.get_unchecked_mut() };
 
-        let start = *this.start.get_or_insert_with(Instant::now);
+        let poll_start = Instant::now();
+        let start = *this.start.get_or_insert(poll_start);
+
+        if let Some(last_poll_end) = this.last_poll_end {
+            this.stats.pending += poll_start.saturating_duration_since(last_poll_end);
+        }
 
         let res = unsafe { Pin::new_unchecked(&mut this.future) }.poll(cx);
 
+        let poll_end = Instant::now();
+        this.stats.polls += 1;
+        this.stats.busy += poll_end - poll_start;
+        this.last_poll_end = Some(poll_end);
+
         match res {
             Poll::Pending => Poll::Pending,
             Poll::Ready(output) => {
                 let elapsed = start.elapsed();
+
+                if let Some(ewma) = &this.ewma {
+                    ewma.record(elapsed);
+                }
+
                 Poll::Ready((output, elapsed))
             }
         }
     }
 }
+
+/// Marks an average that has no sample yet. The bits of a NaN, which the
+/// average of finite samples never is.
+const EMPTY: u64 = u64::MAX;
+
+/// An exponentially weighted moving average of latencies.
+///
+/// Each new sample moves the average by `alpha` times its distance to
+/// it, so recent samples weigh more than older ones. The average is a
+/// cheap, continuous latency signal for adaptive behavior such as
+/// circuit breaking or load shedding.
+///
+/// Clones share the same average, so one handle can be fed by every
+/// [`Instrumented`] future of an operation through
+/// [`Instrumented::with_ewma`] while another is read elsewhere.
+#[derive(Debug, Clone)]
+pub struct LatencyEwma {
+    /// Weight of each new sample, in `(0, 1]`.
+    alpha: f64,
+
+    /// The average in nanoseconds, stored as `f64` bits, or [`EMPTY`].
+    average: Arc<AtomicU64>,
+}
+
+impl LatencyEwma {
+    /// Creates an average giving each new sample a weight of `alpha`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `alpha` is not in `(0, 1]`.
+    pub fn new(alpha: f64) -> Self {
+        assert!(
+            alpha > 0.0 && alpha <= 1.0,
+            "LatencyEwma alpha must be in (0, 1], got {alpha}"
+        );
+
+        Self {
+            alpha,
+            average: Arc::new(AtomicU64::new(EMPTY)),
+        }
+    }
+
+    /// Adds a latency sample to the average.
+    ///
+    /// The first sample becomes the average as is.
+    pub fn record(&self, latency: Duration) {
+        let sample = latency.as_nanos() as f64;
+
+        let _ = self
+            .average
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |bits| {
+                let average = if bits == EMPTY {
+                    sample
+                } else {
+                    let average = f64::from_bits(bits);
+                    average + self.alpha * (sample - average)
+                };
+
+                Some(average.to_bits())
+            });
+    }
+
+    /// Returns the current average, or `None` before the first sample.
+    pub fn get(&self) -> Option<Duration> {
+        match self.average.load(Ordering::Acquire) {
+            EMPTY => None,
+            bits => Some(Duration::from_nanos(f64::from_bits(bits).round() as u64)),
+        }
+    }
+}
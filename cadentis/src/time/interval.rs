@@ -0,0 +1,176 @@
+use crate::time::clock::{Clock, current_clock};
+use crate::time::sleep::Sleep;
+
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+
+/// Creates an interval ticking every `period`, starting immediately.
+///
+/// The first call to [`Interval::tick`] completes right away, and each
+/// following one `period` after the previous tick.
+///
+/// # Panics
+///
+/// Panics if `period` is zero, or if called outside of a running
+/// runtime.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// let mut heartbeat = interval(Duration::from_secs(1));
+///
+/// loop {
+///     heartbeat.tick().await;
+///     send_heartbeat().await;
+/// }
+/// ```
+pub fn interval(period: Duration) -> Interval {
+    interval_at(current_clock().now(), period)
+}
+
+/// Creates an interval ticking every `period`, starting at `start`.
+///
+/// # Panics
+///
+/// Panics if `period` is zero, or if called outside of a running
+/// runtime.
+pub fn interval_at(start: Instant, period: Duration) -> Interval {
+    assert!(!period.is_zero(), "interval period must be non-zero");
+
+    Interval {
+        period,
+        next: start,
+        timer: None,
+        clock: current_clock(),
+    }
+}
+
+/// A stream of ticks spaced by a fixed period.
+///
+/// Created by [`interval`] and [`interval_at`]. Ticks are measured
+/// against the runtime [`Clock`]. When a tick is observed late, for
+/// instance because the task was busy, the missed ticks are skipped and
+/// the schedule restarts one period after that late tick, rather than
+/// firing a burst of ticks to catch up.
+pub struct Interval {
+    /// Time between two ticks.
+    period: Duration,
+
+    /// When the next tick is due.
+    next: Instant,
+
+    /// Timer waking a pending tick, with the deadline it was armed for.
+    timer: Option<(Instant, Sleep)>,
+
+    /// Clock the ticks are measured against.
+    clock: Arc<dyn Clock>,
+}
+
+impl Interval {
+    /// Waits for the next tick.
+    ///
+    /// Resolves to the instant the tick was scheduled for.
+    ///
+    /// This method is cancel safe: dropping the returned future before it
+    /// completes does not consume the tick.
+    pub fn tick(&mut self) -> Tick<'_> {
+        Tick { interval: self }
+    }
+
+    /// Polls for the next tick.
+    ///
+    /// Returns the instant the tick was scheduled for once it is due, or
+    /// `Poll::Pending` after arranging for the task to be woken then. The
+    /// deadline is read on every poll, so a [`reset`](Self::reset) made
+    /// while a tick is pending applies to it.
+    pub fn poll_tick(&mut self, cx: &mut Context<'_>) -> Poll<Instant> {
+        loop {
+            let now = self.clock.now();
+
+            if now >= self.next {
+                let tick = self.next;
+
+                // Skip the ticks missed while the task was late.
+                self.next = tick + self.period;
+                if self.next <= now {
+                    self.next = now + self.period;
+                }
+
+                self.timer = None;
+                return Poll::Ready(tick);
+            }
+
+            // The deadline moved since the timer was armed: re-arm it.
+            if self.timer.as_ref().map(|(armed, _)| *armed) != Some(self.next) {
+                self.timer = Some((self.next, Sleep::until(self.next)));
+            }
+
+            let (_, sleep) = self.timer.as_mut().expect("timer armed above");
+            if Pin::new(sleep).poll(cx).is_pending() {
+                return Poll::Pending;
+            }
+        }
+    }
+
+    /// Restarts the schedule: the next tick is due one period from now.
+    ///
+    /// Later ticks follow at the usual period from that one.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// if buffer.is_full() {
+    ///     buffer.flush().await?;
+    ///     // The buffer was just flushed: the periodic flush can wait a
+    ///     // full period.
+    ///     flush_interval.reset();
+    /// }
+    /// ```
+    pub fn reset(&mut self) {
+        self.reset_at(self.clock.now() + self.period);
+    }
+
+    /// Moves the next tick to `deadline`.
+    ///
+    /// Later ticks follow at the usual period from that one. A deadline
+    /// in the past makes the next tick due immediately.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// let mut sync = interval(Duration::from_secs(30));
+    ///
+    /// loop {
+    ///     sync.tick().await;
+    ///
+    ///     if sync_peers().await.is_err() {
+    ///         // Retry sooner than the regular schedule.
+    ///         sync.reset_at(time::now() + Duration::from_secs(1));
+    ///     }
+    /// }
+    /// ```
+    pub fn reset_at(&mut self, deadline: Instant) {
+        self.next = deadline;
+    }
+
+    /// Returns the period of the interval.
+    pub fn period(&self) -> Duration {
+        self.period
+    }
+}
+
+/// Future returned by [`Interval::tick`].
+pub struct Tick<'a> {
+    interval: &'a mut Interval,
+}
+
+impl Future for Tick<'_> {
+    type Output = Instant;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Instant> {
+        self.get_mut().interval.poll_tick(cx)
+    }
+}
@@ -4,19 +4,43 @@
 //! integrate with the runtime reactor.
 //!
 //! It includes:
-//! - [`sleep`] for scheduling timers,
+//! - [`sleep`] for scheduling timers, and [`try_sleep`] to handle a
+//!   runtime out of timers,
+//! - [`interval`] for ticking at a fixed period,
+//! - [`debounce`] for acting once a stream of triggers goes quiet,
 //! - [`timeout`] for bounding future execution time,
-//! - [`instrumented`] for wrapping and observing async execution.
+//! - [`with_deadline`] for giving a whole task an overall deadline,
+//! - [`instrumented`] for wrapping and observing async execution,
+//! - [`Clock`] and [`now`] for reading the runtime time source,
+//! - [`test`] for controlling time in tests.
 
+pub(crate) mod clock;
+mod deadline;
+mod debounce;
 mod instrumented;
-mod sleep;
-mod timeout;
+mod interval;
+pub(crate) mod sleep;
+pub(crate) mod timeout;
+
+pub mod test;
+
+#[doc(inline)]
+pub use clock::{Clock, SystemClock, now};
+
+#[doc(inline)]
+pub use debounce::{Debounce, debounce};
+
+#[doc(inline)]
+pub use deadline::{Elapsed, WithDeadline, deadline, remaining, with_deadline};
+
+#[doc(inline)]
+pub use instrumented::{InstrumentStats, Instrumented, LatencyEwma, instrumented};
 
 #[doc(inline)]
-pub use instrumented::instrumented;
+pub use interval::{Interval, Tick, interval, interval_at};
 
 #[doc(inline)]
-pub use sleep::sleep;
+pub use sleep::{sleep, try_sleep};
 
 #[doc(inline)]
-pub use timeout::timeout;
+pub use timeout::{timeout, try_timeout};
@@ -1,7 +1,10 @@
 use crate::reactor::command::Command;
+use crate::reactor::timer::TimerSlot;
 use crate::runtime::context::CURRENT_REACTOR;
+use crate::time::clock::{Clock, current_clock};
 
 use std::future::Future;
+use std::io;
 use std::pin::Pin;
 use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, Ordering};
@@ -11,11 +14,14 @@ use std::time::{Duration, Instant};
 /// Creates a future that completes after the given duration.
 ///
 /// The returned sleep future registers a timer with the current
-/// runtime reactor and completes once the duration has elapsed.
+/// runtime reactor and completes once the duration has elapsed
+/// according to the runtime [`Clock`].
 ///
 /// # Panics
 ///
-/// Panics if polled outside of a running runtime.
+/// Panics if polled outside of a running runtime, or if the runtime
+/// limit of armed timers is reached; see
+/// [`RuntimeBuilder::max_timers`](crate::RuntimeBuilder::max_timers).
 ///
 /// # Examples
 ///
@@ -28,6 +34,46 @@ pub fn sleep(duration: Duration) -> Sleep {
     Sleep::new(duration)
 }
 
+/// Creates a future that completes after the given duration, reserving
+/// its timer right away.
+///
+/// Unlike [`sleep`], the timer counts against the runtime limit set with
+/// [`RuntimeBuilder::max_timers`](crate::RuntimeBuilder::max_timers) as
+/// soon as this function returns, so that a full runtime is reported
+/// here rather than by a panic when the future is polled.
+///
+/// # Errors
+///
+/// Returns `QuotaExceeded` if the runtime limit of armed timers is
+/// reached.
+///
+/// # Panics
+///
+/// Panics if called outside of a running runtime.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// match try_sleep(backoff) {
+///     Ok(delay) => delay.await,
+///     Err(err) => return Err(err),
+/// }
+/// ```
+pub fn try_sleep(duration: Duration) -> io::Result<Sleep> {
+    let mut sleep = Sleep::new(duration);
+
+    sleep.slot = Some(CURRENT_REACTOR.with(|cell| {
+        let binding = cell.borrow();
+        let reactor = binding
+            .as_ref()
+            .expect("try_sleep called outside of runtime");
+
+        reactor.timer_slot()
+    })?);
+
+    Ok(sleep)
+}
+
 /// A future that completes once a specific deadline is reached.
 ///
 /// `Sleep` integrates with the runtime reactor by registering a timer
@@ -45,6 +91,12 @@ pub struct Sleep {
 
     /// Cancellation flag shared with the reactor.
     cancelled: Arc<AtomicBool>,
+
+    /// Slot of the runtime timer budget, held while the timer is armed.
+    slot: Option<TimerSlot>,
+
+    /// Clock the deadline is measured against.
+    clock: Arc<dyn Clock>,
 }
 
 impl Sleep {
@@ -52,10 +104,24 @@ impl Sleep {
     ///
     /// The timer is not registered until the future is first polled.
     pub(crate) fn new(duration: Duration) -> Self {
+        let clock = current_clock();
+
+        Self::with_clock(clock.now() + duration, clock)
+    }
+
+    /// Creates a new `Sleep` future that completes at `deadline`.
+    pub(crate) fn until(deadline: Instant) -> Self {
+        Self::with_clock(deadline, current_clock())
+    }
+
+    /// Creates a new `Sleep` future measured against `clock`.
+    fn with_clock(deadline: Instant, clock: Arc<dyn Clock>) -> Self {
         Self {
-            deadline: Instant::now() + duration,
+            deadline,
             registered: false,
             cancelled: Arc::new(AtomicBool::new(false)),
+            slot: None,
+            clock,
         }
     }
 }
@@ -72,7 +138,8 @@ impl Future for Sleep {
     fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
         let this = self.get_mut();
 
-        if this.cancelled.load(Ordering::Acquire) || Instant::now() >= this.deadline {
+        if this.cancelled.load(Ordering::Acquire) || this.clock.now() >= this.deadline {
+            this.slot = None;
             return Poll::Ready(());
         }
 
@@ -83,6 +150,13 @@ impl Future for Sleep {
                 let binding = cell.borrow();
                 let reactor = binding.as_ref().expect("Sleep polled outside of runtime");
 
+                if this.slot.is_none() {
+                    match reactor.timer_slot() {
+                        Ok(slot) => this.slot = Some(slot),
+                        Err(err) => panic!("{err}"),
+                    }
+                }
+
                 let _ = reactor.send(Command::SetTimer {
                     deadline: this.deadline,
                     waker: cx.waker().clone(),
@@ -1,6 +1,7 @@
-use crate::time::sleep::{Sleep, sleep};
+use crate::time::sleep::{Sleep, sleep, try_sleep};
 
 use std::future::Future;
+use std::io;
 use std::pin::Pin;
 use std::task::{Context, Poll};
 use std::time::Duration;
@@ -35,6 +36,30 @@ where
     Timeout::new(duration, future)
 }
 
+/// Like [`timeout`], but reserves the timer right away.
+///
+/// See [`try_sleep`](crate::time::try_sleep): a runtime whose limit of
+/// armed timers is reached is reported here, rather than by a panic when
+/// the returned future is polled.
+///
+/// # Errors
+///
+/// Returns `QuotaExceeded` if the runtime limit of armed timers is
+/// reached.
+///
+/// # Panics
+///
+/// Panics if called outside of a running runtime.
+pub fn try_timeout<F>(duration: Duration, future: F) -> io::Result<Timeout<F>>
+where
+    F: Future,
+{
+    Ok(Timeout {
+        future,
+        sleep: try_sleep(duration)?,
+    })
+}
+
 /// A future that enforces a time limit on another future.
 ///
 /// `Timeout` polls both the wrapped future and an internal sleep future.
@@ -0,0 +1,233 @@
+use crate::time;
+
+use std::error::Error;
+use std::fmt;
+use std::future::Future;
+use std::io;
+use std::sync::{Arc, Mutex as Mutex_std};
+use std::time::{Duration, Instant};
+
+/// A circuit breaker guarding calls to a failing dependency.
+///
+/// The breaker starts [closed](CircuitState::Closed) and lets every call
+/// through. After `failure_threshold` consecutive failures it trips
+/// [open](CircuitState::Open): calls are rejected with [`CircuitOpen`]
+/// without running, giving the dependency time to recover. Once the
+/// cooldown has passed, the breaker turns
+/// [half-open](CircuitState::HalfOpen) and lets a single trial call
+/// through: a success closes the circuit again, a failure reopens it for
+/// another cooldown.
+///
+/// Where [`retry`](super::retry) insists on a single operation, a
+/// breaker protects a dependency from every caller at once. Clones share
+/// the same circuit.
+///
+/// Time is read from the runtime clock, so the cooldown follows a
+/// [`PausedClock`](crate::time::test::PausedClock) in tests.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// let breaker = CircuitBreaker::new(5, Duration::from_secs(30));
+///
+/// let profile = breaker
+///     .call(|| async { client.get_profile(user).await })
+///     .await?;
+/// ```
+#[derive(Clone)]
+pub struct CircuitBreaker {
+    /// Failures in a row that trip the circuit.
+    failure_threshold: u32,
+
+    /// How long the circuit stays open before a trial call.
+    cooldown: Duration,
+
+    /// State shared by every clone.
+    state: Arc<Mutex_std<State>>,
+}
+
+/// The state of a [`CircuitBreaker`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CircuitState {
+    /// Calls go through.
+    Closed,
+
+    /// Calls are rejected until the cooldown passes.
+    Open,
+
+    /// The cooldown passed: the next call is a trial.
+    HalfOpen,
+}
+
+/// Internal state of a [`CircuitBreaker`].
+enum State {
+    /// Counting consecutive failures.
+    Closed { failures: u32 },
+
+    /// Rejecting calls until the given instant.
+    Open { until: Instant },
+
+    /// Letting one trial call through, or waiting for its outcome.
+    HalfOpen { trial: bool },
+}
+
+/// Error returned by [`CircuitBreaker::call`] when the circuit is open.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CircuitOpen;
+
+impl fmt::Display for CircuitOpen {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("circuit breaker is open")
+    }
+}
+
+impl Error for CircuitOpen {}
+
+impl From<CircuitOpen> for io::Error {
+    /// Converts the error into an `Other` I/O error, so that calls
+    /// returning [`io::Result`] can go through a breaker.
+    fn from(err: CircuitOpen) -> Self {
+        io::Error::other(err)
+    }
+}
+
+impl CircuitBreaker {
+    /// Creates a closed breaker tripping after `failure_threshold`
+    /// consecutive failures, and staying open for `cooldown`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `failure_threshold` is zero.
+    pub fn new(failure_threshold: u32, cooldown: Duration) -> Self {
+        assert!(
+            failure_threshold > 0,
+            "failure_threshold must be greater than zero"
+        );
+
+        Self {
+            failure_threshold,
+            cooldown,
+            state: Arc::new(Mutex_std::new(State::Closed { failures: 0 })),
+        }
+    }
+
+    /// Returns the current state of the circuit.
+    pub fn state(&self) -> CircuitState {
+        let mut state = self.state.lock().unwrap();
+
+        self.refresh(&mut state);
+
+        match *state {
+            State::Closed { .. } => CircuitState::Closed,
+            State::Open { .. } => CircuitState::Open,
+            State::HalfOpen { .. } => CircuitState::HalfOpen,
+        }
+    }
+
+    /// Runs the operation returned by `operation` through the breaker.
+    ///
+    /// The operation is only created and awaited if the circuit lets the
+    /// call through. An `Err` counts as a failure, an `Ok` as a success.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of the operation, or [`CircuitOpen`] converted
+    /// with `E::from` if the call was rejected. While a half-open trial
+    /// is running, other calls are rejected as well.
+    pub async fn call<F, Fut, T, E>(&self, operation: F) -> Result<T, E>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+        E: From<CircuitOpen>,
+    {
+        let mut guard = TrialGuard {
+            breaker: self,
+            trial: self.acquire()?,
+        };
+
+        let result = operation().await;
+
+        // The outcome settles the trial: the guard has nothing to undo.
+        guard.trial = false;
+        self.record(result.is_ok());
+
+        result
+    }
+
+    /// Lets a call through, returning whether it is a half-open trial.
+    fn acquire(&self) -> Result<bool, CircuitOpen> {
+        let mut state = self.state.lock().unwrap();
+
+        self.refresh(&mut state);
+
+        match &mut *state {
+            State::Closed { .. } => Ok(false),
+            State::Open { .. } => Err(CircuitOpen),
+            State::HalfOpen { trial } if *trial => Err(CircuitOpen),
+            State::HalfOpen { trial } => {
+                *trial = true;
+                Ok(true)
+            }
+        }
+    }
+
+    /// Updates the circuit with the outcome of a call.
+    ///
+    /// A success only closes a closed or half-open circuit: one reported
+    /// while open comes from a call started before the circuit tripped.
+    fn record(&self, success: bool) {
+        let mut state = self.state.lock().unwrap();
+
+        *state = match (&*state, success) {
+            (State::Open { until }, true) => State::Open { until: *until },
+            (_, true) => State::Closed { failures: 0 },
+            (State::Closed { failures }, false) if failures + 1 < self.failure_threshold => {
+                State::Closed {
+                    failures: failures + 1,
+                }
+            }
+            (State::Open { until }, false) => State::Open { until: *until },
+            (_, false) => State::Open {
+                until: time::now() + self.cooldown,
+            },
+        };
+    }
+
+    /// Turns an open circuit half-open once its cooldown has passed.
+    fn refresh(&self, state: &mut State) {
+        if let State::Open { until } = *state
+            && time::now() >= until
+        {
+            *state = State::HalfOpen { trial: false };
+        }
+    }
+}
+
+impl fmt::Debug for CircuitBreaker {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CircuitBreaker")
+            .field("state", &self.state())
+            .field("failure_threshold", &self.failure_threshold)
+            .field("cooldown", &self.cooldown)
+            .finish()
+    }
+}
+
+/// Releases the half-open trial slot if a call is dropped before its
+/// outcome is known, so that another call can try instead.
+struct TrialGuard<'a> {
+    breaker: &'a CircuitBreaker,
+    trial: bool,
+}
+
+impl Drop for TrialGuard<'_> {
+    fn drop(&mut self) {
+        if !self.trial {
+            return;
+        }
+
+        if let State::HalfOpen { trial } = &mut *self.breaker.state.lock().unwrap() {
+            *trial = false;
+        }
+    }
+}
@@ -1,13 +1,24 @@
-//! Retry utilities for asynchronous operations.
+//! Utilities for asynchronous operations.
 //!
 //! This module provides helpers for retrying fallible asynchronous
 //! operations with optional delays between attempts.
 //!
 //! The main entry point is [`retry`], which creates a future that
 //! retries an operation produced by a factory closure until it
-//! succeeds or the retry limit is reached.
+//! succeeds or the retry limit is reached; [`retry_until`] also gives up
+//! by an absolute deadline. [`RetryConfig`] describes
+//! such a policy once for reuse. [`CircuitBreaker`] complements
+//! it by rejecting calls to a dependency that keeps failing.
+//!
+//! It also defines [`Selected`], the value returned by
+//! [`select_enum!`](crate::select_enum).
 
+mod breaker;
 mod retry;
+mod selected;
+
+pub use breaker::{CircuitBreaker, CircuitOpen, CircuitState};
 
 #[doc(inline)]
-pub use retry::retry;
+pub use retry::{Retry, RetryConfig, retry, retry_until};
+pub use selected::Selected;
@@ -1,9 +1,10 @@
+use crate::time::clock::now;
 use crate::time::sleep;
 
 use std::future::Future;
 use std::pin::Pin;
 use std::task::{Context, Poll};
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 /// Creates a future that retries an asynchronous operation on failure.
 ///
@@ -33,6 +34,114 @@ where
     Retry::new(times, factory)
 }
 
+/// Creates a future that retries an asynchronous operation on failure,
+/// without waiting past `deadline`.
+///
+/// Behaves like [`retry`], except that no retry is started at or after
+/// the deadline: when the wait before the next attempt would reach it,
+/// the wait is skipped and the last error returned right away, rather
+/// than sleeping a full interval only to give up. The retry sequence thus
+/// ends by the deadline, save for the attempt in flight when it passes,
+/// which may be bounded with [`with_deadline`](crate::time::with_deadline).
+///
+/// The first attempt always runs, even once the deadline has passed,
+/// since there is no error to give up with before it.
+///
+/// The deadline is read from the clock of the current runtime.
+///
+/// # Arguments
+///
+/// * `deadline` - Instant after which no retry is started.
+/// * `times` - Number of retry attempts after the first failure.
+/// * `factory` - A closure producing a new future on each attempt.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// let deadline = time::now() + Duration::from_secs(2);
+///
+/// let rows = retry_until(deadline, 10, || fetch_rows())
+///     .set_interval(Duration::from_millis(300))
+///     .await?;
+/// ```
+pub fn retry_until<F, G>(deadline: Instant, times: usize, factory: G) -> Retry<G, F>
+where
+    G: FnMut() -> F + Send + 'static,
+    F: Future + Send + 'static,
+{
+    Retry {
+        deadline: Some(deadline),
+        ..Retry::new(times, factory)
+    }
+}
+
+/// A reusable retry policy: how many times to retry, and how long to
+/// wait between attempts.
+///
+/// APIs retrying on the caller's behalf, such as
+/// [`TcpStream::connect_retry`](crate::net::TcpStream::connect_retry),
+/// take a `RetryConfig`; [`retry`](Self::retry) applies it to any
+/// operation.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// let policy = RetryConfig::new(5).interval(Duration::from_millis(200));
+///
+/// let stream = TcpStream::connect_retry("db.internal:5432", policy).await?;
+/// let rows = policy.retry(|| fetch_rows()).await?;
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryConfig {
+    /// Number of retry attempts after the first failure.
+    retries: usize,
+
+    /// Delay between two attempts.
+    interval: Duration,
+}
+
+impl RetryConfig {
+    /// Creates a policy retrying up to `retries` times after the first
+    /// failure, without waiting between attempts.
+    pub fn new(retries: usize) -> Self {
+        Self {
+            retries,
+            interval: Duration::ZERO,
+        }
+    }
+
+    /// Sets the delay between two attempts.
+    pub fn interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Retries the operation produced by `factory` according to this
+    /// policy.
+    ///
+    /// This is [`retry`] with the retry count and interval of the policy.
+    pub fn retry<F, G>(&self, factory: G) -> Retry<G, F>
+    where
+        G: FnMut() -> F + Send + 'static,
+        F: Future + Send + 'static,
+    {
+        retry(self.retries, factory).set_interval(self.interval)
+    }
+
+    /// Retries the operation produced by `factory` according to this
+    /// policy, without waiting past `deadline`.
+    ///
+    /// This is [`retry_until`] with the retry count and interval of the
+    /// policy.
+    pub fn retry_until<F, G>(&self, deadline: Instant, factory: G) -> Retry<G, F>
+    where
+        G: FnMut() -> F + Send + 'static,
+        F: Future + Send + 'static,
+    {
+        retry_until(deadline, self.retries, factory).set_interval(self.interval)
+    }
+}
+
 /// A future that retries an asynchronous operation until it succeeds
 /// or the retry limit is reached.
 ///
@@ -56,6 +165,9 @@ pub struct Retry<G, F> {
 
     /// Delay interval between retries.
     interval: Duration,
+
+    /// Instant after which no retry is started, if any.
+    deadline: Option<Instant>,
 }
 
 impl<G, F> Retry<G, F> {
@@ -69,6 +181,7 @@ impl<G, F> Retry<G, F> {
             delay: None,
             remaining: times,
             interval: Duration::from_micros(0),
+            deadline: None,
         }
     }
 
@@ -102,7 +215,9 @@ where
     /// The future:
     /// - resolves immediately on the first successful attempt,
     /// - retries on error until the retry count is exhausted,
-    /// - optionally waits for the configured interval between attempts.
+    /// - optionally waits for the configured interval between attempts,
+    /// - gives up with the last error if the next retry would start at
+    ///   or after the deadline, if any. The first attempt always runs.
     fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
         let this = self.get_mut();
 
@@ -133,7 +248,11 @@ where
             Poll::Ready(Err(e)) => {
                 this.future = None;
 
-                if this.remaining > 0 {
+                let past_deadline = this
+                    .deadline
+                    .is_some_and(|deadline| now() + this.interval >= deadline);
+
+                if this.remaining > 0 && !past_deadline {
                     this.remaining -= 1;
 
                     if this.interval != Duration::from_micros(0) {
@@ -0,0 +1,53 @@
+use std::convert::Infallible;
+
+/// The outcome of [`select_enum!`](crate::select_enum): the output of
+/// the winning future, tagged with the index of its branch.
+///
+/// `Branch0` holds the output of the first future, `Branch1` of the
+/// second, and so on. Branches beyond those given to the macro default
+/// to [`Infallible`]: they can never be constructed, so a `match` only
+/// needs an arm per actual branch.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// match select_enum!(rx.recv(), sleep(Duration::from_secs(1))) {
+///     Selected::Branch0(message) => handle(message),
+///     Selected::Branch1(()) => heartbeat(),
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Selected<
+    T0,
+    T1 = Infallible,
+    T2 = Infallible,
+    T3 = Infallible,
+    T4 = Infallible,
+    T5 = Infallible,
+    T6 = Infallible,
+    T7 = Infallible,
+> {
+    /// The first future completed first.
+    Branch0(T0),
+
+    /// The second future completed first.
+    Branch1(T1),
+
+    /// The third future completed first.
+    Branch2(T2),
+
+    /// The fourth future completed first.
+    Branch3(T3),
+
+    /// The fifth future completed first.
+    Branch4(T4),
+
+    /// The sixth future completed first.
+    Branch5(T5),
+
+    /// The seventh future completed first.
+    Branch6(T6),
+
+    /// The eighth future completed first.
+    Branch7(T7),
+}
@@ -0,0 +1,69 @@
+use cadentis::net::TcpListener;
+use cadentis::task;
+use cadentis::time::sleep;
+use std::fs::File;
+use std::future::poll_fn;
+use std::pin::pin;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::time::Duration;
+
+/// `EMFILE`: the process has no file descriptor left.
+const EMFILE: i32 = 24;
+
+/// Opens files until the process runs out of file descriptors.
+fn exhaust_fds() -> Vec<File> {
+    let mut files = Vec::new();
+
+    loop {
+        match File::open("/dev/null") {
+            Ok(file) => files.push(file),
+            Err(err) if err.raw_os_error() == Some(EMFILE) => return files,
+            Err(err) => panic!("unexpected error while exhausting fds: {err}"),
+        }
+    }
+}
+
+#[cadentis::test]
+async fn accept_backs_off_when_out_of_fds() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    // Queue a connection in the backlog before running out of fds.
+    let _client = std::net::TcpStream::connect(addr).unwrap();
+
+    let polls = Arc::new(AtomicUsize::new(0));
+    let accepted = Arc::new(AtomicBool::new(false));
+
+    let files = exhaust_fds();
+
+    let server = task::spawn({
+        let polls = polls.clone();
+        let accepted = accepted.clone();
+
+        async move {
+            let mut accept = pin!(listener.accept());
+
+            let result = poll_fn(|cx| {
+                polls.fetch_add(1, Ordering::Relaxed);
+                accept.as_mut().poll(cx)
+            })
+            .await;
+
+            accepted.store(true, Ordering::Release);
+            result.map(|(_, peer)| peer)
+        }
+    });
+
+    sleep(Duration::from_millis(100)).await;
+
+    // The accept loop is still alive and did not spin in the meantime.
+    assert!(!accepted.load(Ordering::Acquire));
+    let waiting_polls = polls.load(Ordering::Relaxed);
+    assert!(waiting_polls < 50, "accept spun: {waiting_polls} polls");
+
+    drop(files);
+
+    let peer = server.await.unwrap().unwrap();
+    assert!(peer.ip().is_loopback());
+}
@@ -0,0 +1,62 @@
+use cadentis::fs::AppendLog;
+use cadentis::task;
+use cadentis::yield_now;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+#[cadentis::test]
+async fn append_log_concurrent_appenders_keep_whole_lines() {
+    let unique = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("clock drift")
+        .as_nanos();
+
+    let path =
+        std::env::temp_dir().join(format!("append-log-{}-{}.log", std::process::id(), unique));
+
+    std::fs::write(&path, b"header\n").unwrap();
+
+    // Each task uses its own handle: only O_APPEND keeps them apart.
+    let handles: Vec<_> = ["alpha", "beta"]
+        .into_iter()
+        .map(|name| {
+            let path = path.clone();
+            task::spawn(async move {
+                let log = AppendLog::open(&path).await.unwrap();
+
+                for i in 0..200 {
+                    let line = format!("{name}-{i:03}-{}", "x".repeat(64));
+                    log.write_line(line.as_bytes()).await.unwrap();
+                    yield_now().await;
+                }
+
+                log.flush().await.unwrap();
+            })
+        })
+        .collect();
+
+    for handle in handles {
+        handle.await.unwrap();
+    }
+
+    let content = std::fs::read_to_string(&path).unwrap();
+    let lines: Vec<&str> = content.lines().collect();
+
+    assert_eq!(lines.len(), 401);
+    assert_eq!(lines[0], "header");
+
+    for name in ["alpha", "beta"] {
+        let own: Vec<&str> = lines
+            .iter()
+            .copied()
+            .filter(|line| line.starts_with(name))
+            .collect();
+
+        assert_eq!(own.len(), 200);
+
+        for (i, line) in own.iter().enumerate() {
+            assert_eq!(*line, format!("{name}-{i:03}-{}", "x".repeat(64)));
+        }
+    }
+
+    let _ = std::fs::remove_file(path);
+}
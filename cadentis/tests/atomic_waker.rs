@@ -0,0 +1,72 @@
+use cadentis::sync::AtomicWaker;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::task::{Wake, Waker};
+use std::thread;
+
+/// A waker counting how many times it was woken.
+#[derive(Default)]
+struct CountingWaker {
+    wakes: AtomicUsize,
+}
+
+impl CountingWaker {
+    fn wakes(&self) -> usize {
+        self.wakes.load(Ordering::SeqCst)
+    }
+}
+
+impl Wake for CountingWaker {
+    fn wake(self: Arc<Self>) {
+        self.wakes.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+#[test]
+fn atomic_waker_wakes_latest_registration() {
+    let slot = AtomicWaker::new();
+    let first = Arc::new(CountingWaker::default());
+    let second = Arc::new(CountingWaker::default());
+
+    // Waking an empty slot is a no-op.
+    slot.wake();
+
+    slot.register(&Waker::from(first.clone()));
+    slot.register(&Waker::from(second.clone()));
+    slot.wake();
+
+    assert_eq!(first.wakes(), 0);
+    assert_eq!(second.wakes(), 1);
+
+    // The waker is consumed by the wakeup.
+    slot.wake();
+    assert_eq!(second.wakes(), 1);
+}
+
+#[test]
+fn atomic_waker_concurrent_register_and_wake_never_lose_a_wakeup() {
+    for _ in 0..2000 {
+        let slot = Arc::new(AtomicWaker::new());
+        let ready = Arc::new(AtomicBool::new(false));
+        let counter = Arc::new(CountingWaker::default());
+
+        let producer = thread::spawn({
+            let slot = slot.clone();
+            let ready = ready.clone();
+
+            move || {
+                ready.store(true, Ordering::SeqCst);
+                slot.wake();
+            }
+        });
+
+        // Consumer side of a poll: register, then check the condition.
+        slot.register(&Waker::from(counter.clone()));
+        let observed = ready.load(Ordering::SeqCst);
+
+        producer.join().unwrap();
+
+        // Either the consumer saw the event, or the producer woke it.
+        assert!(observed || counter.wakes() == 1);
+    }
+}
@@ -0,0 +1,114 @@
+use cadentis::sync::broadcast::{self, Overflow, RecvError};
+use cadentis::task;
+use cadentis::time::{sleep, timeout};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+#[cadentis::test]
+async fn broadcast_delivers_to_every_receiver() {
+    let (tx, mut rx1) = broadcast::channel(4);
+    let mut rx2 = tx.subscribe();
+
+    tx.send(1).await.unwrap();
+    tx.send(2).await.unwrap();
+
+    assert_eq!(rx1.recv().await, Ok(1));
+    assert_eq!(rx1.recv().await, Ok(2));
+    assert_eq!(rx2.recv().await, Ok(1));
+    assert_eq!(rx2.recv().await, Ok(2));
+
+    drop(tx);
+
+    assert_eq!(rx1.recv().await, Err(RecvError::Closed));
+}
+
+#[cadentis::test]
+async fn broadcast_lag_overwrites_for_slow_receiver() {
+    let (tx, mut rx) = broadcast::channel(2);
+
+    for i in 0..5 {
+        tx.send(i).await.unwrap();
+    }
+
+    assert_eq!(rx.recv().await, Err(RecvError::Lagged(3)));
+    assert_eq!(rx.recv().await, Ok(3));
+    assert_eq!(rx.recv().await, Ok(4));
+}
+
+#[cadentis::test]
+async fn broadcast_wait_blocks_sender_on_slow_receiver() {
+    let (tx, mut fast) = broadcast::channel_with_overflow(2, Overflow::Wait);
+    let mut slow = tx.subscribe();
+    let sent = Arc::new(AtomicUsize::new(0));
+
+    let producer = {
+        let sent = sent.clone();
+        task::spawn(async move {
+            for i in 0..5 {
+                tx.send(i).await.unwrap();
+                sent.fetch_add(1, Ordering::SeqCst);
+            }
+        })
+    };
+
+    // The fast receiver drains everything it can, but the slow one holds
+    // the buffer: the sender must wait instead of overwriting.
+    assert_eq!(fast.recv().await, Ok(0));
+    assert_eq!(fast.recv().await, Ok(1));
+    assert!(
+        timeout(Duration::from_millis(50), fast.recv())
+            .await
+            .is_err()
+    );
+    assert_eq!(sent.load(Ordering::SeqCst), 2);
+
+    let slow = task::spawn(async move {
+        for expected in 0..5 {
+            assert_eq!(slow.recv().await, Ok(expected));
+            sleep(Duration::from_millis(1)).await;
+        }
+    });
+
+    // Nothing was overwritten: the fast receiver sees every value too.
+    for expected in 2..5 {
+        assert_eq!(fast.recv().await, Ok(expected));
+    }
+
+    slow.await.unwrap();
+    producer.await.unwrap();
+
+    assert_eq!(sent.load(Ordering::SeqCst), 5);
+}
+
+#[cadentis::test]
+async fn broadcast_wait_releases_sender_when_slow_receiver_drops() {
+    let (tx, mut fast) = broadcast::channel_with_overflow(1, Overflow::Wait);
+    let slow = tx.subscribe();
+
+    tx.send(1).await.unwrap();
+    assert_eq!(fast.recv().await, Ok(1));
+
+    // `slow` has not seen the only buffered value yet.
+    assert!(
+        timeout(Duration::from_millis(20), tx.send(2))
+            .await
+            .is_err()
+    );
+
+    drop(slow);
+
+    timeout(Duration::from_secs(1), tx.send(2))
+        .await
+        .expect("sender should not wait on a dropped receiver")
+        .unwrap();
+    assert_eq!(fast.recv().await, Ok(2));
+}
+
+#[cadentis::test]
+async fn broadcast_send_without_receivers_fails() {
+    let (tx, rx) = broadcast::channel(1);
+    drop(rx);
+
+    assert_eq!(tx.send(5).await.unwrap_err().0, 5);
+}
@@ -0,0 +1,107 @@
+use cadentis::io::{AsyncWrite, AsyncWriteExt, BufWriter};
+use cadentis::net::{TcpListener, TcpStream};
+use cadentis::task;
+use std::io;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+/// A writer recording every byte and counting the calls it receives.
+#[derive(Default)]
+struct CountingWriter {
+    data: Vec<u8>,
+    writes: usize,
+    flushes: usize,
+}
+
+impl AsyncWrite for CountingWriter {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+        buffer: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        self.writes += 1;
+        self.data.extend_from_slice(buffer);
+        Poll::Ready(Ok(buffer.len()))
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.flushes += 1;
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+}
+
+#[cadentis::test]
+async fn buf_writer_coalesces_small_writes() {
+    let mut writer = BufWriter::with_capacity(64, CountingWriter::default());
+    let mut expected = Vec::new();
+
+    for i in 0..100u8 {
+        let chunk = [i; 4];
+        writer.write_all(&chunk).await.unwrap();
+        expected.extend_from_slice(&chunk);
+    }
+
+    writer.flush().await.unwrap();
+
+    let inner = writer.into_inner();
+
+    assert_eq!(inner.data, expected);
+    assert_eq!(inner.flushes, 1);
+    // 400 bytes through a 64-byte buffer: one write per full buffer.
+    assert!(inner.writes <= 7, "too many writes: {}", inner.writes);
+}
+
+#[cadentis::test]
+async fn buf_writer_bypasses_buffer_for_large_writes() {
+    let mut writer = BufWriter::with_capacity(16, CountingWriter::default());
+
+    writer.write_all(b"abc").await.unwrap();
+    writer.write_all(&[7u8; 32]).await.unwrap();
+
+    assert!(writer.buffer().is_empty());
+
+    let inner = writer.into_inner();
+
+    assert_eq!(inner.writes, 2);
+    assert_eq!(&inner.data[..3], b"abc");
+    assert_eq!(&inner.data[3..], &[7u8; 32]);
+}
+
+#[cadentis::test]
+async fn buf_writer_over_tcp_stream() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    let server = task::spawn(async move {
+        let (stream, _) = listener.accept().await.unwrap();
+        let mut received = Vec::new();
+        let mut buffer = [0u8; 256];
+
+        loop {
+            let n = stream.read(&mut buffer).await.unwrap();
+            if n == 0 {
+                break;
+            }
+            received.extend_from_slice(&buffer[..n]);
+        }
+
+        received
+    });
+
+    let stream = TcpStream::connect(&addr.to_string()).await.unwrap();
+    let (_, write_half) = stream.split();
+    let mut writer = BufWriter::new(write_half);
+
+    for _ in 0..50 {
+        writer.write_all(b"ping\n").await.unwrap();
+    }
+
+    writer.shutdown().await.unwrap();
+
+    let received = server.await.unwrap();
+    assert_eq!(received, b"ping\n".repeat(50));
+}
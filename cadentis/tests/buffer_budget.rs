@@ -0,0 +1,110 @@
+use cadentis::RuntimeBuilder;
+use cadentis::net::TcpStream;
+use cadentis::runtime::Runtime;
+use cadentis::task;
+use std::io::Write;
+use std::net::TcpListener as StdTcpListener;
+use std::thread;
+use std::time::{Duration, Instant};
+
+const CONNECTIONS: usize = 20;
+const PAYLOAD: usize = 1024 * 1024;
+const BUDGET: usize = 256 * 1024;
+
+/// Waits until the bytes buffered by the streams stop growing.
+fn wait_for_plateau(rt: &Runtime) -> usize {
+    let start = Instant::now();
+    let mut last = usize::MAX;
+
+    loop {
+        thread::sleep(Duration::from_millis(200));
+
+        let buffered = rt.metrics().reactor().buffered_bytes();
+        if buffered == last || start.elapsed() > Duration::from_secs(5) {
+            return buffered;
+        }
+        last = buffered;
+    }
+}
+
+#[test]
+fn slow_consumers_are_throttled_by_the_buffer_budget() {
+    let rt = RuntimeBuilder::new()
+        .worker_threads(2)
+        .max_total_buffer_bytes(BUDGET)
+        .build()
+        .unwrap();
+
+    let listener = StdTcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    // Peers sending far more than the budget as fast as they can.
+    let peers = thread::spawn(move || {
+        let payload: Vec<u8> = (0..PAYLOAD).map(|i| i as u8).collect();
+        let writers: Vec<_> = (0..CONNECTIONS)
+            .map(|_| {
+                let (mut peer, _) = listener.accept().unwrap();
+                let payload = payload.clone();
+                thread::spawn(move || peer.write_all(&payload).unwrap())
+            })
+            .collect();
+
+        for writer in writers {
+            writer.join().unwrap();
+        }
+    });
+
+    // Connections whose tasks do not read yet.
+    let streams: Vec<_> = rt.block_on(async move {
+        let mut streams = Vec::new();
+        for _ in 0..CONNECTIONS {
+            streams.push(TcpStream::connect(&addr.to_string()).await.unwrap());
+        }
+        streams
+    });
+
+    let buffered = wait_for_plateau(&rt);
+    let stats = rt.metrics().reactor();
+    assert!(buffered > 0);
+    assert!(
+        buffered <= BUDGET + 64 * 1024,
+        "{buffered} bytes buffered for a budget of {BUDGET}"
+    );
+    assert!(stats.paused_streams() > 0);
+
+    // Draining the buffers resumes the paused reads.
+    let received = rt.block_on(async move {
+        let readers: Vec<_> = streams
+            .into_iter()
+            .map(|stream| {
+                task::spawn(async move {
+                    let mut buffer = vec![0u8; 16 * 1024];
+                    let mut received = 0;
+
+                    while received < PAYLOAD {
+                        let n = stream.read(&mut buffer).await.unwrap();
+                        assert!(n > 0, "stream closed after {received} bytes");
+                        assert!(
+                            buffer[..n]
+                                .iter()
+                                .enumerate()
+                                .all(|(i, &b)| b == (received + i) as u8)
+                        );
+                        received += n;
+                    }
+                    received
+                })
+            })
+            .collect();
+
+        let mut received = 0;
+        for reader in readers {
+            received += reader.await.unwrap();
+        }
+        received
+    });
+
+    peers.join().unwrap();
+    assert_eq!(received, CONNECTIONS * PAYLOAD);
+    assert_eq!(rt.metrics().reactor().buffered_bytes(), 0);
+}
@@ -0,0 +1,106 @@
+use cadentis::select;
+use cadentis::sync::CancellationToken;
+use cadentis::task;
+use cadentis::time::{sleep, timeout};
+use std::time::Duration;
+
+#[cadentis::test]
+async fn cancelling_parent_wakes_parent_and_child_waiters() {
+    let parent = CancellationToken::new();
+    let child = parent.child_token();
+    let grandchild = child.child_token();
+
+    let waiters: Vec<_> = [parent.clone(), child.clone(), grandchild.clone()]
+        .into_iter()
+        .map(|token| {
+            task::spawn(async move {
+                token.cancelled().await;
+                token.is_cancelled()
+            })
+        })
+        .collect();
+
+    // Let every waiter register before cancelling.
+    sleep(Duration::from_millis(20)).await;
+    assert!(!grandchild.is_cancelled());
+
+    parent.cancel();
+
+    for waiter in waiters {
+        let cancelled = timeout(Duration::from_secs(1), waiter)
+            .await
+            .expect("waiter was not woken")
+            .unwrap();
+        assert!(cancelled);
+    }
+}
+
+#[cadentis::test]
+async fn cancelling_child_leaves_parent_running() {
+    let parent = CancellationToken::new();
+    let child = parent.child_token();
+
+    child.cancel();
+    child.cancelled().await;
+
+    assert!(child.is_cancelled());
+    assert!(!parent.is_cancelled());
+    assert!(
+        timeout(Duration::from_millis(20), parent.cancelled())
+            .await
+            .is_err()
+    );
+}
+
+#[cadentis::test]
+async fn child_of_cancelled_token_starts_cancelled() {
+    let parent = CancellationToken::new();
+    parent.cancel();
+
+    let child = parent.child_token();
+
+    assert!(child.is_cancelled());
+    child.cancelled().await;
+}
+
+#[cadentis::test]
+async fn grandchild_of_a_dropped_child_is_cancelled_with_the_root() {
+    let root = CancellationToken::new();
+    let grandchild = root.child_token().child_token();
+
+    root.cancel();
+
+    assert!(grandchild.is_cancelled());
+    grandchild.cancelled().await;
+}
+
+#[cadentis::test]
+async fn select_against_cancelled_exits_the_loop() {
+    let token = CancellationToken::new();
+    let worker_token = token.clone();
+
+    let worker = task::spawn(async move {
+        let mut ticks = 0;
+
+        loop {
+            let stop = select! {
+                worker_token.cancelled() => |_| true,
+                sleep(Duration::from_millis(5)) => |_| false,
+            };
+
+            if stop {
+                return ticks;
+            }
+            ticks += 1;
+        }
+    });
+
+    sleep(Duration::from_millis(30)).await;
+    token.cancel();
+
+    let ticks = timeout(Duration::from_secs(1), worker)
+        .await
+        .expect("worker did not stop")
+        .unwrap();
+    assert!(ticks > 0);
+}
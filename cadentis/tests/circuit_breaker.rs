@@ -0,0 +1,153 @@
+use cadentis::RuntimeBuilder;
+use cadentis::time::test::PausedClock;
+use cadentis::tools::{CircuitBreaker, CircuitOpen, CircuitState};
+use std::io;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+const COOLDOWN: Duration = Duration::from_secs(30);
+
+#[derive(Debug, PartialEq)]
+enum CallError {
+    Open,
+    Failed,
+}
+
+impl From<CircuitOpen> for CallError {
+    fn from(_: CircuitOpen) -> Self {
+        CallError::Open
+    }
+}
+
+/// Calls `breaker` with an operation counted in `calls` and returning
+/// `outcome`.
+async fn call(
+    breaker: &CircuitBreaker,
+    calls: &AtomicUsize,
+    outcome: bool,
+) -> Result<(), CallError> {
+    breaker
+        .call(|| async move {
+            calls.fetch_add(1, Ordering::SeqCst);
+            if outcome {
+                Ok(())
+            } else {
+                Err(CallError::Failed)
+            }
+        })
+        .await
+}
+
+#[test]
+fn repeated_failures_open_the_circuit() {
+    let clock = PausedClock::new();
+    let rt = RuntimeBuilder::new().clock(clock.clone()).build().unwrap();
+
+    rt.block_on(async move {
+        let breaker = CircuitBreaker::new(3, COOLDOWN);
+        let calls = AtomicUsize::new(0);
+
+        // A success resets the count of consecutive failures.
+        for _ in 0..2 {
+            assert_eq!(call(&breaker, &calls, false).await, Err(CallError::Failed));
+        }
+        call(&breaker, &calls, true).await.unwrap();
+
+        for _ in 0..3 {
+            assert_eq!(call(&breaker, &calls, false).await, Err(CallError::Failed));
+        }
+        assert_eq!(breaker.state(), CircuitState::Open);
+
+        // Calls are rejected without running during the cooldown.
+        for _ in 0..5 {
+            assert_eq!(call(&breaker, &calls, true).await, Err(CallError::Open));
+        }
+        clock.advance(COOLDOWN - Duration::from_secs(1));
+        assert_eq!(call(&breaker, &calls, true).await, Err(CallError::Open));
+
+        assert_eq!(calls.load(Ordering::SeqCst), 6);
+    });
+}
+
+#[test]
+fn half_open_trial_closes_or_reopens_the_circuit() {
+    let clock = PausedClock::new();
+    let rt = RuntimeBuilder::new().clock(clock.clone()).build().unwrap();
+
+    rt.block_on(async move {
+        let breaker = CircuitBreaker::new(1, COOLDOWN);
+        let calls = AtomicUsize::new(0);
+
+        call(&breaker, &calls, false).await.unwrap_err();
+        assert_eq!(breaker.state(), CircuitState::Open);
+
+        // A failed trial reopens the circuit for another cooldown.
+        clock.advance(COOLDOWN);
+        assert_eq!(breaker.state(), CircuitState::HalfOpen);
+        assert_eq!(call(&breaker, &calls, false).await, Err(CallError::Failed));
+        assert_eq!(call(&breaker, &calls, true).await, Err(CallError::Open));
+
+        // A successful trial closes it.
+        clock.advance(COOLDOWN);
+        call(&breaker, &calls, true).await.unwrap();
+        assert_eq!(breaker.state(), CircuitState::Closed);
+        call(&breaker, &calls, true).await.unwrap();
+
+        assert_eq!(calls.load(Ordering::SeqCst), 4);
+    });
+}
+
+#[test]
+fn half_open_lets_a_single_trial_through() {
+    let clock = PausedClock::new();
+    let rt = RuntimeBuilder::new().clock(clock.clone()).build().unwrap();
+
+    rt.block_on(async move {
+        let breaker = CircuitBreaker::new(1, COOLDOWN);
+        let calls = AtomicUsize::new(0);
+
+        call(&breaker, &calls, false).await.unwrap_err();
+        clock.advance(COOLDOWN);
+
+        // Another call arriving while the trial runs is rejected.
+        let result = breaker
+            .call(|| async {
+                let concurrent: io::Result<()> = breaker.call(|| async { Ok(()) }).await;
+                assert_eq!(
+                    concurrent.unwrap_err().to_string(),
+                    "circuit breaker is open"
+                );
+                Ok::<_, io::Error>(())
+            })
+            .await;
+        result.unwrap();
+
+        assert_eq!(breaker.state(), CircuitState::Closed);
+    });
+}
+
+#[test]
+fn success_of_a_call_started_before_the_trip_keeps_the_circuit_open() {
+    let clock = PausedClock::new();
+    let rt = RuntimeBuilder::new().clock(clock.clone()).build().unwrap();
+
+    rt.block_on(async move {
+        let breaker = CircuitBreaker::new(1, COOLDOWN);
+
+        // A call fails and trips the circuit while this one runs.
+        let result = breaker
+            .call(|| async {
+                let failed: io::Result<()> = breaker
+                    .call(|| async { Err(io::Error::other("down")) })
+                    .await;
+                failed.unwrap_err();
+                assert_eq!(breaker.state(), CircuitState::Open);
+
+                Ok::<_, io::Error>(())
+            })
+            .await;
+        result.unwrap();
+
+        assert_eq!(breaker.state(), CircuitState::Open);
+    });
+}
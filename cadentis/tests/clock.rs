@@ -0,0 +1,60 @@
+use cadentis::RuntimeBuilder;
+use cadentis::task;
+use cadentis::time::test::PausedClock;
+use cadentis::time::{self, Clock, sleep, timeout};
+use std::time::{Duration, Instant};
+
+#[test]
+fn paused_clock_resolves_sleep_without_waiting() {
+    let clock = PausedClock::new();
+    let rt = RuntimeBuilder::new().clock(clock.clone()).build().unwrap();
+
+    let real_start = Instant::now();
+
+    rt.block_on(async move {
+        let start = time::now();
+
+        // The deadline is fixed when the sleep is created.
+        let sleeper = task::spawn(sleep(Duration::from_secs(3600)));
+
+        clock.advance(Duration::from_secs(3600));
+
+        sleeper.await.unwrap();
+
+        assert_eq!(time::now() - start, Duration::from_secs(3600));
+    });
+
+    assert!(real_start.elapsed() < Duration::from_secs(5));
+}
+
+#[test]
+fn paused_clock_does_not_advance_on_its_own() {
+    let clock = PausedClock::new();
+    let before = clock.now();
+
+    std::thread::sleep(Duration::from_millis(10));
+
+    assert_eq!(clock.now(), before);
+
+    clock.advance(Duration::from_millis(250));
+    assert_eq!(clock.now() - before, Duration::from_millis(250));
+}
+
+#[test]
+fn paused_clock_drives_timeout() {
+    let clock = PausedClock::new();
+    let rt = RuntimeBuilder::new().clock(clock.clone()).build().unwrap();
+
+    let result = rt.block_on(async move {
+        let pending = task::spawn(timeout(
+            Duration::from_secs(60),
+            sleep(Duration::from_secs(120)),
+        ));
+
+        clock.advance(Duration::from_secs(60));
+
+        pending.await.unwrap()
+    });
+
+    assert_eq!(result, Err(()));
+}
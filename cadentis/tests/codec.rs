@@ -0,0 +1,154 @@
+use cadentis::codec::{Decoder, Framed, LengthDelimitedCodec, LinesCodec};
+use cadentis::io::AsyncWriteExt;
+use cadentis::net::{TcpListener, TcpStream};
+use cadentis::stream::StreamExt;
+use cadentis::task;
+use cadentis::time::sleep;
+use std::io;
+use std::net::Shutdown;
+use std::time::Duration;
+
+#[cadentis::test]
+async fn length_delimited_round_trip_over_loopback() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    let server = task::spawn(async move {
+        let (stream, _) = listener.accept().await.unwrap();
+        let mut framed = Framed::new(stream, LengthDelimitedCodec::new());
+
+        // Echo every frame back, reversed.
+        while let Some(frame) = framed.next().await {
+            let mut frame = frame.unwrap();
+            frame.reverse();
+            framed.send(frame).await.unwrap();
+        }
+    });
+
+    let stream = TcpStream::connect(&addr.to_string()).await.unwrap();
+    let mut framed = Framed::new(stream, LengthDelimitedCodec::new());
+    let messages: Vec<Vec<u8>> = vec![b"hello".to_vec(), Vec::new(), vec![7u8; 100_000]];
+
+    for message in &messages {
+        framed.send(message).await.unwrap();
+        let mut echoed = framed.next().await.unwrap().unwrap();
+        echoed.reverse();
+        assert_eq!(&echoed, message);
+    }
+
+    framed.get_ref().shutdown(Shutdown::Write).unwrap();
+    server.await.unwrap();
+}
+
+#[cadentis::test]
+async fn length_delimited_decodes_fragmented_reads() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    let client = task::spawn(async move {
+        let mut stream = TcpStream::connect(&addr.to_string()).await.unwrap();
+        let mut bytes = Vec::new();
+
+        for message in [&b"first"[..], b"second frame", b"3"] {
+            bytes.extend_from_slice(&(message.len() as u32).to_be_bytes());
+            bytes.extend_from_slice(message);
+        }
+
+        // Split the header and payloads across many small writes.
+        for chunk in bytes.chunks(3) {
+            stream.write_all(chunk).await.unwrap();
+            stream.flush().await.unwrap();
+            sleep(Duration::from_millis(2)).await;
+        }
+
+        stream.shutdown(Shutdown::Write).unwrap();
+    });
+
+    let (stream, _) = listener.accept().await.unwrap();
+    let framed = Framed::new(stream, LengthDelimitedCodec::new());
+    let frames: Vec<Vec<u8>> = framed.map(Result::unwrap).collect().await;
+
+    assert_eq!(
+        frames,
+        vec![b"first".to_vec(), b"second frame".to_vec(), b"3".to_vec()]
+    );
+
+    client.await.unwrap();
+}
+
+#[test]
+fn length_delimited_rejects_oversized_frames() {
+    let mut codec = LengthDelimitedCodec::new().max_frame_length(4);
+    let mut src = vec![0, 0, 0, 5, 1, 2, 3, 4, 5];
+
+    let err = codec.decode(&mut src).unwrap_err();
+
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+}
+
+#[test]
+fn length_delimited_reports_truncated_frame_at_eof() {
+    let mut codec = LengthDelimitedCodec::new();
+    let mut src = vec![0, 0, 0, 5, 1, 2];
+
+    assert!(codec.decode(&mut src).unwrap().is_none());
+
+    let err = codec.decode_eof(&mut src).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+}
+
+#[cadentis::test]
+async fn lines_codec_splits_lines() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    let client = task::spawn(async move {
+        let mut stream = TcpStream::connect(&addr.to_string()).await.unwrap();
+        stream.write_all(b"one\r\ntw").await.unwrap();
+        stream.flush().await.unwrap();
+        sleep(Duration::from_millis(5)).await;
+        stream.write_all(b"o\nthree").await.unwrap();
+        stream.flush().await.unwrap();
+        stream.shutdown(Shutdown::Write).unwrap();
+    });
+
+    let (stream, _) = listener.accept().await.unwrap();
+    let framed = Framed::new(stream, LinesCodec::new());
+    let lines: Vec<String> = framed.map(Result::unwrap).collect().await;
+
+    assert_eq!(lines, vec!["one", "two", "three"]);
+
+    client.await.unwrap();
+}
+
+#[test]
+fn lines_codec_enforces_max_length() {
+    let mut codec = LinesCodec::new_with_max_length(4);
+    let mut src = b"toolong".to_vec();
+
+    let err = codec.decode(&mut src).unwrap_err();
+
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+}
+
+#[test]
+fn lines_codec_skips_the_line_over_max_length() {
+    let mut codec = LinesCodec::new_with_max_length(4);
+
+    // The whole line is buffered.
+    let mut src = b"toolong\nok\n".to_vec();
+    assert!(codec.decode(&mut src).is_err());
+    assert_eq!(codec.decode(&mut src).unwrap().as_deref(), Some("ok"));
+
+    // Its newline arrives after the error.
+    let mut src = b"toolong".to_vec();
+    assert!(codec.decode(&mut src).is_err());
+    assert!(src.is_empty());
+
+    src.extend_from_slice(b"er still");
+    assert_eq!(codec.decode(&mut src).unwrap(), None);
+
+    src.extend_from_slice(b"\nnext\nrest");
+    assert_eq!(codec.decode(&mut src).unwrap().as_deref(), Some("next"));
+    assert_eq!(codec.decode_eof(&mut src).unwrap().as_deref(), Some("rest"));
+}
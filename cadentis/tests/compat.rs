@@ -0,0 +1,48 @@
+#![cfg(feature = "futures")]
+
+use cadentis::compat::{Compat, CompatExt};
+use cadentis::io::{AsyncReadExt, AsyncWriteExt};
+use cadentis::net::{TcpListener, TcpStream};
+use cadentis::stream::StreamExt;
+use cadentis::task;
+
+#[cadentis::test]
+async fn tcp_stream_as_futures_async_read() {
+    use futures::AsyncReadExt as _;
+
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    let server = task::spawn(async move {
+        let (mut stream, _) = listener.accept().await.unwrap();
+        stream.write_all(b"hello from cadentis").await.unwrap();
+        AsyncWriteExt::shutdown(&mut stream).await.unwrap();
+    });
+
+    let stream = TcpStream::connect(&addr.to_string()).await.unwrap();
+    let mut reader = stream.compat();
+    let mut received = Vec::new();
+
+    reader.read_to_end(&mut received).await.unwrap();
+
+    assert_eq!(received, b"hello from cadentis");
+    server.await.unwrap();
+}
+
+#[cadentis::test]
+async fn futures_async_read_as_cadentis_async_read() {
+    let mut reader = Compat::new(futures::io::Cursor::new(b"abcdef".to_vec()));
+    let mut buffer = [0u8; 6];
+
+    reader.read_exact(&mut buffer).await.unwrap();
+
+    assert_eq!(&buffer, b"abcdef");
+}
+
+#[cadentis::test]
+async fn futures_stream_as_cadentis_stream() {
+    let stream = Compat::from(futures::stream::iter(1..=3));
+    let values: Vec<i32> = stream.collect().await;
+
+    assert_eq!(values, vec![1, 2, 3]);
+}
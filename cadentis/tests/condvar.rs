@@ -0,0 +1,72 @@
+use cadentis::sync::{Condvar, Mutex};
+use cadentis::task;
+use cadentis::time::sleep;
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+#[cadentis::test]
+async fn consumer_waits_while_queue_is_empty() {
+    let state = Arc::new((Mutex::new(VecDeque::new()), Condvar::new()));
+
+    let consumer = {
+        let state = state.clone();
+        task::spawn(async move {
+            let (queue, condvar) = &*state;
+            let mut received = Vec::new();
+
+            while received.len() < 3 {
+                let mut queue = condvar
+                    .wait_while(queue.lock().await, |queue| queue.is_empty())
+                    .await;
+                received.push(queue.pop_front().unwrap());
+            }
+
+            received
+        })
+    };
+
+    let (queue, condvar) = &*state;
+    for job in 1..=3 {
+        sleep(Duration::from_millis(10)).await;
+        queue.lock().await.push_back(job);
+        condvar.notify_one();
+    }
+
+    assert_eq!(consumer.await.unwrap(), vec![1, 2, 3]);
+}
+
+#[cadentis::test]
+async fn notify_all_wakes_every_waiter() {
+    let state = Arc::new((Mutex::new(false), Condvar::new()));
+    let done = Arc::new(AtomicUsize::new(0));
+
+    let waiters: Vec<_> = (0..4)
+        .map(|_| {
+            let state = state.clone();
+            let done = done.clone();
+            task::spawn(async move {
+                let (ready, condvar) = &*state;
+                let ready = condvar.wait_while(ready.lock().await, |ready| !ready).await;
+                assert!(*ready);
+                done.fetch_add(1, Ordering::SeqCst);
+            })
+        })
+        .collect();
+
+    // A notification while the condition does not hold is not enough.
+    sleep(Duration::from_millis(20)).await;
+    let (ready, condvar) = &*state;
+    condvar.notify_all();
+    sleep(Duration::from_millis(20)).await;
+    assert_eq!(done.load(Ordering::SeqCst), 0);
+
+    *ready.lock().await = true;
+    condvar.notify_all();
+
+    for waiter in waiters {
+        waiter.await.unwrap();
+    }
+    assert_eq!(done.load(Ordering::SeqCst), 4);
+}
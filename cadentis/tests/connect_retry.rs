@@ -0,0 +1,61 @@
+use cadentis::net::TcpStream;
+use cadentis::time::timeout;
+use cadentis::tools::RetryConfig;
+use std::io::{self, Read};
+use std::net::TcpListener as StdTcpListener;
+use std::thread;
+use std::time::Duration;
+
+#[cadentis::test]
+async fn connect_retry_succeeds_once_the_server_is_up() {
+    // Reserve a port, then leave it closed: connects are refused until
+    // the server comes up on it.
+    let addr = StdTcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap();
+
+    let server = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(100));
+
+        let listener = StdTcpListener::bind(addr).unwrap();
+        let (mut socket, _) = listener.accept().unwrap();
+        let mut greeting = [0u8; 5];
+        socket.read_exact(&mut greeting).unwrap();
+        greeting
+    });
+
+    let policy = RetryConfig::new(100).interval(Duration::from_millis(10));
+    let stream = TcpStream::connect_retry(addr, policy).await.unwrap();
+
+    stream.write_all(b"hello").await.unwrap();
+    assert_eq!(&server.join().unwrap(), b"hello");
+}
+
+#[cadentis::test]
+async fn connect_retry_gives_up_after_the_configured_retries() {
+    let addr = StdTcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap();
+
+    let policy = RetryConfig::new(3).interval(Duration::from_millis(1));
+    let error = TcpStream::connect_retry(addr, policy).await.err().unwrap();
+
+    assert_eq!(error.kind(), io::ErrorKind::ConnectionRefused);
+}
+
+#[cadentis::test]
+async fn connect_retry_fails_fast_on_invalid_addresses() {
+    // Retrying would take well over a minute.
+    let policy = RetryConfig::new(100).interval(Duration::from_secs(1));
+
+    let result = timeout(
+        Duration::from_secs(5),
+        TcpStream::connect_retry("no port here", policy),
+    )
+    .await
+    .expect("an invalid address was retried");
+
+    assert!(result.is_err());
+}
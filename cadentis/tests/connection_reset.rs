@@ -0,0 +1,70 @@
+use cadentis::net::TcpStream;
+use cadentis::time::timeout;
+use std::io;
+use std::net::TcpListener as StdTcpListener;
+use std::time::Duration;
+
+/// Restores the default action of `SIGPIPE`, which kills the process.
+///
+/// Rust programs ignore `SIGPIPE` by default, which would hide a write
+/// raising it.
+#[cfg(target_os = "linux")]
+fn restore_default_sigpipe() {
+    const SIGPIPE: i32 = 13;
+    const SIG_DFL: usize = 0;
+
+    unsafe extern "C" {
+        fn signal(signum: i32, handler: usize) -> usize;
+    }
+
+    // SAFETY: `SIG_DFL` is a valid disposition for `SIGPIPE`.
+    unsafe { signal(SIGPIPE, SIG_DFL) };
+}
+
+#[cfg(not(target_os = "linux"))]
+fn restore_default_sigpipe() {}
+
+fn is_reset(err: &io::Error) -> bool {
+    matches!(
+        err.kind(),
+        io::ErrorKind::BrokenPipe | io::ErrorKind::ConnectionReset
+    )
+}
+
+#[cadentis::test]
+async fn writes_to_a_closed_peer_fail_instead_of_raising_sigpipe() {
+    restore_default_sigpipe();
+
+    let listener = StdTcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    let stream = TcpStream::connect(addr).await.unwrap();
+    drop(listener.accept().unwrap());
+
+    // The first writes may still be accepted by the kernel: the peer only
+    // answers them with a reset, after which writing fails. With
+    // `SIGPIPE` back to its default action, raising it would kill the
+    // test process here.
+    let error = timeout(Duration::from_secs(5), async {
+        loop {
+            if let Err(err) = stream.write_all(&[1u8; 4096]).await {
+                break err;
+            }
+            cadentis::time::sleep(Duration::from_millis(1)).await;
+        }
+    })
+    .await
+    .expect("writes kept succeeding on a closed connection");
+
+    assert!(is_reset(&error), "unexpected error: {error:?}");
+
+    // Reads report the reset as well rather than waiting for data.
+    let mut buffer = [0u8; 16];
+    let read = timeout(Duration::from_secs(5), stream.read(&mut buffer))
+        .await
+        .expect("read hung on a reset connection");
+    match read {
+        Ok(n) => assert_eq!(n, 0),
+        Err(err) => assert!(is_reset(&err), "unexpected error: {err:?}"),
+    }
+}
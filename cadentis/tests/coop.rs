@@ -0,0 +1,70 @@
+use cadentis::{RuntimeBuilder, task};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+#[test]
+fn long_loop_yields_once_its_budget_is_spent() {
+    let rt = RuntimeBuilder::new()
+        .worker_threads(1)
+        .time_slice(Duration::from_millis(5))
+        .build()
+        .unwrap();
+
+    rt.block_on(async {
+        // A loop never awaiting anything else: on a single worker, the
+        // task it spawns only runs if the loop yields.
+        let busy = task::spawn(async {
+            let ran = Arc::new(AtomicBool::new(false));
+            let other = {
+                let ran = ran.clone();
+                task::spawn(async move { ran.store(true, Ordering::SeqCst) })
+            };
+
+            let start = Instant::now();
+            let mut yields = 0;
+
+            while !ran.load(Ordering::SeqCst) {
+                assert!(
+                    start.elapsed() < Duration::from_secs(5),
+                    "the other task never ran"
+                );
+
+                if !task::has_budget() {
+                    yields += 1;
+                    task::coop_yield().await;
+                }
+            }
+
+            other.await.unwrap();
+            yields
+        });
+
+        assert!(busy.await.unwrap() > 0);
+    });
+}
+
+#[test]
+fn budget_is_fresh_at_each_poll_and_unlimited_outside_tasks() {
+    assert!(task::has_budget());
+
+    let rt = RuntimeBuilder::new()
+        .worker_threads(1)
+        .time_slice(Duration::from_millis(5))
+        .build()
+        .unwrap();
+
+    rt.block_on(async {
+        task::spawn(async {
+            assert!(task::has_budget());
+
+            std::thread::sleep(Duration::from_millis(10));
+            assert!(!task::has_budget());
+
+            task::coop_yield().await;
+            assert!(task::has_budget());
+        })
+        .await
+        .unwrap();
+    });
+}
@@ -0,0 +1,152 @@
+use cadentis::time::sleep;
+use cadentis::{RuntimeBuilder, task, yield_now};
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+#[test]
+fn current_thread_block_on_accepts_non_send_future() {
+    let runtime = RuntimeBuilder::current_thread().build().unwrap();
+    let log = Rc::new(RefCell::new(Vec::new()));
+
+    let result = runtime.block_on({
+        let log = log.clone();
+
+        async move {
+            log.borrow_mut().push(1);
+            yield_now().await;
+            log.borrow_mut().push(2);
+            sleep(Duration::from_millis(10)).await;
+            log.borrow_mut().push(3);
+
+            log.borrow().len()
+        }
+    });
+
+    assert_eq!(result, 3);
+    assert_eq!(*log.borrow(), vec![1, 2, 3]);
+}
+
+#[test]
+fn current_thread_runs_spawned_tasks_on_the_calling_thread() {
+    let runtime = RuntimeBuilder::current_thread().build().unwrap();
+    let caller = std::thread::current().id();
+
+    let ids = runtime.block_on(async {
+        let handles: Vec<_> = (0..8)
+            .map(|_| {
+                task::spawn(async {
+                    yield_now().await;
+                    std::thread::current().id()
+                })
+            })
+            .collect();
+
+        let mut ids = Vec::new();
+        for handle in handles {
+            ids.push(handle.await.unwrap());
+        }
+        ids
+    });
+
+    assert_eq!(ids.len(), 8);
+    assert!(ids.iter().all(|id| *id == caller));
+}
+
+#[test]
+fn current_thread_block_on_can_be_called_repeatedly() {
+    let runtime = RuntimeBuilder::current_thread().build().unwrap();
+    let counter = Rc::new(RefCell::new(0));
+
+    for _ in 0..3 {
+        runtime.block_on(async {
+            let value = task::spawn(async { 1 }).await.unwrap();
+            *counter.borrow_mut() += value;
+        });
+    }
+
+    assert_eq!(*counter.borrow(), 3);
+    assert_eq!(runtime.metrics().num_workers(), 1);
+}
+
+#[test]
+fn current_thread_runs_tasks_spawned_before_block_on() {
+    let runtime = RuntimeBuilder::current_thread().build().unwrap();
+    let ran = Arc::new(AtomicUsize::new(0));
+
+    for _ in 0..4 {
+        let ran = ran.clone();
+        runtime.spawn(async move {
+            ran.fetch_add(1, Ordering::SeqCst);
+        });
+    }
+
+    // The root future completes at its first poll.
+    runtime.block_on(async {});
+    assert_eq!(ran.load(Ordering::SeqCst), 4);
+
+    let handle = runtime.spawn(async {
+        yield_now().await;
+        7
+    });
+    assert_eq!(runtime.block_on(async move { handle.await.unwrap() }), 7);
+}
+
+#[test]
+fn current_thread_within_a_worker_keeps_its_tasks() {
+    let workers = RuntimeBuilder::new().worker_threads(2).build().unwrap();
+
+    let ids = workers.block_on(async {
+        task::spawn(async {
+            let runtime = RuntimeBuilder::current_thread().build().unwrap();
+            let caller = std::thread::current().id();
+
+            let ids = runtime.block_on(async {
+                let handle = task::spawn(async { std::thread::current().id() });
+                handle.await.unwrap()
+            });
+
+            (caller, ids)
+        })
+        .await
+        .unwrap()
+    });
+
+    assert_eq!(ids.0, ids.1);
+}
+
+#[test]
+fn current_thread_block_on_leaves_the_runtime_when_the_future_panics() {
+    let runtime = RuntimeBuilder::current_thread().build().unwrap();
+
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        runtime.block_on(async { panic!("future failed") })
+    }));
+    assert!(result.is_err());
+
+    assert!(cadentis::runtime::Handle::try_current().is_none());
+}
+
+#[test]
+fn current_thread_within_a_worker_keeps_its_worker_id_when_the_future_panics() {
+    let workers = RuntimeBuilder::new().worker_threads(2).build().unwrap();
+
+    workers.block_on(async {
+        task::spawn(async {
+            let worker = task::current_worker_id();
+            assert!(worker.is_some());
+
+            let runtime = RuntimeBuilder::current_thread().build().unwrap();
+            let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+                runtime.block_on(async { panic!("future failed") })
+            }));
+            assert!(result.is_err());
+
+            assert_eq!(task::current_worker_id(), worker);
+        })
+        .await
+        .unwrap();
+    });
+}
@@ -0,0 +1,84 @@
+use cadentis::time::{self, Elapsed, sleep, with_deadline};
+use std::future::Future;
+use std::panic::{self, AssertUnwindSafe};
+use std::pin::pin;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::task::{Context, Waker};
+use std::time::{Duration, Instant};
+
+#[cadentis::test]
+async fn with_deadline_cancels_multi_step_future_midway() {
+    let steps = Arc::new(AtomicUsize::new(0));
+    let steps_inner = steps.clone();
+
+    let start = Instant::now();
+    let result = with_deadline(start + Duration::from_millis(100), async move {
+        for _ in 0..10 {
+            sleep(Duration::from_millis(40)).await;
+            steps_inner.fetch_add(1, Ordering::SeqCst);
+        }
+    })
+    .await;
+
+    assert_eq!(result, Err(Elapsed));
+
+    let steps = steps.load(Ordering::SeqCst);
+    assert!((1..10).contains(&steps), "ran {steps} steps");
+    assert!(start.elapsed() < Duration::from_millis(400));
+}
+
+#[cadentis::test]
+async fn with_deadline_completes_in_time() {
+    let result = with_deadline(Instant::now() + Duration::from_secs(5), async {
+        sleep(Duration::from_millis(5)).await;
+        7
+    })
+    .await;
+
+    assert_eq!(result, Ok(7));
+}
+
+#[cadentis::test]
+async fn with_deadline_propagates_to_nested_calls() {
+    assert_eq!(time::deadline(), None);
+
+    let outer = Instant::now() + Duration::from_millis(500);
+
+    let result = with_deadline(outer, async move {
+        assert_eq!(time::deadline(), Some(outer));
+        assert!(time::remaining().unwrap() <= Duration::from_millis(500));
+
+        // A nested, later deadline never extends the enclosing one.
+        with_deadline(outer + Duration::from_secs(10), async move {
+            assert_eq!(time::deadline(), Some(outer));
+        })
+        .await
+        .unwrap();
+
+        let inner = Instant::now() + Duration::from_millis(50);
+        with_deadline(inner, async move {
+            assert_eq!(time::deadline(), Some(inner));
+        })
+        .await
+        .unwrap();
+    })
+    .await;
+
+    assert_eq!(result, Ok(()));
+    assert_eq!(time::deadline(), None);
+}
+
+#[cadentis::test]
+async fn with_deadline_is_cleared_when_the_future_panics() {
+    let mut future = pin!(with_deadline(
+        Instant::now() + Duration::from_secs(5),
+        async { panic!("step failed") }
+    ));
+    let mut cx = Context::from_waker(Waker::noop());
+
+    let poll = panic::catch_unwind(AssertUnwindSafe(|| future.as_mut().poll(&mut cx)));
+
+    assert!(poll.is_err());
+    assert_eq!(time::deadline(), None);
+}
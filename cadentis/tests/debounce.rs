@@ -0,0 +1,65 @@
+use cadentis::RuntimeBuilder;
+use cadentis::stream::{Stream, StreamExt};
+use cadentis::sync::mpsc;
+use cadentis::time::debounce;
+use cadentis::time::test::PausedClock;
+use std::future::poll_fn;
+use std::pin::Pin;
+use std::task::Poll;
+use std::time::Duration;
+
+const QUIET: Duration = Duration::from_millis(200);
+
+/// Returns `true` if `stream` has an item or its end ready.
+async fn is_ready<S: Stream + Unpin>(stream: &mut S) -> bool {
+    poll_fn(|cx| Poll::Ready(Pin::new(&mut *stream).poll_next(cx).is_ready())).await
+}
+
+#[test]
+fn burst_of_triggers_emits_once_after_quiet_period() {
+    let clock = PausedClock::new();
+    let rt = RuntimeBuilder::new().clock(clock.clone()).build().unwrap();
+
+    rt.block_on(async move {
+        let (tx, rx) = mpsc::unbounded_channel();
+        let mut events = debounce(rx, QUIET);
+
+        for trigger in 1..=5 {
+            tx.send(trigger).await.unwrap();
+            assert!(!is_ready(&mut events).await);
+            clock.advance(QUIET / 2);
+        }
+
+        // Each trigger pushed the deadline back: it is still ahead.
+        assert!(!is_ready(&mut events).await);
+
+        clock.advance(QUIET / 2);
+        assert_eq!(events.next().await, Some(5));
+
+        // Nothing else fires while the source stays silent.
+        clock.advance(QUIET * 10);
+        assert!(!is_ready(&mut events).await);
+
+        drop(tx);
+        assert_eq!(events.next().await, None);
+    });
+}
+
+#[test]
+fn pending_trigger_is_emitted_when_source_ends() {
+    let clock = PausedClock::new();
+    let rt = RuntimeBuilder::new().clock(clock.clone()).build().unwrap();
+
+    rt.block_on(async move {
+        let (tx, rx) = mpsc::unbounded_channel();
+        let mut events = debounce(rx, QUIET);
+
+        tx.send("saved").await.unwrap();
+        tx.send("saved again").await.unwrap();
+        assert!(!is_ready(&mut events).await);
+        drop(tx);
+
+        assert_eq!(events.next().await, Some("saved again"));
+        assert_eq!(events.next().await, None);
+    });
+}
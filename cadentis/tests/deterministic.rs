@@ -0,0 +1,58 @@
+use cadentis::RuntimeBuilder;
+use cadentis::task;
+use cadentis::yield_now;
+use std::sync::{Arc, Mutex};
+
+/// Runs interleaved tasks and returns the order of their steps.
+fn completion_order(seed: u64) -> Vec<(usize, usize)> {
+    let rt = RuntimeBuilder::new()
+        .worker_threads(4)
+        .deterministic(seed)
+        .build()
+        .unwrap();
+
+    assert_eq!(rt.metrics().num_workers(), 1);
+
+    rt.block_on(async {
+        let order = Arc::new(Mutex::new(Vec::new()));
+
+        let handles: Vec<_> = (0..8)
+            .map(|id| {
+                let order = order.clone();
+                task::spawn(async move {
+                    for step in 0..4 {
+                        order.lock().unwrap().push((id, step));
+                        yield_now().await;
+                    }
+                })
+            })
+            .collect();
+
+        for handle in handles {
+            handle.await.unwrap();
+        }
+
+        order.lock().unwrap().clone()
+    })
+}
+
+#[test]
+fn deterministic_same_seed_same_order() {
+    let first = completion_order(42);
+
+    assert_eq!(first.len(), 32);
+
+    for _ in 0..5 {
+        assert_eq!(completion_order(42), first);
+    }
+}
+
+#[test]
+fn deterministic_seed_drives_the_order() {
+    let reference = completion_order(1);
+
+    assert!(
+        (2..10).any(|seed| completion_order(seed) != reference),
+        "every seed produced the same interleaving"
+    );
+}
@@ -0,0 +1,102 @@
+use cadentis::codec::{Framed, LinesCodec};
+use cadentis::io::{AsyncReadExt, AsyncWriteExt, duplex};
+use cadentis::stream::StreamExt;
+use cadentis::task;
+use cadentis::time::timeout;
+use std::io;
+use std::time::Duration;
+
+#[cadentis::test]
+async fn duplex_carries_bytes_both_ways() {
+    let (mut client, mut server) = duplex(64);
+
+    client.write_all(b"ping").await.unwrap();
+    let mut buffer = [0u8; 4];
+    server.read_exact(&mut buffer).await.unwrap();
+    assert_eq!(&buffer, b"ping");
+
+    server.write_all(b"pong").await.unwrap();
+    client.read_exact(&mut buffer).await.unwrap();
+    assert_eq!(&buffer, b"pong");
+}
+
+#[cadentis::test]
+async fn duplex_write_waits_for_the_reader_when_full() {
+    let (mut client, mut server) = duplex(8);
+
+    client.write_all(b"12345678").await.unwrap();
+
+    // The buffer is full: the next write waits.
+    let blocked = timeout(Duration::from_millis(20), client.write(b"9")).await;
+    assert!(blocked.is_err(), "write did not wait for room");
+
+    let mut buffer = [0u8; 4];
+    server.read_exact(&mut buffer).await.unwrap();
+    assert_eq!(&buffer, b"1234");
+
+    // Reading made room for four more bytes.
+    assert_eq!(client.write(b"9abcdef").await.unwrap(), 4);
+
+    let mut rest = [0u8; 8];
+    server.read_exact(&mut rest).await.unwrap();
+    assert_eq!(&rest, b"56789abc");
+}
+
+#[cadentis::test]
+async fn duplex_streams_more_than_its_capacity() {
+    let (mut client, mut server) = duplex(16);
+    let payload: Vec<u8> = (0..10_000u32).map(|i| i as u8).collect();
+    let expected = payload.clone();
+
+    let writer = task::spawn(async move {
+        client.write_all(&payload).await.unwrap();
+        client.shutdown().await.unwrap();
+    });
+
+    let mut received = Vec::new();
+    let mut buffer = [0u8; 100];
+    loop {
+        let n = server.read(&mut buffer).await.unwrap();
+        if n == 0 {
+            break;
+        }
+        received.extend_from_slice(&buffer[..n]);
+    }
+
+    writer.await.unwrap();
+    assert_eq!(received, expected);
+}
+
+#[cadentis::test]
+async fn duplex_reports_closed_ends() {
+    let (mut client, mut server) = duplex(8);
+
+    client.write_all(b"bye").await.unwrap();
+    drop(client);
+
+    // Buffered bytes are still delivered before the end of stream.
+    let mut buffer = [0u8; 8];
+    assert_eq!(server.read(&mut buffer).await.unwrap(), 3);
+    assert_eq!(server.read(&mut buffer).await.unwrap(), 0);
+
+    let error = server.write(b"anyone?").await.unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::BrokenPipe);
+}
+
+#[cadentis::test]
+async fn duplex_drives_a_codec_without_sockets() {
+    let (client, server) = duplex(4);
+
+    let mut client = Framed::new(client, LinesCodec::new());
+    let server = Framed::new(server, LinesCodec::new());
+
+    let reader =
+        task::spawn(async move { server.map(Result::unwrap).collect::<Vec<String>>().await });
+
+    for line in ["first", "second line", "third"] {
+        client.send(line.to_string()).await.unwrap();
+    }
+    drop(client);
+
+    assert_eq!(reader.await.unwrap(), vec!["first", "second line", "third"]);
+}
@@ -1,5 +1,6 @@
-use cadentis::fs::File;
-use std::time::{SystemTime, UNIX_EPOCH};
+use cadentis::fs::{File, truncate};
+use cadentis::time::timeout;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 #[cadentis::test]
 async fn file_read_write_roundtrip() {
@@ -28,3 +29,167 @@ async fn file_read_write_roundtrip() {
 
     let _ = std::fs::remove_file(path);
 }
+
+#[cadentis::test]
+async fn file_set_len_grows_with_zeros_and_shrinks() {
+    let unique = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("clock drift")
+        .as_nanos();
+
+    let path = std::env::temp_dir().join(format!(
+        "reactor-set-len-{}-{}.tmp",
+        std::process::id(),
+        unique
+    ));
+    let path_string = path.to_string_lossy().into_owned();
+
+    let file = File::create(&path_string).await.unwrap();
+    file.write_all(b"data").await.unwrap();
+
+    file.set_len(4096).unwrap();
+    assert_eq!(std::fs::metadata(&path).unwrap().len(), 4096);
+
+    let reader = File::open(&path_string).await.unwrap();
+    let mut contents = Vec::new();
+    let mut buffer = [0u8; 1024];
+    loop {
+        let n = reader.read(&mut buffer).await.unwrap();
+        if n == 0 {
+            break;
+        }
+        contents.extend_from_slice(&buffer[..n]);
+    }
+
+    assert_eq!(contents.len(), 4096);
+    assert_eq!(&contents[..4], b"data");
+    assert!(contents[4..].iter().all(|&b| b == 0));
+
+    file.set_len(2).unwrap();
+    assert_eq!(std::fs::metadata(&path).unwrap().len(), 2);
+
+    truncate(&path, 0).await.unwrap();
+    assert_eq!(std::fs::metadata(&path).unwrap().len(), 0);
+
+    let _ = std::fs::remove_file(path);
+}
+
+#[cadentis::test]
+async fn file_open_missing_file_is_not_found() {
+    let path = std::env::temp_dir().join(format!(
+        "reactor-missing-{}/does-not-exist.tmp",
+        std::process::id()
+    ));
+    let path_string = path.to_string_lossy().into_owned();
+
+    let error = File::open(&path_string)
+        .await
+        .err()
+        .expect("opened a missing file");
+    assert_eq!(error.kind(), std::io::ErrorKind::NotFound);
+    assert!(error.raw_os_error().is_some());
+
+    // The parent directory does not exist either.
+    let error = File::create(&path_string)
+        .await
+        .err()
+        .expect("created in a missing directory");
+    assert_eq!(error.kind(), std::io::ErrorKind::NotFound);
+
+    let error = File::open("bad\0path")
+        .await
+        .err()
+        .expect("opened a path with a NUL byte");
+    assert_eq!(error.kind(), std::io::ErrorKind::InvalidInput);
+}
+
+#[cadentis::test]
+async fn file_close_flushes_written_data() {
+    let path = std::env::temp_dir().join(format!("reactor-close-{}.tmp", std::process::id()));
+    let path_string = path.to_string_lossy().into_owned();
+
+    let file = File::create(&path_string).await.unwrap();
+    file.write_all(b"durable").await.unwrap();
+    file.close().await.unwrap();
+
+    assert_eq!(std::fs::read(&path).unwrap(), b"durable");
+
+    let _ = std::fs::remove_file(path);
+}
+
+#[cfg(target_os = "linux")]
+#[cadentis::test]
+async fn file_close_releases_the_descriptor() {
+    let path = std::env::temp_dir().join(format!("reactor-close-fd-{}.tmp", std::process::id()));
+
+    // Descriptors of the process currently open on `path`.
+    let open_on_path = || {
+        std::fs::read_dir("/proc/self/fd")
+            .unwrap()
+            .filter_map(|entry| std::fs::read_link(entry.ok()?.path()).ok())
+            .filter(|target| *target == path)
+            .count()
+    };
+
+    let file = File::create(&path.to_string_lossy()).await.unwrap();
+    assert_eq!(open_on_path(), 1);
+
+    file.close().await.unwrap();
+    assert_eq!(open_on_path(), 0);
+
+    let _ = std::fs::remove_file(&path);
+}
+
+#[cfg(target_os = "linux")]
+#[cadentis::test]
+async fn file_close_reports_flush_errors() {
+    // Writes to `/dev/null` succeed, but it cannot be synced: the flush
+    // done by `close` fails where dropping the file would stay silent.
+    let file = File::create("/dev/null").await.unwrap();
+    file.write_all(b"lost").await.unwrap();
+
+    let error = file.close().await.unwrap_err();
+    assert_eq!(error.kind(), std::io::ErrorKind::InvalidInput);
+}
+
+#[cfg(target_os = "linux")]
+#[cadentis::test]
+async fn file_write_of_a_large_buffer_survives_a_slow_reader() {
+    use std::io::Read;
+    use std::os::fd::AsRawFd;
+    use std::thread;
+
+    const LEN: usize = 4 * 1024 * 1024;
+
+    // A pipe fills up after a few kilobytes, so the write has to wait for
+    // the reader many times over.
+    let (mut reader, writer) = std::io::pipe().unwrap();
+    let file = File::create(&format!("/proc/self/fd/{}", writer.as_raw_fd()))
+        .await
+        .unwrap();
+    drop(writer);
+
+    let slow_reader = thread::spawn(move || {
+        let mut received = Vec::with_capacity(LEN);
+        let mut chunk = [0u8; 64 * 1024];
+
+        loop {
+            let n = reader.read(&mut chunk).unwrap();
+            if n == 0 {
+                return received;
+            }
+            received.extend_from_slice(&chunk[..n]);
+            thread::sleep(Duration::from_micros(200));
+        }
+    });
+
+    let payload: Vec<u8> = (0..LEN).map(|i| (i % 253) as u8).collect();
+    let written = timeout(Duration::from_secs(20), file.write(&payload))
+        .await
+        .expect("write stalled after the pipe filled up")
+        .unwrap();
+    drop(file);
+
+    assert_eq!(written, LEN);
+    assert!(slow_reader.join().unwrap() == payload);
+}
@@ -0,0 +1,94 @@
+use cadentis::fs::File;
+use cadentis::io::AsyncWriteExt;
+use cadentis::net::TcpStream;
+use cadentis::time::timeout;
+use std::io::{self, Read};
+use std::net::TcpListener as StdTcpListener;
+use std::thread;
+use std::time::Duration;
+
+const LEN: usize = 4 * 1024 * 1024;
+
+#[cadentis::test]
+async fn tcp_flush_hands_every_byte_to_the_peer() {
+    let listener = StdTcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    let peer = thread::spawn(move || {
+        let (mut socket, _) = listener.accept().unwrap();
+        let mut received = vec![0u8; LEN];
+        socket.read_exact(&mut received).unwrap();
+        received
+    });
+
+    let mut stream = TcpStream::connect(addr).await.unwrap();
+    let payload: Vec<u8> = (0..LEN).map(|i| (i % 251) as u8).collect();
+
+    // Through `AsyncWrite`, the write only queues the bytes.
+    AsyncWriteExt::write_all(&mut stream, &payload)
+        .await
+        .unwrap();
+    AsyncWriteExt::flush(&mut stream).await.unwrap();
+
+    // Nothing is left to the reactor once the flush resolved: the peer
+    // gets everything without any further action from this side.
+    assert_eq!(stream.bytes_written(), LEN as u64);
+    assert!(peer.join().unwrap() == payload);
+}
+
+#[cadentis::test]
+async fn tcp_flush_fails_once_the_peer_is_gone() {
+    let listener = StdTcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    let mut stream = TcpStream::connect(addr).await.unwrap();
+    drop(listener.accept().unwrap());
+
+    let payload = vec![7u8; LEN];
+    AsyncWriteExt::write_all(&mut stream, &payload)
+        .await
+        .unwrap();
+
+    // The closed peer resets the connection: the flush reports it rather
+    // than waiting for output that can no longer be written.
+    let result = timeout(Duration::from_secs(5), AsyncWriteExt::flush(&mut stream))
+        .await
+        .expect("flush hung on a closed connection");
+    assert!(matches!(
+        result.unwrap_err().kind(),
+        io::ErrorKind::BrokenPipe | io::ErrorKind::ConnectionReset
+    ));
+}
+
+#[cadentis::test]
+async fn file_flush_hands_bytes_to_the_os() {
+    let path = std::env::temp_dir().join(format!("cadentis-flush-{}.tmp", std::process::id()));
+    let path_string = path.to_string_lossy().into_owned();
+
+    let mut file = File::create(&path_string).await.unwrap();
+    AsyncWriteExt::write_all(&mut file, b"first ")
+        .await
+        .unwrap();
+    AsyncWriteExt::write_all(&mut file, b"second")
+        .await
+        .unwrap();
+    AsyncWriteExt::flush(&mut file).await.unwrap();
+
+    assert_eq!(std::fs::read(&path).unwrap(), b"first second");
+
+    let _ = std::fs::remove_file(path);
+}
+
+#[cfg(target_os = "linux")]
+#[cadentis::test]
+async fn file_flush_syncs_when_asked() {
+    // `/dev/null` accepts writes but cannot be synced, which shows whether
+    // the flush tried to.
+    let mut file = File::create("/dev/null").await.unwrap();
+    AsyncWriteExt::write_all(&mut file, b"lost").await.unwrap();
+    AsyncWriteExt::flush(&mut file).await.unwrap();
+
+    file.set_sync_on_flush(true);
+    let error = AsyncWriteExt::flush(&mut file).await.unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+}
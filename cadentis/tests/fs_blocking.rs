@@ -0,0 +1,64 @@
+use cadentis::fs::blocking;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+#[cadentis::test]
+async fn offloaded_set_permissions_is_visible_in_metadata() {
+    let unique = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("clock drift")
+        .as_nanos();
+    let path = std::env::temp_dir().join(format!("cadentis-permissions-{unique}.tmp"));
+    std::fs::write(&path, b"#!/bin/sh\n").unwrap();
+
+    let mut permissions = blocking::metadata(&path).await.unwrap().permissions();
+    assert!(!permissions.readonly());
+
+    permissions.set_readonly(true);
+    blocking::set_permissions(&path, permissions).await.unwrap();
+
+    assert!(
+        blocking::metadata(&path)
+            .await
+            .unwrap()
+            .permissions()
+            .readonly()
+    );
+    assert!(std::fs::metadata(&path).unwrap().permissions().readonly());
+
+    blocking::remove_file(&path).await.unwrap();
+    assert!(blocking::metadata(&path).await.is_err());
+}
+
+#[cfg(unix)]
+#[cadentis::test]
+async fn offloaded_links_and_closures_run_on_the_pool() {
+    let unique = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("clock drift")
+        .as_nanos();
+    let dir = std::env::temp_dir().join(format!("cadentis-links-{unique}"));
+    std::fs::create_dir(&dir).unwrap();
+
+    let target = dir.join("target.txt");
+    let link = dir.join("link.txt");
+    std::fs::write(&target, b"hello").unwrap();
+
+    blocking::symlink(&target, &link).await.unwrap();
+    assert_eq!(blocking::read_link(&link).await.unwrap(), target);
+    assert!(
+        blocking::symlink_metadata(&link)
+            .await
+            .unwrap()
+            .is_symlink()
+    );
+
+    let read_back = blocking::run({
+        let link = link.clone();
+        move || std::fs::read(link)
+    })
+    .await
+    .unwrap();
+    assert_eq!(read_back, b"hello");
+
+    blocking::remove_dir_all(&dir).await.unwrap();
+}
@@ -0,0 +1,50 @@
+use cadentis::RuntimeBuilder;
+use cadentis::runtime::{self, Handle};
+use cadentis::task;
+use std::thread;
+
+#[test]
+fn spawn_from_foreign_thread_uses_global_handle() {
+    let rt = RuntimeBuilder::new().worker_threads(2).build().unwrap();
+
+    runtime::set_global(rt.handle());
+
+    let handle = thread::spawn(|| task::spawn(async { 40 + 2 }))
+        .join()
+        .unwrap();
+
+    assert_eq!(rt.block_on(async move { handle.await.unwrap() }), 42);
+
+    runtime::clear_global();
+
+    // Without a global handle, spawning outside a runtime panics again.
+    let result = thread::spawn(|| task::spawn(async {})).join();
+    assert!(result.is_err());
+}
+
+#[test]
+fn handle_spawns_onto_its_runtime() {
+    let rt = RuntimeBuilder::new().worker_threads(2).build().unwrap();
+    let handle = rt.handle();
+
+    let spawned = thread::spawn(move || handle.spawn(async { task::current_worker_id() }))
+        .join()
+        .unwrap();
+
+    let worker_id = rt.block_on(async move { spawned.await.unwrap() });
+
+    assert!(worker_id.is_some(), "task should run on a runtime worker");
+}
+
+#[test]
+fn current_handle_matches_runtime_context() {
+    assert!(Handle::try_current().is_none());
+
+    let rt = RuntimeBuilder::new().build().unwrap();
+    let value = rt.block_on(async {
+        let handle = Handle::current();
+        handle.spawn(async { 7 }).await.unwrap()
+    });
+
+    assert_eq!(value, 7);
+}
@@ -9,7 +9,7 @@ async fn test_global_spawn_basic() {
     let handle = task::spawn(async move {
         *completed_clone.lock().unwrap() = true;
     });
-    handle.await;
+    handle.await.unwrap();
 
     assert!(
         *completed.lock().unwrap(),
@@ -37,9 +37,9 @@ async fn test_global_spawn_multiple() {
         *c3.lock().unwrap() += 100;
     });
 
-    h1.await;
-    h2.await;
-    h3.await;
+    h1.await.unwrap();
+    h2.await.unwrap();
+    h3.await.unwrap();
 
     assert_eq!(
         *counter.lock().unwrap(),
@@ -65,15 +65,15 @@ async fn test_global_spawn_nested() {
         let h_inner = task::spawn(async move {
             v2.lock().unwrap().push(3);
         });
-        h_inner.await;
+        h_inner.await.unwrap();
     });
 
     let h2 = task::spawn(async move {
         v3.lock().unwrap().push(4);
     });
 
-    h1.await;
-    h2.await;
+    h1.await.unwrap();
+    h2.await.unwrap();
 
     let mut vals = values.lock().unwrap().clone();
     vals.sort();
@@ -93,9 +93,9 @@ async fn test_global_spawn_from_spawned_task() {
         let h_inner = task::spawn(async move {
             *c2.lock().unwrap() += 10;
         });
-        h_inner.await;
+        h_inner.await.unwrap();
     });
-    h.await;
+    h.await.unwrap();
 
     assert_eq!(*counter.lock().unwrap(), 11, "Nested spawn should work");
 }
@@ -125,8 +125,8 @@ async fn test_global_spawn_with_return_values() {
         r2.lock().unwrap().push(value);
     });
 
-    h1.await;
-    h2.await;
+    h1.await.unwrap();
+    h2.await.unwrap();
 
     let mut res = results.lock().unwrap().clone();
     res.sort();
@@ -163,8 +163,8 @@ async fn do_work_with_spawn(counter: Arc<Mutex<i32>>) {
         *c2.lock().unwrap() += 32;
     });
 
-    h1.await;
-    h2.await;
+    h1.await.unwrap();
+    h2.await.unwrap();
 }
 
 #[cadentis::test]
@@ -194,7 +194,7 @@ async fn nested_function_a(values: Arc<Mutex<Vec<i32>>>) {
 
     let h2 = nested_function_b(v2);
 
-    h1.await;
+    h1.await.unwrap();
     h2.await;
 }
 
@@ -210,6 +210,6 @@ async fn nested_function_b(values: Arc<Mutex<Vec<i32>>>) {
         v2.lock().unwrap().push(4);
     });
 
-    h1.await;
-    h2.await;
+    h1.await.unwrap();
+    h2.await.unwrap();
 }
@@ -0,0 +1,73 @@
+use cadentis::net::{GracefulShutdown, TcpListener, TcpStream};
+use cadentis::task;
+use cadentis::time::sleep;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::time::Duration;
+
+#[cadentis::test]
+async fn graceful_shutdown_stops_accepting_and_drains_connections() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap().to_string();
+
+    let shutdown = GracefulShutdown::new();
+    let accepted = Arc::new(AtomicUsize::new(0));
+    let handler_done = Arc::new(AtomicBool::new(false));
+
+    let server = task::spawn({
+        let shutdown = shutdown.clone();
+        let accepted = accepted.clone();
+        let handler_done = handler_done.clone();
+
+        async move {
+            while let Some(conn) = shutdown.accept(&listener).await {
+                let (_stream, _, guard) = conn.unwrap();
+                accepted.fetch_add(1, Ordering::SeqCst);
+
+                let handler_done = handler_done.clone();
+                task::spawn(async move {
+                    sleep(Duration::from_millis(200)).await;
+                    handler_done.store(true, Ordering::SeqCst);
+                    drop(guard);
+                });
+            }
+
+            listener
+        }
+    });
+
+    let _client = TcpStream::connect(&addr).await.unwrap();
+
+    while shutdown.active_connections() == 0 {
+        sleep(Duration::from_millis(5)).await;
+    }
+
+    shutdown.trigger();
+
+    // The accept loop ends even though the listener is still open.
+    let listener = server.await.unwrap();
+    let _late = TcpStream::connect(&listener.local_addr().unwrap().to_string()).await;
+
+    assert!(shutdown.is_triggered());
+    assert_eq!(accepted.load(Ordering::SeqCst), 1);
+    assert!(!handler_done.load(Ordering::SeqCst));
+
+    shutdown.shutdown().await;
+
+    assert!(handler_done.load(Ordering::SeqCst));
+    assert_eq!(shutdown.active_connections(), 0);
+}
+
+#[cadentis::test]
+async fn graceful_shutdown_without_connections_resolves_immediately() {
+    let shutdown = GracefulShutdown::new();
+    let guard = shutdown.track();
+
+    assert_eq!(shutdown.active_connections(), 1);
+    drop(guard);
+
+    shutdown.shutdown().await;
+
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    assert!(shutdown.accept(&listener).await.is_none());
+}
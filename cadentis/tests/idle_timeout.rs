@@ -0,0 +1,87 @@
+use cadentis::io::{AsyncReadExt, AsyncWriteExt};
+use cadentis::net::{IdleTimeout, TcpListener, TcpStream};
+use cadentis::task;
+use cadentis::time::sleep;
+use std::io;
+use std::time::{Duration, Instant};
+
+const IDLE: Duration = Duration::from_millis(100);
+
+/// Returns a connected client and the server side of the connection.
+async fn connected_pair() -> (TcpStream, TcpStream) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    let client = task::spawn(async move { TcpStream::connect(addr).await.unwrap() });
+    let (server, _) = listener.accept().await.unwrap();
+
+    (client.await.unwrap(), server)
+}
+
+#[cadentis::test]
+async fn silent_connection_trips_the_idle_timeout() {
+    let (_client, server) = connected_pair().await;
+    let mut conn = IdleTimeout::new(server, IDLE);
+
+    let start = Instant::now();
+    let mut buffer = [0u8; 16];
+    let error = conn.read(&mut buffer).await.unwrap_err();
+
+    assert_eq!(error.kind(), io::ErrorKind::TimedOut);
+    assert!(
+        start.elapsed() >= IDLE,
+        "timed out after {:?}",
+        start.elapsed()
+    );
+}
+
+#[cadentis::test]
+async fn active_connection_stays_open() {
+    let (client, server) = connected_pair().await;
+    let mut conn = IdleTimeout::new(server, IDLE);
+
+    // Sends a message every half idle timeout, for several timeouts.
+    let writer = task::spawn(async move {
+        for _ in 0..8 {
+            sleep(IDLE / 2).await;
+            client.write_all(b"tick").await.unwrap();
+        }
+        client
+    });
+
+    let mut buffer = [0u8; 4];
+    for _ in 0..8 {
+        conn.read_exact(&mut buffer).await.unwrap();
+        assert_eq!(&buffer, b"tick");
+    }
+
+    // Once the peer goes silent, the connection times out again.
+    let _client = writer.await.unwrap();
+    let error = conn.read(&mut buffer).await.unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::TimedOut);
+}
+
+#[cadentis::test]
+async fn writes_restart_the_idle_countdown() {
+    let (client, server) = connected_pair().await;
+    let mut conn = IdleTimeout::new(server, IDLE);
+
+    sleep(IDLE * 3 / 4).await;
+    let written = Instant::now();
+    conn.write_all(b"ping").await.unwrap();
+    assert!(conn.idle_time() < IDLE / 2);
+
+    let mut buffer = [0u8; 4];
+    let n = client.read(&mut buffer).await.unwrap();
+    assert_eq!(&buffer[..n], b"ping");
+
+    // The peer stays silent: the read times out a full idle timeout
+    // after the write, not after the connection was wrapped.
+    let error = conn.read(&mut buffer).await.unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::TimedOut);
+    assert!(
+        written.elapsed() >= IDLE,
+        "timed out after {:?}",
+        written.elapsed()
+    );
+}
@@ -0,0 +1,85 @@
+use cadentis::RuntimeBuilder;
+use cadentis::task;
+use cadentis::time::test::PausedClock;
+use cadentis::time::{self, Interval, interval};
+use std::future::poll_fn;
+use std::task::Poll;
+use std::time::Duration;
+
+const PERIOD: Duration = Duration::from_secs(10);
+
+/// Returns `true` if the next tick of `ticks` is already due.
+async fn is_due(ticks: &mut Interval) -> bool {
+    poll_fn(|cx| Poll::Ready(ticks.poll_tick(cx).is_ready())).await
+}
+
+#[test]
+fn interval_ticks_every_period() {
+    let clock = PausedClock::new();
+    let rt = RuntimeBuilder::new().clock(clock.clone()).build().unwrap();
+
+    rt.block_on(async move {
+        let start = time::now();
+        let mut ticks = interval(PERIOD);
+
+        // The first tick is immediate.
+        assert_eq!(ticks.tick().await, start);
+
+        for n in 1..4 {
+            let next = task::spawn(async move {
+                let tick = ticks.tick().await;
+                (ticks, tick)
+            });
+            clock.advance(PERIOD);
+
+            let (returned, tick) = next.await.unwrap();
+            ticks = returned;
+            assert_eq!(tick, start + PERIOD * n);
+        }
+    });
+}
+
+#[test]
+fn reset_shifts_the_following_ticks() {
+    let clock = PausedClock::new();
+    let rt = RuntimeBuilder::new().clock(clock.clone()).build().unwrap();
+
+    rt.block_on(async move {
+        let start = time::now();
+        let mut ticks = interval(PERIOD);
+        ticks.tick().await;
+
+        // Halfway through the period, realign the schedule.
+        clock.advance(PERIOD / 2);
+        ticks.reset();
+
+        // The original deadline passed, but the tick now comes one
+        // period after the reset.
+        clock.advance(PERIOD / 2);
+        assert!(!is_due(&mut ticks).await);
+
+        clock.advance(PERIOD / 2);
+        assert_eq!(ticks.tick().await, start + PERIOD * 3 / 2);
+
+        // Later ticks keep the period from the new schedule.
+        let next = task::spawn(async move { ticks.tick().await });
+        clock.advance(PERIOD);
+        assert_eq!(next.await.unwrap(), start + PERIOD * 5 / 2);
+    });
+}
+
+#[cadentis::test]
+async fn reset_applies_to_a_pending_tick() {
+    let mut ticks = interval(Duration::from_secs(3600));
+    ticks.tick().await;
+
+    // Poll the tick once so that its timer is armed for the old deadline.
+    let start = time::now();
+    assert!(!is_due(&mut ticks).await);
+
+    ticks.reset_at(start + Duration::from_millis(20));
+    let tick = ticks.tick().await;
+
+    assert_eq!(tick, start + Duration::from_millis(20));
+    assert!(time::now() >= tick);
+}
@@ -0,0 +1,53 @@
+use cadentis::RuntimeBuilder;
+use cadentis::task;
+use cadentis::time::sleep;
+use std::time::Duration;
+
+#[cadentis::test]
+async fn aborted_task_reports_cancellation() {
+    let handle = task::spawn(async {
+        sleep(Duration::from_secs(60)).await;
+        1
+    });
+
+    handle.abort();
+
+    let error = handle.await.unwrap_err();
+    assert!(error.is_cancelled());
+    assert!(!error.is_panic());
+}
+
+#[cadentis::test]
+async fn panicking_task_reports_its_payload() {
+    let handle = task::spawn(async {
+        panic!("boom");
+    });
+
+    let error = handle.await.unwrap_err();
+    assert!(error.is_panic());
+    assert!(!error.is_cancelled());
+
+    let payload = error.into_panic();
+    assert_eq!(payload.downcast_ref::<&str>(), Some(&"boom"));
+}
+
+#[cadentis::test]
+async fn worker_survives_a_panicking_task() {
+    let failed = task::spawn(async {
+        panic!("first task fails");
+    });
+    assert!(failed.await.unwrap_err().is_panic());
+
+    let value = task::spawn(async { 7 }).await.unwrap();
+    assert_eq!(value, 7);
+}
+
+#[test]
+#[should_panic(expected = "root future failed")]
+fn block_on_propagates_panics() {
+    let rt = RuntimeBuilder::new().worker_threads(1).build().unwrap();
+
+    rt.block_on(async {
+        panic!("root future failed");
+    });
+}
@@ -99,3 +99,132 @@ async fn joinset_is_empty_and_len() {
     set.join_next().await;
     assert!(set.is_empty());
 }
+
+/// Error returned by the fallible tasks below.
+#[derive(Debug, PartialEq)]
+enum TaskError {
+    Failed(&'static str),
+    Join,
+}
+
+impl From<cadentis::task::JoinError> for TaskError {
+    fn from(_: cadentis::task::JoinError) -> Self {
+        TaskError::Join
+    }
+}
+
+#[cadentis::test]
+async fn joinset_join_all_or_first_err_collects_in_spawn_order() {
+    let mut set = JoinSet::new();
+
+    for i in 0..5u64 {
+        set.spawn(async move {
+            sleep(Duration::from_millis(50 - i * 10)).await;
+            Ok::<_, std::io::Error>(i)
+        });
+    }
+
+    let values = set
+        .join_all_or_first_err::<u64, std::io::Error>()
+        .await
+        .unwrap();
+
+    assert_eq!(values, vec![0, 1, 2, 3, 4]);
+    assert!(set.is_empty());
+}
+
+#[cadentis::test]
+async fn joinset_join_all_or_first_err_aborts_on_error() {
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Instant;
+
+    let finished = Arc::new(AtomicUsize::new(0));
+    let mut set = JoinSet::new();
+
+    for _ in 0..4 {
+        let finished = finished.clone();
+        set.spawn(async move {
+            sleep(Duration::from_secs(5)).await;
+            finished.fetch_add(1, Ordering::SeqCst);
+            Ok::<u32, TaskError>(0)
+        });
+    }
+
+    set.spawn(async move {
+        sleep(Duration::from_millis(10)).await;
+        Err::<u32, TaskError>(TaskError::Failed("boom"))
+    });
+
+    let start = Instant::now();
+    let err = set
+        .join_all_or_first_err::<u32, TaskError>()
+        .await
+        .unwrap_err();
+
+    assert_eq!(err, TaskError::Failed("boom"));
+    assert!(start.elapsed() < Duration::from_secs(1));
+    assert!(set.is_empty(), "remaining tasks should have been aborted");
+
+    sleep(Duration::from_millis(50)).await;
+    assert_eq!(finished.load(Ordering::SeqCst), 0);
+}
+
+#[cadentis::test]
+async fn joinset_join_all_or_first_err_reports_panics() {
+    let mut set = JoinSet::new();
+
+    set.spawn(async move { Ok::<u32, std::io::Error>(1) });
+    set.spawn(async move {
+        if true {
+            panic!("task failed");
+        }
+        Ok::<u32, std::io::Error>(2)
+    });
+
+    let err = set
+        .join_all_or_first_err::<u32, std::io::Error>()
+        .await
+        .unwrap_err();
+
+    assert_eq!(err.kind(), std::io::ErrorKind::Other);
+    assert!(err.to_string().contains("task failed"));
+}
+
+#[cadentis::test]
+async fn joinset_join_all_ordered_returns_spawn_order() {
+    let mut set = JoinSet::new();
+
+    // Later tasks finish first.
+    for i in 0..5u64 {
+        set.spawn(async move {
+            sleep(Duration::from_millis(50 - i * 10)).await;
+            format!("item {i}")
+        });
+    }
+
+    let values = set.join_all_ordered::<String>().await;
+
+    assert_eq!(values, ["item 0", "item 1", "item 2", "item 3", "item 4"]);
+    assert!(set.is_empty());
+}
+
+#[cadentis::test]
+async fn joinset_join_all_ordered_keeps_spawn_order_after_join_next() {
+    let mut set = JoinSet::new();
+
+    set.spawn(async move { "item 0".to_string() });
+    for i in 1..5u64 {
+        set.spawn(async move {
+            sleep(Duration::from_millis(50 - i * 10)).await;
+            format!("item {i}")
+        });
+    }
+
+    // Joining the first task moves the last one into its slot.
+    set.join_next().await.unwrap();
+
+    let values = set.join_all_ordered::<String>().await;
+
+    assert_eq!(values, ["item 1", "item 2", "item 3", "item 4"]);
+}
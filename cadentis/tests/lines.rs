@@ -0,0 +1,113 @@
+use cadentis::io::{AsyncBufReadExt, AsyncRead, BufReader};
+use cadentis::net::{TcpListener, TcpStream};
+use cadentis::stream::StreamExt;
+use cadentis::task;
+use cadentis::time::sleep;
+use std::collections::VecDeque;
+use std::io;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+/// A reader returning its data in predefined chunks.
+struct ChunkedReader {
+    chunks: VecDeque<&'static [u8]>,
+}
+
+impl AsyncRead for ChunkedReader {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+        buffer: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        let Some(chunk) = self.chunks.pop_front() else {
+            return Poll::Ready(Ok(0));
+        };
+
+        let n = chunk.len().min(buffer.len());
+        buffer[..n].copy_from_slice(&chunk[..n]);
+
+        if n < chunk.len() {
+            self.chunks.push_front(&chunk[n..]);
+        }
+
+        Poll::Ready(Ok(n))
+    }
+}
+
+#[cadentis::test]
+async fn lines_handle_split_lines_and_missing_trailing_newline() {
+    let reader = ChunkedReader {
+        chunks: VecDeque::from([
+            &b"fir"[..],
+            b"st\nsec",
+            b"ond\r",
+            b"\n\nthird line is longer than the buffer\n",
+            b"last",
+        ]),
+    };
+
+    let lines: Vec<String> = BufReader::with_capacity(8, reader)
+        .lines()
+        .map(|line| line.unwrap())
+        .collect()
+        .await;
+
+    assert_eq!(
+        lines,
+        [
+            "first",
+            "second",
+            "",
+            "third line is longer than the buffer",
+            "last"
+        ]
+    );
+}
+
+#[cadentis::test]
+async fn lines_reject_invalid_utf8() {
+    let reader = ChunkedReader {
+        chunks: VecDeque::from([&b"\xff\xfe\n"[..]]),
+    };
+
+    let mut lines = BufReader::new(reader).lines();
+    let error = lines.next().await.unwrap().unwrap_err();
+
+    assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+    assert!(lines.next().await.is_none());
+}
+
+#[cadentis::test]
+async fn lines_over_tcp_with_unaligned_packets() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    let server = task::spawn(async move {
+        let (stream, _) = listener.accept().await.unwrap();
+
+        for packet in [
+            &b"GET / HT"[..],
+            b"TP/1.1\r\nHost: exa",
+            b"mple\r\n",
+            b"\r\n",
+        ] {
+            stream.write_all(packet).await.unwrap();
+            sleep(Duration::from_millis(20)).await;
+        }
+
+        stream.shutdown(std::net::Shutdown::Write).unwrap();
+    });
+
+    let stream = TcpStream::connect(&addr.to_string()).await.unwrap();
+    let mut lines = BufReader::new(stream).lines();
+    let mut received = Vec::new();
+
+    while let Some(line) = lines.next().await {
+        received.push(line.unwrap());
+    }
+
+    server.await.unwrap();
+
+    assert_eq!(received, ["GET / HTTP/1.1", "Host: example", ""]);
+}
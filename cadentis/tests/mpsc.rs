@@ -0,0 +1,214 @@
+use cadentis::stream::StreamExt;
+use cadentis::sync::mpsc::{self, RecvTimeoutError, SendTimeoutError};
+use cadentis::time::{sleep, timeout};
+use cadentis::{select, task};
+use std::pin::pin;
+use std::time::{Duration, Instant};
+
+#[cadentis::test]
+async fn mpsc_receiver_collects_as_stream() {
+    let (tx, rx) = mpsc::channel(4);
+
+    let producer = task::spawn(async move {
+        for i in 0..10 {
+            tx.send(i).await.unwrap();
+        }
+    });
+
+    let values: Vec<i32> = rx.collect().await;
+    producer.await.unwrap();
+
+    assert_eq!(values, (0..10).collect::<Vec<_>>());
+}
+
+#[cadentis::test]
+async fn mpsc_receiver_next_and_combinators() {
+    let (tx, mut rx) = mpsc::unbounded_channel();
+
+    for i in 1..=6 {
+        tx.send(i).await.unwrap();
+    }
+
+    assert_eq!(rx.next().await, Some(1));
+
+    drop(tx);
+
+    let evens: Vec<i32> = rx.filter(|v| v % 2 == 0).map(|v| v * 10).collect().await;
+    assert_eq!(evens, vec![20, 40, 60]);
+}
+
+#[cadentis::test]
+async fn mpsc_send_timeout_returns_unsent_value() {
+    let (tx, mut rx) = mpsc::channel(1);
+
+    tx.send(String::from("first")).await.unwrap();
+
+    let start = Instant::now();
+    let result = tx
+        .send_timeout(String::from("second"), Duration::from_millis(50))
+        .await;
+
+    assert!(start.elapsed() >= Duration::from_millis(50));
+    assert_eq!(
+        result,
+        Err(SendTimeoutError::Timeout(String::from("second")))
+    );
+
+    // The timed-out value was not queued.
+    assert_eq!(rx.recv().await.as_deref(), Some("first"));
+    tx.send_timeout(String::from("third"), Duration::from_millis(50))
+        .await
+        .unwrap();
+    assert_eq!(rx.recv().await.as_deref(), Some("third"));
+
+    drop(rx);
+    let closed = tx.send_timeout(String::from("late"), Duration::from_millis(50));
+    assert_eq!(closed.await.unwrap_err().into_inner(), "late");
+}
+
+#[cadentis::test]
+async fn mpsc_recv_timeout_times_out_then_receives() {
+    let (tx, mut rx) = mpsc::channel::<u32>(4);
+
+    assert_eq!(
+        rx.recv_timeout(Duration::from_millis(20)).await,
+        Err(RecvTimeoutError::Timeout)
+    );
+
+    let producer = task::spawn(async move {
+        cadentis::time::sleep(Duration::from_millis(10)).await;
+        tx.send(7).await.unwrap();
+    });
+
+    assert_eq!(rx.recv_timeout(Duration::from_secs(5)).await, Ok(7));
+    producer.await.unwrap();
+
+    assert_eq!(
+        rx.recv_timeout(Duration::from_secs(5)).await,
+        Err(RecvTimeoutError::Closed)
+    );
+}
+
+#[cadentis::test]
+async fn mpsc_buffered_values_outlive_the_last_sender() {
+    // Bounded, filled to capacity by several senders.
+    let (tx, mut rx) = mpsc::channel(4);
+    let other = tx.clone();
+
+    tx.send(1).await.unwrap();
+    other.send(2).await.unwrap();
+    tx.send(3).await.unwrap();
+    other.send(4).await.unwrap();
+
+    drop(tx);
+    drop(other);
+
+    for i in 1..=4 {
+        assert_eq!(rx.recv().await, Some(i));
+    }
+    assert_eq!(rx.recv().await, None);
+    assert_eq!(rx.recv().await, None);
+
+    // Unbounded.
+    let (tx, mut rx) = mpsc::unbounded_channel();
+
+    for i in 0..100 {
+        tx.send(i).await.unwrap();
+    }
+    drop(tx);
+
+    for i in 0..100 {
+        assert_eq!(rx.recv().await, Some(i));
+    }
+    assert_eq!(rx.recv().await, None);
+}
+
+#[cadentis::test]
+async fn mpsc_waiting_receiver_gets_the_last_value_before_none() {
+    let (tx, mut rx) = mpsc::channel(1);
+
+    let receiver = task::spawn(async move {
+        let mut values = Vec::new();
+        while let Some(value) = rx.recv().await {
+            values.push(value);
+        }
+        values
+    });
+
+    // The receiver is parked when the final value and the close land.
+    cadentis::time::sleep(Duration::from_millis(10)).await;
+    tx.send(42).await.unwrap();
+    drop(tx);
+
+    assert_eq!(receiver.await.unwrap(), vec![42]);
+}
+
+#[cadentis::test]
+async fn mpsc_cancelled_send_does_not_swallow_the_next_wakeup() {
+    let (tx, mut rx) = mpsc::channel(1);
+    tx.send(0).await.unwrap();
+
+    // A send given up on while the channel is full.
+    assert!(
+        timeout(Duration::from_millis(10), tx.send(1))
+            .await
+            .is_err()
+    );
+
+    let blocked = {
+        let tx = tx.clone();
+        task::spawn(async move { tx.send(2).await.unwrap() })
+    };
+    sleep(Duration::from_millis(10)).await;
+
+    // Making room wakes the sender still waiting, not the cancelled one.
+    assert_eq!(rx.recv().await, Some(0));
+    timeout(Duration::from_secs(1), blocked)
+        .await
+        .expect("the waiting sender was never woken")
+        .unwrap();
+    assert_eq!(rx.recv().await, Some(2));
+}
+
+#[cadentis::test]
+async fn mpsc_sender_polled_repeatedly_is_queued_once() {
+    let (tx, mut rx) = mpsc::channel(1);
+    tx.send(0).await.unwrap();
+
+    // Each iteration polls the same pending send again.
+    let repolled = {
+        let tx = tx.clone();
+        task::spawn(async move {
+            let mut send = pin!(tx.send(1));
+            loop {
+                let sent = select! {
+                    &mut send => |result| Some(result),
+                    sleep(Duration::from_millis(1)) => |_| None,
+                };
+
+                if let Some(result) = sent {
+                    break result.unwrap();
+                }
+            }
+        })
+    };
+    sleep(Duration::from_millis(20)).await;
+
+    let blocked = {
+        let tx = tx.clone();
+        task::spawn(async move { tx.send(2).await.unwrap() })
+    };
+    sleep(Duration::from_millis(10)).await;
+
+    let mut received = Vec::new();
+    for _ in 0..3 {
+        let value = timeout(Duration::from_secs(1), rx.recv())
+            .await
+            .expect("a waiting sender was never woken");
+        received.push(value.unwrap());
+    }
+
+    repolled.await.unwrap();
+    blocked.await.unwrap();
+    assert_eq!(received, vec![0, 1, 2]);
+}
@@ -1,6 +1,14 @@
-use cadentis::sync::Mutex;
+use cadentis::sync::{MappedMutexGuard, Mutex, MutexGuard};
 use cadentis::task;
+use cadentis::time::sleep;
 use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+struct State {
+    name: String,
+    peers: Vec<u32>,
+}
 
 #[cadentis::test]
 async fn async_mutex_shared_counter() {
@@ -17,9 +25,70 @@ async fn async_mutex_shared_counter() {
     }
 
     for handle in handles {
-        handle.await;
+        handle.await.unwrap();
     }
 
     let guard = counter.lock().await;
     assert_eq!(*guard, 10);
 }
+
+#[cadentis::test]
+async fn mapped_guard_mutates_a_field() {
+    let state = Mutex::new(State {
+        name: "node".to_string(),
+        peers: Vec::new(),
+    });
+
+    {
+        let mut peers = MutexGuard::map(state.lock().await, |state| &mut state.peers);
+        peers.push(1);
+        peers.push(2);
+    }
+
+    {
+        let guard = state.lock().await;
+        let mut first =
+            MappedMutexGuard::map(MutexGuard::map(guard, |state| &mut state.peers), |peers| {
+                &mut peers[0]
+            });
+        *first = 10;
+    }
+
+    let guard = state.lock().await;
+    assert_eq!(guard.name, "node");
+    assert_eq!(guard.peers, vec![10, 2]);
+}
+
+#[cadentis::test]
+async fn mapped_guard_holds_and_releases_the_whole_lock() {
+    let state = Arc::new(Mutex::new(State {
+        name: String::new(),
+        peers: Vec::new(),
+    }));
+
+    let acquired = Arc::new(AtomicBool::new(false));
+    let mut name = MutexGuard::map(state.lock().await, |state| &mut state.name);
+
+    let contender = task::spawn({
+        let state = state.clone();
+        let acquired = acquired.clone();
+
+        async move {
+            state.lock().await.peers.push(7);
+            acquired.store(true, Ordering::Release);
+        }
+    });
+
+    // The lock stays held by the mapped guard.
+    sleep(Duration::from_millis(20)).await;
+    assert!(!acquired.load(Ordering::Acquire));
+
+    name.push_str("released");
+    drop(name);
+
+    contender.await.unwrap();
+
+    let guard = state.lock().await;
+    assert_eq!(guard.name, "released");
+    assert_eq!(guard.peers, vec![7]);
+}
@@ -0,0 +1,80 @@
+#![cfg(unix)]
+
+use cadentis::io::{AsyncReadExt, AsyncWriteExt};
+use cadentis::process::{Command, Stdio};
+
+#[cadentis::test]
+async fn child_stdout_is_read_asynchronously() {
+    let mut child = Command::new("echo")
+        .arg("hello")
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+
+    let mut stdout = child.stdout.take().unwrap();
+    let mut output = Vec::new();
+    let mut buffer = [0u8; 64];
+
+    loop {
+        let n = stdout.read(&mut buffer).await.unwrap();
+        if n == 0 {
+            break;
+        }
+        output.extend_from_slice(&buffer[..n]);
+    }
+
+    assert_eq!(output, b"hello\n");
+    assert!(child.wait().await.unwrap().success());
+}
+
+#[cadentis::test]
+async fn child_stdin_round_trips_through_cat() {
+    let mut child = Command::new("cat")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+
+    let mut stdin = child.stdin.take().unwrap();
+    stdin.write_all(b"ping").await.unwrap();
+    stdin.shutdown().await.unwrap();
+
+    let mut stdout = child.stdout.take().unwrap();
+    let mut output = Vec::new();
+    let mut buffer = [0u8; 64];
+
+    loop {
+        let n = stdout.read(&mut buffer).await.unwrap();
+        if n == 0 {
+            break;
+        }
+        output.extend_from_slice(&buffer[..n]);
+    }
+
+    assert_eq!(output, b"ping");
+    assert!(child.wait().await.unwrap().success());
+}
+
+#[cadentis::test]
+async fn output_collects_both_streams_and_status() {
+    let output = Command::new("sh")
+        .args(["-c", "echo out; echo err >&2; exit 3"])
+        .output()
+        .await
+        .unwrap();
+
+    assert_eq!(output.stdout, b"out\n");
+    assert_eq!(output.stderr, b"err\n");
+    assert_eq!(output.status.code(), Some(3));
+}
+
+#[cadentis::test]
+async fn killed_child_reports_failure() {
+    let mut child = Command::new("sleep").arg("10").spawn().unwrap();
+
+    assert!(child.try_wait().unwrap().is_none());
+
+    child.kill().unwrap();
+
+    assert!(!child.wait().await.unwrap().success());
+}
@@ -0,0 +1,58 @@
+use cadentis::RuntimeBuilder;
+use cadentis::net::TcpListener;
+use cadentis::time::Clock;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+/// A clock that panics once armed, killing the reactor thread that
+/// reads it after every poll.
+#[derive(Clone, Default)]
+struct FaultyClock {
+    armed: Arc<AtomicBool>,
+}
+
+impl Clock for FaultyClock {
+    fn now(&self) -> Instant {
+        if self.armed.load(Ordering::SeqCst) {
+            panic!("injected clock failure");
+        }
+
+        Instant::now()
+    }
+}
+
+#[test]
+fn reactor_failure_surfaces_in_block_on() {
+    let clock = FaultyClock::default();
+    let rt = RuntimeBuilder::new()
+        .worker_threads(2)
+        .clock(clock.clone())
+        .build()
+        .unwrap();
+
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    clock.armed.store(true, Ordering::SeqCst);
+
+    let start = Instant::now();
+
+    // Registering the listener wakes the reactor, which then panics while
+    // reading the clock. Nobody ever connects: without failure detection
+    // this would wait forever.
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        rt.block_on(async move {
+            let _ = listener.accept().await;
+        })
+    }));
+
+    let payload = result.expect_err("block_on should fail once the reactor died");
+    let message = payload
+        .downcast_ref::<String>()
+        .cloned()
+        .unwrap_or_default();
+
+    assert!(message.contains("reactor"), "unexpected panic: {message}");
+    assert!(message.contains("injected clock failure"), "{message}");
+    assert!(start.elapsed() < Duration::from_secs(5));
+}
@@ -0,0 +1,70 @@
+use cadentis::net::{TcpListener, TcpStream};
+use cadentis::{RuntimeBuilder, task};
+
+const CONNECTIONS: usize = 256;
+const ROUNDS: usize = 200;
+
+#[test]
+fn reactor_hands_tasks_of_many_ready_connections_over_in_bulk() {
+    let rt = RuntimeBuilder::new().worker_threads(4).build().unwrap();
+
+    let pairs = rt.block_on(async {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        let mut pairs = Vec::with_capacity(CONNECTIONS);
+        for _ in 0..CONNECTIONS {
+            let client = task::spawn(async move { TcpStream::connect(addr).await.unwrap() });
+            let (server, _) = listener.accept().await.unwrap();
+            pairs.push((client.await.unwrap(), server));
+        }
+        pairs
+    });
+
+    let before = rt.metrics().reactor();
+
+    rt.block_on(async {
+        // Every connection ping-pongs in lockstep, so that hundreds of
+        // sockets become readable within the same poll.
+        let handles: Vec<_> = pairs
+            .into_iter()
+            .map(|(client, server)| {
+                task::spawn(async move {
+                    let mut buffer = [0u8; 8];
+
+                    for round in 0..ROUNDS as u64 {
+                        client.write_all(&round.to_le_bytes()).await.unwrap();
+
+                        let n = server.read(&mut buffer).await.unwrap();
+                        server.write_all(&buffer[..n]).await.unwrap();
+
+                        let mut filled = 0;
+                        while filled < 8 {
+                            filled += client.read(&mut buffer[filled..]).await.unwrap();
+                        }
+                        assert_eq!(u64::from_le_bytes(buffer), round);
+                    }
+                })
+            })
+            .collect();
+
+        for handle in handles {
+            handle.await.unwrap();
+        }
+    });
+
+    let after = rt.metrics().reactor();
+    let handoffs = after.handoffs() - before.handoffs();
+    let tasks = after.handed_off_tasks() - before.handed_off_tasks();
+
+    // The reactor pushes the tasks woken by one poll to the injector at
+    // once, instead of scheduling each of them on its own.
+    assert!(
+        tasks >= (CONNECTIONS * ROUNDS) as u64,
+        "{tasks} tasks woken"
+    );
+    assert!(
+        tasks >= 4 * handoffs,
+        "{tasks} tasks handed over in {handoffs} batches"
+    );
+}
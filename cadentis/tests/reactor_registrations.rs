@@ -0,0 +1,49 @@
+use cadentis::RuntimeBuilder;
+use cadentis::net::{TcpListener, TcpStream};
+use cadentis::task;
+
+/// Sends `rounds` messages back and forth between `client` and `server`.
+async fn ping_pong(client: &TcpStream, server: &TcpStream, rounds: usize) {
+    let mut buffer = [0u8; 4];
+
+    for _ in 0..rounds {
+        client.write_all(b"ping").await.unwrap();
+        let n = server.read(&mut buffer).await.unwrap();
+        assert_eq!(&buffer[..n], b"ping");
+
+        server.write_all(b"pong").await.unwrap();
+        let n = client.read(&mut buffer).await.unwrap();
+        assert_eq!(&buffer[..n], b"pong");
+    }
+}
+
+#[test]
+fn repeated_io_does_not_grow_registrations() {
+    let rt = RuntimeBuilder::new().build().unwrap();
+
+    let (listener, client, server) = rt.block_on(async {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        let client = task::spawn(async move { TcpStream::connect(&addr.to_string()).await });
+        let (server, _) = listener.accept().await.unwrap();
+        let client = client.await.unwrap().unwrap();
+
+        ping_pong(&client, &server, 1).await;
+
+        (listener, client, server)
+    });
+
+    // The two streams stay registered; the fired accept does not.
+    let before = rt.metrics().io_registrations();
+    assert_eq!(before, 2);
+
+    let (client, server) = rt.block_on(async move {
+        ping_pong(&client, &server, 200).await;
+        (client, server)
+    });
+
+    assert_eq!(rt.metrics().io_registrations(), before);
+
+    drop((listener, client, server));
+}
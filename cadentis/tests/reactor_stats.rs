@@ -0,0 +1,143 @@
+use cadentis::net::{TcpListener, TcpStream};
+use cadentis::time::sleep;
+use cadentis::{RuntimeBuilder, task};
+use std::io::Write;
+use std::net;
+use std::thread;
+use std::time::Duration;
+
+const STREAMS: usize = 8;
+
+#[test]
+fn registered_streams_are_reported() {
+    let rt = RuntimeBuilder::new().worker_threads(2).build().unwrap();
+
+    let streams = rt.block_on(async {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap().to_string();
+        let mut streams = Vec::new();
+
+        for _ in 0..STREAMS {
+            let connect = {
+                let addr = addr.clone();
+                task::spawn(async move { TcpStream::connect(&addr).await })
+            };
+            let (server, _) = listener.accept().await.unwrap();
+            let client = connect.await.unwrap().unwrap();
+
+            streams.push((client, server));
+        }
+
+        // Statistics are published by the reactor thread asynchronously.
+        sleep(Duration::from_millis(20)).await;
+
+        streams
+    });
+
+    let stats = rt.metrics().reactor();
+
+    assert_eq!(stats.registered_fds(), 2 * STREAMS);
+    assert_eq!(stats.registered_fds(), rt.metrics().io_registrations());
+    assert!(stats.polls() > 0);
+
+    drop(streams);
+}
+
+#[test]
+fn pending_timers_are_reported() {
+    let rt = RuntimeBuilder::new().worker_threads(2).build().unwrap();
+
+    let sleepers = rt.block_on(async {
+        let sleepers: Vec<_> = (0..4)
+            .map(|_| task::spawn(sleep(Duration::from_secs(3600))))
+            .collect();
+
+        sleep(Duration::from_millis(20)).await;
+
+        sleepers
+    });
+
+    let timers = rt.metrics().reactor().timers();
+    assert!(timers >= 4, "timers: {timers}");
+
+    for sleeper in &sleepers {
+        sleeper.abort();
+    }
+}
+
+#[test]
+fn registration_bursts_coalesce_reactor_wakeups() {
+    const BURST: usize = 256;
+
+    let rt = RuntimeBuilder::new().worker_threads(1).build().unwrap();
+
+    let listener = net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    let sockets: Vec<_> = (0..BURST / 2)
+        .flat_map(|_| {
+            let client = net::TcpStream::connect(addr).unwrap();
+            let (server, _) = listener.accept().unwrap();
+            [client, server]
+        })
+        .collect();
+
+    // Let the reactor settle in a blocking poll.
+    rt.block_on(sleep(Duration::from_millis(20)));
+    let before = rt.metrics().reactor().wakeups();
+
+    let streams = rt.block_on(async {
+        let streams: Vec<_> = sockets
+            .into_iter()
+            .map(|socket| TcpStream::from_std(socket).unwrap())
+            .collect();
+
+        sleep(Duration::from_millis(20)).await;
+        streams
+    });
+
+    let stats = rt.metrics().reactor();
+    let wakeups = stats.wakeups() - before;
+
+    assert_eq!(stats.registered_fds(), BURST);
+    assert!(
+        wakeups < BURST as u64 / 4,
+        "{wakeups} wakeups for {BURST} registrations"
+    );
+
+    drop(streams);
+}
+
+#[test]
+fn bulk_transfers_are_read_in_large_chunks() {
+    const LEN: usize = 4 * 1024 * 1024;
+
+    let rt = RuntimeBuilder::new().worker_threads(1).build().unwrap();
+
+    let listener = net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    let sender = thread::spawn(move || {
+        let (mut socket, _) = listener.accept().unwrap();
+        socket.write_all(&vec![5u8; LEN]).unwrap();
+    });
+
+    let received = rt.block_on(async move {
+        let stream = TcpStream::connect(addr).await.unwrap();
+        let mut buffer = vec![0u8; 64 * 1024];
+        let mut received = 0;
+
+        loop {
+            let n = stream.read(&mut buffer).await.unwrap();
+            if n == 0 {
+                return received;
+            }
+            received += n;
+        }
+    });
+    sender.join().unwrap();
+
+    // Reading 1 KiB at a time would take 4096 reads.
+    let reads = rt.metrics().reactor().stream_reads();
+    assert_eq!(received, LEN);
+    assert!(reads < (LEN / 8192) as u64, "{reads} reads for {LEN} bytes");
+}
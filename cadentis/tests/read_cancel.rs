@@ -0,0 +1,60 @@
+use cadentis::io::AsyncWriteExt;
+use cadentis::net::{TcpListener, TcpStream};
+use cadentis::select;
+use cadentis::task;
+use cadentis::time::sleep;
+use std::future::{Future, poll_fn};
+use std::pin::pin;
+use std::task::Poll;
+use std::time::Duration;
+
+/// Returns a connected `(client, server)` pair.
+async fn pair() -> (TcpStream, TcpStream) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    let client = task::spawn(async move { TcpStream::connect(&addr.to_string()).await });
+    let (server, _) = listener.accept().await.unwrap();
+
+    (client.await.unwrap().unwrap(), server)
+}
+
+#[cadentis::test]
+async fn read_dropped_by_select_loses_no_data() {
+    let (client, server) = pair().await;
+    let mut buffer = [0u8; 16];
+
+    // The read is polled while no data is available, then loses the race.
+    let timed_out = select! {
+        server.read(&mut buffer) => |_| false,
+        sleep(Duration::from_millis(20)) => |_| true,
+    };
+    assert!(timed_out);
+
+    client.write_all(b"hello").await.unwrap();
+
+    let n = server.read(&mut buffer).await.unwrap();
+    assert_eq!(&buffer[..n], b"hello");
+}
+
+#[cadentis::test]
+async fn read_dropped_after_data_arrived_loses_no_data() {
+    let (mut client, server) = pair().await;
+    let mut buffer = [0u8; 16];
+
+    {
+        let mut read = pin!(server.read(&mut buffer));
+
+        let first = poll_fn(|cx| Poll::Ready(read.as_mut().poll(cx))).await;
+        assert!(first.is_pending());
+
+        // Data reaches the stream's input buffer while the read is pending,
+        // and the read is dropped without being polled again.
+        client.write_all(b"world").await.unwrap();
+        client.flush().await.unwrap();
+        sleep(Duration::from_millis(20)).await;
+    }
+
+    let n = server.read(&mut buffer).await.unwrap();
+    assert_eq!(&buffer[..n], b"world");
+}
@@ -0,0 +1,45 @@
+use cadentis::io::{AsyncReadExt, AsyncWriteExt, duplex};
+use cadentis::net::{TcpListener, TcpStream};
+use std::io;
+use std::time::Duration;
+
+const LIMIT: Duration = Duration::from_millis(30);
+
+#[cadentis::test]
+async fn read_timeout_on_a_duplex_pipe() {
+    let (mut client, mut server) = duplex(64);
+    let mut buffer = [0u8; 16];
+
+    let error = server.read_timeout(&mut buffer, LIMIT).await.unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::TimedOut);
+
+    // The pipe is still usable after the timeout.
+    client.write_all(b"late").await.unwrap();
+    let n = server.read_timeout(&mut buffer, LIMIT).await.unwrap();
+    assert_eq!(&buffer[..n], b"late");
+
+    drop(client);
+    assert_eq!(server.read_timeout(&mut buffer, LIMIT).await.unwrap(), 0);
+}
+
+#[cadentis::test]
+async fn read_timeout_on_a_socket() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    let client = TcpStream::connect(addr).await.unwrap();
+    let (mut server, _) = listener.accept().await.unwrap();
+    let mut buffer = [0u8; 16];
+
+    let error = AsyncReadExt::read_timeout(&mut server, &mut buffer, LIMIT)
+        .await
+        .unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::TimedOut);
+
+    // Nothing was lost: data sent after the timeout is read in full.
+    client.write_all(b"hello").await.unwrap();
+    let n = AsyncReadExt::read_timeout(&mut server, &mut buffer, LIMIT * 10)
+        .await
+        .unwrap();
+    assert_eq!(&buffer[..n], b"hello");
+}
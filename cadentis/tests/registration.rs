@@ -0,0 +1,115 @@
+#![cfg(unix)]
+
+use cadentis::io::{Interest, Registration};
+use cadentis::time::timeout;
+use std::io::{self, Read, Write};
+use std::os::unix::net::UnixStream;
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+#[cadentis::test]
+async fn registered_pipe_wakes_when_written() {
+    let (reader, mut writer) = io::pipe().unwrap();
+    let mut pipe = Registration::new(reader, Interest::READABLE).unwrap();
+
+    // Nothing was written yet.
+    assert!(
+        timeout(Duration::from_millis(30), pipe.readable())
+            .await
+            .is_err()
+    );
+
+    let producer = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(20));
+        writer.write_all(b"event").unwrap();
+        writer
+    });
+
+    timeout(Duration::from_secs(5), pipe.readable())
+        .await
+        .expect("the write did not wake the task")
+        .unwrap();
+
+    let mut buffer = [0u8; 16];
+    let n = pipe.get_mut().read(&mut buffer).unwrap();
+    assert_eq!(&buffer[..n], b"event");
+
+    drop(producer.join().unwrap());
+    let reader = pipe.into_inner();
+    assert_eq!((&reader).read(&mut buffer).unwrap(), 0);
+}
+
+#[cadentis::test]
+async fn registration_makes_the_source_non_blocking() {
+    let (reader, _writer) = io::pipe().unwrap();
+    let mut pipe = Registration::new(reader, Interest::READABLE).unwrap();
+
+    // An empty pipe would block forever in blocking mode.
+    let mut buffer = [0u8; 16];
+    assert_eq!(
+        pipe.get_mut().read(&mut buffer).unwrap_err().kind(),
+        io::ErrorKind::WouldBlock
+    );
+}
+
+#[cadentis::test]
+async fn registration_checks_its_interest() {
+    let (reader, writer) = io::pipe().unwrap();
+
+    assert_eq!(
+        Registration::new(writer, Interest::READABLE)
+            .unwrap()
+            .writable()
+            .await
+            .unwrap_err()
+            .kind(),
+        io::ErrorKind::InvalidInput
+    );
+
+    let both = Interest::READABLE | Interest::WRITABLE;
+    assert!(both.is_readable() && both.is_writable());
+    assert!(Registration::new(reader, both).is_ok());
+}
+
+#[cadentis::test]
+async fn registration_waits_in_both_directions_at_once() {
+    let (local, mut peer) = UnixStream::pair().unwrap();
+
+    // Fill the socket buffer, so that the source is not writable.
+    local.set_nonblocking(true).unwrap();
+    while (&local).write(&[0u8; 4096]).is_ok() {}
+
+    let socket =
+        Arc::new(Registration::new(local, Interest::READABLE | Interest::WRITABLE).unwrap());
+
+    let readable = cadentis::task::spawn({
+        let socket = socket.clone();
+        async move { socket.readable().await }
+    });
+    let writable = cadentis::task::spawn({
+        let socket = socket.clone();
+        async move { socket.writable().await }
+    });
+
+    // Let both tasks wait, in opposite directions.
+    cadentis::time::sleep(Duration::from_millis(50)).await;
+
+    peer.write_all(b"event").unwrap();
+    timeout(Duration::from_secs(5), readable)
+        .await
+        .expect("the write did not wake the reader")
+        .unwrap()
+        .unwrap();
+
+    // Draining the peer side makes room for the writer.
+    peer.set_nonblocking(true).unwrap();
+    let mut buffer = [0u8; 4096];
+    while peer.read(&mut buffer).is_ok() {}
+
+    timeout(Duration::from_secs(5), writable)
+        .await
+        .expect("draining the socket did not wake the writer")
+        .unwrap()
+        .unwrap();
+}
@@ -0,0 +1,152 @@
+use cadentis::net::resolver::{self, Resolve, Resolving, SystemResolver};
+use cadentis::net::{TcpListener, TcpStream};
+use cadentis::task;
+use cadentis::time::sleep;
+use std::io;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+/// Resolves `*.mesh` names to a fixed address, and everything else with
+/// the system resolver.
+struct MeshResolver {
+    target: SocketAddr,
+    lookups: Arc<AtomicUsize>,
+}
+
+impl Resolve for MeshResolver {
+    fn resolve(&self, host: String) -> Resolving {
+        if !host.contains(".mesh:") {
+            return SystemResolver.resolve(host);
+        }
+
+        self.lookups.fetch_add(1, Ordering::SeqCst);
+        let target = self.target;
+
+        Box::pin(async move {
+            if host.starts_with("missing.") {
+                return Err(io::Error::new(io::ErrorKind::NotFound, "unknown service"));
+            }
+            Ok(vec![target])
+        })
+    }
+}
+
+/// Resolves `*.mesh` names to `addr` after `delay`, and everything else
+/// with the system resolver.
+struct SlowResolver {
+    addr: SocketAddr,
+    delay: Duration,
+}
+
+impl Resolve for SlowResolver {
+    fn resolve(&self, host: String) -> Resolving {
+        if !host.contains(".mesh:") {
+            return SystemResolver.resolve(host);
+        }
+
+        let (addr, delay) = (self.addr, self.delay);
+
+        Box::pin(async move {
+            sleep(delay).await;
+            Ok(vec![addr])
+        })
+    }
+}
+
+#[cadentis::test]
+async fn lookup_host_resolves_localhost_to_loopback() {
+    let addrs: Vec<_> = resolver::lookup_host("localhost:8080")
+        .await
+        .unwrap()
+        .collect();
+
+    assert!(!addrs.is_empty());
+    for addr in addrs {
+        assert!(addr.ip().is_loopback(), "{addr} is not a loopback address");
+        assert_eq!(addr.port(), 8080);
+    }
+}
+
+#[cadentis::test]
+async fn lookup_host_returns_literal_addresses_as_is() {
+    let addrs: Vec<_> = resolver::lookup_host("[::1]:53").await.unwrap().collect();
+
+    assert_eq!(addrs, vec!["[::1]:53".parse::<SocketAddr>().unwrap()]);
+}
+
+#[cadentis::test]
+async fn lookup_host_reports_invalid_names() {
+    assert!(resolver::lookup_host("no port here").await.is_err());
+}
+
+#[cadentis::test]
+async fn custom_resolver_is_used_and_cached() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let target = listener.local_addr().unwrap();
+    let lookups = Arc::new(AtomicUsize::new(0));
+
+    resolver::set_resolver(MeshResolver {
+        target,
+        lookups: lookups.clone(),
+    });
+
+    let name = format!("api.mesh:{}", target.port());
+
+    let addrs: Vec<_> = resolver::lookup_host(&name).await.unwrap().collect();
+    assert_eq!(addrs, vec![target]);
+
+    // The second lookup, and the one made by `connect`, hit the cache.
+    let _ = resolver::lookup_host(&name).await.unwrap();
+    let stream = TcpStream::connect(&name).await.unwrap();
+    let (accepted, _) = listener.accept().await.unwrap();
+
+    stream.write_all(b"hello").await.unwrap();
+    let mut buffer = [0u8; 5];
+    let n = accepted.read(&mut buffer).await.unwrap();
+    assert_eq!(&buffer[..n], b"hello");
+    assert_eq!(lookups.load(Ordering::SeqCst), 1);
+
+    // Errors are reported and never cached.
+    let missing = format!("missing.mesh:{}", target.port());
+    for _ in 0..2 {
+        let error = TcpStream::connect(&missing).await.err().unwrap();
+        assert_eq!(error.kind(), io::ErrorKind::NotFound);
+    }
+    assert_eq!(lookups.load(Ordering::SeqCst), 3);
+
+    // A lookup in flight while the resolver is replaced returns the
+    // previous answer, but does not cache it.
+    let stale: SocketAddr = "10.0.0.1:80".parse().unwrap();
+    resolver::set_resolver(SlowResolver {
+        addr: stale,
+        delay: Duration::from_millis(100),
+    });
+
+    let in_flight = task::spawn(resolver::lookup_host("replaced.mesh:80"));
+    sleep(Duration::from_millis(20)).await;
+
+    resolver::set_resolver(SlowResolver {
+        addr: target,
+        delay: Duration::ZERO,
+    });
+
+    let addrs: Vec<_> = in_flight.await.unwrap().unwrap().collect();
+    assert_eq!(addrs, vec![stale]);
+
+    let addrs: Vec<_> = resolver::lookup_host("replaced.mesh:80")
+        .await
+        .unwrap()
+        .collect();
+    assert_eq!(addrs, vec![target]);
+
+    // A TTL too long to be represented caches addresses forever.
+    resolver::set_cache_ttl(Duration::MAX);
+    let addrs: Vec<_> = resolver::lookup_host("forever.mesh:80")
+        .await
+        .unwrap()
+        .collect();
+    assert_eq!(addrs, vec![target]);
+    resolver::set_cache_ttl(resolver::DEFAULT_CACHE_TTL);
+}
@@ -1,5 +1,5 @@
 use cadentis::task;
-use cadentis::tools::retry;
+use cadentis::tools::{retry, retry_until};
 use std::sync::Arc;
 use std::sync::atomic::{AtomicUsize, Ordering};
 
@@ -10,14 +10,16 @@ async fn test_retry_succeeds_before_limit() {
     let attempts_clone = attempts.clone();
     let result = retry(5, move || {
         let attempts = attempts_clone.clone();
-        task::spawn(async move {
+        let handle = task::spawn(async move {
             let n = attempts.fetch_add(1, Ordering::SeqCst);
             if n < 2 {
                 Err::<i32, &'static str>("fail")
             } else {
                 Ok(42)
             }
-        })
+        });
+
+        async move { handle.await.unwrap() }
     })
     .await;
 
@@ -39,10 +41,12 @@ async fn test_retry_fails_after_limit() {
     let attempts_clone = attempts.clone();
     let result = retry(3, move || {
         let attempts = attempts_clone.clone();
-        task::spawn(async move {
+        let handle = task::spawn(async move {
             attempts.fetch_add(1, Ordering::SeqCst);
             Err::<usize, &'static str>("fail")
-        })
+        });
+
+        async move { handle.await.unwrap() }
     })
     .await;
 
@@ -69,7 +73,7 @@ async fn test_retry_with_interval() {
     let result = retry(3, move || {
         let attempts_clone = attempts_clone.clone();
         let last_time_clone = last_time_clone.clone();
-        task::spawn(async move {
+        let handle = task::spawn(async move {
             let now = Instant::now();
             let n = attempts_clone.fetch_add(1, Ordering::SeqCst);
             if n > 0 {
@@ -92,7 +96,9 @@ async fn test_retry_with_interval() {
             } else {
                 Ok(77)
             }
-        })
+        });
+
+        async move { handle.await.unwrap() }
     })
     .await;
 
@@ -117,7 +123,7 @@ async fn test_timeout_with_retry() {
     let attempts_clone = attempts.clone();
     let result = retry(5, move || {
         let attempts = attempts_clone.clone();
-        task::spawn(async move {
+        let handle = task::spawn(async move {
             let n = attempts.fetch_add(1, Ordering::SeqCst);
 
             timeout(Duration::from_millis(50), async move {
@@ -130,7 +136,9 @@ async fn test_timeout_with_retry() {
             })
             .await
             .map_err(|_| "timeout")
-        })
+        });
+
+        async move { handle.await.unwrap() }
     })
     .await;
 
@@ -143,3 +151,50 @@ async fn test_timeout_with_retry() {
         "Doit avoir tenté au moins 4 fois"
     );
 }
+
+#[cadentis::test]
+async fn test_retry_until_stops_at_deadline() {
+    use std::time::{Duration, Instant};
+
+    let attempts = Arc::new(AtomicUsize::new(0));
+    let attempts_clone = attempts.clone();
+    let start = Instant::now();
+    let deadline = start + Duration::from_millis(250);
+
+    // Plain retries would keep failing for ten intervals, a full second.
+    let result = retry_until(deadline, 10, move || {
+        attempts_clone.fetch_add(1, Ordering::SeqCst);
+        async { Err::<(), &'static str>("fail") }
+    })
+    .set_interval(Duration::from_millis(100))
+    .await;
+
+    assert_eq!(result, Err("fail"));
+
+    // The wait that would end past the deadline is skipped.
+    let elapsed = start.elapsed();
+    assert!(
+        elapsed < Duration::from_millis(250),
+        "gave up after {elapsed:?}"
+    );
+
+    let attempts = attempts.load(Ordering::SeqCst);
+    assert!((2..=3).contains(&attempts), "{attempts} attempts");
+}
+
+#[cadentis::test]
+async fn test_retry_until_past_deadline_makes_one_attempt() {
+    use std::time::Instant;
+
+    let attempts = Arc::new(AtomicUsize::new(0));
+    let attempts_clone = attempts.clone();
+
+    let result = retry_until(Instant::now(), 5, move || {
+        attempts_clone.fetch_add(1, Ordering::SeqCst);
+        async { Err::<(), &'static str>("fail") }
+    })
+    .await;
+
+    assert_eq!(result, Err("fail"));
+    assert_eq!(attempts.load(Ordering::SeqCst), 1);
+}
@@ -0,0 +1,96 @@
+#![cfg(unix)]
+
+use cadentis::RuntimeBuilder;
+use std::fs::File;
+use std::panic;
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+const RLIMIT_NOFILE: i32 = 7;
+#[cfg(not(any(target_os = "linux", target_os = "android")))]
+const RLIMIT_NOFILE: i32 = 8;
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+type Rlim = std::ffi::c_ulong;
+#[cfg(not(any(target_os = "linux", target_os = "android")))]
+type Rlim = u64;
+
+#[repr(C)]
+struct Rlimit {
+    current: Rlim,
+    max: Rlim,
+}
+
+unsafe extern "C" {
+    fn getrlimit(resource: i32, limit: *mut Rlimit) -> i32;
+    fn setrlimit(resource: i32, limit: *const Rlimit) -> i32;
+}
+
+/// Lowers the soft limit on open descriptors to at most `limit`, so that
+/// exhausting them stays cheap, and returns the previous soft limit.
+fn lower_descriptor_limit(limit: Rlim) -> Rlim {
+    let mut current = Rlimit { current: 0, max: 0 };
+
+    // SAFETY: `current` is a valid `struct rlimit` to fill.
+    assert_eq!(unsafe { getrlimit(RLIMIT_NOFILE, &mut current) }, 0);
+
+    let previous = current.current;
+    let lowered = Rlimit {
+        current: previous.min(limit),
+        max: current.max,
+    };
+
+    // SAFETY: `lowered` is a valid `struct rlimit`, below the hard limit.
+    assert_eq!(unsafe { setrlimit(RLIMIT_NOFILE, &lowered) }, 0);
+
+    previous
+}
+
+/// Restores the soft limit on open descriptors to `limit`.
+fn restore_descriptor_limit(limit: Rlim) {
+    let mut current = Rlimit { current: 0, max: 0 };
+
+    // SAFETY: `current` is a valid `struct rlimit` to fill.
+    assert_eq!(unsafe { getrlimit(RLIMIT_NOFILE, &mut current) }, 0);
+    current.current = limit;
+
+    // SAFETY: `current` is a valid `struct rlimit`, within the hard limit.
+    assert_eq!(unsafe { setrlimit(RLIMIT_NOFILE, &current) }, 0);
+}
+
+/// Opens files until the process runs out of descriptors.
+fn exhaust_file_descriptors() -> Vec<File> {
+    let mut files = Vec::new();
+
+    loop {
+        match File::open("/dev/null") {
+            Ok(file) => files.push(file),
+            Err(e) => {
+                assert_eq!(e.raw_os_error(), Some(24), "unexpected error: {e}");
+                return files;
+            }
+        }
+    }
+}
+
+#[test]
+fn poller_creation_failure_is_returned_by_build() {
+    let limit = lower_descriptor_limit(256);
+    let files = exhaust_file_descriptors();
+
+    let runtime = RuntimeBuilder::new().worker_threads(2).build();
+    let current_thread = RuntimeBuilder::current_thread().build();
+    let unwrapped = panic::catch_unwind(|| RuntimeBuilder::new().build_unwrap());
+
+    drop(files);
+    restore_descriptor_limit(limit);
+
+    // The poller reports running out of descriptors, `EMFILE`.
+    let error = runtime.err().expect("build succeeded without descriptors");
+    assert_eq!(error.raw_os_error(), Some(24), "{error}");
+    assert!(current_thread.is_err());
+    assert!(unwrapped.is_err());
+
+    // Once descriptors are available again, building works.
+    let runtime = RuntimeBuilder::new().worker_threads(2).build().unwrap();
+    assert_eq!(runtime.block_on(async { 1 + 1 }), 2);
+}
@@ -1,15 +1,16 @@
 use cadentis::RuntimeBuilder;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::{Arc, Mutex};
 
 #[test]
 fn test_builder_creation() {
-    let rt = RuntimeBuilder::new().build();
+    let rt = RuntimeBuilder::new().build().unwrap();
     drop(rt);
 }
 
 #[test]
 fn test_builder_simple_future() {
-    let rt = RuntimeBuilder::new().build();
+    let rt = RuntimeBuilder::new().build().unwrap();
     let completed = Arc::new(Mutex::new(false));
     let completed_clone = completed.clone();
 
@@ -23,7 +24,7 @@ fn test_builder_simple_future() {
 
 #[test]
 fn test_builder_immediate_result() {
-    let rt = RuntimeBuilder::new().build();
+    let rt = RuntimeBuilder::new().build().unwrap();
     let value = 42;
 
     let future = async move { value };
@@ -34,8 +35,8 @@ fn test_builder_immediate_result() {
 
 #[test]
 fn test_builder_multiple_instances() {
-    let rt1 = RuntimeBuilder::new().build();
-    let rt2 = RuntimeBuilder::new().build();
+    let rt1 = RuntimeBuilder::new().build().unwrap();
+    let rt2 = RuntimeBuilder::new().build().unwrap();
 
     let result1 = rt1.block_on(async { 10 });
     let result2 = rt2.block_on(async { 20 });
@@ -46,7 +47,7 @@ fn test_builder_multiple_instances() {
 
 #[test]
 fn test_builder_with_async_function() {
-    let rt = RuntimeBuilder::new().build();
+    let rt = RuntimeBuilder::new().build().unwrap();
     let counter = Arc::new(Mutex::new(0));
 
     async fn increment_counter(counter: Arc<Mutex<i32>>) -> i32 {
@@ -64,7 +65,7 @@ fn test_builder_with_async_function() {
 
 #[test]
 fn test_builder_with_complex_async_function() {
-    let rt = RuntimeBuilder::new().build();
+    let rt = RuntimeBuilder::new().build().unwrap();
     let result = rt.block_on(complex_computation());
 
     assert_eq!(result, 15, "Complex computation should return 15");
@@ -82,7 +83,7 @@ async fn complex_computation() -> i32 {
 
 #[test]
 fn test_spawn_simple_task() {
-    let rt = RuntimeBuilder::new().build();
+    let rt = RuntimeBuilder::new().build().unwrap();
     let completed = Arc::new(Mutex::new(false));
     let completed_clone = completed.clone();
 
@@ -100,7 +101,7 @@ fn test_spawn_simple_task() {
 
 #[test]
 fn test_spawn_multiple_tasks() {
-    let rt = RuntimeBuilder::new().build();
+    let rt = RuntimeBuilder::new().build().unwrap();
     let counter = Arc::new(Mutex::new(0));
 
     for _ in 0..5 {
@@ -118,7 +119,7 @@ fn test_spawn_multiple_tasks() {
 
 #[test]
 fn test_spawn_with_shared_state() {
-    let rt = RuntimeBuilder::new().build();
+    let rt = RuntimeBuilder::new().build().unwrap();
     let state = Arc::new(Mutex::new(Vec::new()));
 
     for i in 0..3 {
@@ -134,9 +135,36 @@ fn test_spawn_with_shared_state() {
     assert_eq!(final_state.len(), 3, "Should have 3 values");
 }
 
+#[test]
+fn test_spawn_returns_join_handles_awaitable_in_block_on() {
+    let rt = RuntimeBuilder::new().build().unwrap();
+    let ran = Arc::new(AtomicUsize::new(0));
+
+    let first = {
+        let ran = ran.clone();
+        rt.spawn(async move {
+            ran.fetch_add(1, Ordering::SeqCst);
+            "first"
+        })
+    };
+
+    let second = {
+        let ran = ran.clone();
+        rt.spawn(async move {
+            ran.fetch_add(1, Ordering::SeqCst);
+            2
+        })
+    };
+
+    let outputs = rt.block_on(async move { (first.await.unwrap(), second.await.unwrap()) });
+
+    assert_eq!(outputs, ("first", 2));
+    assert_eq!(ran.load(Ordering::SeqCst), 2);
+}
+
 #[test]
 fn test_block_on_waits_for_spawned_tasks() {
-    let rt = RuntimeBuilder::new().build();
+    let rt = RuntimeBuilder::new().build().unwrap();
     let executed = Arc::new(Mutex::new(false));
     let executed_clone = executed.clone();
 
@@ -151,3 +179,147 @@ fn test_block_on_waits_for_spawned_tasks() {
         "Spawned task should execute before block_on returns"
     );
 }
+
+#[test]
+fn test_nested_block_on_panics_instead_of_hanging() {
+    let rt = RuntimeBuilder::new().build().unwrap();
+
+    let message = rt.block_on(async {
+        let inner = RuntimeBuilder::new().worker_threads(1).build().unwrap();
+
+        let result =
+            std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| inner.block_on(async { 1 })));
+
+        match result {
+            Ok(_) => String::from("nested block_on returned"),
+            Err(payload) => payload
+                .downcast_ref::<&str>()
+                .map(|s| s.to_string())
+                .or_else(|| payload.downcast_ref::<String>().cloned())
+                .unwrap_or_default(),
+        }
+    });
+
+    assert!(
+        message.contains("block_on cannot be called from within a runtime worker thread"),
+        "unexpected outcome: {message}"
+    );
+}
+
+#[test]
+fn test_local_queue_overflow_spills_to_injector() {
+    let rt = RuntimeBuilder::new()
+        .worker_threads(2)
+        .local_queue_capacity(4)
+        .build()
+        .unwrap();
+
+    let completed = rt.block_on(async {
+        let counter = Arc::new(AtomicUsize::new(0));
+
+        // Spawned from a worker: far more tasks than the local queue holds.
+        let handles: Vec<_> = (0..1000)
+            .map(|_| {
+                let counter = counter.clone();
+                cadentis::task::spawn(async move {
+                    counter.fetch_add(1, Ordering::SeqCst);
+                })
+            })
+            .collect();
+
+        for handle in handles {
+            handle.await.unwrap();
+        }
+
+        counter.load(Ordering::SeqCst)
+    });
+
+    assert_eq!(completed, 1000);
+}
+
+#[test]
+#[should_panic(expected = "local_queue_capacity must be > 0")]
+fn test_zero_local_queue_capacity_panics() {
+    let _ = RuntimeBuilder::new().local_queue_capacity(0);
+}
+
+#[test]
+fn test_pinned_workers_run_tasks() {
+    let rt = RuntimeBuilder::new()
+        .worker_threads(2)
+        .pin_workers(true)
+        .build()
+        .unwrap();
+
+    let sum = rt.block_on(async {
+        let handles: Vec<_> = (0..100u64)
+            .map(|i| cadentis::task::spawn(async move { i }))
+            .collect();
+
+        let mut sum = 0;
+        for handle in handles {
+            sum += handle.await.unwrap();
+        }
+        sum
+    });
+
+    assert_eq!(sum, 4950);
+}
+
+#[test]
+#[should_panic(expected = "core_ids must not be empty")]
+fn test_empty_core_ids_panics() {
+    let _ = RuntimeBuilder::new().core_ids(Vec::new());
+}
+
+#[cfg(target_os = "linux")]
+mod affinity {
+    /// Returns the cores the calling thread is allowed to run on, as
+    /// listed by `/proc`, such as `0-3,6`.
+    pub fn current_cores() -> Vec<usize> {
+        let status = std::fs::read_to_string("/proc/thread-self/status").unwrap();
+        let list = status
+            .lines()
+            .find_map(|line| line.strip_prefix("Cpus_allowed_list:"))
+            .expect("no allowed cpus in /proc");
+
+        list.trim()
+            .split(',')
+            .flat_map(|range| match range.split_once('-') {
+                Some((first, last)) => first.parse().unwrap()..=last.parse().unwrap(),
+                None => range.parse().unwrap()..=range.parse().unwrap(),
+            })
+            .collect()
+    }
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn test_core_ids_sets_worker_affinity() {
+    let core = affinity::current_cores()[0];
+
+    let rt = RuntimeBuilder::new()
+        .worker_threads(2)
+        .core_ids(vec![core])
+        .build()
+        .unwrap();
+
+    let cores = rt.block_on(async {
+        let handles: Vec<_> = (0..8)
+            .map(|_| cadentis::task::spawn(async { affinity::current_cores() }))
+            .collect();
+
+        let mut cores = Vec::new();
+        for handle in handles {
+            cores.push(handle.await.unwrap());
+        }
+        cores
+    });
+
+    for worker_cores in cores {
+        assert_eq!(worker_cores, vec![core]);
+    }
+
+    // The thread that built the runtime is left untouched.
+    assert!(!affinity::current_cores().is_empty());
+}
@@ -0,0 +1,100 @@
+use cadentis::sync::{RwLock, RwLockPolicy};
+use cadentis::task;
+use cadentis::time::{sleep, timeout};
+use std::sync::{Arc, Mutex as StdMutex};
+use std::time::Duration;
+
+#[cadentis::test]
+async fn readers_share_the_lock_and_writers_exclude_them() {
+    let lock = RwLock::new(1);
+
+    let first = lock.read().await;
+    let second = lock.read().await;
+    assert_eq!(*first + *second, 2);
+    drop((first, second));
+
+    *lock.write().await += 1;
+    assert_eq!(*lock.read().await, 2);
+}
+
+/// Holds a read lock while a writer queues up, then lets readers arrive
+/// one after the other. Returns the order in which they acquired.
+async fn acquisition_order(policy: RwLockPolicy) -> Vec<&'static str> {
+    let lock = Arc::new(RwLock::with_policy(0, policy));
+    let log = Arc::new(StdMutex::new(Vec::new()));
+
+    let held = lock.read().await;
+
+    let writer = {
+        let (lock, log) = (lock.clone(), log.clone());
+        task::spawn(async move {
+            *lock.write().await += 1;
+            log.lock().unwrap().push("writer");
+        })
+    };
+    sleep(Duration::from_millis(20)).await;
+
+    let mut readers = Vec::new();
+    for _ in 0..5 {
+        let (lock, log) = (lock.clone(), log.clone());
+        readers.push(task::spawn(async move {
+            let _guard = lock.read().await;
+            log.lock().unwrap().push("reader");
+        }));
+        sleep(Duration::from_millis(5)).await;
+    }
+
+    drop(held);
+
+    writer.await.unwrap();
+    for reader in readers {
+        reader.await.unwrap();
+    }
+
+    assert_eq!(*lock.read().await, 1);
+    Arc::try_unwrap(log).unwrap().into_inner().unwrap()
+}
+
+#[cadentis::test]
+async fn waiting_writer_goes_before_new_readers_with_writer_preference() {
+    let order = acquisition_order(RwLockPolicy::WriterPreference).await;
+    assert_eq!(
+        order,
+        ["writer", "reader", "reader", "reader", "reader", "reader"]
+    );
+}
+
+#[cadentis::test]
+async fn new_readers_overtake_a_waiting_writer_with_reader_preference() {
+    let order = acquisition_order(RwLockPolicy::ReaderPreference).await;
+    assert_eq!(
+        order,
+        ["reader", "reader", "reader", "reader", "reader", "writer"]
+    );
+}
+
+#[cadentis::test]
+async fn cancelled_writer_lets_queued_readers_in() {
+    let lock = Arc::new(RwLock::with_policy((), RwLockPolicy::WriterPreference));
+    let held = lock.read().await;
+
+    // The writer gives up while the read lock is still held.
+    let writer = {
+        let lock = lock.clone();
+        task::spawn(async move {
+            timeout(Duration::from_millis(50), lock.write())
+                .await
+                .is_err()
+        })
+    };
+    sleep(Duration::from_millis(20)).await;
+
+    // Queued behind the writer, not behind the held read lock.
+    let reader = lock.read();
+    timeout(Duration::from_secs(5), reader)
+        .await
+        .expect("reader never acquired");
+
+    assert!(writer.await.unwrap());
+    drop(held);
+}
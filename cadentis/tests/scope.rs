@@ -0,0 +1,121 @@
+use cadentis::task;
+use cadentis::time::{sleep, timeout};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+#[cadentis::test]
+async fn scope_waits_for_every_child() {
+    let finished = Arc::new(AtomicUsize::new(0));
+
+    let output = task::scope(|s| {
+        let finished = finished.clone();
+        async move {
+            for i in 0..8 {
+                let finished = finished.clone();
+                s.spawn(async move {
+                    sleep(Duration::from_millis(5 * i)).await;
+                    finished.fetch_add(1, Ordering::SeqCst);
+                });
+            }
+            "body done"
+        }
+    })
+    .await;
+
+    assert_eq!(output, "body done");
+    assert_eq!(finished.load(Ordering::SeqCst), 8);
+}
+
+#[cadentis::test]
+async fn scope_waits_for_grandchildren() {
+    let finished = Arc::new(AtomicUsize::new(0));
+
+    task::scope(|s| {
+        let finished = finished.clone();
+        async move {
+            let nested = s.clone();
+            s.spawn(async move {
+                sleep(Duration::from_millis(10)).await;
+
+                // Spawned while the scope is already waiting.
+                nested.spawn(async move {
+                    sleep(Duration::from_millis(20)).await;
+                    finished.fetch_add(1, Ordering::SeqCst);
+                });
+            });
+        }
+    })
+    .await;
+
+    assert_eq!(finished.load(Ordering::SeqCst), 1);
+}
+
+#[cadentis::test]
+async fn dropping_a_scope_aborts_its_children() {
+    let finished = Arc::new(AtomicUsize::new(0));
+    let child_finished = finished.clone();
+
+    let result = timeout(
+        Duration::from_millis(20),
+        task::scope(|s| async move {
+            s.spawn(async move {
+                sleep(Duration::from_millis(100)).await;
+                child_finished.fetch_add(1, Ordering::SeqCst);
+            });
+        }),
+    )
+    .await;
+    assert!(result.is_err());
+
+    sleep(Duration::from_millis(150)).await;
+    assert_eq!(finished.load(Ordering::SeqCst), 0);
+}
+
+#[cadentis::test]
+async fn borrowed_children_sum_a_local_slice() {
+    let numbers: Vec<u64> = (1..=1000).collect();
+    let total = AtomicUsize::new(0);
+    let (numbers_ref, total_ref) = (&numbers[..], &total);
+
+    let parts = task::scope(|s| async move {
+        let mut parts = 0;
+        for chunk in numbers_ref.chunks(100) {
+            parts += 1;
+            s.spawn_borrowed(async move {
+                sleep(Duration::from_millis(1)).await;
+                let sum: u64 = chunk.iter().sum();
+                total_ref.fetch_add(sum as usize, Ordering::SeqCst);
+            });
+        }
+        parts
+    })
+    .await;
+
+    assert_eq!(parts, 10);
+    assert_eq!(total.load(Ordering::SeqCst), 500_500);
+    assert_eq!(numbers.len(), 1000);
+}
+
+#[cadentis::test]
+async fn borrowed_children_may_spawn_more() {
+    let finished = AtomicUsize::new(0);
+    let finished_ref = &finished;
+
+    task::scope(|s| async move {
+        let nested = s.clone();
+        s.spawn_borrowed(async move {
+            sleep(Duration::from_millis(5)).await;
+
+            // Spawned while the scope is already waiting.
+            nested.spawn_borrowed(async move {
+                sleep(Duration::from_millis(5)).await;
+                finished_ref.fetch_add(1, Ordering::SeqCst);
+            });
+            finished_ref.fetch_add(1, Ordering::SeqCst);
+        });
+    })
+    .await;
+
+    assert_eq!(finished.load(Ordering::SeqCst), 2);
+}
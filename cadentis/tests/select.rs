@@ -1,6 +1,12 @@
-use cadentis::select;
+use cadentis::sync::mpsc;
+use cadentis::task;
+use cadentis::time::sleep;
+use cadentis::tools::Selected;
+use cadentis::{select, select_enum};
+use std::pin::pin;
 use std::sync::Arc;
 use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
 
 #[cadentis::test]
 async fn test_select_single_future() {
@@ -111,3 +117,67 @@ async fn test_select_option_pattern() {
 
     assert!(result == 42 || result == -1);
 }
+
+#[cadentis::test]
+async fn test_select_enum_returns_winning_branch() {
+    let result = select_enum!(async { 1u8 }, std::future::pending::<&str>());
+
+    match result {
+        Selected::Branch0(v) => assert_eq!(v, 1),
+        Selected::Branch1(_) => panic!("pending future should not win"),
+    }
+}
+
+#[cadentis::test]
+async fn test_select_enum_drops_slower_future() {
+    let result = select_enum!(
+        async {
+            sleep(Duration::from_secs(5)).await;
+            "slow"
+        },
+        async { "fast" },
+    );
+
+    assert_eq!(result, Selected::Branch1("fast"));
+}
+
+#[cadentis::test]
+async fn test_select_by_reference_keeps_state_across_iterations() {
+    let (tx, mut rx) = mpsc::channel::<u32>(4);
+
+    let producer = task::spawn(async move {
+        for i in 0..20 {
+            tx.send(i).await.unwrap();
+            sleep(Duration::from_millis(2)).await;
+        }
+    });
+
+    // Accumulates every message: restarting it would lose the ones
+    // already collected.
+    let mut collect = pin!(async {
+        let mut messages = Vec::new();
+        while let Some(message) = rx.recv().await {
+            messages.push(message);
+        }
+        messages
+    });
+
+    let mut ticks = 0;
+
+    let messages = loop {
+        let done = select! {
+            &mut collect => |messages| Some(messages),
+            sleep(Duration::from_millis(1)) => |_| None,
+        };
+
+        match done {
+            Some(messages) => break messages,
+            None => ticks += 1,
+        }
+    };
+
+    producer.await.unwrap();
+
+    assert_eq!(messages, (0..20).collect::<Vec<_>>());
+    assert!(ticks > 0, "the timer branch should have won at least once");
+}
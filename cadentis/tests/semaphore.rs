@@ -0,0 +1,66 @@
+use cadentis::sync::{Notify, Semaphore};
+use cadentis::task;
+use cadentis::time::sleep;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+#[cadentis::test]
+async fn semaphore_grants_permits_in_fifo_order() {
+    let semaphore = Arc::new(Semaphore::new(1));
+    let order = Arc::new(Mutex::new(Vec::new()));
+
+    let held = semaphore.acquire().await;
+
+    let mut handles = Vec::new();
+    for i in 0..8 {
+        let semaphore = semaphore.clone();
+        let order = order.clone();
+
+        handles.push(task::spawn(async move {
+            let _permit = semaphore.acquire().await;
+            order.lock().unwrap().push(i);
+        }));
+
+        // Let the task enqueue itself before spawning the next one.
+        sleep(Duration::from_millis(5)).await;
+    }
+
+    drop(held);
+
+    for handle in handles {
+        handle.await.unwrap();
+    }
+
+    assert_eq!(*order.lock().unwrap(), (0..8).collect::<Vec<_>>());
+    assert_eq!(semaphore.available_permits(), 1);
+}
+
+#[cadentis::test]
+async fn notify_one_wakes_waiters_in_fifo_order() {
+    let notify = Arc::new(Notify::new());
+    let order = Arc::new(Mutex::new(Vec::new()));
+
+    let mut handles = Vec::new();
+    for i in 0..8 {
+        let notify = notify.clone();
+        let order = order.clone();
+
+        handles.push(task::spawn(async move {
+            notify.notified().await;
+            order.lock().unwrap().push(i);
+        }));
+
+        sleep(Duration::from_millis(5)).await;
+    }
+
+    for _ in 0..8 {
+        notify.notify_one();
+        sleep(Duration::from_millis(5)).await;
+    }
+
+    for handle in handles {
+        handle.await.unwrap();
+    }
+
+    assert_eq!(*order.lock().unwrap(), (0..8).collect::<Vec<_>>());
+}
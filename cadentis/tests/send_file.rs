@@ -0,0 +1,83 @@
+use cadentis::fs::File;
+use cadentis::io::AsyncWriteExt;
+use cadentis::net::{TcpListener, TcpStream};
+use cadentis::task;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Writes `contents` to a fresh temporary file and returns its path.
+fn temp_file(name: &str, contents: &[u8]) -> PathBuf {
+    let unique = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("clock drift")
+        .as_nanos();
+
+    let path = std::env::temp_dir().join(format!(
+        "send-file-{name}-{}-{}.tmp",
+        std::process::id(),
+        unique
+    ));
+    std::fs::write(&path, contents).unwrap();
+
+    path
+}
+
+/// Serves `len` bytes of the file at `path` from `offset`, after `header`,
+/// and returns everything the client received along with the count sent.
+async fn serve(path: &Path, header: &[u8], offset: u64, len: u64) -> (Vec<u8>, u64) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    let client = task::spawn(async move {
+        let stream = TcpStream::connect(&addr.to_string()).await.unwrap();
+        let mut received = Vec::new();
+        let mut buffer = [0u8; 64 * 1024];
+
+        loop {
+            let n = stream.read(&mut buffer).await.unwrap();
+            if n == 0 {
+                break;
+            }
+            received.extend_from_slice(&buffer[..n]);
+        }
+
+        received
+    });
+
+    let (mut stream, _) = listener.accept().await.unwrap();
+    let file = File::open(&path.to_string_lossy()).await.unwrap();
+
+    stream.write_all(header).await.unwrap();
+    let sent = stream.send_file(&file, offset, len).await.unwrap();
+    AsyncWriteExt::shutdown(&mut stream).await.unwrap();
+
+    (client.await.unwrap(), sent)
+}
+
+#[cadentis::test]
+async fn send_file_serves_file_contents() {
+    // Large enough to fill the socket buffers several times over.
+    let contents: Vec<u8> = (0..4 * 1024 * 1024u32).map(|i| (i % 251) as u8).collect();
+    let path = temp_file("whole", &contents);
+
+    let (received, sent) = serve(&path, b"HEADER\n", 0, contents.len() as u64).await;
+
+    assert_eq!(sent, contents.len() as u64);
+    assert_eq!(&received[..7], b"HEADER\n");
+    assert!(received[7..] == contents[..], "file contents differ");
+
+    let _ = std::fs::remove_file(path);
+}
+
+#[cadentis::test]
+async fn send_file_honors_offset_and_stops_at_end_of_file() {
+    let contents: Vec<u8> = (0..10_000u32).map(|i| (i % 97) as u8).collect();
+    let path = temp_file("range", &contents);
+
+    let (received, sent) = serve(&path, b"", 1_000, 1_000_000).await;
+
+    assert_eq!(sent, 9_000);
+    assert_eq!(received, contents[1_000..]);
+
+    let _ = std::fs::remove_file(path);
+}
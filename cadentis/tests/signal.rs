@@ -0,0 +1,75 @@
+#![cfg(unix)]
+
+use cadentis::signal::{self, SignalKind};
+use cadentis::stream::StreamExt;
+use cadentis::time::timeout;
+use std::io;
+use std::process::Command;
+use std::time::Duration;
+
+/// Sends `kind` to the current process from a `kill` child process.
+fn raise(kind: SignalKind) {
+    let status = Command::new("kill")
+        .args([
+            format!("-{}", kind.as_raw()),
+            std::process::id().to_string(),
+        ])
+        .status()
+        .unwrap();
+
+    assert!(status.success());
+}
+
+#[cadentis::test]
+async fn sigusr1_wakes_listening_stream() {
+    let mut first = signal::unix(SignalKind::user_defined1()).unwrap();
+    let mut second = signal::unix(SignalKind::user_defined1()).unwrap();
+
+    raise(SignalKind::user_defined1());
+
+    timeout(Duration::from_secs(5), first.recv())
+        .await
+        .expect("first listener not woken");
+    timeout(Duration::from_secs(5), second.next())
+        .await
+        .expect("second listener not woken");
+}
+
+#[cadentis::test]
+async fn listener_ignores_other_signals() {
+    let mut usr2 = signal::unix(SignalKind::user_defined2()).unwrap();
+    let mut hangup = signal::unix(SignalKind::hangup()).unwrap();
+
+    raise(SignalKind::hangup());
+
+    timeout(Duration::from_secs(5), hangup.recv())
+        .await
+        .expect("hangup listener not woken");
+    assert!(
+        timeout(Duration::from_millis(50), usr2.recv())
+            .await
+            .is_err()
+    );
+}
+
+#[cadentis::test]
+async fn handler_stays_installed_across_deliveries() {
+    // `SIGWINCH`, ignored by default, has the same number on every unix.
+    let window_change = SignalKind::from_raw(28);
+    let mut listener = signal::unix(window_change).unwrap();
+
+    for _ in 0..2 {
+        raise(window_change);
+
+        timeout(Duration::from_secs(5), listener.recv())
+            .await
+            .expect("listener not woken");
+    }
+}
+
+#[test]
+fn uncatchable_signals_are_rejected() {
+    let err = signal::unix(SignalKind::from_raw(9)).err().unwrap();
+
+    assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+}
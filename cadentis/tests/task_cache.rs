@@ -0,0 +1,147 @@
+use cadentis::RuntimeBuilder;
+use cadentis::task;
+use cadentis::time::sleep;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+/// Allocator counting the allocations made by each thread.
+struct CountingAllocator;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let _ = ALLOCATIONS.try_with(|count| count.set(count.get() + 1));
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+fn allocations() -> usize {
+    ALLOCATIONS.with(Cell::get)
+}
+
+/// Spawns and awaits `n` tasks one after the other.
+async fn spawn_sequentially(n: usize) -> usize {
+    let mut sum = 0;
+
+    for i in 0..n {
+        sum += task::spawn(async move { i * 2 }).await.unwrap();
+    }
+
+    sum
+}
+
+#[test]
+fn recycled_tasks_return_correct_results() {
+    let rt = RuntimeBuilder::new().worker_threads(1).build().unwrap();
+
+    rt.block_on(async {
+        for round in 0..10 {
+            let sum = spawn_sequentially(100).await;
+            assert_eq!(sum, 99 * 100, "round {round}");
+
+            let text = task::spawn(async move { format!("round {round}") })
+                .await
+                .unwrap();
+            assert_eq!(text, format!("round {round}"));
+        }
+
+        // A burst of detached tasks finishing while others are spawned.
+        let handles: Vec<_> = (0..200).map(|i| task::spawn(async move { i })).collect();
+        for (i, handle) in handles.into_iter().enumerate() {
+            assert_eq!(handle.await.unwrap(), i);
+        }
+    });
+}
+
+#[test]
+fn detached_task_output_is_not_kept_alive() {
+    struct Tracked(Arc<AtomicUsize>);
+
+    impl Drop for Tracked {
+        fn drop(&mut self) {
+            self.0.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    let rt = RuntimeBuilder::new().worker_threads(1).build().unwrap();
+    let dropped = Arc::new(AtomicUsize::new(0));
+    let dropped_clone = dropped.clone();
+
+    rt.block_on(async move {
+        for _ in 0..10 {
+            let dropped = dropped_clone.clone();
+            drop(task::spawn(async move { Tracked(dropped) }));
+        }
+
+        for _ in 0..100 {
+            if dropped_clone.load(Ordering::SeqCst) == 10 {
+                return;
+            }
+            sleep(Duration::from_millis(5)).await;
+        }
+    });
+
+    assert_eq!(dropped.load(Ordering::SeqCst), 10);
+}
+
+#[test]
+fn spawning_on_a_worker_reuses_task_allocations() {
+    const SPAWNS: usize = 1000;
+
+    let rt = RuntimeBuilder::new().worker_threads(1).build().unwrap();
+
+    let per_spawn = rt.block_on(async {
+        // Warm up the cache and every lazily allocated structure.
+        spawn_sequentially(100).await;
+
+        let before = allocations();
+        spawn_sequentially(SPAWNS).await;
+        (allocations() - before) as f64 / SPAWNS as f64
+    });
+
+    // The future lives in the task, which comes from the cache of the
+    // worker: only the waker registered by the awaiting handle allocates.
+    assert!(per_spawn <= 1.0, "{per_spawn} allocations per spawn");
+}
+
+#[test]
+fn spawning_allocates_the_task_and_its_future_at_once() {
+    const SPAWNS: usize = 100;
+
+    let rt = RuntimeBuilder::new().worker_threads(1).build().unwrap();
+
+    let spawn = || {
+        let state = [1u8; 1024];
+        rt.spawn(async move { state.iter().map(|&b| b as usize).sum::<usize>() })
+    };
+
+    // Warm up the queues of the runtime.
+    let handles: Vec<_> = (0..SPAWNS).map(|_| spawn()).collect();
+
+    let mut more = Vec::with_capacity(SPAWNS);
+    let before = allocations();
+    more.extend((0..SPAWNS).map(|_| spawn()));
+    let per_spawn = (allocations() - before) as f64 / SPAWNS as f64;
+
+    rt.block_on(async move {
+        for handle in handles.into_iter().chain(more) {
+            assert_eq!(handle.await.unwrap(), 1024);
+        }
+    });
+
+    // Growing a queue of the runtime may take a few more.
+    assert!(per_spawn < 1.5, "{per_spawn} allocations per spawn");
+}
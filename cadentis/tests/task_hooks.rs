@@ -0,0 +1,137 @@
+use cadentis::task::{self, TaskId};
+use cadentis::time::sleep;
+use cadentis::{BusyLoopAction, RuntimeBuilder, yield_now};
+use std::collections::{HashMap, HashSet};
+use std::future::poll_fn;
+use std::sync::{Arc, Mutex};
+use std::task::Poll;
+use std::thread;
+use std::time::Duration;
+
+const TASKS: usize = 50;
+
+#[test]
+fn spawn_hook_fires_once_per_task_with_distinct_ids() {
+    let spawned = Arc::new(Mutex::new(Vec::new()));
+    let recorder = spawned.clone();
+
+    let rt = RuntimeBuilder::new()
+        .worker_threads(2)
+        .on_task_spawn(move |id| recorder.lock().unwrap().push(id))
+        .build()
+        .unwrap();
+
+    let ids = rt.block_on(async {
+        let handles: Vec<_> = (0..TASKS).map(|i| task::spawn(async move { i })).collect();
+        let ids: Vec<TaskId> = handles.iter().map(|handle| handle.id()).collect();
+
+        for handle in handles {
+            handle.await.unwrap();
+        }
+
+        ids
+    });
+
+    let spawned = spawned.lock().unwrap();
+    for id in &ids {
+        let count = spawned.iter().filter(|spawned| *spawned == id).count();
+        assert_eq!(count, 1, "task {id} reported {count} times");
+    }
+
+    let distinct: HashSet<_> = spawned.iter().collect();
+    assert_eq!(distinct.len(), spawned.len());
+}
+
+#[test]
+fn poll_hook_fires_before_each_poll() {
+    let polls = Arc::new(Mutex::new(HashMap::<TaskId, usize>::new()));
+    let recorder = polls.clone();
+
+    let rt = RuntimeBuilder::new()
+        .worker_threads(1)
+        .on_task_poll(move |id| *recorder.lock().unwrap().entry(id).or_default() += 1)
+        .build()
+        .unwrap();
+
+    let id = rt.block_on(async {
+        let handle = task::spawn(async {
+            for _ in 0..3 {
+                yield_now().await;
+            }
+        });
+        let id = handle.id();
+
+        handle.await.unwrap();
+        id
+    });
+
+    // Polled once per yield, and once more to complete.
+    assert_eq!(polls.lock().unwrap().get(&id), Some(&4));
+}
+
+#[test]
+fn slow_poll_hook_names_the_blocking_task() {
+    let slow = Arc::new(Mutex::new(Vec::new()));
+    let recorder = slow.clone();
+
+    let rt = RuntimeBuilder::new()
+        .worker_threads(1)
+        .on_slow_poll(Duration::from_millis(20), move |id, elapsed| {
+            recorder.lock().unwrap().push((id, elapsed))
+        })
+        .build()
+        .unwrap();
+
+    let (blocking, quick) = rt.block_on(async {
+        // Blocks its worker instead of awaiting.
+        let blocking = task::spawn(async { thread::sleep(Duration::from_millis(60)) });
+        let quick = task::spawn(async { yield_now().await });
+        let ids = (blocking.id(), quick.id());
+
+        blocking.await.unwrap();
+        quick.await.unwrap();
+        ids
+    });
+
+    let slow = slow.lock().unwrap();
+    let reported: Vec<_> = slow.iter().filter(|(id, _)| *id == blocking).collect();
+
+    assert_eq!(reported.len(), 1);
+    assert!(reported[0].1 >= Duration::from_millis(60));
+    assert!(slow.iter().all(|(id, _)| *id != quick));
+}
+
+#[test]
+fn busy_loop_guard_aborts_a_task_waking_itself_forever() {
+    let rt = RuntimeBuilder::new()
+        .worker_threads(1)
+        .busy_loop_limit(1_000, BusyLoopAction::Abort)
+        .build()
+        .unwrap();
+
+    let (spinning, waiting) = rt.block_on(async {
+        let spinning = task::spawn(poll_fn::<(), _>(|cx| {
+            cx.waker().wake_by_ref();
+            Poll::Pending
+        }));
+
+        // Stays under the limit between two real waits. The waits are long
+        // enough for their wake-ups not to land while the task is polled,
+        // which would count as self-wakes.
+        let waiting = task::spawn(async {
+            for _ in 0..3 {
+                for _ in 0..900 {
+                    yield_now().await;
+                }
+                sleep(Duration::from_millis(20)).await;
+            }
+        });
+
+        (spinning.await, waiting.await)
+    });
+
+    let error = spinning.unwrap_err();
+    assert!(error.is_panic());
+    assert!(format!("{error:?}").contains("woke itself 1001 times in a row"));
+    assert!(waiting.is_ok());
+}
@@ -0,0 +1,81 @@
+use cadentis::RuntimeBuilder;
+use cadentis::task::{self, SpanId, TaskContext, Tracer};
+use cadentis::time::sleep;
+use cadentis::yield_now;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+#[derive(Clone, Default)]
+struct Recorder {
+    events: Arc<Mutex<Vec<(&'static str, u64)>>>,
+}
+
+impl Tracer for Recorder {
+    fn enter(&self, span: SpanId) {
+        self.events.lock().unwrap().push(("enter", span.as_u64()));
+    }
+
+    fn exit(&self, span: SpanId) {
+        self.events.lock().unwrap().push(("exit", span.as_u64()));
+    }
+}
+
+#[cadentis::test]
+async fn traced_task_observes_its_span_across_awaits() {
+    assert_eq!(TaskContext::current_span(), None);
+
+    let handles: Vec<_> = (1..=8)
+        .map(|i| {
+            task::spawn_traced(SpanId::new(i), async move {
+                let mut seen = vec![TaskContext::current_span()];
+
+                sleep(Duration::from_millis(5)).await;
+                seen.push(TaskContext::current_span());
+
+                yield_now().await;
+                seen.push(TaskContext::current_span());
+
+                // Children do not inherit the span.
+                let child = task::spawn(async { TaskContext::current_span() });
+                assert_eq!(child.await.unwrap(), None);
+
+                seen.push(TaskContext::current_span());
+                (i, seen)
+            })
+        })
+        .collect();
+
+    for handle in handles {
+        let (i, seen) = handle.await.unwrap();
+        assert!(seen.iter().all(|span| *span == Some(SpanId::new(i))));
+    }
+
+    let untraced = task::spawn(async { TaskContext::current_span() });
+    assert_eq!(untraced.await.unwrap(), None);
+}
+
+#[test]
+fn tracer_brackets_every_poll_of_traced_tasks() {
+    let recorder = Recorder::default();
+
+    let rt = RuntimeBuilder::new()
+        .worker_threads(2)
+        .tracer(recorder.clone())
+        .build()
+        .unwrap();
+
+    rt.block_on(async {
+        let traced = task::spawn_traced(SpanId::new(7), async {
+            yield_now().await;
+            yield_now().await;
+        });
+        let untraced = task::spawn(async { yield_now().await });
+
+        traced.await.unwrap();
+        untraced.await.unwrap();
+    });
+
+    let events = recorder.events.lock().unwrap();
+    let expected: Vec<_> = (0..3).flat_map(|_| [("enter", 7), ("exit", 7)]).collect();
+    assert_eq!(*events, expected);
+}
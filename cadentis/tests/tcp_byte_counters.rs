@@ -0,0 +1,59 @@
+use cadentis::net::{TcpListener, TcpStream};
+use cadentis::task;
+use std::net::Shutdown;
+
+/// Reads from `stream` until the peer closes its write half.
+async fn read_to_end(stream: &TcpStream) -> Vec<u8> {
+    let mut received = Vec::new();
+    let mut buffer = [0u8; 4096];
+
+    loop {
+        let n = stream.read(&mut buffer).await.unwrap();
+        if n == 0 {
+            return received;
+        }
+        received.extend_from_slice(&buffer[..n]);
+    }
+}
+
+#[cadentis::test]
+async fn byte_counters_match_transferred_payloads() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    let request: Vec<u8> = (0..100_000u32).map(|i| i as u8).collect();
+    let response = b"accepted".repeat(1000);
+    let expected_response = response.clone();
+
+    let server = task::spawn(async move {
+        let (stream, _) = listener.accept().await.unwrap();
+
+        let received = read_to_end(&stream).await;
+        stream.write_all(&response).await.unwrap();
+        stream.shutdown(Shutdown::Write).unwrap();
+
+        (
+            received.len() as u64,
+            stream.bytes_read(),
+            stream.bytes_written(),
+        )
+    });
+
+    let client = TcpStream::connect(&addr.to_string()).await.unwrap();
+    assert_eq!(client.bytes_read(), 0);
+    assert_eq!(client.bytes_written(), 0);
+
+    client.write_all(&request).await.unwrap();
+    client.shutdown(Shutdown::Write).unwrap();
+
+    let received = read_to_end(&client).await;
+    assert_eq!(received, expected_response);
+
+    let (server_received, server_read, server_written) = server.await.unwrap();
+    assert_eq!(server_received, request.len() as u64);
+    assert_eq!(server_read, request.len() as u64);
+    assert_eq!(server_written, expected_response.len() as u64);
+
+    assert_eq!(client.bytes_written(), request.len() as u64);
+    assert_eq!(client.bytes_read(), expected_response.len() as u64);
+}
@@ -1,8 +1,12 @@
-use cadentis::net::TcpListener;
+use cadentis::net::{TcpListener, TcpStream};
+use cadentis::sync::Semaphore;
 use cadentis::task;
-use std::io::{Read, Write};
+use cadentis::time::timeout;
+use std::io::{self, Read, Write};
 use std::net::TcpStream as StdTcpStream;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
 use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
 #[cadentis::test]
 async fn tcp_accept_and_echo() {
@@ -26,7 +30,7 @@ async fn tcp_accept_and_echo() {
         buf.to_vec()
     });
 
-    handle.await;
+    handle.await.unwrap();
 
     let result = client_thread.join().unwrap();
     assert_eq!(&result[..], b"pong");
@@ -56,9 +60,459 @@ async fn tcp_write_all_large_payload() {
         *received_clone.lock().unwrap() = buf;
     });
 
-    handle.await;
+    handle.await.unwrap();
     client_thread.join().expect("client thread join");
 
     assert_eq!(received_main.lock().unwrap().len(), payload_len);
     assert!(received_main.lock().unwrap().iter().all(|&b| b == 7));
 }
+
+#[cadentis::test]
+async fn tcp_write_after_peer_half_close() {
+    let listener = TcpListener::bind("127.0.0.1:0").expect("bind listener");
+    let port = listener.local_addr().expect("local addr").port();
+
+    let handle = task::spawn(async move {
+        let (stream, _peer) = listener.accept().await.expect("accept");
+
+        let mut request = Vec::new();
+        let mut buf = [0u8; 16];
+        loop {
+            let n = stream.read(&mut buf).await.expect("read");
+            if n == 0 {
+                break;
+            }
+            request.extend_from_slice(&buf[..n]);
+        }
+
+        assert_eq!(&request[..], b"request");
+        assert!(stream.is_eof());
+
+        // Reads keep reporting EOF while the write half stays usable.
+        assert_eq!(stream.read(&mut buf).await.expect("read"), 0);
+        stream.write_all(b"response").await.expect("write_all");
+    });
+
+    let client_thread = std::thread::spawn(move || {
+        let mut c = StdTcpStream::connect(("127.0.0.1", port)).expect("connect");
+        c.write_all(b"request").expect("write");
+        c.shutdown(std::net::Shutdown::Write).expect("shutdown");
+
+        let mut buf = [0u8; 8];
+        c.read_exact(&mut buf).expect("read_exact");
+        buf.to_vec()
+    });
+
+    handle.await.unwrap();
+
+    let result = client_thread.join().unwrap();
+    assert_eq!(&result[..], b"response");
+}
+
+#[cadentis::test]
+async fn tcp_read_timeout_against_silent_peer() {
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").expect("bind listener");
+    let addr = listener.local_addr().expect("local addr");
+
+    // The peer accepts the connection and never writes.
+    let server_thread = std::thread::spawn(move || {
+        let (stream, _peer) = listener.accept().expect("accept");
+        std::thread::sleep(Duration::from_millis(500));
+        drop(stream);
+    });
+
+    let stream = TcpStream::connect(&addr.to_string())
+        .await
+        .expect("connect");
+
+    stream
+        .set_read_timeout(Some(Duration::from_millis(100)))
+        .expect("set_read_timeout");
+    assert_eq!(stream.read_timeout(), Some(Duration::from_millis(100)));
+
+    let start = Instant::now();
+    let mut buf = [0u8; 16];
+    let err = stream
+        .read(&mut buf)
+        .await
+        .expect_err("read should time out");
+
+    assert_eq!(err.kind(), io::ErrorKind::TimedOut);
+    assert!(start.elapsed() >= Duration::from_millis(100));
+    assert!(start.elapsed() < Duration::from_millis(400));
+
+    assert!(stream.set_read_timeout(Some(Duration::ZERO)).is_err());
+
+    server_thread.join().unwrap();
+}
+
+#[cadentis::test]
+async fn tcp_finish_flushes_pending_writes() {
+    use cadentis::io::AsyncWriteExt;
+
+    let payload: Vec<u8> = (0..256 * 1024).map(|i| (i % 251) as u8).collect();
+    let expected = payload.clone();
+
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").expect("bind listener");
+    let addr = listener.local_addr().expect("local addr");
+
+    let server_thread = std::thread::spawn(move || {
+        let (mut stream, _peer) = listener.accept().expect("accept");
+        let mut buf = vec![0u8; expected.len()];
+        stream.read_exact(&mut buf).expect("read_exact");
+        buf == expected
+    });
+
+    let mut stream = TcpStream::connect(&addr.to_string())
+        .await
+        .expect("connect");
+
+    // `AsyncWrite` only queues the bytes for the reactor.
+    AsyncWriteExt::write_all(&mut stream, &payload)
+        .await
+        .expect("write_all");
+
+    stream.finish().await.expect("finish");
+
+    assert!(server_thread.join().unwrap());
+}
+
+#[cadentis::test]
+async fn tcp_local_addr_reports_ephemeral_port() {
+    let mut addresses = vec!["127.0.0.1:0"];
+
+    // IPv6 may be unavailable on the host; only test it when it is.
+    if std::net::TcpListener::bind("[::1]:0").is_ok() {
+        addresses.push("[::1]:0");
+    }
+
+    for address in addresses {
+        let listener = TcpListener::bind(address).expect("bind listener");
+        let local = listener.local_addr().expect("local addr");
+
+        assert_ne!(local.port(), 0, "{address} kept port 0");
+        assert_eq!(local.is_ipv6(), address.starts_with('['));
+
+        let handle = task::spawn(async move {
+            let (stream, _peer) = listener.accept().await.expect("accept");
+            stream.write_all(b"hi").await.expect("write_all");
+        });
+
+        let client = TcpStream::connect(&local.to_string())
+            .await
+            .expect("connect");
+
+        let mut buf = [0u8; 2];
+        let mut read = 0;
+        while read < buf.len() {
+            let n = client.read(&mut buf[read..]).await.expect("read");
+            assert_ne!(n, 0, "unexpected eof");
+            read += n;
+        }
+
+        assert_eq!(&buf, b"hi");
+        handle.await.unwrap();
+    }
+}
+
+#[cadentis::test]
+async fn tcp_read_buf_reuses_owned_buffer() {
+    let listener = TcpListener::bind("127.0.0.1:0").expect("bind listener");
+    let addr = listener.local_addr().expect("local addr");
+
+    let handle = task::spawn(async move {
+        let (stream, _peer) = listener.accept().await.expect("accept");
+        stream.write_all(b"first").await.expect("write first");
+
+        // Wait for the client to consume the first message.
+        let mut ack = [0u8; 1];
+        stream.read(&mut ack).await.expect("read ack");
+
+        stream.write_all(b"second").await.expect("write second");
+    });
+
+    let client = TcpStream::connect(&addr.to_string())
+        .await
+        .expect("connect");
+
+    // The future owns its buffer, so it can be stored before being awaited.
+    let pending = client.read_buf(vec![0u8; 64]);
+    let (buffer, n) = pending.await.expect("first read");
+    assert_eq!(&buffer[..n], b"first");
+
+    let capacity = buffer.capacity();
+    let pointer = buffer.as_ptr() as usize;
+
+    client.write_all(b"k").await.expect("write ack");
+
+    let (buffer, n) = client.read_buf(buffer).await.expect("second read");
+    assert_eq!(&buffer[..n], b"second");
+    assert_eq!(buffer.capacity(), capacity);
+    assert_eq!(buffer.as_ptr() as usize, pointer);
+
+    handle.await.unwrap();
+}
+
+#[cadentis::test]
+async fn tcp_from_std_and_into_std_round_trip() {
+    let std_listener = std::net::TcpListener::bind("127.0.0.1:0").expect("bind std listener");
+    let addr = std_listener.local_addr().expect("local addr");
+
+    let listener = TcpListener::from_std(std_listener).expect("from_std listener");
+    assert_eq!(listener.local_addr().expect("local addr"), addr);
+
+    let std_client = StdTcpStream::connect(addr).expect("connect");
+    let client = TcpStream::from_std(std_client).expect("from_std stream");
+    client.write_all(b"hello").await.expect("write hello");
+
+    let (server, _peer) = listener.accept().await.expect("accept");
+
+    let mut buf = [0u8; 5];
+    let mut read = 0;
+    while read < buf.len() {
+        let n = server.read(&mut buf[read..]).await.expect("read");
+        assert_ne!(n, 0, "unexpected eof");
+        read += n;
+    }
+    assert_eq!(&buf, b"hello");
+
+    // Hand the accepted connection back to blocking std code.
+    let Ok(mut std_server) = server.into_std() else {
+        panic!("into_std refused a stream without other handles");
+    };
+    std_server.set_nonblocking(false).expect("set blocking");
+    std_server.write_all(b"ok").expect("std write");
+
+    let mut reply = [0u8; 2];
+    let mut read = 0;
+    while read < reply.len() {
+        let n = client.read(&mut reply[read..]).await.expect("read reply");
+        assert_ne!(n, 0, "unexpected eof");
+        read += n;
+    }
+    assert_eq!(&reply, b"ok");
+
+    let std_listener = listener.into_std();
+    assert_eq!(std_listener.local_addr().expect("local addr"), addr);
+}
+
+#[cadentis::test]
+async fn tcp_socket_buffer_sizes_are_applied() {
+    let listener = TcpListener::bind("127.0.0.1:0").expect("bind listener");
+    let addr = listener.local_addr().expect("local addr");
+
+    let client = TcpStream::connect(&addr.to_string())
+        .await
+        .expect("connect");
+
+    let requested = 256 * 1024;
+
+    client
+        .set_recv_buffer_size(requested)
+        .expect("set recv buffer");
+    client
+        .set_send_buffer_size(requested)
+        .expect("set send buffer");
+
+    // The OS may round or double the value (Linux doubles it), but
+    // never reports less than requested within the system limits.
+    assert!(client.recv_buffer_size().expect("recv buffer") >= requested);
+    assert!(client.send_buffer_size().expect("send buffer") >= requested);
+
+    assert_eq!(
+        client.set_recv_buffer_size(usize::MAX).unwrap_err().kind(),
+        io::ErrorKind::InvalidInput
+    );
+}
+
+#[cadentis::test]
+async fn tcp_bind_socket_addr_and_connect_ip_port_tuple() {
+    let listener =
+        TcpListener::bind(SocketAddr::from((Ipv4Addr::LOCALHOST, 0))).expect("bind listener");
+    let port = listener.local_addr().expect("local addr").port();
+
+    let accept = task::spawn(async move {
+        let (stream, _peer) = listener.accept().await.expect("accept");
+        let mut buf = [0u8; 4];
+        let n = stream.read(&mut buf).await.expect("read");
+        buf[..n].to_vec()
+    });
+
+    let ip = IpAddr::V4(Ipv4Addr::LOCALHOST);
+    let stream = TcpStream::connect((ip, port)).await.expect("connect");
+    stream.write_all(b"ping").await.expect("write_all");
+
+    assert_eq!(accept.await.unwrap(), b"ping");
+}
+
+#[cadentis::test]
+async fn tcp_connect_host_port_tuple() {
+    let listener = TcpListener::bind("127.0.0.1:0").expect("bind listener");
+    let port = listener.local_addr().expect("local addr").port();
+
+    let accept = task::spawn(async move {
+        for _ in 0..2 {
+            listener.accept().await?;
+        }
+        io::Result::Ok(())
+    });
+
+    TcpStream::connect(("localhost", port))
+        .await
+        .expect("connect by host name");
+    TcpStream::connect(("127.0.0.1", port))
+        .await
+        .expect("connect by literal ip");
+
+    accept.await.unwrap().expect("accept");
+}
+
+#[cadentis::test]
+async fn accept_limited_waits_for_a_connection_to_close() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    let limit = Arc::new(Semaphore::new(2));
+
+    let mut clients = Vec::new();
+    for _ in 0..3 {
+        clients.push(TcpStream::connect(addr).await.unwrap());
+    }
+
+    let (first, _, first_permit) = listener.accept_limited(&limit).await.unwrap();
+    let (_second, _, _second_permit) = listener.accept_limited(&limit).await.unwrap();
+
+    // Two connections are live: the third one stays in the backlog.
+    let third = timeout(Duration::from_millis(50), listener.accept_limited(&limit)).await;
+    assert!(third.is_err(), "a third connection was accepted");
+
+    // Closing the first connection lets the third one in.
+    drop(first);
+    drop(first_permit);
+
+    let (third, _, _third_permit) =
+        timeout(Duration::from_secs(1), listener.accept_limited(&limit))
+            .await
+            .expect("third connection not accepted")
+            .unwrap();
+
+    clients[2].write_all(b"hi").await.unwrap();
+    let mut buffer = [0u8; 2];
+    third.read(&mut buffer).await.unwrap();
+    assert_eq!(&buffer, b"hi");
+}
+
+#[cfg(target_os = "linux")]
+#[cadentis::test]
+async fn accepted_sockets_are_non_blocking_and_close_on_exec() {
+    use std::os::fd::AsRawFd;
+
+    const O_NONBLOCK: u32 = 0o4000;
+    const O_CLOEXEC: u32 = 0o2000000;
+
+    for host in ["127.0.0.1:0", "[::1]:0"] {
+        // Hosts without IPv6 only check IPv4.
+        let Ok(listener) = TcpListener::bind(host) else {
+            assert_ne!(host, "127.0.0.1:0", "bind listener");
+            continue;
+        };
+        let addr = listener.local_addr().expect("local addr");
+
+        let client = StdTcpStream::connect(addr).expect("connect");
+        let (server, peer) = listener.accept().await.expect("accept");
+        assert_eq!(peer, client.local_addr().unwrap());
+
+        let Ok(server) = server.into_std() else {
+            panic!("into_std refused a stream without other handles");
+        };
+
+        // The open flags of the socket, in octal, including `O_CLOEXEC`.
+        let info = std::fs::read_to_string(format!("/proc/self/fdinfo/{}", server.as_raw_fd()))
+            .expect("fdinfo");
+        let flags = info
+            .lines()
+            .find_map(|line| line.strip_prefix("flags:"))
+            .map(|flags| u32::from_str_radix(flags.trim(), 8).unwrap())
+            .expect("no flags in fdinfo");
+
+        assert_ne!(flags & O_CLOEXEC, 0, "{host}: not close-on-exec");
+        assert_ne!(flags & O_NONBLOCK, 0, "{host}: blocking");
+    }
+}
+
+#[cadentis::test]
+async fn tcp_into_std_refuses_a_stream_with_other_handles() {
+    let listener = TcpListener::bind("127.0.0.1:0").expect("bind listener");
+    let addr = listener.local_addr().expect("local addr");
+
+    let mut client = StdTcpStream::connect(addr).expect("connect");
+    let (server, _) = listener.accept().await.expect("accept");
+
+    let clone = server.clone();
+    let Err(server) = server.into_std() else {
+        panic!("into_std accepted a stream with a live clone");
+    };
+
+    // The stream handed back is still served by the reactor.
+    client.write_all(b"ping").expect("std write");
+    let mut buf = [0u8; 4];
+    let mut read = 0;
+    while read < buf.len() {
+        let n = server.read(&mut buf[read..]).await.expect("read");
+        assert_ne!(n, 0, "unexpected eof");
+        read += n;
+    }
+    assert_eq!(&buf, b"ping");
+
+    drop(clone);
+    let Ok(mut std_server) = server.into_std() else {
+        panic!("into_std refused a stream without other handles");
+    };
+    std_server.set_nonblocking(false).expect("set blocking");
+    std_server.write_all(b"pong").expect("std write");
+
+    client.read_exact(&mut buf).expect("read reply");
+    assert_eq!(&buf, b"pong");
+}
+
+#[cfg(unix)]
+#[cadentis::test]
+async fn accept_is_notified_on_a_reused_descriptor() {
+    use std::os::fd::AsRawFd;
+
+    let first = std::net::TcpListener::bind("127.0.0.1:0").expect("bind first");
+    let fd = first.as_raw_fd();
+    let first = TcpListener::from_std(first).expect("from_std");
+
+    // The abandoned accept leaves the descriptor registered with the
+    // reactor when the listener closes it. It runs in another task, so
+    // that replacing its registration does not wake the accept below.
+    task::spawn(async move {
+        let accepted = timeout(Duration::from_millis(20), first.accept()).await;
+        assert!(accepted.is_err());
+    })
+    .await
+    .unwrap();
+
+    // Descriptors are reused lowest first, so the new listener usually
+    // gets the number of the closed one.
+    let second = std::net::TcpListener::bind("127.0.0.1:0").expect("bind second");
+    let reused = second.as_raw_fd() == fd;
+    let second = TcpListener::from_std(second).expect("from_std");
+    let addr = second.local_addr().expect("local addr");
+
+    // Connect once the accept below waits on the reactor.
+    let client = std::thread::spawn(move || {
+        std::thread::sleep(Duration::from_millis(50));
+        StdTcpStream::connect(addr).expect("connect")
+    });
+
+    let start = Instant::now();
+    let accepted = timeout(Duration::from_secs(5), second.accept()).await;
+    assert!(matches!(accepted, Ok(Ok(_))), "accept not notified");
+    assert!(
+        start.elapsed() < Duration::from_secs(1),
+        "accept took {:?} (descriptor reused: {reused})",
+        start.elapsed()
+    );
+    client.join().unwrap();
+}
@@ -0,0 +1,67 @@
+use cadentis::net::{TcpListener, TcpStream};
+use cadentis::task;
+use cadentis::time::timeout;
+use std::io;
+use std::net::Shutdown;
+use std::time::Duration;
+
+#[cadentis::test]
+async fn readable_completes_once_peer_sends() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    let client = TcpStream::connect(&addr.to_string()).await.unwrap();
+    let (server, _) = listener.accept().await.unwrap();
+
+    let mut buffer = [0u8; 16];
+    assert_eq!(
+        server.try_read(&mut buffer).unwrap_err().kind(),
+        io::ErrorKind::WouldBlock
+    );
+
+    let writer = task::spawn(async move {
+        client.write_all(b"hello").await.unwrap();
+        client
+    });
+
+    timeout(Duration::from_secs(5), server.readable())
+        .await
+        .expect("stream never became readable")
+        .unwrap();
+
+    let n = server.try_read(&mut buffer).unwrap();
+    assert_eq!(&buffer[..n], b"hello");
+
+    let client = writer.await.unwrap();
+    client.shutdown(Shutdown::Write).unwrap();
+
+    server.readable().await.unwrap();
+    assert_eq!(server.try_read(&mut buffer).unwrap(), 0);
+}
+
+#[cadentis::test]
+async fn try_write_queues_once_writable() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    let client = TcpStream::connect(&addr.to_string()).await.unwrap();
+    let (server, _) = listener.accept().await.unwrap();
+
+    client.writable().await.unwrap();
+    assert_eq!(client.try_write(b"ping").unwrap(), 4);
+
+    // Writable again once the reactor has flushed the first bytes.
+    client.writable().await.unwrap();
+    assert_eq!(client.try_write(b"pong").unwrap(), 4);
+    client.writable().await.unwrap();
+
+    let mut received = Vec::new();
+    let mut buffer = [0u8; 16];
+
+    while received.len() < 8 {
+        let n = server.read(&mut buffer).await.unwrap();
+        received.extend_from_slice(&buffer[..n]);
+    }
+
+    assert_eq!(received, b"pingpong");
+}
@@ -0,0 +1,350 @@
+use cadentis::join;
+use cadentis::net::{ReadHalf, SharedWriteHalf, TcpListener, TcpStream, WriteHalf};
+use cadentis::task;
+use cadentis::time::timeout;
+use std::io::{Read, Write};
+use std::net::{Shutdown, TcpListener as StdTcpListener};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::thread;
+use std::time::Duration;
+
+/// Fails to compile unless `T` can be moved into a spawned task.
+fn assert_send_static<T: Send + 'static>() {}
+
+/// Echoes everything received on `stream` until the peer closes it.
+async fn echo(stream: TcpStream) {
+    let mut buffer = [0u8; 4096];
+
+    loop {
+        let n = stream.read(&mut buffer).await.unwrap();
+        if n == 0 {
+            return;
+        }
+        stream.write_all(&buffer[..n]).await.unwrap();
+    }
+}
+
+#[test]
+fn split_halves_are_send_and_static() {
+    assert_send_static::<ReadHalf>();
+    assert_send_static::<WriteHalf>();
+    assert_send_static::<SharedWriteHalf>();
+}
+
+#[cadentis::test]
+async fn split_halves_read_and_write_concurrently_in_join() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    let server = task::spawn(async move {
+        let (stream, _) = listener.accept().await.unwrap();
+        echo(stream).await;
+    });
+
+    let stream = TcpStream::connect(&addr.to_string()).await.unwrap();
+    let (reader, writer) = stream.split();
+
+    // Large enough to need both directions in flight at once.
+    let payload: Vec<u8> = (0..1_000_000u32).map(|i| (i % 251) as u8).collect();
+
+    let (written, received) = join!(
+        async {
+            for chunk in payload.chunks(16 * 1024) {
+                writer.write_all(chunk).await.unwrap();
+            }
+            payload.len()
+        },
+        async {
+            let mut received = Vec::with_capacity(payload.len());
+            let mut buffer = [0u8; 8192];
+
+            while received.len() < payload.len() {
+                let n = reader.read(&mut buffer).await.unwrap();
+                assert_ne!(n, 0, "connection closed early");
+                received.extend_from_slice(&buffer[..n]);
+            }
+            received
+        }
+    );
+
+    assert_eq!(written, payload.len());
+    assert!(received == payload, "echoed bytes differ");
+
+    stream.shutdown(Shutdown::Write).unwrap();
+    server.await.unwrap();
+}
+
+#[cadentis::test]
+async fn split_halves_can_move_to_separate_tasks() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    let server = task::spawn(async move {
+        let (stream, _) = listener.accept().await.unwrap();
+        echo(stream).await;
+    });
+
+    let stream = TcpStream::connect(&addr.to_string()).await.unwrap();
+    let (reader, writer) = stream.split();
+
+    let write = task::spawn(async move {
+        writer.write_all(b"ping").await.unwrap();
+    });
+    let read = task::spawn(async move {
+        let mut buffer = [0u8; 4];
+        let mut filled = 0;
+
+        while filled < buffer.len() {
+            filled += reader.read(&mut buffer[filled..]).await.unwrap();
+        }
+        buffer
+    });
+
+    write.await.unwrap();
+    assert_eq!(&read.await.unwrap(), b"ping");
+
+    stream.shutdown(Shutdown::Write).unwrap();
+    server.await.unwrap();
+}
+
+#[cadentis::test]
+async fn shared_write_half_never_interleaves_messages() {
+    const FRAMES: usize = 200;
+    const FRAME_LEN: usize = 4096;
+
+    let listener = StdTcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    let collector = thread::spawn(move || {
+        let (mut socket, _) = listener.accept().unwrap();
+        let mut received = Vec::new();
+        socket.read_to_end(&mut received).unwrap();
+        received
+    });
+
+    let stream = TcpStream::connect(&addr.to_string()).await.unwrap();
+    let (_, writer) = stream.split_shared();
+
+    let writers: Vec<_> = [b'a', b'b']
+        .into_iter()
+        .map(|tag| {
+            let writer = writer.clone();
+            task::spawn(async move {
+                // A length-prefixed frame filled with the tag of its writer.
+                let mut frame = (FRAME_LEN as u32).to_be_bytes().to_vec();
+                frame.resize(4 + FRAME_LEN, tag);
+
+                for _ in 0..FRAMES {
+                    writer.write_all(&frame).await.unwrap();
+                    cadentis::yield_now().await;
+                }
+            })
+        })
+        .collect();
+
+    for handle in writers {
+        handle.await.unwrap();
+    }
+    stream.shutdown(Shutdown::Write).unwrap();
+
+    let received = collector.join().unwrap();
+    let mut frames = [0usize; 2];
+    let mut rest = &received[..];
+
+    while !rest.is_empty() {
+        let len = u32::from_be_bytes(rest[..4].try_into().unwrap()) as usize;
+        assert_eq!(len, FRAME_LEN, "frame header corrupted");
+
+        let body = &rest[4..4 + len];
+        assert!(
+            body.iter().all(|&byte| byte == body[0]),
+            "frames were interleaved"
+        );
+        frames[(body[0] - b'a') as usize] += 1;
+        rest = &rest[4 + len..];
+    }
+
+    assert_eq!(frames, [FRAMES, FRAMES]);
+}
+
+#[cadentis::test]
+async fn shared_write_half_is_not_starved_by_busy_clones() {
+    const BUSY_WRITERS: usize = 4;
+    const FRAME_LEN: usize = 256 * 1024;
+
+    let listener = StdTcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    // A slow reader, so the output of the stream never drains at once.
+    let collector = thread::spawn(move || {
+        let (mut socket, _) = listener.accept().unwrap();
+        let mut buffer = [0u8; 64 * 1024];
+
+        while socket.read(&mut buffer).unwrap() > 0 {
+            thread::sleep(Duration::from_millis(1));
+        }
+    });
+
+    let stream = TcpStream::connect(&addr.to_string()).await.unwrap();
+    let (_, writer) = stream.split_shared();
+    let stop = Arc::new(AtomicBool::new(false));
+
+    let busy: Vec<_> = (0..BUSY_WRITERS)
+        .map(|_| {
+            let writer = writer.clone();
+            let stop = stop.clone();
+            task::spawn(async move {
+                let frame = vec![0u8; FRAME_LEN];
+
+                while !stop.load(Ordering::Relaxed) {
+                    writer.write_all(&frame).await.unwrap();
+                }
+            })
+        })
+        .collect();
+
+    cadentis::time::sleep(Duration::from_millis(50)).await;
+
+    let written = timeout(Duration::from_secs(5), writer.write_all(b"marker")).await;
+    stop.store(true, Ordering::Relaxed);
+
+    for handle in busy {
+        handle.await.unwrap();
+    }
+    stream.shutdown(Shutdown::Write).unwrap();
+    collector.join().unwrap();
+
+    assert!(written.is_ok(), "write starved by the other clones");
+}
+
+#[cadentis::test]
+async fn full_duplex_exchange_progresses_both_ways() {
+    const LEN: usize = 8 * 1024 * 1024;
+    const CHUNK: usize = 64 * 1024;
+
+    let listener = StdTcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    // The peer floods the stream while draining what it is sent.
+    let peer = thread::spawn(move || {
+        let (socket, _) = listener.accept().unwrap();
+        let mut sink = socket.try_clone().unwrap();
+
+        let drain = thread::spawn(move || {
+            let mut received = Vec::new();
+            sink.read_to_end(&mut received).unwrap();
+            received.len()
+        });
+
+        let mut socket = socket;
+        for _ in 0..LEN / CHUNK {
+            socket.write_all(&[7u8; CHUNK]).unwrap();
+        }
+        socket.shutdown(Shutdown::Write).unwrap();
+        drain.join().unwrap()
+    });
+
+    let stream = TcpStream::connect(&addr.to_string()).await.unwrap();
+    let (reader, writer) = stream.split();
+    let sent = AtomicUsize::new(0);
+
+    let (_, sent_when_flooded) = join!(
+        async {
+            let chunk = vec![3u8; CHUNK];
+            for _ in 0..LEN / CHUNK {
+                writer.write_all(&chunk).await.unwrap();
+                sent.fetch_add(CHUNK, Ordering::SeqCst);
+            }
+        },
+        async {
+            let mut received = 0;
+            let mut buffer = vec![0u8; CHUNK];
+
+            loop {
+                let n = reader.read(&mut buffer).await.unwrap();
+                if n == 0 {
+                    break;
+                }
+                received += n;
+            }
+            assert_eq!(received, LEN);
+            sent.load(Ordering::SeqCst)
+        }
+    );
+
+    // Outgoing data kept flowing while the peer flooded the stream,
+    // instead of waiting for the flood to end.
+    assert!(
+        sent_when_flooded > 0,
+        "nothing was sent while receiving {LEN} bytes"
+    );
+
+    stream.shutdown(Shutdown::Write).unwrap();
+    assert_eq!(peer.join().unwrap(), LEN);
+}
+
+#[cadentis::test]
+async fn write_half_shutdown_sends_eof_while_reads_continue() {
+    let listener = StdTcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    // Reads the request up to EOF, then answers on the still-open side.
+    let server = thread::spawn(move || {
+        let (mut socket, _) = listener.accept().unwrap();
+        let mut request = Vec::new();
+        socket.read_to_end(&mut request).unwrap();
+
+        socket
+            .write_all(format!("got {} bytes", request.len()).as_bytes())
+            .unwrap();
+        request
+    });
+
+    let stream = TcpStream::connect(&addr.to_string()).await.unwrap();
+    let (reader, writer) = stream.split();
+
+    writer.write_all(&[7u8; 100_000]).await.unwrap();
+    writer.shutdown().await.unwrap();
+
+    let mut response = Vec::new();
+    let mut buffer = [0u8; 64];
+    loop {
+        let n = reader.read(&mut buffer).await.unwrap();
+        if n == 0 {
+            break;
+        }
+        response.extend_from_slice(&buffer[..n]);
+    }
+
+    assert_eq!(response, b"got 100000 bytes");
+    assert_eq!(server.join().unwrap(), vec![7u8; 100_000]);
+}
+
+#[cadentis::test]
+async fn shared_write_half_shutdown_closes_writes_for_every_clone() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    let client = TcpStream::connect(&addr.to_string()).await.unwrap();
+    let (server, _) = listener.accept().await.unwrap();
+
+    let (_, writer) = client.split_shared();
+    let other = writer.clone();
+
+    writer.write_all(b"last words").await.unwrap();
+    other.shutdown().await.unwrap();
+
+    let mut received = Vec::new();
+    let mut buffer = [0u8; 64];
+    loop {
+        let n = server.read(&mut buffer).await.unwrap();
+        if n == 0 {
+            break;
+        }
+        received.extend_from_slice(&buffer[..n]);
+    }
+    assert_eq!(received, b"last words");
+
+    assert!(writer.write_all(b"too late").await.is_err());
+}
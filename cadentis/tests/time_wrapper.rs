@@ -1,5 +1,7 @@
-use cadentis::time::instrumented;
 use cadentis::time::sleep;
+use cadentis::time::{LatencyEwma, instrumented};
+use cadentis::yield_now;
+use std::pin::pin;
 use std::time::Duration;
 
 #[cadentis::test]
@@ -11,3 +13,85 @@ async fn test_time_wrapper_with_sleep() {
         "Time wrapper should measure at least the sleep duration"
     );
 }
+
+#[cadentis::test]
+async fn test_time_wrapper_counts_polls() {
+    let mut future = pin!(instrumented(async {
+        for _ in 0..3 {
+            yield_now().await;
+        }
+        7
+    }));
+
+    let (value, elapsed) = (&mut future).await;
+    let stats = future.stats();
+
+    assert_eq!(value, 7);
+    // One poll per yield, plus the one completing the future.
+    assert_eq!(stats.polls(), 4);
+    assert!(stats.busy_time() + stats.pending_time() <= elapsed);
+}
+
+#[cadentis::test]
+async fn test_time_wrapper_reports_pending_time() {
+    let mut future = pin!(instrumented(sleep(Duration::from_millis(30))));
+
+    (&mut future).await;
+    let stats = future.stats();
+
+    assert!(stats.polls() >= 2);
+    assert!(stats.pending_time() >= Duration::from_millis(25));
+    assert!(stats.busy_time() < stats.pending_time());
+}
+
+#[test]
+fn test_time_wrapper_into_inner_returns_future() {
+    let wrapped = instrumented(std::future::ready(5));
+
+    assert_eq!(wrapped.stats().polls(), 0);
+    assert_eq!(wrapped.get_ref().clone().into_inner(), 5);
+    assert_eq!(wrapped.into_inner().into_inner(), 5);
+}
+
+#[test]
+fn latency_ewma_converges_to_recorded_latencies() {
+    let ewma = LatencyEwma::new(0.25);
+    assert_eq!(ewma.get(), None);
+
+    // The first sample is taken as is.
+    ewma.record(Duration::from_millis(100));
+    assert_eq!(ewma.get(), Some(Duration::from_millis(100)));
+
+    // Each sample closes a quarter of the distance: 100 + 0.25 * 100.
+    ewma.record(Duration::from_millis(200));
+    assert_eq!(ewma.get(), Some(Duration::from_millis(125)));
+
+    for _ in 0..50 {
+        ewma.record(Duration::from_millis(200));
+    }
+
+    let average = ewma.get().unwrap();
+    assert!(
+        Duration::from_micros(199_900) < average && average <= Duration::from_millis(200),
+        "{average:?}"
+    );
+}
+
+#[cadentis::test]
+async fn instrumented_with_ewma_records_completion_latency() {
+    let ewma = LatencyEwma::new(0.5);
+    let reader = ewma.clone();
+
+    for _ in 0..3 {
+        let (_, elapsed) = instrumented(sleep(Duration::from_millis(20)))
+            .with_ewma(&ewma)
+            .await;
+        assert!(elapsed >= Duration::from_millis(20));
+    }
+
+    let average = reader.get().unwrap();
+    assert!(
+        average >= Duration::from_millis(20) && average < Duration::from_millis(500),
+        "{average:?}"
+    );
+}
@@ -13,7 +13,7 @@ async fn test_timeout_completes_before_deadline() {
     let result = timeout(Duration::from_millis(50), handle).await;
 
     assert!(
-        matches!(result, Ok(v) if v == 123),
+        matches!(result, Ok(Ok(v)) if v == 123),
         "Timeout should return Ok(123)"
     );
 }
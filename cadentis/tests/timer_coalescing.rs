@@ -0,0 +1,94 @@
+use cadentis::runtime::Runtime;
+use cadentis::time::sleep;
+use cadentis::time::test::PausedClock;
+use cadentis::{RuntimeBuilder, task};
+use std::thread;
+use std::time::Duration;
+
+const SLEEPERS: usize = 10_000;
+const KEEPALIVE: Duration = Duration::from_secs(30);
+const GRANULARITY: Duration = Duration::from_millis(50);
+
+/// Waits until the reactor reports `timers` pending timers.
+fn wait_for_timers(rt: &Runtime, timers: usize) {
+    while rt.metrics().reactor().timers() != timers {
+        thread::sleep(Duration::from_millis(1));
+    }
+}
+
+#[test]
+fn near_simultaneous_sleeps_fire_as_one_group() {
+    let clock = PausedClock::new();
+    let rt = RuntimeBuilder::new()
+        .worker_threads(2)
+        .clock(clock.clone())
+        .timer_granularity(GRANULARITY)
+        .build()
+        .unwrap();
+
+    // Keep-alive timers armed over 10 milliseconds.
+    let arming = clock.clone();
+    let sleepers: Vec<_> = rt.block_on(async move {
+        (0..SLEEPERS)
+            .map(|i| {
+                if i % 1000 == 0 {
+                    arming.advance(Duration::from_millis(1));
+                }
+                task::spawn(sleep(KEEPALIVE))
+            })
+            .collect()
+    });
+
+    wait_for_timers(&rt, SLEEPERS);
+
+    // The deadlines may straddle two groups, never more.
+    let stats = rt.metrics().reactor();
+    assert!(
+        stats.timer_groups() <= 2,
+        "{} groups for {SLEEPERS} timers",
+        stats.timer_groups()
+    );
+
+    let polls = stats.polls();
+
+    rt.block_on(async move {
+        // Advanced from within the runtime, so that the reactor is woken.
+        clock.advance(KEEPALIVE + GRANULARITY);
+
+        for sleeper in sleepers {
+            sleeper.await.unwrap();
+        }
+    });
+
+    let polls = rt.metrics().reactor().polls() - polls;
+    assert!(polls < 100, "{polls} polls to fire {SLEEPERS} timers");
+    wait_for_timers(&rt, 0);
+}
+
+#[test]
+fn zero_granularity_only_groups_equal_deadlines() {
+    let clock = PausedClock::new();
+    let rt = RuntimeBuilder::new()
+        .worker_threads(1)
+        .clock(clock.clone())
+        .timer_granularity(Duration::ZERO)
+        .build()
+        .unwrap();
+
+    let arming = clock.clone();
+    let sleepers: Vec<_> = rt.block_on(async move {
+        (0..4)
+            .flat_map(|_| {
+                arming.advance(Duration::from_micros(10));
+                [task::spawn(sleep(KEEPALIVE)), task::spawn(sleep(KEEPALIVE))]
+            })
+            .collect()
+    });
+
+    wait_for_timers(&rt, 8);
+    assert_eq!(rt.metrics().reactor().timer_groups(), 4);
+
+    for sleeper in &sleepers {
+        sleeper.abort();
+    }
+}
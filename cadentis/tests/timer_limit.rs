@@ -0,0 +1,60 @@
+use cadentis::RuntimeBuilder;
+use cadentis::task;
+use cadentis::time::{sleep, try_sleep, try_timeout};
+use std::io;
+use std::time::Duration;
+
+#[test]
+fn try_sleep_reports_a_full_runtime() {
+    let rt = RuntimeBuilder::new().max_timers(3).build().unwrap();
+
+    rt.block_on(async {
+        let armed: Vec<_> = (0..3)
+            .map(|_| try_sleep(Duration::from_secs(60)).unwrap())
+            .collect();
+
+        let error = try_sleep(Duration::from_secs(60)).err().unwrap();
+        assert_eq!(error.kind(), io::ErrorKind::QuotaExceeded);
+
+        let error = try_timeout(Duration::from_secs(60), async {})
+            .err()
+            .unwrap();
+        assert_eq!(error.kind(), io::ErrorKind::QuotaExceeded);
+
+        // Dropped timers give their slot back.
+        drop(armed);
+        try_sleep(Duration::from_millis(1)).unwrap().await;
+
+        let armed: Vec<_> = (0..3)
+            .map(|_| try_sleep(Duration::from_secs(60)).unwrap())
+            .collect();
+        assert_eq!(armed.len(), 3);
+    });
+}
+
+#[test]
+fn sleep_past_the_limit_fails_its_task() {
+    let rt = RuntimeBuilder::new()
+        .worker_threads(1)
+        .max_timers(2)
+        .build()
+        .unwrap();
+
+    rt.block_on(async {
+        let armed: Vec<_> = (0..2)
+            .map(|_| try_sleep(Duration::from_millis(20)).unwrap())
+            .collect();
+
+        let error = task::spawn(sleep(Duration::from_millis(20)))
+            .await
+            .unwrap_err();
+        assert!(error.is_panic());
+        assert!(format!("{error:?}").contains("limit of 2 armed timers"));
+
+        // Completed timers give their slot back.
+        for timer in armed {
+            timer.await;
+        }
+        task::spawn(sleep(Duration::from_millis(1))).await.unwrap();
+    });
+}
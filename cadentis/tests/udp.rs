@@ -0,0 +1,179 @@
+use cadentis::net::{UdpFramed, UdpSocket};
+use cadentis::stream::StreamExt;
+use cadentis::task;
+use cadentis::time::sleep;
+use std::io;
+use std::time::{Duration, Instant};
+
+#[cadentis::test]
+async fn udp_socket_send_and_receive() {
+    let a = UdpSocket::bind("127.0.0.1:0").unwrap();
+    let b = UdpSocket::bind("127.0.0.1:0").unwrap();
+    let a_addr = a.local_addr().unwrap();
+    let b_addr = b.local_addr().unwrap();
+
+    // The receiver waits for readiness before the datagram is sent.
+    let receiver = task::spawn(async move {
+        let mut buffer = [0u8; 64];
+        let (n, from) = b.recv_from(&mut buffer).await.unwrap();
+        (buffer[..n].to_vec(), from)
+    });
+
+    sleep(Duration::from_millis(20)).await;
+
+    let n = a.send_to(b"ping", b_addr).await.unwrap();
+    assert_eq!(n, 4);
+
+    let (received, from) = receiver.await.unwrap();
+    assert_eq!(received, b"ping");
+    assert_eq!(from, a_addr);
+}
+
+#[cadentis::test]
+async fn udp_framed_preserves_message_boundaries() {
+    let sender = UdpFramed::new(UdpSocket::bind("127.0.0.1:0").unwrap());
+    let mut receiver = UdpFramed::new(UdpSocket::bind("127.0.0.1:0").unwrap());
+    let sender_addr = sender.get_ref().local_addr().unwrap();
+    let receiver_addr = receiver.get_ref().local_addr().unwrap();
+
+    let messages: Vec<Vec<u8>> = vec![
+        b"hello".to_vec(),
+        Vec::new(),
+        vec![7u8; 1000],
+        b"world".to_vec(),
+    ];
+
+    for message in &messages {
+        sender.send(message, receiver_addr).await.unwrap();
+    }
+
+    let (first, from) = receiver.recv().await.unwrap();
+    assert_eq!(first, messages[0]);
+    assert_eq!(from, sender_addr);
+
+    for expected in &messages[1..] {
+        let (message, from) = receiver.next().await.unwrap().unwrap();
+        assert_eq!(&message, expected);
+        assert_eq!(from, sender_addr);
+    }
+}
+
+#[cadentis::test]
+async fn udp_framed_splits_datagrams_carrying_several_frames() {
+    let raw = UdpSocket::bind("127.0.0.1:0").unwrap();
+    let mut receiver = UdpFramed::new(UdpSocket::bind("127.0.0.1:0").unwrap());
+    let receiver_addr = receiver.get_ref().local_addr().unwrap();
+
+    let mut datagram = Vec::new();
+    for message in [&b"one"[..], b"two", b"three"] {
+        datagram.extend_from_slice(&(message.len() as u32).to_be_bytes());
+        datagram.extend_from_slice(message);
+    }
+    raw.send_to(&datagram, receiver_addr).await.unwrap();
+
+    // A truncated frame is rejected.
+    raw.send_to(&[0, 0, 0, 9, 1, 2], receiver_addr)
+        .await
+        .unwrap();
+
+    for expected in [&b"one"[..], b"two", b"three"] {
+        let (message, _) = receiver.recv().await.unwrap();
+        assert_eq!(message, expected);
+    }
+
+    let err = receiver.recv().await.unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+}
+
+#[cadentis::test]
+async fn udp_multicast_group_receives_datagrams() {
+    use std::net::{Ipv4Addr, SocketAddr};
+
+    let group = Ipv4Addr::new(239, 255, 42, 99);
+
+    let receiver = UdpSocket::bind("0.0.0.0:0").unwrap();
+    let port = receiver.local_addr().unwrap().port();
+    receiver
+        .join_multicast_v4(group, Ipv4Addr::LOCALHOST)
+        .unwrap();
+
+    let sender = UdpSocket::bind("127.0.0.1:0").unwrap();
+    sender.set_multicast_loop_v4(true).unwrap();
+    sender.set_multicast_ttl_v4(1).unwrap();
+    assert!(sender.multicast_loop_v4().unwrap());
+    assert_eq!(sender.multicast_ttl_v4().unwrap(), 1);
+
+    sender
+        .send_to(b"hello group", SocketAddr::from((group, port)))
+        .await
+        .unwrap();
+
+    let mut buffer = [0u8; 64];
+    let (n, _) = receiver.recv_from(&mut buffer).await.unwrap();
+    assert_eq!(&buffer[..n], b"hello group");
+
+    receiver
+        .leave_multicast_v4(group, Ipv4Addr::LOCALHOST)
+        .unwrap();
+    assert!(
+        receiver
+            .leave_multicast_v4(group, Ipv4Addr::LOCALHOST)
+            .is_err()
+    );
+}
+
+#[cadentis::test]
+async fn udp_query_times_out_against_silent_peer() {
+    let socket = UdpSocket::bind("127.0.0.1:0").unwrap();
+    let silent = UdpSocket::bind("127.0.0.1:0").unwrap();
+    let silent_addr = silent.local_addr().unwrap();
+
+    let start = Instant::now();
+    let mut reply = [0u8; 64];
+    let error = socket
+        .query(b"ping", silent_addr, &mut reply, Duration::from_millis(50))
+        .await
+        .unwrap_err();
+
+    assert_eq!(error.kind(), io::ErrorKind::TimedOut);
+    assert!(start.elapsed() >= Duration::from_millis(50));
+
+    // The request did go out, and the socket is still usable.
+    let mut buffer = [0u8; 64];
+    let (n, from) = silent.recv_from(&mut buffer).await.unwrap();
+    assert_eq!(&buffer[..n], b"ping");
+
+    silent.send_to(b"late", from).await.unwrap();
+    let (n, _) = socket.recv_from(&mut reply).await.unwrap();
+    assert_eq!(&reply[..n], b"late");
+}
+
+#[cadentis::test]
+async fn udp_query_ignores_datagrams_from_other_peers() {
+    let socket = UdpSocket::bind("127.0.0.1:0").unwrap();
+    let socket_addr = socket.local_addr().unwrap();
+    let server = UdpSocket::bind("127.0.0.1:0").unwrap();
+    let server_addr = server.local_addr().unwrap();
+    let stranger = UdpSocket::bind("127.0.0.1:0").unwrap();
+
+    let responder = task::spawn(async move {
+        let mut buffer = [0u8; 64];
+        let (n, from) = server.recv_from(&mut buffer).await.unwrap();
+        assert_eq!(from, socket_addr);
+
+        stranger.send_to(b"spoofed", socket_addr).await.unwrap();
+        sleep(Duration::from_millis(10)).await;
+
+        buffer[..n].reverse();
+        server.send_to(&buffer[..n], from).await.unwrap();
+    });
+
+    let mut reply = [0u8; 64];
+    let n = socket
+        .query(b"ping", server_addr, &mut reply, Duration::from_secs(5))
+        .await
+        .unwrap();
+
+    assert_eq!(&reply[..n], b"gnip");
+    responder.await.unwrap();
+}
@@ -0,0 +1,112 @@
+#![cfg(unix)]
+
+use cadentis::net::UnixDatagram;
+use cadentis::time::{sleep, timeout};
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Returns a socket path unique to this process and `name`.
+fn socket_path(name: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("cadentis-{}-{name}.sock", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    path
+}
+
+#[cadentis::test]
+async fn recv_from_returns_one_datagram_at_a_time() {
+    let server_path = socket_path("server");
+    let client_path = socket_path("client");
+
+    let server = UnixDatagram::bind(&server_path).unwrap();
+    let client = UnixDatagram::bind(&client_path).unwrap();
+
+    let messages: [&[u8]; 3] = [b"first", b"second message", b"3"];
+    for message in messages {
+        client.send_to(message, &server_path).await.unwrap();
+    }
+
+    // Each receive returns exactly one message, even though the buffer
+    // could hold them all.
+    let mut buffer = [0u8; 1024];
+    for message in messages {
+        let (n, peer) = server.recv_from(&mut buffer).await.unwrap();
+        assert_eq!(&buffer[..n], message);
+        assert_eq!(peer.as_pathname(), Some(client_path.as_path()));
+    }
+
+    // Reply to the sender through its address.
+    server.send_to(b"ack", &client_path).await.unwrap();
+    let n = client.recv(&mut buffer).await.unwrap();
+    assert_eq!(&buffer[..n], b"ack");
+
+    std::fs::remove_file(server_path).unwrap();
+    std::fs::remove_file(client_path).unwrap();
+}
+
+#[cadentis::test]
+async fn pair_waits_for_datagrams() {
+    let (a, b) = UnixDatagram::pair().unwrap();
+
+    let receiver = cadentis::task::spawn(async move {
+        let mut buffer = [0u8; 64];
+        let mut received = Vec::new();
+
+        for _ in 0..2 {
+            let n = b.recv(&mut buffer).await.unwrap();
+            received.push(buffer[..n].to_vec());
+        }
+        received
+    });
+
+    a.send(b"ping").await.unwrap();
+    a.send(b"pong").await.unwrap();
+
+    assert_eq!(
+        receiver.await.unwrap(),
+        vec![b"ping".to_vec(), b"pong".to_vec()]
+    );
+}
+
+#[cadentis::test]
+async fn receive_and_send_wait_on_the_same_socket() {
+    let (a, b) = std::os::unix::net::UnixDatagram::pair().unwrap();
+
+    // Fill the queue towards `b`, so that sending from `a` waits.
+    a.set_nonblocking(true).unwrap();
+    while a.send(b"filler").is_ok() {}
+
+    let a = Arc::new(UnixDatagram::from_std(a).unwrap());
+    let b = UnixDatagram::from_std(b).unwrap();
+
+    let receiver = cadentis::task::spawn({
+        let a = a.clone();
+        async move {
+            let mut buffer = [0u8; 64];
+            let n = a.recv(&mut buffer).await.unwrap();
+            buffer[..n].to_vec()
+        }
+    });
+    let sender = cadentis::task::spawn({
+        let a = a.clone();
+        async move { a.send(b"last").await.unwrap() }
+    });
+
+    // Let both tasks wait on `a`, in opposite directions.
+    sleep(Duration::from_millis(50)).await;
+
+    // Waking the receiver does not need the sender to give up.
+    b.send(b"reply").await.unwrap();
+    let received = timeout(Duration::from_secs(5), receiver).await;
+    assert_eq!(received.unwrap().unwrap(), b"reply");
+
+    // Draining `b` then lets the sender through.
+    let mut buffer = [0u8; 64];
+    timeout(Duration::from_secs(5), async {
+        while b.recv(&mut buffer).await.unwrap() != 4 {}
+    })
+    .await
+    .unwrap();
+    assert_eq!(&buffer[..4], b"last");
+    assert_eq!(sender.await.unwrap(), 4);
+}
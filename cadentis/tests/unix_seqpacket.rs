@@ -0,0 +1,81 @@
+#![cfg(any(target_os = "linux", target_os = "android"))]
+
+use cadentis::net::{UnixSeqpacket, UnixSeqpacketListener};
+use cadentis::task;
+use std::path::PathBuf;
+
+/// Returns a socket path unique to this process and `name`.
+fn socket_path(name: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!(
+        "cadentis-seqpacket-{}-{name}.sock",
+        std::process::id()
+    ));
+    let _ = std::fs::remove_file(&path);
+    path
+}
+
+#[cadentis::test]
+async fn recv_returns_one_message_at_a_time() {
+    let path = socket_path("messages");
+    let listener = UnixSeqpacketListener::bind(&path).unwrap();
+
+    let server = task::spawn(async move {
+        let socket = listener.accept().await.unwrap();
+
+        let mut buffer = [0u8; 1024];
+        let mut messages = Vec::new();
+        loop {
+            let n = socket.recv(&mut buffer).await.unwrap();
+            if n == 0 {
+                break;
+            }
+            messages.push(buffer[..n].to_vec());
+        }
+        messages
+    });
+
+    let client = UnixSeqpacket::connect(&path).unwrap();
+    let sent: [&[u8]; 3] = [b"first", b"second message", b"3"];
+    for message in sent {
+        client.send(message).await.unwrap();
+    }
+    drop(client);
+
+    // Each receive returns exactly one message, even though the buffer
+    // could hold them all.
+    assert_eq!(server.await.unwrap(), sent);
+
+    let _ = std::fs::remove_file(path);
+}
+
+#[cadentis::test]
+async fn pair_truncates_messages_larger_than_the_buffer() {
+    let (a, b) = UnixSeqpacket::pair().unwrap();
+
+    a.send(b"truncated message").await.unwrap();
+    a.send(b"next").await.unwrap();
+
+    let mut buffer = [0u8; 9];
+    let n = b.recv(&mut buffer).await.unwrap();
+    assert_eq!(&buffer[..n], b"truncated");
+
+    // The rest of the first message is discarded.
+    let n = b.recv(&mut buffer).await.unwrap();
+    assert_eq!(&buffer[..n], b"next");
+}
+
+#[cadentis::test]
+async fn recv_waits_for_a_message() {
+    let (a, b) = UnixSeqpacket::pair().unwrap();
+
+    let receiver = task::spawn(async move {
+        let mut buffer = [0u8; 16];
+        let n = b.recv(&mut buffer).await.unwrap();
+        buffer[..n].to_vec()
+    });
+
+    cadentis::time::sleep(std::time::Duration::from_millis(20)).await;
+    a.send(b"late").await.unwrap();
+
+    assert_eq!(receiver.await.unwrap(), b"late");
+}
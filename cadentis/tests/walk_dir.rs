@@ -0,0 +1,135 @@
+use cadentis::fs::{read_dir, walk_dir};
+use cadentis::stream::StreamExt;
+
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+fn unique_temp_base() -> PathBuf {
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+
+    let base = std::env::temp_dir();
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_nanos();
+    let pid = std::process::id();
+    let seq = COUNTER.fetch_add(1, Ordering::Relaxed);
+
+    base.join(format!("reactor_walk_dir_test_{}_{}_{}", pid, nanos, seq))
+}
+
+/// Creates `root/{a.txt, b/c.txt, b/d/e.txt, b/d/f/g.txt}`.
+fn make_tree() -> PathBuf {
+    let root = unique_temp_base();
+
+    fs::create_dir_all(root.join("b").join("d").join("f")).expect("create tree");
+    fs::write(root.join("a.txt"), b"a").expect("write a");
+    fs::write(root.join("b").join("c.txt"), b"c").expect("write c");
+    fs::write(root.join("b").join("d").join("e.txt"), b"e").expect("write e");
+    fs::write(root.join("b").join("d").join("f").join("g.txt"), b"g").expect("write g");
+
+    root
+}
+
+/// Counts the yielded paths, relative to `root`.
+fn count(
+    root: &PathBuf,
+    entries: Vec<io::Result<cadentis::fs::DirEntry>>,
+) -> HashMap<PathBuf, usize> {
+    let mut seen = HashMap::new();
+
+    for entry in entries {
+        let entry = entry.expect("entry");
+        let relative = entry.path().strip_prefix(root).unwrap().to_path_buf();
+        *seen.entry(relative).or_insert(0) += 1;
+    }
+
+    seen
+}
+
+#[cadentis::test]
+async fn walk_dir_visits_every_entry_once() {
+    let root = make_tree();
+
+    let entries: Vec<_> = walk_dir(&root).collect().await;
+    let seen = count(&root, entries);
+
+    let expected = [
+        "a.txt",
+        "b",
+        "b/c.txt",
+        "b/d",
+        "b/d/e.txt",
+        "b/d/f",
+        "b/d/f/g.txt",
+    ];
+
+    assert_eq!(seen.len(), expected.len(), "{seen:?}");
+    for path in expected {
+        assert_eq!(seen.get(&PathBuf::from(path)), Some(&1), "{path}");
+    }
+
+    fs::remove_dir_all(&root).expect("cleanup");
+}
+
+#[cadentis::test]
+async fn walk_dir_respects_max_depth() {
+    let root = make_tree();
+
+    let entries: Vec<_> = walk_dir(&root).max_depth(2).collect().await;
+    let seen = count(&root, entries);
+
+    let mut paths: Vec<_> = seen.keys().cloned().collect();
+    paths.sort();
+
+    let expected: Vec<PathBuf> = ["a.txt", "b", "b/c.txt", "b/d"]
+        .into_iter()
+        .map(PathBuf::from)
+        .collect();
+    assert_eq!(paths, expected);
+
+    fs::remove_dir_all(&root).expect("cleanup");
+}
+
+#[cfg(unix)]
+#[cadentis::test]
+async fn walk_dir_following_symlinks_survives_cycles() {
+    let root = make_tree();
+
+    // b/d/f/up -> b: following it naively would loop forever.
+    std::os::unix::fs::symlink(
+        root.join("b"),
+        root.join("b").join("d").join("f").join("up"),
+    )
+    .expect("symlink");
+
+    let entries: Vec<_> = walk_dir(&root).follow_symlinks(true).collect().await;
+    let seen = count(&root, entries);
+
+    assert_eq!(seen.get(&PathBuf::from("b/d/f/up")), Some(&1));
+    assert_eq!(seen.get(&PathBuf::from("b/d/f/g.txt")), Some(&1));
+    assert_eq!(seen.len(), 8, "{seen:?}");
+
+    fs::remove_dir_all(&root).expect("cleanup");
+}
+
+#[cadentis::test]
+async fn read_dir_lists_direct_children() {
+    let root = make_tree();
+
+    let mut names: Vec<_> = read_dir(&root)
+        .await
+        .expect("read_dir")
+        .map(|entry| entry.expect("entry").file_name())
+        .collect::<Vec<_>>()
+        .await;
+    names.sort();
+
+    assert_eq!(names, ["a.txt", "b"]);
+
+    fs::remove_dir_all(&root).expect("cleanup");
+}
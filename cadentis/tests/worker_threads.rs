@@ -1,12 +1,13 @@
-use cadentis::RuntimeBuilder;
-use cadentis::task::spawn;
+use cadentis::task::{current_worker_id, spawn};
+use cadentis::{RuntimeBuilder, StealStrategy};
 use std::collections::HashSet;
 use std::sync::{Arc, Mutex};
 use std::thread;
+use std::time::{Duration, Instant};
 
 #[test]
 fn test_single_worker_thread() {
-    let rt = RuntimeBuilder::new().worker_threads(1).build();
+    let rt = RuntimeBuilder::new().worker_threads(1).build().unwrap();
 
     let result = rt.block_on(async { 42 });
     assert_eq!(result, 42);
@@ -14,7 +15,7 @@ fn test_single_worker_thread() {
 
 #[test]
 fn test_multiple_worker_threads() {
-    let rt = RuntimeBuilder::new().worker_threads(4).build();
+    let rt = RuntimeBuilder::new().worker_threads(4).build().unwrap();
 
     let result = rt.block_on(async { 100 });
     assert_eq!(result, 100);
@@ -22,7 +23,7 @@ fn test_multiple_worker_threads() {
 
 #[test]
 fn test_worker_threads_parallel_execution() {
-    let rt = RuntimeBuilder::new().worker_threads(4).build();
+    let rt = RuntimeBuilder::new().worker_threads(4).build().unwrap();
 
     let counter = Arc::new(Mutex::new(0));
     let results = Arc::new(Mutex::new(Vec::new()));
@@ -58,7 +59,7 @@ fn test_worker_threads_parallel_execution() {
 
 #[test]
 fn test_worker_threads_stress() {
-    let rt = RuntimeBuilder::new().worker_threads(8).build();
+    let rt = RuntimeBuilder::new().worker_threads(8).build().unwrap();
 
     let counter = Arc::new(Mutex::new(0));
     let counter_clone = counter.clone();
@@ -75,7 +76,7 @@ fn test_worker_threads_stress() {
             .collect();
 
         for handle in handles {
-            handle.await;
+            handle.await.unwrap();
         }
     });
 
@@ -88,7 +89,10 @@ fn test_worker_threads_max_parallelism() {
         .map(|n| n.get())
         .unwrap_or(4);
 
-    let rt = RuntimeBuilder::new().worker_threads(num_threads).build();
+    let rt = RuntimeBuilder::new()
+        .worker_threads(num_threads)
+        .build()
+        .unwrap();
 
     let result = rt.block_on(async {
         let sum = Arc::new(Mutex::new(0));
@@ -102,7 +106,7 @@ fn test_worker_threads_max_parallelism() {
             .collect();
 
         for handle in handles {
-            handle.await;
+            handle.await.unwrap();
         }
 
         *sum.lock().unwrap()
@@ -113,17 +117,17 @@ fn test_worker_threads_max_parallelism() {
 
 #[test]
 fn test_worker_threads_chain_spawn() {
-    let rt = RuntimeBuilder::new().worker_threads(4).build();
+    let rt = RuntimeBuilder::new().worker_threads(4).build().unwrap();
 
     let result = rt.block_on(async {
         let handle1 = spawn(async {
             let handle2 = spawn(async {
                 let handle3 = spawn(async { 10 });
-                handle3.await + 20
+                handle3.await.unwrap() + 20
             });
-            handle2.await + 30
+            handle2.await.unwrap() + 30
         });
-        handle1.await + 40
+        handle1.await.unwrap() + 40
     });
 
     assert_eq!(result, 100);
@@ -131,7 +135,7 @@ fn test_worker_threads_chain_spawn() {
 
 #[test]
 fn test_worker_threads_two_threads() {
-    let rt = RuntimeBuilder::new().worker_threads(2).build();
+    let rt = RuntimeBuilder::new().worker_threads(2).build().unwrap();
 
     let completed = Arc::new(Mutex::new(HashSet::new()));
     let completed_clone = completed.clone();
@@ -148,7 +152,7 @@ fn test_worker_threads_two_threads() {
             .collect();
 
         for handle in handles {
-            handle.await;
+            handle.await.unwrap();
         }
     });
 
@@ -162,13 +166,13 @@ fn test_worker_threads_two_threads() {
 #[test]
 #[should_panic(expected = "worker_threads must be > 0")]
 fn test_worker_threads_zero_panics() {
-    let _ = RuntimeBuilder::new().worker_threads(0).build();
+    let _ = RuntimeBuilder::new().worker_threads(0).build().unwrap();
 }
 
 #[test]
 fn test_worker_threads_sequential_runtimes() {
     for n in 1..=4 {
-        let rt = RuntimeBuilder::new().worker_threads(n).build();
+        let rt = RuntimeBuilder::new().worker_threads(n).build().unwrap();
         let result = rt.block_on(async move { n * 10 });
         assert_eq!(result, n * 10);
         drop(rt);
@@ -177,7 +181,7 @@ fn test_worker_threads_sequential_runtimes() {
 
 #[test]
 fn test_worker_threads_nested_spawns() {
-    let rt = RuntimeBuilder::new().worker_threads(4).build();
+    let rt = RuntimeBuilder::new().worker_threads(4).build().unwrap();
 
     let results = Arc::new(Mutex::new(Vec::new()));
     let results_clone = results.clone();
@@ -197,17 +201,229 @@ fn test_worker_threads_nested_spawns() {
                         .collect();
 
                     for handle in inner_handles {
-                        handle.await;
+                        handle.await.unwrap();
                     }
                 })
             })
             .collect();
 
         for handle in outer_handles {
-            handle.await;
+            handle.await.unwrap();
         }
     });
 
     let final_results = results.lock().unwrap();
     assert_eq!(final_results.len(), 20);
 }
+
+#[test]
+fn test_spawn_on_runs_on_the_requested_worker() {
+    let rt = RuntimeBuilder::new().worker_threads(4).build().unwrap();
+    let workers = rt.metrics().num_workers();
+
+    assert_eq!(workers, 4);
+
+    let ids = rt.block_on(async move {
+        let handles: Vec<_> = (0..workers)
+            .map(|worker| {
+                cadentis::task::spawn_on(worker, async move {
+                    let before = cadentis::task::current_worker_id();
+
+                    // Suspend so the task is rescheduled after a wake-up.
+                    cadentis::time::sleep(std::time::Duration::from_millis(5)).await;
+
+                    let after = cadentis::task::current_worker_id();
+                    (worker, before, after)
+                })
+            })
+            .collect();
+
+        let mut ids = Vec::new();
+        for handle in handles {
+            ids.push(handle.await.unwrap());
+        }
+        ids
+    });
+
+    for (worker, before, after) in ids {
+        assert_eq!(before, Some(worker));
+        assert_eq!(after, Some(worker));
+    }
+}
+
+#[test]
+fn test_spawn_on_invalid_worker_panics() {
+    let rt = RuntimeBuilder::new().worker_threads(2).build().unwrap();
+
+    let panicked = rt.block_on(async {
+        std::panic::catch_unwind(|| {
+            cadentis::task::spawn_on(2, async {});
+        })
+        .is_err()
+    });
+
+    assert!(panicked);
+}
+
+#[test]
+fn test_high_priority_task_runs_before_queued_normal_tasks() {
+    use cadentis::task::{Priority, spawn_with_priority};
+
+    let rt = RuntimeBuilder::new().worker_threads(1).build().unwrap();
+    let order = Arc::new(Mutex::new(Vec::new()));
+    let order_clone = order.clone();
+
+    rt.block_on(async move {
+        let mut handles: Vec<_> = (0..100)
+            .map(|i| {
+                let order = order_clone.clone();
+                spawn(async move {
+                    order.lock().unwrap().push(i);
+                })
+            })
+            .collect();
+
+        let order = order_clone.clone();
+        handles.push(spawn_with_priority(Priority::High, async move {
+            order.lock().unwrap().push(usize::MAX);
+        }));
+
+        for handle in handles {
+            handle.await.unwrap();
+        }
+    });
+
+    let order = order.lock().unwrap();
+    assert_eq!(order.len(), 101);
+    assert_eq!(order[0], usize::MAX, "high-priority task should run first");
+}
+
+#[test]
+fn test_half_steal_spreads_a_burst_across_workers() {
+    let rt = RuntimeBuilder::new()
+        .worker_threads(4)
+        .steal_batch(StealStrategy::Half)
+        .build()
+        .unwrap();
+
+    let workers = rt.block_on(async {
+        // The root task runs on a worker: the burst lands in its local queue.
+        let handles: Vec<_> = (0..200)
+            .map(|_| {
+                spawn(async {
+                    // Keep the worker busy long enough for others to steal.
+                    let start = Instant::now();
+                    while start.elapsed() < Duration::from_micros(200) {
+                        std::hint::spin_loop();
+                    }
+
+                    current_worker_id().unwrap()
+                })
+            })
+            .collect();
+
+        let mut workers = HashSet::new();
+        for handle in handles {
+            workers.insert(handle.await.unwrap());
+        }
+        workers
+    });
+
+    assert!(workers.len() > 1, "burst ran on workers {workers:?}");
+}
+
+#[test]
+fn test_self_waking_high_priority_task_yields_after_time_slice() {
+    use cadentis::task::{Priority, spawn_with_priority};
+    use std::future::poll_fn;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::task::Poll;
+
+    let rt = RuntimeBuilder::new()
+        .worker_threads(1)
+        .time_slice(Duration::from_millis(2))
+        .build()
+        .unwrap();
+
+    rt.block_on(async {
+        let done = Arc::new(AtomicBool::new(false));
+
+        // Without time slicing, this task would be picked from the
+        // high-priority queue forever and starve the task below.
+        let spinner_done = done.clone();
+        let spinner = spawn_with_priority(
+            Priority::High,
+            poll_fn(move |cx| {
+                if spinner_done.load(Ordering::Acquire) {
+                    return Poll::Ready(());
+                }
+                cx.waker().wake_by_ref();
+                Poll::Pending
+            }),
+        );
+
+        let worker_done = done.clone();
+        let worker = spawn(async move {
+            worker_done.store(true, Ordering::Release);
+        });
+
+        worker.await.unwrap();
+        spinner.await.unwrap();
+    });
+}
+
+#[test]
+fn test_contended_steals_run_every_task_exactly_once() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    const TASKS: usize = 20_000;
+
+    for strategy in [StealStrategy::One, StealStrategy::Half] {
+        let rt = RuntimeBuilder::new()
+            .worker_threads(8)
+            .local_queue_capacity(1024)
+            .steal_batch(strategy)
+            .build()
+            .unwrap();
+
+        let runs: Arc<Vec<AtomicUsize>> =
+            Arc::new((0..TASKS).map(|_| AtomicUsize::new(0)).collect());
+
+        rt.block_on({
+            let runs = runs.clone();
+            async move {
+                // Bursts of tiny tasks land in a few local queues, which
+                // every idle worker then steals from at once. Half of the
+                // tasks spawn the other half, from whichever worker stole
+                // them.
+                let handles: Vec<_> = (0..TASKS / 2)
+                    .map(|i| {
+                        let runs = runs.clone();
+                        spawn(async move {
+                            runs[i].fetch_add(1, Ordering::Relaxed);
+
+                            let runs = runs.clone();
+                            spawn(async move {
+                                runs[TASKS / 2 + i].fetch_add(1, Ordering::Relaxed);
+                            })
+                            .await
+                            .unwrap();
+                        })
+                    })
+                    .collect();
+
+                for handle in handles {
+                    handle.await.unwrap();
+                }
+            }
+        });
+
+        for (i, count) in runs.iter().enumerate() {
+            assert_eq!(
+                count.load(Ordering::Relaxed),
+                1,
+                "task {i} ran a wrong number of times with {strategy:?}"
+            );
+        }
+    }
+}
@@ -6,7 +6,7 @@ use cadentis::RuntimeBuilder;
 #[cadentis::main]
 async fn main() {
     // Create a runtime with 4 worker threads
-    let runtime = RuntimeBuilder::new().worker_threads(4).build();
+    let runtime = RuntimeBuilder::new().worker_threads(4).build().expect("failed to build the runtime");
 
     // Spawn a simple async task
     runtime.spawn(async {